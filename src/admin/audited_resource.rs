@@ -0,0 +1,255 @@
+// src/admin/audited_resource.rs
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::{body::{BoxBody, MessageBody}, HttpRequest, HttpResponse, ResponseError};
+use adminx::{AdmixResource, actions::CustomAction, error::AdminxError, menu::{MenuAction, MenuItem}, nested::AdmixNestedResource};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use mongodb::{Collection, bson::Document};
+use serde_json::Value;
+
+use crate::admin::references;
+use crate::repositories::Repositories;
+use crate::services::audit_service::AuditTrail;
+
+/// Wraps a resource so every create, update, delete and custom action that succeeds
+/// is written to `audit_logs`, and creates/updates are refused when a reference field
+/// points at a missing record. Everything else is delegated to the inner resource.
+pub struct Audited<R> {
+    inner: R,
+}
+
+impl<R: AdmixResource> Audited<R> {
+    fn trail(&self, req: &HttpRequest) -> Option<AuditTrail> {
+        AuditTrail::capture(req, self.inner.resource_name(), self.inner.collection_name())
+    }
+
+    fn audit_create(&self, req: &HttpRequest, create: BoxFuture<'static, HttpResponse>) -> BoxFuture<'static, HttpResponse> {
+        let trail = self.trail(req);
+        Box::pin(async move {
+            let response = create.await;
+            let Some(trail) = trail.filter(|_| response.status().is_success()) else {
+                return response;
+            };
+
+            let (response, document_id) = created_id(response);
+            trail.record("create", document_id, None);
+            response
+        })
+    }
+
+    fn audit_change(&self, req: &HttpRequest, action: &'static str, id: String, change: BoxFuture<'static, HttpResponse>) -> BoxFuture<'static, HttpResponse> {
+        let trail = self.trail(req);
+        Box::pin(async move {
+            let Some(trail) = trail else {
+                return change.await;
+            };
+
+            let before = trail.snapshot(&id).await;
+            let response = change.await;
+            if response.status().is_success() {
+                trail.record(action, Some(id), before);
+            }
+            response
+        })
+    }
+}
+
+/// Audits a custom action of `R`. Action handlers are plain fn pointers, so the wrapped
+/// handler cannot capture the original one; it looks it up again by the last path segment.
+fn audited_action<R: AdmixResource + 'static>(req: HttpRequest, path: Vec<String>, body: Value) -> Pin<Box<dyn Future<Output = HttpResponse>>> {
+    let resource = R::new();
+    let name = req.path().rsplit('/').next().unwrap_or_default().to_string();
+    let Some(action) = resource.custom_actions().into_iter().find(|action| action.name == name) else {
+        return Box::pin(async { AdminxError::NotFound.error_response() });
+    };
+
+    let trail = AuditTrail::capture(&req, resource.resource_name(), resource.collection_name());
+    let document_id = path.first().cloned();
+    Box::pin(async move {
+        let before = match (&trail, document_id.as_deref()) {
+            (Some(trail), Some(id)) => trail.snapshot(id).await,
+            _ => None,
+        };
+
+        let response = (action.handler)(req, path, body).await;
+        if let Some(trail) = trail.filter(|_| response.status().is_success()) {
+            trail.record(action.name, document_id, before);
+        }
+        response
+    })
+}
+
+/// Pulls the inserted id out of a create response (`{"id": {"$oid": ...}}`) and rebuilds it
+fn created_id(response: HttpResponse) -> (HttpResponse, Option<String>) {
+    let (head, body) = response.into_parts();
+    match body.try_into_bytes() {
+        Ok(bytes) => {
+            let id = serde_json::from_slice::<Value>(&bytes).ok().and_then(|json| {
+                let id = json.get("id")?;
+                id.get("$oid").unwrap_or(id).as_str().map(str::to_string)
+            });
+            (head.set_body(BoxBody::new(bytes)), id)
+        }
+        Err(body) => (head.set_body(body), None),
+    }
+}
+
+#[async_trait]
+impl<R: AdmixResource + 'static> AdmixResource for Audited<R> {
+    fn new() -> Self {
+        Audited { inner: R::new() }
+    }
+
+    fn resource_name(&self) -> &'static str {
+        self.inner.resource_name()
+    }
+
+    fn base_path(&self) -> &'static str {
+        self.inner.base_path()
+    }
+
+    fn collection_name(&self) -> &'static str {
+        self.inner.collection_name()
+    }
+
+    fn get_collection(&self) -> Collection<Document> {
+        self.inner.get_collection()
+    }
+
+    fn clone_box(&self) -> Box<dyn AdmixResource> {
+        Box::new(Self::new())
+    }
+
+    fn menu_group(&self) -> Option<&'static str> {
+        self.inner.menu_group()
+    }
+
+    fn menu(&self) -> &'static str {
+        self.inner.menu()
+    }
+
+    fn allowed_roles(&self) -> Vec<String> {
+        self.inner.allowed_roles()
+    }
+
+    fn allowed_roles_with_permissions(&self) -> Value {
+        self.inner.allowed_roles_with_permissions()
+    }
+
+    fn visible_fields_for_role(&self, roles: &[String]) -> Vec<String> {
+        self.inner.visible_fields_for_role(roles)
+    }
+
+    fn nested_resources(&self) -> Vec<Box<dyn AdmixNestedResource>> {
+        self.inner.nested_resources()
+    }
+
+    fn custom_actions(&self) -> Vec<CustomAction> {
+        self.inner.custom_actions()
+            .into_iter()
+            .map(|action| CustomAction { handler: audited_action::<R>, ..action })
+            .collect()
+    }
+
+    fn allowed_actions(&self) -> Option<Vec<MenuAction>> {
+        self.inner.allowed_actions()
+    }
+
+    fn permit_keys(&self) -> Vec<&'static str> {
+        self.inner.permit_keys()
+    }
+
+    fn readonly_keys(&self) -> Vec<&'static str> {
+        self.inner.readonly_keys()
+    }
+
+    fn supports_file_upload(&self) -> bool {
+        self.inner.supports_file_upload()
+    }
+
+    fn max_file_size(&self) -> usize {
+        self.inner.max_file_size()
+    }
+
+    fn allowed_file_extensions(&self) -> Vec<&'static str> {
+        self.inner.allowed_file_extensions()
+    }
+
+    fn file_upload_config(&self) -> Option<Value> {
+        self.inner.file_upload_config()
+    }
+
+    fn process_file_upload(&self, field_name: &str, file_data: &[u8], filename: &str) -> BoxFuture<'static, Result<HashMap<String, String>, AdminxError>> {
+        self.inner.process_file_upload(field_name, file_data, filename)
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        let (payload, references) = references::prepare(self.inner.form_structure(), payload);
+        self.audit_create(req, references.guard(Repositories::from_request(req), self.inner.create(req, payload)))
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        let (payload, references) = references::prepare(self.inner.form_structure(), payload);
+        self.audit_change(req, "update", id.clone(), references.guard(Repositories::from_request(req), self.inner.update(req, id, payload)))
+    }
+
+    // The default *_with_files call `create`/`update` with a synthetic request that has
+    // no session, so they are audited here against the real one instead.
+    fn create_with_files(
+        &self,
+        req: &HttpRequest,
+        form_data: HashMap<String, String>,
+        files: HashMap<String, (String, Vec<u8>)>,
+    ) -> BoxFuture<'static, HttpResponse> {
+        self.audit_create(req, self.inner.create_with_files(req, form_data, files))
+    }
+
+    fn update_with_files(
+        &self,
+        req: &HttpRequest,
+        id: String,
+        form_data: HashMap<String, String>,
+        files: HashMap<String, (String, Vec<u8>)>,
+    ) -> BoxFuture<'static, HttpResponse> {
+        self.audit_change(req, "update", id.clone(), self.inner.update_with_files(req, id, form_data, files))
+    }
+
+    fn form_structure(&self) -> Option<Value> {
+        self.inner.form_structure()
+    }
+
+    fn list_structure(&self) -> Option<Value> {
+        self.inner.list_structure()
+    }
+
+    fn view_structure(&self) -> Option<Value> {
+        self.inner.view_structure()
+    }
+
+    fn filters(&self) -> Option<Value> {
+        self.inner.filters()
+    }
+
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        self.inner.list(req, query)
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        self.inner.get(req, id)
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        self.audit_change(req, "delete", id.clone(), self.inner.delete(req, id))
+    }
+
+    fn generate_menu(&self) -> Option<MenuItem> {
+        self.inner.generate_menu()
+    }
+
+    fn build_adminx_menus(&self) -> Option<MenuItem> {
+        self.inner.build_adminx_menus()
+    }
+}
@@ -0,0 +1,227 @@
+// src/admin/bulk_actions.rs
+//
+// Multi-select actions for AdminX list pages. A resource declares its bulk actions by
+// implementing `BulkActions`; `POST /adminx/{base_path}/bulk/{name}` with
+// `{"ids": [...], "params": {...}}` runs one over the ids a batch at a time and answers
+// with a result per id. Each changed document gets its own audit entry.
+use std::collections::HashSet;
+
+use actix_session::Session;
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use adminx::{AdmixResource, AdminxConfig, helpers::resource_helper::check_resource_permission};
+use futures::future::join_all;
+use mongodb::bson::{self, doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::repositories::Repositories;
+use crate::services::audit_service::AuditTrail;
+
+/// Documents updated concurrently; the next batch starts once this one is done
+const BULK_BATCH_SIZE: usize = 50;
+
+const MAX_BULK_IDS: usize = 1000;
+
+
+/*----- START DECLARATIONS -----*/
+pub enum BulkOperation {
+    /// Sets `field` to a fixed value
+    Set { field: &'static str, value: fn() -> Result<Bson, Error> },
+    /// Sets `field` to `params[field]`, which must be the value of one of `options`
+    Choose { field: &'static str, options: fn() -> Vec<Value> },
+    /// Sets `field` to the current time
+    Stamp { field: &'static str },
+    SoftDelete,
+    Restore,
+}
+
+pub struct BulkAction {
+    pub name: &'static str,
+    pub label: &'static str,
+    pub operation: BulkOperation,
+}
+
+pub trait BulkActions {
+    fn bulk_actions() -> Vec<BulkAction> {
+        Vec::new()
+    }
+}
+
+/// What the list page needs to offer the actions: a select of them, plus a value
+/// picker for the ones that take a parameter
+pub fn bulk_actions_meta(actions: &[BulkAction]) -> Vec<Value> {
+    actions.iter()
+        .map(|action| {
+            let mut meta = json!({ "name": action.name, "label": action.label });
+            match &action.operation {
+                BulkOperation::Choose { field, options } => {
+                    meta["field"] = json!(field);
+                    meta["options"] = json!(options());
+                }
+                BulkOperation::SoftDelete => meta["confirm"] = json!("Delete the selected records?"),
+                BulkOperation::Restore => meta["confirm"] = json!("Restore the selected records?"),
+                _ => {}
+            }
+            meta
+        })
+        .collect()
+}
+/*----- END DECLARATIONS -----*/
+
+
+/*----- START OPERATIONS -----*/
+#[derive(Debug, Serialize)]
+struct BulkResult {
+    id: String,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+}
+
+type BulkFailure = (&'static str, String);
+
+impl BulkOperation {
+    /// The update applied to every record, built once per request so a bad parameter
+    /// fails the whole request instead of every id
+    fn update(&self, params: &Value) -> Result<Document, HttpResponse> {
+        let set = match self {
+            BulkOperation::Set { field, value } => {
+                let value = value().map_err(|e| e.error_response())?;
+                doc! { *field: value }
+            }
+            BulkOperation::Choose { field, options } => {
+                let chosen = params.get(*field).filter(|chosen| {
+                    options().iter().any(|option| option.get("value") == Some(*chosen))
+                });
+                let Some(value) = chosen.and_then(|chosen| bson::to_bson(chosen).ok()) else {
+                    return Err(HttpResponse::BadRequest().json(json!({
+                        "error": format!("invalid_{}", field),
+                        "message": format!("{} is missing or not an allowed value", field)
+                    })));
+                };
+                doc! { *field: value }
+            }
+            BulkOperation::Stamp { field } => doc! { *field: BsonDateTime::now() },
+            BulkOperation::SoftDelete => doc! { "deleted": true },
+            BulkOperation::Restore => doc! { "deleted": false },
+        };
+
+        Ok(doc! { "$set": set, "$currentDate": { "updated_at": true } })
+    }
+
+    /// Deleted and locked records are read-only; only restoring touches deleted ones
+    fn check(&self, record: &Document) -> Result<(), BulkFailure> {
+        let deleted = record.get_bool("deleted").unwrap_or(false);
+        match self {
+            BulkOperation::Restore if !deleted => Err(("not_deleted", "Record is not deleted".to_string())),
+            BulkOperation::Restore => Ok(()),
+            _ if deleted => Err(("record_deleted", "Record is deleted".to_string())),
+            _ if record.get_bool("locked").unwrap_or(false) => Err(("record_locked", "Record is locked".to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// `check` as a filter, so a record changed since it was read is left alone
+    fn filter(&self, id: ObjectId) -> Document {
+        match self {
+            BulkOperation::Restore => doc! { "_id": id, "deleted": true },
+            _ => doc! { "_id": id, "deleted": { "$ne": true }, "locked": { "$ne": true } },
+        }
+    }
+}
+
+/// Applies `update` to one record, returning the record as it was before
+async fn apply(repos: &Repositories, collection: &str, operation: &BulkOperation, update: &Document, id: &str) -> Result<Document, BulkFailure> {
+    let id = ObjectId::parse_str(id).map_err(|_| ("invalid_object_id", "Not a valid id".to_string()))?;
+
+    let before = repos.records.find_one(collection, doc! { "_id": id }).await
+        .map_err(|e| ("db_read_failed", e.to_string()))?
+        .ok_or_else(|| ("not_found", "Record not found".to_string()))?;
+    operation.check(&before)?;
+
+    let result = repos.records.update_one(collection, operation.filter(id), update.clone()).await
+        .map_err(|e| ("db_update_failed", e.to_string()))?;
+    if result.matched_count == 0 {
+        return Err(("record_changed", "Record was changed meanwhile".to_string()));
+    }
+    Ok(before)
+}
+/*----- END OPERATIONS -----*/
+
+
+/*----- START HANDLER -----*/
+#[derive(Debug, Deserialize)]
+pub struct BulkRequest {
+    pub ids: Vec<String>,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// POST /adminx/{base_path}/bulk/{name}
+pub async fn bulk_action<R: AdmixResource + BulkActions + 'static>(
+    req: HttpRequest,
+    path: web::Path<String>,
+    body: web::Json<BulkRequest>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let resource = R::new();
+    let name = path.into_inner();
+    if let Err(response) = check_resource_permission(&session, &config, &resource, &format!("bulk_{}", name)).await {
+        return response;
+    }
+
+    let Some(action) = R::bulk_actions().into_iter().find(|action| action.name == name) else {
+        return HttpResponse::NotFound().json(json!({"error":"unknown_bulk_action"}));
+    };
+
+    let BulkRequest { mut ids, params } = body.into_inner();
+    let mut seen = HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+    if ids.is_empty() {
+        return HttpResponse::BadRequest().json(json!({"error":"no_ids","message":"Select at least one record"}));
+    }
+    if ids.len() > MAX_BULK_IDS {
+        return HttpResponse::BadRequest().json(json!({
+            "error": "too_many_ids",
+            "message": format!("At most {} records can be changed at once", MAX_BULK_IDS)
+        }));
+    }
+
+    let update = match action.operation.update(&params) {
+        Ok(update) => update,
+        Err(response) => return response,
+    };
+
+    let collection = resource.collection_name();
+    let trail = AuditTrail::capture(&req, resource.resource_name(), collection);
+    let audit_action = format!("bulk_{}", action.name);
+
+    let mut results = Vec::with_capacity(ids.len());
+    for batch in ids.chunks(BULK_BATCH_SIZE) {
+        let outcomes = join_all(batch.iter().map(|id| apply(&repos, collection, &action.operation, &update, id))).await;
+        for (id, outcome) in batch.iter().zip(outcomes) {
+            results.push(match outcome {
+                Ok(before) => {
+                    if let Some(trail) = &trail {
+                        trail.clone().record(&audit_action, Some(id.clone()), Some(before));
+                    }
+                    BulkResult { id: id.clone(), success: true, error: None, message: None }
+                }
+                Err((error, message)) => BulkResult { id: id.clone(), success: false, error: Some(error), message: Some(message) },
+            });
+        }
+    }
+
+    let succeeded = results.iter().filter(|result| result.success).count();
+    HttpResponse::Ok().json(json!({
+        "success": succeeded == results.len(),
+        "succeeded": succeeded,
+        "failed": results.len() - succeeded,
+        "results": results,
+    }))
+}
+/*----- END HANDLER -----*/
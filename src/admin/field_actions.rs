@@ -0,0 +1,180 @@
+// src/admin/field_actions.rs
+//
+// Shared runtime behind the `toggle_action!` and `set_enum_action!` macros. AdminX action
+// handlers are plain fn pointers, so the macros expand to non-capturing closures that
+// forward their literal collection/field names here.
+use actix_web::{HttpRequest, HttpResponse};
+use adminx::actions::{ActionField, ActionUi};
+use convert_case::{Casing, Case};
+use mongodb::bson::{self, doc, oid::ObjectId, Bson, Document};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+use crate::admin::options::enum_options;
+use crate::repositories::Repositories;
+
+// Locking a record must not block unlocking it
+const LOCK_FIELD: &str = "locked";
+
+
+/*----- START HANDLERS -----*/
+/// Sets a boolean field to `body[field]`, or flips its current value when absent
+pub async fn toggle_field(req: HttpRequest, body: Value, collection: &'static str, field: &'static str) -> HttpResponse {
+    let Some(id) = record_id(&req) else {
+        return HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"}));
+    };
+    let Some(repos) = Repositories::from_request(&req) else {
+        return repositories_missing();
+    };
+
+    let current = match guarded_record(&repos, collection, field, &id).await {
+        Ok(current) => current,
+        Err(resp) => return resp,
+    };
+
+    let on = match body.get(field) {
+        Some(value) => match value.as_bool() {
+            Some(on) => on,
+            None => return HttpResponse::BadRequest().json(json!({
+                "error": format!("invalid_{}", field),
+                "message": format!("{} must be true or false", field)
+            })),
+        },
+        None => !current.get_bool(field).unwrap_or(false),
+    };
+
+    set_guarded(&repos, collection, field, &id, Bson::Boolean(on)).await
+}
+
+/// Sets an enum field to `body[field]`, rejecting anything `E` does not deserialize from
+pub async fn set_enum_field<E>(req: HttpRequest, body: Value, collection: &'static str, field: &'static str) -> HttpResponse
+where
+    E: Serialize + DeserializeOwned,
+{
+    let Some(id) = record_id(&req) else {
+        return HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"}));
+    };
+    let Some(repos) = Repositories::from_request(&req) else {
+        return repositories_missing();
+    };
+
+    let parsed = body.get(field)
+        .cloned()
+        .and_then(|value| serde_json::from_value::<E>(value).ok())
+        .and_then(|variant| bson::to_bson(&variant).ok());
+    let Some(value) = parsed else {
+        return HttpResponse::BadRequest().json(json!({
+            "error": format!("invalid_{}", field),
+            "message": format!("{} is missing or not an allowed value", field)
+        }));
+    };
+
+    if let Err(resp) = guarded_record(&repos, collection, field, &id).await {
+        return resp;
+    }
+
+    set_guarded(&repos, collection, field, &id, value).await
+}
+/*----- END HANDLERS -----*/
+
+
+/*----- START UI -----*/
+pub fn toggle_ui(field: &str) -> ActionUi {
+    let label = field.to_case(Case::Title);
+    ActionUi {
+        label: Some(format!("Toggle {}", label)),
+        confirm: None,
+        fields: Some(vec![
+            ActionField {
+                name: field.into(),
+                label: Some(label),
+                field_type: "boolean".into(),
+                required: Some(true),
+                options: None,
+            },
+        ]),
+    }
+}
+
+/// Select over every variant of `E`, valued by its serde representation
+pub fn enum_ui<E>(field: &str) -> ActionUi
+where
+    E: Serialize + IntoEnumIterator,
+{
+    let label = field.to_case(Case::Title);
+
+    ActionUi {
+        label: Some(format!("Set {}", label)),
+        confirm: None,
+        fields: Some(vec![
+            ActionField {
+                name: field.into(),
+                label: Some(label),
+                field_type: "select".into(),
+                required: Some(true),
+                options: Some(enum_options::<E>()),
+            },
+        ]),
+    }
+}
+/*----- END UI -----*/
+
+
+/*----- START HELPERS -----*/
+fn record_id(req: &HttpRequest) -> Option<ObjectId> {
+    req.match_info().get("id").and_then(|id| ObjectId::parse_str(id).ok())
+}
+
+fn repositories_missing() -> HttpResponse {
+    HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}))
+}
+
+/// Deleted and locked records are read-only, except for unlocking
+fn guard_filter(field: &str, id: &ObjectId) -> Document {
+    let mut filter = doc! { "_id": id, "deleted": { "$ne": true } };
+    if field != LOCK_FIELD {
+        filter.insert(LOCK_FIELD, doc! { "$ne": true });
+    }
+    filter
+}
+
+/// Loads the record, turning a missing, deleted or locked one into the matching response
+async fn guarded_record(repos: &Repositories, collection: &'static str, field: &str, id: &ObjectId) -> Result<Document, HttpResponse> {
+    let record = repos.records.find_one(collection, doc! { "_id": id })
+        .await
+        .map_err(|e| HttpResponse::InternalServerError().json(json!({"error":"db_read_failed","message":e.to_string()})))?;
+
+    let Some(record) = record else {
+        return Err(HttpResponse::NotFound().json(json!({"error":"not_found"})));
+    };
+    if record.get_bool("deleted").unwrap_or(false) {
+        return Err(HttpResponse::Conflict().json(json!({"error":"record_deleted","message":"Record is deleted"})));
+    }
+    if field != LOCK_FIELD && record.get_bool(LOCK_FIELD).unwrap_or(false) {
+        return Err(HttpResponse::Conflict().json(json!({"error":"record_locked","message":"Record is locked"})));
+    }
+    Ok(record)
+}
+
+/// Writes the field under the same guards, so a record locked in between is left alone
+async fn set_guarded(repos: &Repositories, collection: &'static str, field: &'static str, id: &ObjectId, value: Bson) -> HttpResponse {
+    let update = doc! {
+        "$set": { field: value.clone() },
+        "$currentDate": { "updated_at": true }
+    };
+
+    match repos.records.update_one(collection, guard_filter(field, id), update).await {
+        Ok(res) if res.matched_count == 0 => {
+            HttpResponse::Conflict().json(json!({"error":"record_changed","message":"Record was deleted or locked"}))
+        }
+        Ok(res) => HttpResponse::Ok().json(json!({
+            "success": true,
+            "message": format!("{} updated", field.to_case(Case::Title)),
+            field: value.into_relaxed_extjson(),
+            "modified": res.modified_count
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error":"db_update_failed","message":e.to_string()})),
+    }
+}
+/*----- END HELPERS -----*/
@@ -19,6 +19,30 @@ use crate::admin::resources::picture_resource::PictureResource;
 use crate::admin::resources::contact_resource::ContactResource;
 use crate::admin::resources::event_attendee_resource::EventAttendeeResource;
 use crate::admin::resources::event_resource::EventResource;
+use crate::admin::resources::audit_log_resource::AuditLogResource;
+use crate::admin::resources::sent_email_resource::SentEmailResource;
+use crate::admin::resources::admin_user_resource::AdminUserResource;
+use crate::admin::audited_resource::Audited;
+use crate::models::{contact::Contact, event_attendee::EventAttendee};
+use crate::admin::bulk_actions::bulk_action;
+use crate::admin::pages::account_page::{password_action, password_page};
+use crate::admin::pages::analytics_page::event_analytics_page;
+use crate::admin::pages::event_page::event_view_page;
+use crate::admin::pages::import_page::{import_page, import_rows};
+use crate::admin::pages::reference_page::{reference_edit_page, reference_list_page, reference_search, reference_view_page};
+use crate::admin::pages::search_page::search_page;
+use crate::admin::pages::two_factor_page::{
+    api_login_action,
+    login_action,
+    setup_action,
+    setup_page,
+    verify_action,
+    verify_page,
+};
+use crate::admin::pages::user_page::user_view_page;
+
+/// Largest CSV import request, in bytes
+const IMPORT_BODY_LIMIT: usize = 8 * 1024 * 1024;
 
 pub struct AdminxInitializer;
 
@@ -45,17 +69,21 @@ impl AdminxInitializer {
         adminx_config
     }
     
-    /// Register all AdminX resources
+    /// Register all AdminX resources. Each is wrapped in `Audited` so its changes
+    /// land in `audit_logs`; the audit log and email log are read-only and left unwrapped.
     fn register_resources() {
         println!("📝 Registering AdminX resources...");
         // Register your resources with AdminX
-        register_resource(Box::new(UserResource::new()));
-        register_resource(Box::new(NotificationResource::new()));
-        register_resource(Box::new(ConfigResource::new()));
-        register_resource(Box::new(PictureResource::new()));
-        register_resource(Box::new(ContactResource::new()));
-        register_resource(Box::new(EventAttendeeResource::new()));
-        register_resource(Box::new(EventResource::new()));
+        register_resource(Box::new(Audited::<UserResource>::new()));
+        register_resource(Box::new(Audited::<NotificationResource>::new()));
+        register_resource(Box::new(Audited::<ConfigResource>::new()));
+        register_resource(Box::new(Audited::<PictureResource>::new()));
+        register_resource(Box::new(Audited::<ContactResource>::new()));
+        register_resource(Box::new(Audited::<EventAttendeeResource>::new()));
+        register_resource(Box::new(Audited::<EventResource>::new()));
+        register_resource(Box::new(AuditLogResource::new()));
+        register_resource(Box::new(SentEmailResource::new()));
+        register_resource(Box::new(Audited::<AdminUserResource>::new()));
         println!("All resources registered successfully!");
     }
     
@@ -82,4 +110,73 @@ impl AdminxInitializer {
     pub fn get_routes_service() -> actix_web::Scope {
         register_all_admix_routes()
     }
-}
\ No newline at end of file
+
+    /// Custom AdminX pages. Must be configured before `get_routes_service()`,
+    /// since the `/adminx` scope does not fall through to later services.
+    pub fn configure_pages(cfg: &mut actix_web::web::ServiceConfig) {
+        // Sign-in with a TOTP step; the AdminX logins would issue a session on the password alone
+        cfg.route("/adminx/login", actix_web::web::post().to(login_action));
+        cfg.route("/adminx/api/login", actix_web::web::post().to(api_login_action));
+        cfg.route("/adminx/2fa", actix_web::web::get().to(verify_page));
+        cfg.route("/adminx/2fa", actix_web::web::post().to(verify_action));
+        cfg.route("/adminx/2fa/setup", actix_web::web::get().to(setup_page));
+        cfg.route("/adminx/2fa/setup", actix_web::web::post().to(setup_action));
+        cfg.route("/adminx/account/password", actix_web::web::get().to(password_page));
+        cfg.route("/adminx/account/password", actix_web::web::post().to(password_action));
+        cfg.route("/adminx/analytics", actix_web::web::get().to(event_analytics_page));
+        cfg.route("/adminx/search", actix_web::web::get().to(search_page));
+        // Shadows the generic AdminX view to add the ban history
+        cfg.route("/adminx/users/view/{id}", actix_web::web::get().to(user_view_page));
+        cfg.route("/adminx/references/{target}", actix_web::web::get().to(reference_search));
+        // Shadow the generic list/view of resources with reference fields to show labels, not ids
+        cfg.route("/adminx/contacts/list", actix_web::web::get().to(reference_list_page::<ContactResource>));
+        cfg.route("/adminx/contacts/view/{id}", actix_web::web::get().to(reference_view_page::<ContactResource>));
+        cfg.route("/adminx/notifications/list", actix_web::web::get().to(reference_list_page::<NotificationResource>));
+        cfg.route("/adminx/notifications/view/{id}", actix_web::web::get().to(reference_view_page::<NotificationResource>));
+        cfg.route("/adminx/events/list", actix_web::web::get().to(reference_list_page::<EventResource>));
+        // Labelled view plus the event's attendees
+        cfg.route("/adminx/events/view/{id}", actix_web::web::get().to(event_view_page));
+        cfg.route("/adminx/event_attendees/list", actix_web::web::get().to(reference_list_page::<EventAttendeeResource>));
+        cfg.route("/adminx/event_attendees/view/{id}", actix_web::web::get().to(reference_view_page::<EventAttendeeResource>));
+        // Lists of the remaining resources with bulk actions, and the bulk actions themselves
+        cfg.route("/adminx/users/list", actix_web::web::get().to(reference_list_page::<UserResource>));
+        cfg.route("/adminx/configs/list", actix_web::web::get().to(reference_list_page::<ConfigResource>));
+        cfg.route("/adminx/pictures/list", actix_web::web::get().to(reference_list_page::<PictureResource>));
+        cfg.route("/adminx/audit_logs/list", actix_web::web::get().to(reference_list_page::<AuditLogResource>));
+        cfg.route("/adminx/sent_emails/list", actix_web::web::get().to(reference_list_page::<SentEmailResource>));
+        cfg.route("/adminx/admin_users/list", actix_web::web::get().to(reference_list_page::<AdminUserResource>));
+        // Views and edit forms read through the record repository like the lists
+        cfg.route("/adminx/configs/view/{id}", actix_web::web::get().to(reference_view_page::<ConfigResource>));
+        cfg.route("/adminx/pictures/view/{id}", actix_web::web::get().to(reference_view_page::<PictureResource>));
+        cfg.route("/adminx/audit_logs/view/{id}", actix_web::web::get().to(reference_view_page::<AuditLogResource>));
+        cfg.route("/adminx/sent_emails/view/{id}", actix_web::web::get().to(reference_view_page::<SentEmailResource>));
+        cfg.route("/adminx/admin_users/view/{id}", actix_web::web::get().to(reference_view_page::<AdminUserResource>));
+        cfg.route("/adminx/users/edit/{id}", actix_web::web::get().to(reference_edit_page::<UserResource>));
+        cfg.route("/adminx/configs/edit/{id}", actix_web::web::get().to(reference_edit_page::<ConfigResource>));
+        cfg.route("/adminx/pictures/edit/{id}", actix_web::web::get().to(reference_edit_page::<PictureResource>));
+        cfg.route("/adminx/contacts/edit/{id}", actix_web::web::get().to(reference_edit_page::<ContactResource>));
+        cfg.route("/adminx/notifications/edit/{id}", actix_web::web::get().to(reference_edit_page::<NotificationResource>));
+        cfg.route("/adminx/events/edit/{id}", actix_web::web::get().to(reference_edit_page::<EventResource>));
+        cfg.route("/adminx/event_attendees/edit/{id}", actix_web::web::get().to(reference_edit_page::<EventAttendeeResource>));
+        cfg.route("/adminx/admin_users/edit/{id}", actix_web::web::get().to(reference_edit_page::<AdminUserResource>));
+        cfg.route("/adminx/users/bulk/{name}", actix_web::web::post().to(bulk_action::<UserResource>));
+        cfg.route("/adminx/configs/bulk/{name}", actix_web::web::post().to(bulk_action::<ConfigResource>));
+        cfg.route("/adminx/contacts/bulk/{name}", actix_web::web::post().to(bulk_action::<ContactResource>));
+        cfg.route("/adminx/notifications/bulk/{name}", actix_web::web::post().to(bulk_action::<NotificationResource>));
+        cfg.route("/adminx/events/bulk/{name}", actix_web::web::post().to(bulk_action::<EventResource>));
+        cfg.route("/adminx/event_attendees/bulk/{name}", actix_web::web::post().to(bulk_action::<EventAttendeeResource>));
+        // CSV import page, and the dry runs and imports it posts. A few thousand rows outgrow the default JSON limit.
+        cfg.service(
+            actix_web::web::resource("/adminx/contacts/import")
+                .app_data(actix_web::web::JsonConfig::default().limit(IMPORT_BODY_LIMIT))
+                .route(actix_web::web::get().to(import_page::<ContactResource, Contact>))
+                .route(actix_web::web::post().to(import_rows::<ContactResource, Contact>))
+        );
+        cfg.service(
+            actix_web::web::resource("/adminx/event_attendees/import")
+                .app_data(actix_web::web::JsonConfig::default().limit(IMPORT_BODY_LIMIT))
+                .route(actix_web::web::get().to(import_page::<EventAttendeeResource, EventAttendee>))
+                .route(actix_web::web::post().to(import_rows::<EventAttendeeResource, EventAttendee>))
+        );
+    }
+}
@@ -1,2 +1,11 @@
 pub mod initializer;
-pub mod resources;
\ No newline at end of file
+pub mod resources;
+pub mod pages;
+pub mod audited_resource;
+pub mod bulk_actions;
+pub mod csv_export;
+pub mod field_actions;
+pub mod model_schema;
+pub mod options;
+pub mod records;
+pub mod references;
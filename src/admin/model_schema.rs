@@ -0,0 +1,356 @@
+// src/admin/model_schema.rs
+//
+// Builds AdminX form/list/view/filter JSON from a model instead of hand-written `json!`
+// blobs. `#[derive(JsonSchema, AdminxModel)]` on the model supplies both halves: schemars
+// knows each field's type, enum variants and whether it is required; `#[adminx(...)]`
+// field attributes add the label, group, widget and list/filter flags.
+use std::collections::BTreeSet;
+
+use convert_case::{Casing, Case};
+use schemars::{
+    r#gen::SchemaGenerator,
+    schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec},
+    JsonSchema,
+};
+use serde_json::{json, Value};
+
+pub use xard_derive::AdminxModel;
+
+use crate::admin::options::{boolean_options, select_option};
+use crate::admin::references::{self, REFERENCE_WIDGET};
+
+const DEFAULT_GROUP: &str = "Details";
+
+// Same as AdmixResource::readonly_keys: shown, never written from a form
+const SYSTEM_FIELDS: [&str; 3] = ["_id", "created_at", "updated_at"];
+
+/// One field's `#[adminx(...)]` attributes, generated by `#[derive(AdminxModel)]`
+pub struct FieldAttrs {
+    pub name: &'static str,
+    pub label: Option<&'static str>,
+    pub group: Option<&'static str>,
+    pub widget: Option<&'static str>,
+    pub reference: Option<&'static str>,
+    pub list: bool,
+    pub filter: bool,
+    pub readonly: bool,
+    pub skip: bool,
+}
+
+pub trait AdminxModel: JsonSchema {
+    fn adminx_fields() -> Vec<FieldAttrs>;
+}
+
+
+/*----- START SCHEMA STAND-INS -----*/
+/// `#[schemars(with = "ObjectIdSchema")]` for `ObjectId` fields
+pub struct ObjectIdSchema;
+
+impl JsonSchema for ObjectIdSchema {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "ObjectId".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema("objectid")
+    }
+}
+
+/// `#[schemars(with = "DateTimeSchema")]` for BSON `DateTime` fields
+pub struct DateTimeSchema;
+
+impl JsonSchema for DateTimeSchema {
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn schema_name() -> String {
+        "DateTime".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema("date-time")
+    }
+}
+
+fn string_schema(format: &str) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        format: Some(format.to_string()),
+        ..Default::default()
+    }
+    .into()
+}
+/*----- END SCHEMA STAND-INS -----*/
+
+
+/*----- START MODEL SCHEMA -----*/
+pub struct ModelField {
+    pub name: &'static str,
+    pub label: String,
+    pub group: &'static str,
+    pub widget: &'static str,
+    pub reference: Option<&'static str>,
+    pub options: Option<Vec<Value>>,
+    pub required: bool,
+    pub list: bool,
+    pub filter: bool,
+    pub readonly: bool,
+}
+
+pub struct ModelSchema {
+    fields: Vec<ModelField>,
+}
+
+impl ModelSchema {
+    pub fn of<T: AdminxModel>() -> Self {
+        let root = schemars::schema_for!(T);
+        let properties = root.schema.object.as_ref().map(|object| (&object.properties, &object.required));
+
+        let fields = T::adminx_fields()
+            .into_iter()
+            .filter(|attrs| !attrs.skip)
+            .map(|attrs| {
+                let (property, required) = match properties {
+                    Some((properties, required)) => (properties.get(attrs.name), required.contains(attrs.name)),
+                    None => (None, false),
+                };
+                let shape = property.map(|schema| Shape::of(&root, schema)).unwrap_or_default();
+
+                ModelField {
+                    name: attrs.name,
+                    label: attrs.label
+                        .map(str::to_string)
+                        .unwrap_or_else(|| attrs.name.trim_start_matches('_').to_case(Case::Title)),
+                    group: attrs.group.unwrap_or(DEFAULT_GROUP),
+                    widget: match attrs.reference {
+                        Some(_) => REFERENCE_WIDGET,
+                        None => attrs.widget.unwrap_or_else(|| shape.widget()),
+                    },
+                    reference: attrs.reference,
+                    options: shape.options(),
+                    required,
+                    list: attrs.list,
+                    filter: attrs.filter,
+                    readonly: attrs.readonly || SYSTEM_FIELDS.contains(&attrs.name),
+                }
+            })
+            .collect();
+
+        Self { fields }
+    }
+
+    /// Every field but `_id`. AdminX reads records for list, view and filters through
+    /// permit_keys as well as writes, so readonly fields have to be listed too; they are
+    /// kept out of the form instead.
+    pub fn permit_keys(&self) -> Vec<&'static str> {
+        self.fields.iter()
+            .filter(|field| field.name != "_id")
+            .map(|field| field.name)
+            .collect()
+    }
+
+    pub fn form_structure(&self) -> Value {
+        let groups: Vec<Value> = self.groups(|field| !field.readonly)
+            .into_iter()
+            .map(|(title, fields)| json!({
+                "title": title,
+                "fields": fields.iter().map(|field| {
+                    let mut entry = json!({
+                        "name": field.name,
+                        "field_type": field.widget,
+                        "label": field.label,
+                        "value": "",
+                        "required": field.required,
+                        "options": field.options,
+                    });
+                    if let Some(target) = field.reference {
+                        entry["reference"] = json!(target);
+                        entry["help_text"] = json!(references::picker_html(field.name, target));
+                    }
+                    entry
+                }).collect::<Vec<_>>()
+            }))
+            .collect();
+
+        json!({ "groups": groups })
+    }
+
+    pub fn list_structure(&self, actions: &[&str]) -> Value {
+        let columns: Vec<Value> = self.fields.iter()
+            .filter(|field| field.list)
+            .map(|field| {
+                let mut column = json!({ "field": field.name, "label": field.label, "sortable": true });
+                if let Some(kind) = field.display_type() {
+                    column["type"] = json!(kind);
+                }
+                column
+            })
+            .collect();
+
+        json!({ "columns": columns, "actions": actions })
+    }
+
+    pub fn view_structure(&self) -> Value {
+        let sections: Vec<Value> = self.groups(|_| true)
+            .into_iter()
+            .map(|(title, fields)| json!({
+                "title": title,
+                "fields": fields.iter().map(|field| {
+                    let mut entry = json!({ "field": field.name, "label": field.label });
+                    if let Some(kind) = field.display_type() {
+                        entry["type"] = json!(kind);
+                    }
+                    entry
+                }).collect::<Vec<_>>()
+            }))
+            .collect();
+
+        json!({ "sections": sections })
+    }
+
+    pub fn filters(&self) -> Value {
+        let filters: Vec<Value> = self.fields.iter()
+            .filter(|field| field.filter)
+            .map(|field| match field.widget {
+                "select" | "boolean" => json!({
+                    "field": field.name,
+                    "type": field.widget,
+                    "label": field.label,
+                    "options": field.options,
+                }),
+                "datetime" | "date" => json!({ "field": field.name, "type": "date_range", "label": field.label }),
+                "number" => json!({ "field": field.name, "type": "number_range", "label": field.label }),
+                _ => json!({
+                    "field": field.name,
+                    "type": "text",
+                    "label": field.label,
+                    "placeholder": format!("Search by {}...", field.label.to_lowercase()),
+                }),
+            })
+            .collect();
+
+        json!({ "filters": filters })
+    }
+
+    /// Fields grouped by `#[adminx(group)]`, groups in order of first appearance except
+    /// that groups holding only readonly fields (System, usually) go last
+    fn groups(&self, include: impl Fn(&ModelField) -> bool) -> Vec<(&'static str, Vec<&ModelField>)> {
+        let mut groups: Vec<(&'static str, Vec<&ModelField>)> = Vec::new();
+        for field in self.fields.iter().filter(|field| include(field)) {
+            match groups.iter_mut().find(|(title, _)| *title == field.group) {
+                Some((_, fields)) => fields.push(field),
+                None => groups.push((field.group, vec![field])),
+            }
+        }
+        groups.sort_by_key(|(_, fields)| fields.iter().all(|field| field.readonly));
+        groups
+    }
+}
+
+impl ModelField {
+    fn display_type(&self) -> Option<&'static str> {
+        match self.widget {
+            "datetime" | "date" => Some("datetime"),
+            "boolean" => Some("boolean"),
+            "number" => Some("number"),
+            _ => None,
+        }
+    }
+}
+/*----- END MODEL SCHEMA -----*/
+
+
+/*----- START SHAPE -----*/
+/// What the JSON schema says about a field once `$ref`s, `Option`'s null branch and
+/// enum `oneOf`s are flattened
+#[derive(Default)]
+struct Shape {
+    types: BTreeSet<String>,
+    format: Option<String>,
+    variants: Vec<String>,
+}
+
+impl Shape {
+    fn of(root: &RootSchema, schema: &Schema) -> Self {
+        let mut shape = Shape::default();
+        shape.collect(root, schema);
+        shape
+    }
+
+    fn collect(&mut self, root: &RootSchema, schema: &Schema) {
+        let Schema::Object(object) = schema else { return };
+
+        if let Some(reference) = &object.reference {
+            let name = reference.trim_start_matches("#/definitions/");
+            if let Some(definition) = root.definitions.get(name) {
+                self.collect(root, definition);
+            }
+            return;
+        }
+
+        match &object.instance_type {
+            Some(SingleOrVec::Single(kind)) => self.add_type(kind),
+            Some(SingleOrVec::Vec(kinds)) => kinds.iter().for_each(|kind| self.add_type(kind)),
+            None => {}
+        }
+        if self.format.is_none() {
+            self.format = object.format.clone();
+        }
+        for value in object.enum_values.iter().flatten() {
+            if let Some(variant) = value.as_str() {
+                self.variants.push(variant.to_string());
+            }
+        }
+
+        if let Some(subschemas) = &object.subschemas {
+            let nested = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+            for schema in nested.into_iter().flatten().flatten() {
+                self.collect(root, schema);
+            }
+        }
+    }
+
+    fn add_type(&mut self, kind: &InstanceType) {
+        if *kind != InstanceType::Null {
+            self.types.insert(format!("{:?}", kind));
+        }
+    }
+
+    fn has(&self, kind: &str) -> bool {
+        self.types.contains(kind)
+    }
+
+    fn widget(&self) -> &'static str {
+        if !self.variants.is_empty() {
+            "select"
+        } else if self.has("Boolean") {
+            "boolean"
+        } else if self.has("Integer") || self.has("Number") {
+            "number"
+        } else if self.format.as_deref() == Some("date-time") {
+            "datetime"
+        } else if self.format.as_deref() == Some("email") {
+            "email"
+        } else if self.types.is_empty() || self.has("Object") {
+            "editor_json"
+        } else {
+            "text"
+        }
+    }
+
+    fn options(&self) -> Option<Vec<Value>> {
+        if !self.variants.is_empty() {
+            Some(self.variants.iter().map(|variant| select_option(variant)).collect())
+        } else if self.has("Boolean") {
+            Some(boolean_options())
+        } else {
+            None
+        }
+    }
+}
+/*----- END SHAPE -----*/
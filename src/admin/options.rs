@@ -0,0 +1,34 @@
+// src/admin/options.rs
+//
+// Select/boolean options for AdminX forms, filters and action UIs. Values always come
+// from serde so a form can never submit something the model fails to deserialize.
+use convert_case::{Casing, Case};
+use serde::Serialize;
+use serde_json::{json, Value};
+use strum::IntoEnumIterator;
+
+/// `{value, label}` for every unit variant of `E`, valued by its serde name.
+/// Data-carrying variants such as `Custom(String)` are left out: they don't serialize
+/// to a plain string, so there is nothing a select could submit for them.
+pub fn enum_options<E>() -> Vec<Value>
+where
+    E: IntoEnumIterator + Serialize,
+{
+    E::iter()
+        .filter_map(|variant| serde_json::to_value(&variant).ok())
+        .filter_map(|value| value.as_str().map(select_option))
+        .collect()
+}
+
+/// Booleans as JSON booleans, never `"true"`/`"false"` strings
+pub fn boolean_options() -> Vec<Value> {
+    vec![
+        json!({ "value": true,  "label": "True"  }),
+        json!({ "value": false, "label": "False" }),
+    ]
+}
+
+/// One option for a serde value, labelled in Title Case (`invite_only` / `InviteOnly` → "Invite Only")
+pub fn select_option(value: &str) -> Value {
+    json!({ "value": value, "label": value.to_case(Case::Title) })
+}
@@ -0,0 +1,65 @@
+// src/admin/pages/account_page.rs
+//
+// The signed-in operator's own account. A reset password lands here on the next
+// sign-in, and the rest of AdminX stays closed until it is replaced.
+use actix_session::Session;
+use actix_web::{web, HttpResponse};
+use adminx::{
+    AdminxConfig,
+    helpers::auth_helper::create_base_template_context_with_auth,
+    utils::auth::extract_claims_from_session,
+};
+use tera::Context;
+
+use crate::admin::pages::render_page;
+use crate::middlewares::admin_two_factor_middleware::PASSWORD_CHANGE_SESSION_KEY;
+use crate::repositories::Repositories;
+use crate::requests::structures::admin_user_structure::{ChangeAdminPasswordRequest, ADMIN_PASSWORD_MIN_LENGTH};
+use crate::services::admin_user_service;
+
+fn password_context(mut ctx: Context, session: &Session) -> Context {
+    let must_change = session.get::<bool>(PASSWORD_CHANGE_SESSION_KEY).ok().flatten() == Some(true);
+    ctx.insert("must_change", &must_change);
+    ctx.insert("min_length", &ADMIN_PASSWORD_MIN_LENGTH);
+    ctx
+}
+
+/// GET /adminx/account/password
+pub async fn password_page(session: Session, config: web::Data<AdminxConfig>) -> HttpResponse {
+    let ctx = match create_base_template_context_with_auth("Change password", "account/password", &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+    render_page("account_password.html.tera", password_context(ctx, &session)).await
+}
+
+/// POST /adminx/account/password
+pub async fn password_action(
+    form: web::Form<ChangeAdminPasswordRequest>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let ctx = match create_base_template_context_with_auth("Change password", "account/password", &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+    let Ok(claims) = extract_claims_from_session(&session, &config).await else {
+        return HttpResponse::Found().append_header(("Location", "/adminx/login")).finish();
+    };
+
+    let changed = match admin_user_service::acting_admin(&repos, &claims.sub).await {
+        Ok(actor) => admin_user_service::change_password(&repos, &actor, form.into_inner()).await,
+        Err(e) => Err(e),
+    };
+    let mut ctx = password_context(ctx, &session);
+    match changed {
+        Ok(()) => {
+            session.remove(PASSWORD_CHANGE_SESSION_KEY);
+            ctx.insert("must_change", &false);
+            ctx.insert("success", "Your password has been changed");
+        }
+        Err(e) => ctx.insert("error", &e.to_string()),
+    }
+    render_page("account_password.html.tera", ctx).await
+}
@@ -0,0 +1,65 @@
+// src/admin/pages/analytics_page.rs
+use actix_session::Session;
+use actix_web::{web, HttpResponse};
+use adminx::{
+    AdminxConfig,
+    helpers::auth_helper::create_base_template_context_with_auth,
+};
+use mongodb::Database;
+
+use crate::admin::options::enum_options;
+use crate::admin::pages::render_page;
+use crate::requests::enums::event_enums::EventCategoryEnum;
+use crate::requests::structures::event_structure::{EventAnalyticsRequest, ANALYTICS_PERIODS};
+use crate::requests::validators::open_validator::validate_params;
+use crate::services::analytics_service;
+
+/// GET /adminx/analytics
+pub async fn event_analytics_page(
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    db: web::Data<Database>,
+    query: web::Query<EventAnalyticsRequest>,
+) -> HttpResponse {
+    let mut ctx = match create_base_template_context_with_auth("Analytics", "analytics", &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+
+    let mut request = query.into_inner();
+    // The dashboard always shows every section
+    request.include_attendee_stats = Some(true);
+    request.include_revenue_stats = Some(true);
+    request.include_engagement_stats = Some(true);
+    request.include_geographic_stats = Some(true);
+
+    let report = match validate_params(&request).await {
+        Ok(()) => analytics_service::event_analytics(&db, &request).await,
+        Err(e) => Err(e),
+    };
+
+    match report {
+        Ok(report) => {
+            // Widest timeline bar, used to scale the others
+            let peak = report["timeline"].as_array()
+                .map(|buckets| buckets.iter().filter_map(|b| b["registrations"].as_f64()).fold(0.0, f64::max))
+                .unwrap_or_default();
+            ctx.insert("timeline_peak", &peak.max(1.0));
+            ctx.insert("report", &report);
+        }
+        Err(e) => ctx.insert("error", &e.to_string()),
+    }
+
+    ctx.insert("period", request.period.as_deref().unwrap_or("month"));
+    ctx.insert("start_date", request.start_date.as_deref().unwrap_or_default());
+    ctx.insert("end_date", request.end_date.as_deref().unwrap_or_default());
+    // The serde name, as stored and as the select submits it
+    let category = request.category.as_ref()
+        .and_then(|c| serde_json::to_value(c).ok())
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default();
+    ctx.insert("category", &category);
+    ctx.insert("periods", &ANALYTICS_PERIODS);
+    ctx.insert("categories", &enum_options::<EventCategoryEnum>());
+    render_page("event_analytics.html.tera", ctx).await
+}
@@ -0,0 +1,100 @@
+// src/admin/pages/event_page.rs
+use actix_session::Session;
+use actix_web::{web, HttpRequest, HttpResponse};
+use adminx::AdminxConfig;
+use mongodb::bson::oid::ObjectId;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::admin::pages::{reference_page::view_context, render_page};
+use crate::admin::resources::event_resource::EventResource;
+use crate::enums::common_enums::StatusEnum;
+use crate::models::event_attendee::EventAttendee;
+use crate::repositories::Repositories;
+
+const ATTENDEES_PER_PAGE: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+pub struct EventViewQuery {
+    pub attendees_page: Option<u64>,
+}
+
+fn format_date(date: Option<mongodb::bson::DateTime>) -> Value {
+    json!(date.map(|date| date.to_chrono().format("%Y-%m-%d %H:%M").to_string()))
+}
+
+fn attendee_row(attendee: &EventAttendee) -> Value {
+    let name = [attendee.first_name.as_deref(), attendee.last_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    json!({
+        "id": attendee.id.map(|id| id.to_hex()),
+        "name": (!name.is_empty()).then_some(name),
+        "email": attendee.email,
+        "registration_status": attendee.registration_status,
+        "waitlisted": attendee.registration_status == StatusEnum::Pending
+            && attendee.waitlisted_at.is_some()
+            && attendee.promoted_at.is_none(),
+        "registration_date": format_date(attendee.registration_date),
+        "check_in_date": format_date(attendee.check_in_date),
+        "check_out_date": format_date(attendee.check_out_date),
+    })
+}
+
+/// GET /adminx/events/view/{id}
+/// The event view plus its attendees: counts, a capacity bar and a paginated list with
+/// check-in/check-out/cancel buttons that post to the EventAttendee custom actions
+pub async fn event_view_page(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<EventViewQuery>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let item_id = path.into_inner();
+    let mut ctx = match view_context::<EventResource>(&req, &item_id, &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(response) => return response,
+    };
+
+    let page = query.attendees_page.unwrap_or(1).max(1);
+    let Ok(event_id) = ObjectId::parse_str(&item_id) else {
+        return HttpResponse::BadRequest().body("Invalid event id");
+    };
+
+    let attendees = async {
+        let counts = repos.attendees.counts_for_event(event_id).await?;
+        let attendees = repos.attendees.list_for_event(event_id, page, ATTENDEES_PER_PAGE).await?;
+        Ok::<_, actix_web::Error>((counts, attendees))
+    };
+    match attendees.await {
+        Ok((counts, attendees)) => {
+            let pages = counts.total.div_ceil(ATTENDEES_PER_PAGE as u64).max(1);
+            ctx.insert("attendee_counts", &counts);
+            ctx.insert("attendees", &attendees.iter().map(attendee_row).collect::<Vec<Value>>());
+            ctx.insert("attendee_pagination", &json!({
+                "current": page,
+                "total": pages,
+                "prev": (page > 1).then(|| page - 1),
+                "next": (page < pages).then(|| page + 1),
+            }));
+
+            // Deleted events have no live record to read capacity from; the list still shows
+            let max_attendees = repos.events.find_by_id(event_id).await.ok().and_then(|event| event.max_attendees);
+            if let Some(max) = max_attendees.filter(|max| *max > 0) {
+                let percent = (counts.registered * 100 / u64::from(max)).min(100);
+                ctx.insert("capacity", &json!({ "max": max, "used": counts.registered, "percent": percent }));
+            }
+        }
+        Err(e) => {
+            ctx.insert("attendees", &Vec::<Value>::new());
+            ctx.insert("attendees_error", &e.to_string());
+        }
+    }
+
+    render_page("event_view.html.tera", ctx).await
+}
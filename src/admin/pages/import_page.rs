@@ -0,0 +1,80 @@
+// src/admin/pages/import_page.rs
+use actix_session::Session;
+use actix_web::{web, HttpRequest, HttpResponse};
+use adminx::{
+    AdmixResource,
+    AdminxConfig,
+    helpers::auth_helper::create_base_template_context_with_auth,
+    helpers::resource_helper::check_resource_permission,
+};
+use serde_json::json;
+
+use crate::admin::pages::render_page;
+use crate::models::{contact::Contact, event_attendee::EventAttendee};
+use crate::repositories::Repositories;
+use crate::services::audit_service::AuditTrail;
+use crate::services::import_service::{self, CsvImport, ImportRequest, RowStatus};
+
+/// Import page of the resource at `base_path`, when it has one
+pub fn import_path(base_path: &str) -> Option<String> {
+    [Contact::BASE_PATH, EventAttendee::BASE_PATH]
+        .contains(&base_path)
+        .then(|| format!("/adminx/{}/import", base_path))
+}
+
+/// GET /adminx/{base_path}/import
+pub async fn import_page<R: AdmixResource + 'static, T: CsvImport + 'static>(
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> HttpResponse {
+    let resource = R::new();
+    let mut ctx = match create_base_template_context_with_auth(resource.resource_name(), resource.base_path(), &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+
+    let fields: Vec<_> = T::FIELDS.iter().map(|field| json!({ "name": field.name, "label": field.label })).collect();
+    ctx.insert("import_title", &format!("Import {}", T::RESOURCE_NAME));
+    ctx.insert("import_fields", &fields);
+    ctx.insert("import_path", &format!("/adminx/{}/import", T::BASE_PATH));
+    ctx.insert("list_path", &format!("/adminx/{}/list", T::BASE_PATH));
+    ctx.insert("scope_target", T::SCOPE);
+    ctx.insert("scope_label", T::SCOPE_LABEL);
+    // Lets the event view link straight to an import for that event
+    ctx.insert("scope_id", query.get("scope_id").map(String::as_str).unwrap_or_default());
+    render_page("import.html.tera", ctx).await
+}
+
+/// POST /adminx/{base_path}/import
+/// Dry run unless `dry_run` is false; answers with an outcome per row
+pub async fn import_rows<R: AdmixResource + 'static, T: CsvImport + 'static>(
+    req: HttpRequest,
+    body: web::Json<ImportRequest>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let resource = R::new();
+    if let Err(response) = check_resource_permission(&session, &config, &resource, "create").await {
+        return response;
+    }
+
+    let report = match import_service::import::<T>(&repos, body.into_inner()).await {
+        Ok(report) => report,
+        Err(e) => return e.error_response(),
+    };
+
+    if !report.dry_run {
+        let imported = report.rows.iter()
+            .filter(|row| row.status == RowStatus::Imported)
+            .filter_map(|row| row.id.clone());
+        if let Some(trail) = AuditTrail::capture(&req, resource.resource_name(), resource.collection_name()) {
+            for id in imported {
+                trail.clone().record("import", Some(id), None);
+            }
+        }
+    }
+
+    HttpResponse::Ok().json(report)
+}
@@ -0,0 +1,55 @@
+// src/admin/pages/mod.rs
+pub mod account_page;
+pub mod analytics_page;
+pub mod event_page;
+pub mod import_page;
+pub mod reference_page;
+pub mod search_page;
+pub mod two_factor_page;
+pub mod user_page;
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use once_cell::sync::Lazy;
+use tera::{Context, Tera};
+use adminx::helpers::template_helper::{ADMINX_TEMPLATES, render_500};
+
+/// AdminX templates plus our own pages, so custom pages can extend `layout.html.tera`
+static PAGE_TEMPLATES: Lazy<Arc<Tera>> = Lazy::new(|| {
+    let mut tera = (**ADMINX_TEMPLATES).clone();
+
+    tera.add_raw_template("account_password.html.tera", include_str!("../templates/account_password.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add account_password.html.tera: {}", e));
+    tera.add_raw_template("event_analytics.html.tera", include_str!("../templates/event_analytics.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add event_analytics.html.tera: {}", e));
+    tera.add_raw_template("event_view.html.tera", include_str!("../templates/event_view.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add event_view.html.tera: {}", e));
+    tera.add_raw_template("import.html.tera", include_str!("../templates/import.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add import.html.tera: {}", e));
+    tera.add_raw_template("resource_list.html.tera", include_str!("../templates/resource_list.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add resource_list.html.tera: {}", e));
+    tera.add_raw_template("search.html.tera", include_str!("../templates/search.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add search.html.tera: {}", e));
+    tera.add_raw_template("two_factor_recovery_codes.html.tera", include_str!("../templates/two_factor_recovery_codes.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add two_factor_recovery_codes.html.tera: {}", e));
+    tera.add_raw_template("two_factor_setup.html.tera", include_str!("../templates/two_factor_setup.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add two_factor_setup.html.tera: {}", e));
+    tera.add_raw_template("two_factor_verify.html.tera", include_str!("../templates/two_factor_verify.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add two_factor_verify.html.tera: {}", e));
+    tera.add_raw_template("user_view.html.tera", include_str!("../templates/user_view.html.tera"))
+        .unwrap_or_else(|e| panic!("Failed to add user_view.html.tera: {}", e));
+
+    tera.autoescape_on(vec![]);
+    Arc::new(tera)
+});
+
+pub async fn render_page(template_name: &str, ctx: Context) -> HttpResponse {
+    match PAGE_TEMPLATES.render(template_name, &ctx) {
+        Ok(html) => HttpResponse::Ok().content_type("text/html").body(html),
+        Err(err) => {
+            log::error!("Template render error for {}: {:?}", template_name, err);
+            render_500(Some(&err.to_string())).await
+        }
+    }
+}
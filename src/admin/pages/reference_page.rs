@@ -0,0 +1,237 @@
+// src/admin/pages/reference_page.rs
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use actix_session::Session;
+use actix_web::{web, HttpRequest, HttpResponse};
+use adminx::{
+    AdmixResource,
+    AdminxConfig,
+    helpers::auth_helper::create_base_template_context_with_auth,
+    helpers::form_helper::{extract_fields_for_form, to_map},
+    helpers::resource_helper::{actions_to_meta, get_default_form_structure, get_default_view_structure, get_filters_data},
+    utils::auth::extract_claims_from_session,
+};
+use mongodb::bson::oid::ObjectId;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tera::Context;
+
+use crate::admin::bulk_actions::{bulk_actions_meta, BulkActions};
+use crate::admin::csv_export::export_csv;
+use crate::admin::pages::{import_page::import_path, render_page};
+use crate::admin::records;
+use crate::admin::references::{self, ReferenceTarget};
+use crate::repositories::Repositories;
+
+#[derive(Debug, Deserialize)]
+pub struct ReferenceSearchQuery {
+    #[serde(default)]
+    pub q: String,
+}
+
+/// GET /adminx/references/{target}?q=
+/// Options for the reference picker
+pub async fn reference_search(
+    path: web::Path<String>,
+    query: web::Query<ReferenceSearchQuery>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    if extract_claims_from_session(&session, &config).await.is_err() {
+        return HttpResponse::Unauthorized().json(json!({"error":"unauthorized"}));
+    }
+    let Some(target) = references::target(&path) else {
+        return HttpResponse::NotFound().json(json!({"error":"unknown_reference_target"}));
+    };
+
+    match references::search(&repos, target, &query.q).await {
+        Ok(options) => HttpResponse::Ok().json(options),
+        Err(e) => HttpResponse::InternalServerError().json(json!({"error":"reference_search_failed","message":e.to_string()})),
+    }
+}
+
+/// Replaces each reference field of `records` with the referenced record's label
+async fn label_references(repos: &Repositories, resource: &Arc<Box<dyn AdmixResource>>, records: &mut [Map<String, Value>]) {
+    let fields: Vec<(String, &'static ReferenceTarget)> = references::reference_fields(resource.form_structure().as_ref());
+    let ids = records.iter()
+        .filter_map(|record| record.get("id").and_then(Value::as_str))
+        .filter_map(|id| ObjectId::parse_str(id).ok())
+        .collect();
+
+    match references::resolve_labels(repos, resource.collection_name(), &fields, ids).await {
+        Ok(labels) => {
+            for record in records.iter_mut() {
+                let Some(record_labels) = record.get("id").and_then(Value::as_str).and_then(|id| labels.get(id)) else {
+                    continue;
+                };
+                for (field, label) in record_labels {
+                    record.insert(field.clone(), json!(label));
+                }
+            }
+        }
+        Err(e) => log::error!("Failed to resolve references for {}: {}", resource.resource_name(), e),
+    }
+}
+
+fn repositories(req: &HttpRequest) -> Result<web::Data<Repositories>, HttpResponse> {
+    Repositories::from_request(req).ok_or_else(|| HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"})))
+}
+
+fn export_failed(e: Box<dyn std::error::Error + Send + Sync>) -> HttpResponse {
+    HttpResponse::InternalServerError().content_type("text/plain").body(format!("Failed to export data: {}", e))
+}
+
+/// GET /adminx/{base_path}/list
+/// The stock AdminX list with reference columns showing the referenced record, row
+/// selection for the resource's bulk actions, and a CSV export that honours the filters
+pub async fn reference_list_page<R: AdmixResource + BulkActions + 'static>(
+    req: HttpRequest,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let resource: Arc<Box<dyn AdmixResource>> = Arc::new(Box::new(R::new()));
+    let mut ctx = match create_base_template_context_with_auth(resource.resource_name(), resource.base_path(), &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+
+    let query_string = req.query_string().to_string();
+    let query_params: HashMap<String, String> = web::Query::from_query(&query_string)
+        .map(web::Query::into_inner)
+        .unwrap_or_default();
+
+    match query_params.get("download").map(String::as_str) {
+        Some("json") => return records::export_json(&repos, &**resource, &query_params).await.unwrap_or_else(export_failed),
+        Some("csv") => return export_csv(&repos, &resource, &query_params).await.unwrap_or_else(export_failed),
+        Some(format) => {
+            return HttpResponse::BadRequest()
+                .content_type("text/plain")
+                .body(format!("Unsupported download format: {}. Supported formats: json, csv", format));
+        }
+        None => {}
+    }
+
+    let toast = match (query_params.get("success").map(String::as_str), query_params.get("error").map(String::as_str)) {
+        (Some("created"), _) => Some(("Successfully created new item!", "success")),
+        (Some("updated"), _) => Some(("Successfully updated item!", "success")),
+        (Some("deleted"), _) => Some(("Successfully deleted item!", "success")),
+        (_, Some("create_failed")) => Some(("Failed to create item. Please try again.", "error")),
+        (_, Some("update_failed")) => Some(("Failed to update item. Please try again.", "error")),
+        (_, Some("delete_failed")) => Some(("Failed to delete item. Please try again.", "error")),
+        _ => None,
+    };
+    if let Some((message, kind)) = toast {
+        ctx.insert("toast_message", message);
+        ctx.insert("toast_type", kind);
+    }
+
+    let (filters, current_filters) = get_filters_data(&resource, &query_params);
+    ctx.insert("filters", &filters);
+    ctx.insert("has_active_filters", &!current_filters.is_empty());
+    ctx.insert("current_filters", &current_filters);
+
+    match records::list_data(&repos, &**resource, &query_params).await {
+        Ok((headers, mut rows, pagination)) => {
+            label_references(&repos, &resource, &mut rows).await;
+            ctx.insert("headers", &headers);
+            ctx.insert("rows", &rows);
+            ctx.insert("pagination", &pagination);
+        }
+        Err(e) => {
+            log::error!("Failed to fetch list data for {}: {}", resource.resource_name(), e);
+            ctx.insert("headers", &Vec::<String>::new());
+            ctx.insert("rows", &Vec::<Value>::new());
+            ctx.insert("pagination", &json!({ "current": 1, "total": 1, "prev": null, "next": null, "filter_params": "" }));
+            ctx.insert("toast_message", "Failed to load data. Please refresh the page.");
+            ctx.insert("toast_type", "error");
+        }
+    }
+
+    ctx.insert("bulk_actions", &bulk_actions_meta(&R::bulk_actions()));
+    ctx.insert("bulk_path", &format!("/adminx/{}/bulk", resource.base_path()));
+    ctx.insert("import_path", &import_path(resource.base_path()));
+    render_page("resource_list.html.tera", ctx).await
+}
+
+/// Context of the stock AdminX view page, with reference fields labelled. Pages that
+/// add their own sections to a view start from this.
+pub async fn view_context<R: AdmixResource + 'static>(
+    req: &HttpRequest,
+    item_id: &str,
+    session: &Session,
+    config: &AdminxConfig,
+) -> Result<Context, HttpResponse> {
+    let resource: Arc<Box<dyn AdmixResource>> = Arc::new(Box::new(R::new()));
+    let mut ctx = create_base_template_context_with_auth(resource.resource_name(), resource.base_path(), session, config).await?;
+
+    let repos = repositories(req)?;
+
+    let record = records::item_data(&repos, &**resource, item_id).await
+        .map_err(|e| HttpResponse::NotFound().body(format!("Item not found: {}", e)))?;
+    let mut records = [record];
+    label_references(&repos, &resource, &mut records).await;
+    let [record] = records;
+
+    if req.query_string().contains("success=updated") {
+        ctx.insert("toast_message", "Successfully updated item!");
+        ctx.insert("toast_type", "success");
+    }
+
+    ctx.insert("view_structure", &resource.view_structure().unwrap_or_else(get_default_view_structure));
+    ctx.insert("item_id", item_id);
+    ctx.insert("record", &record);
+    ctx.insert("base_path", resource.base_path());
+    ctx.insert("actions", &actions_to_meta(resource.custom_actions()));
+    Ok(ctx)
+}
+
+/// GET /adminx/{base_path}/view/{id}
+/// The stock AdminX view with reference fields showing the referenced record
+pub async fn reference_view_page<R: AdmixResource + 'static>(
+    req: HttpRequest,
+    path: web::Path<String>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+) -> HttpResponse {
+    match view_context::<R>(&req, &path, &session, &config).await {
+        Ok(ctx) => render_page("view.html.tera", ctx).await,
+        Err(response) => response,
+    }
+}
+
+/// GET /adminx/{base_path}/edit/{id}
+/// The stock AdminX edit form, filled from the record repository
+pub async fn reference_edit_page<R: AdmixResource + 'static>(
+    path: web::Path<String>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let resource = R::new();
+    let mut ctx = match create_base_template_context_with_auth(resource.resource_name(), resource.base_path(), &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+
+    let item_id = path.into_inner();
+    let record = match records::item_data(&repos, &resource, &item_id).await {
+        Ok(record) => record,
+        Err(e) => {
+            log::error!("Failed to fetch item {} for edit: {}", item_id, e);
+            return HttpResponse::NotFound().body(format!("Item not found: {}", e));
+        }
+    };
+
+    let form = resource.form_structure().unwrap_or_else(get_default_form_structure);
+    ctx.insert("fields", &extract_fields_for_form(&to_map(&form)));
+    ctx.insert("form_structure", &form);
+    ctx.insert("form", &form);
+    ctx.insert("item_id", &item_id);
+    ctx.insert("is_edit_mode", &true);
+    ctx.insert("record", &record);
+    ctx.insert("supports_upload", &resource.supports_file_upload());
+    render_page("edit.html.tera", ctx).await
+}
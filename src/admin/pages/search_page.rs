@@ -0,0 +1,45 @@
+// src/admin/pages/search_page.rs
+use actix_session::Session;
+use actix_web::{web, HttpResponse};
+use adminx::{
+    AdminxConfig,
+    helpers::auth_helper::create_base_template_context_with_auth,
+};
+use mongodb::Database;
+
+use crate::admin::pages::render_page;
+use crate::requests::structures::search_structure::{SearchRequest, SEARCH_COLLECTIONS};
+use crate::requests::validators::open_validator::validate_params;
+use crate::services::search_service;
+
+/// GET /adminx/search
+pub async fn search_page(
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    db: web::Data<Database>,
+    query: web::Query<SearchRequest>,
+) -> HttpResponse {
+    let mut ctx = match create_base_template_context_with_auth("Search", "search", &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+
+    let request = query.into_inner();
+    let has_query = request.query.as_deref().is_some_and(|q| !q.trim().is_empty());
+
+    if has_query {
+        let results = match validate_params(&request).await {
+            Ok(()) => search_service::search(&db, &request).await,
+            Err(e) => Err(e),
+        };
+        match results {
+            Ok(results) => ctx.insert("results", &results),
+            Err(e) => ctx.insert("error", &e.to_string()),
+        }
+    }
+
+    ctx.insert("query", request.query.as_deref().unwrap_or_default());
+    ctx.insert("collection", request.collections.as_deref().unwrap_or_default());
+    ctx.insert("search_collections", &SEARCH_COLLECTIONS);
+    render_page("search.html.tera", ctx).await
+}
@@ -0,0 +1,282 @@
+// src/admin/pages/two_factor_page.rs
+//
+// AdminX sign-in against `admin_users`, with a TOTP step. The password form posts here
+// instead of to AdminX: valid credentials only park a pending login in the session, and
+// the AdminX token, carrying the operator's role, is issued once the operator enters an
+// authenticator or recovery code. Operators without two-factor set up are taken through
+// enrolment first, and those with a reset password on to the password change.
+use std::time::Duration;
+
+use actix_session::Session;
+use actix_web::{web, HttpResponse};
+use adminx::{
+    AdminxConfig,
+    utils::auth::{is_rate_limited, reset_rate_limit},
+    utils::jwt::create_jwt_token,
+    utils::structs::LoginForm,
+};
+use log::{error, info, warn};
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tera::Context;
+
+use crate::admin::pages::render_page;
+use crate::config::constants::ADMIN_LOGIN_PENDING_SECONDS;
+use crate::middlewares::admin_two_factor_middleware::{
+    ADMIN_TOKEN_SESSION_KEY,
+    PASSWORD_CHANGE_PATH,
+    PASSWORD_CHANGE_SESSION_KEY,
+    TWO_FACTOR_SESSION_KEY,
+};
+use crate::repositories::Repositories;
+use crate::services::{admin_user_service, two_factor_service};
+
+/// Session key of a login that passed the password check but not yet the TOTP step
+const PENDING_LOGIN_SESSION_KEY: &str = "adminx_pending_login";
+
+/// Password attempts per email before AdminX's limiter blocks it
+const LOGIN_MAX_ATTEMPTS: u32 = 5;
+
+/// Window of the password attempt limit
+const LOGIN_WINDOW: Duration = Duration::from_secs(900);
+
+#[derive(Serialize, Deserialize)]
+struct PendingLogin {
+    admin_id: String,
+    email: String,
+    role: String,
+    #[serde(default)]
+    must_change_password: bool,
+    started_at: i64,
+}
+
+#[derive(Deserialize)]
+pub struct TwoFactorForm {
+    pub code: String,
+}
+
+fn redirect(location: &str) -> HttpResponse {
+    HttpResponse::Found()
+        .append_header(("Location", location))
+        .finish()
+}
+
+async fn login_error(message: &str) -> HttpResponse {
+    let mut ctx = Context::new();
+    ctx.insert("is_authenticated", &false);
+    ctx.insert("page_title", "Login");
+    ctx.insert("error", message);
+    render_page("login.html.tera", ctx).await
+}
+
+/// The pending login, if there is one and it has not timed out
+fn pending_login(session: &Session) -> Option<PendingLogin> {
+    let pending = session.get::<PendingLogin>(PENDING_LOGIN_SESSION_KEY).ok().flatten()?;
+    if chrono::Utc::now().timestamp() - pending.started_at > ADMIN_LOGIN_PENDING_SECONDS {
+        session.remove(PENDING_LOGIN_SESSION_KEY);
+        return None;
+    }
+    Some(pending)
+}
+
+/// Issues the AdminX token for a login that passed both steps and returns where to go
+/// next. The session id is renewed so a session fixed before sign-in never becomes an
+/// authenticated one.
+async fn complete_login(
+    session: &Session,
+    config: &AdminxConfig,
+    repos: &Repositories,
+    pending: &PendingLogin,
+) -> Result<&'static str, String> {
+    let token = create_jwt_token(&pending.admin_id, &pending.email, &pending.role, config)
+        .map_err(|e| {
+            error!("JWT generation failed for {}: {}", pending.email, e);
+            "Authentication failed - token generation error".to_string()
+        })?;
+
+    session.renew();
+    session.remove(PENDING_LOGIN_SESSION_KEY);
+    session.insert(ADMIN_TOKEN_SESSION_KEY, &token)
+        .and_then(|_| session.insert(TWO_FACTOR_SESSION_KEY, true))
+        .and_then(|_| match pending.must_change_password {
+            true => session.insert(PASSWORD_CHANGE_SESSION_KEY, true),
+            false => Ok(()),
+        })
+        .map_err(|e| {
+            error!("Session insertion failed: {}", e);
+            "Session creation failed".to_string()
+        })?;
+
+    if let Ok(admin_id) = ObjectId::parse_str(&pending.admin_id)
+        && let Err(e) = admin_user_service::record_login(repos, admin_id).await
+    {
+        warn!("Failed to record the login of {}: {}", pending.email, e);
+    }
+    info!("Login successful for: {}", pending.email);
+    Ok(if pending.must_change_password { PASSWORD_CHANGE_PATH } else { "/adminx" })
+}
+
+/// POST /adminx/login
+/// Shadows the AdminX login: checks the password, then hands over to the TOTP step
+pub async fn login_action(
+    form: web::Form<LoginForm>,
+    session: Session,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let email = form.email.trim().to_lowercase();
+    let password = form.password.as_str();
+
+    if email.is_empty() || password.is_empty() {
+        return login_error("Email and password are required").await;
+    }
+    if is_rate_limited(&email, LOGIN_MAX_ATTEMPTS, LOGIN_WINDOW) {
+        warn!("Rate limit exceeded for: {}", email);
+        return login_error("Too many login attempts. Please try again later.").await;
+    }
+
+    let admin = match admin_user_service::authenticate(&repos, &email, password).await {
+        Ok(Some(admin)) => admin,
+        Ok(None) => {
+            warn!("Invalid AdminX credentials for: {}", email);
+            return login_error("Invalid email or password").await;
+        }
+        Err(e) => return login_error(&e.to_string()).await,
+    };
+    let Some(admin_id) = admin.id else {
+        error!("Admin has no ID: {}", email);
+        return login_error("Authentication failed - missing admin ID").await;
+    };
+    reset_rate_limit(&email);
+
+    let enrolled = match two_factor_service::is_enrolled(&repos, &admin.email).await {
+        Ok(enrolled) => enrolled,
+        Err(e) => return login_error(&e.to_string()).await,
+    };
+
+    // Whatever the session held before belongs to nobody now
+    session.clear();
+    session.renew();
+    let pending = PendingLogin {
+        admin_id: admin_id.to_hex(),
+        role: admin.role.as_str().to_string(),
+        must_change_password: admin.must_change_password,
+        email: admin.email,
+        started_at: chrono::Utc::now().timestamp(),
+    };
+    if let Err(e) = session.insert(PENDING_LOGIN_SESSION_KEY, &pending) {
+        error!("Session insertion failed: {}", e);
+        return login_error("Session creation failed").await;
+    }
+
+    redirect(if enrolled { "/adminx/2fa" } else { "/adminx/2fa/setup" })
+}
+
+/// POST /adminx/api/login
+/// Shadows the AdminX JSON login, which would hand out a token without the TOTP step
+pub async fn api_login_action() -> HttpResponse {
+    HttpResponse::Forbidden().json(json!({
+        "error": "two_factor_required",
+        "message": "Sign in through /adminx/login to complete two-factor authentication",
+    }))
+}
+
+/// GET /adminx/2fa/setup
+pub async fn setup_page(session: Session, repos: web::Data<Repositories>) -> HttpResponse {
+    let Some(pending) = pending_login(&session) else {
+        return redirect("/adminx/login");
+    };
+    render_setup(&repos, &pending, None).await
+}
+
+async fn render_setup(repos: &Repositories, pending: &PendingLogin, error: Option<String>) -> HttpResponse {
+    let enrolment = match two_factor_service::enrolment(repos, &pending.email).await {
+        Ok(enrolment) => enrolment,
+        // Set up in the meantime, e.g. from another tab
+        Err(_) if matches!(two_factor_service::is_enrolled(repos, &pending.email).await, Ok(true)) => {
+            return redirect("/adminx/2fa");
+        }
+        Err(e) => return login_error(&e.to_string()).await,
+    };
+
+    let mut ctx = Context::new();
+    ctx.insert("is_authenticated", &false);
+    ctx.insert("page_title", "Set up two-factor authentication");
+    ctx.insert("email", &pending.email);
+    ctx.insert("secret", &enrolment.secret);
+    ctx.insert("uri", &enrolment.uri);
+    ctx.insert("qr_svg", &enrolment.qr_svg);
+    if let Some(error) = error {
+        ctx.insert("error", &error);
+    }
+    render_page("two_factor_setup.html.tera", ctx).await
+}
+
+/// POST /adminx/2fa/setup
+/// Confirms the authenticator with its first code and shows the recovery codes, once
+pub async fn setup_action(
+    form: web::Form<TwoFactorForm>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let Some(pending) = pending_login(&session) else {
+        return redirect("/adminx/login");
+    };
+
+    let recovery_codes = match two_factor_service::confirm_enrolment(&repos, &pending.email, &form.code).await {
+        Ok(codes) => codes,
+        Err(e) => return render_setup(&repos, &pending, Some(e.to_string())).await,
+    };
+    let next = match complete_login(&session, &config, &repos, &pending).await {
+        Ok(next) => next,
+        Err(message) => return login_error(&message).await,
+    };
+
+    let mut ctx = Context::new();
+    ctx.insert("is_authenticated", &false);
+    ctx.insert("page_title", "Recovery codes");
+    ctx.insert("recovery_codes", &recovery_codes);
+    ctx.insert("next", next);
+    render_page("two_factor_recovery_codes.html.tera", ctx).await
+}
+
+/// GET /adminx/2fa
+pub async fn verify_page(session: Session) -> HttpResponse {
+    let Some(pending) = pending_login(&session) else {
+        return redirect("/adminx/login");
+    };
+    render_verify(&pending, None).await
+}
+
+async fn render_verify(pending: &PendingLogin, error: Option<String>) -> HttpResponse {
+    let mut ctx = Context::new();
+    ctx.insert("is_authenticated", &false);
+    ctx.insert("page_title", "Two-factor authentication");
+    ctx.insert("email", &pending.email);
+    if let Some(error) = error {
+        ctx.insert("error", &error);
+    }
+    render_page("two_factor_verify.html.tera", ctx).await
+}
+
+/// POST /adminx/2fa
+/// Accepts an authenticator or recovery code and finishes the sign-in
+pub async fn verify_action(
+    form: web::Form<TwoFactorForm>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let Some(pending) = pending_login(&session) else {
+        return redirect("/adminx/login");
+    };
+
+    if let Err(e) = two_factor_service::verify(&repos, &pending.email, &form.code).await {
+        return render_verify(&pending, Some(e.to_string())).await;
+    }
+    match complete_login(&session, &config, &repos, &pending).await {
+        Ok(next) => redirect(next),
+        Err(message) => login_error(&message).await,
+    }
+}
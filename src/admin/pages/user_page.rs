@@ -0,0 +1,82 @@
+// src/admin/pages/user_page.rs
+use actix_session::Session;
+use actix_web::{web, HttpRequest, HttpResponse};
+use adminx::{
+    AdmixResource,
+    AdminxConfig,
+    helpers::auth_helper::create_base_template_context_with_auth,
+    helpers::resource_helper::{actions_to_meta, get_default_view_structure},
+};
+use mongodb::bson::oid::ObjectId;
+use serde_json::{json, Value};
+
+use crate::admin::pages::render_page;
+use crate::admin::records;
+use crate::admin::resources::user_resource::UserResource;
+use crate::models::user_ban::UserBan;
+use crate::repositories::Repositories;
+
+fn format_date(date: Option<mongodb::bson::DateTime>) -> Value {
+    json!(date.map(|date| date.to_chrono().format("%Y-%m-%d %H:%M").to_string()))
+}
+
+fn ban_row(ban: &UserBan) -> Value {
+    json!({
+        "reason": ban.reason,
+        "ban_type": ban.ban_type,
+        "duration_days": ban.duration_days,
+        "issued_by": ban.issued_by,
+        "created_at": format_date(Some(ban.created_at)),
+        "expires_at": format_date(ban.expires_at),
+        "active": ban.active,
+        "lifted_at": format_date(ban.lifted_at),
+        "lifted_by": ban.lifted_by,
+        "lift_reason": ban.lift_reason,
+    })
+}
+
+/// GET /adminx/users/view/{id}
+/// The stock AdminX view plus the user's ban history
+pub async fn user_view_page(
+    req: HttpRequest,
+    path: web::Path<String>,
+    session: Session,
+    config: web::Data<AdminxConfig>,
+    repos: web::Data<Repositories>,
+) -> HttpResponse {
+    let mut ctx = match create_base_template_context_with_auth("Users", "users", &session, &config).await {
+        Ok(ctx) => ctx,
+        Err(redirect) => return redirect,
+    };
+
+    let item_id = path.into_inner();
+    let resource = UserResource::new();
+    let record = match records::item_data(&repos, &resource, &item_id).await {
+        Ok(record) => record,
+        Err(e) => return HttpResponse::NotFound().body(format!("Item not found: {}", e)),
+    };
+
+    if req.query_string().contains("success=updated") {
+        ctx.insert("toast_message", "Successfully updated item!");
+        ctx.insert("toast_type", "success");
+    }
+
+    let bans = match ObjectId::parse_str(&item_id) {
+        Ok(user_id) => repos.bans.history(user_id).await,
+        Err(_) => Ok(Vec::new()),
+    };
+    match bans {
+        Ok(bans) => ctx.insert("bans", &bans.iter().map(ban_row).collect::<Vec<Value>>()),
+        Err(e) => {
+            ctx.insert("bans", &Vec::<Value>::new());
+            ctx.insert("bans_error", &e.to_string());
+        }
+    }
+
+    ctx.insert("view_structure", &resource.view_structure().unwrap_or_else(get_default_view_structure));
+    ctx.insert("item_id", &item_id);
+    ctx.insert("record", &record);
+    ctx.insert("base_path", resource.base_path());
+    ctx.insert("actions", &actions_to_meta(resource.custom_actions()));
+    render_page("user_view.html.tera", ctx).await
+}
@@ -0,0 +1,555 @@
+// src/admin/records.rs
+//
+// The stock AdminX reads and writes over `Repositories::records` rather than the global
+// collection behind `get_collection()`: the JSON CRUD the resources delegate to, and the
+// list, view, edit and export data of the pages that shadow the stock ones. Responses are
+// those of the AdminX defaults they replace.
+use std::collections::{HashMap, HashSet};
+
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+use adminx::{
+    AdmixResource,
+    error::AdminxError,
+    filters::parse_query,
+    helpers::resource_helper::convert_form_data_to_json,
+    pagination::PaginatedResponse,
+};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use mongodb::bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use serde_json::{json, Map, Value};
+
+use crate::admin::csv_export::{export_query, list_filter};
+use crate::repositories::{Repositories, record_repository::RecordQuery};
+
+/// Rows per list page when the query string has no `per_page`
+const DEFAULT_PER_PAGE: u64 = 10;
+
+type PageError = Box<dyn std::error::Error + Send + Sync>;
+
+
+/*----- START CRUD -----*/
+/// What a CRUD future needs from the resource and the request, taken before it starts:
+/// AdminX futures are `Send + 'static`, so they can hold neither
+struct Target {
+    repos: Option<web::Data<Repositories>>,
+    collection: &'static str,
+    name: &'static str,
+    permitted: HashSet<&'static str>,
+}
+
+impl Target {
+    fn of(resource: &dyn AdmixResource, req: &HttpRequest) -> Self {
+        Target {
+            repos: Repositories::from_request(req),
+            collection: resource.collection_name(),
+            name: resource.resource_name(),
+            permitted: resource.permit_keys().into_iter().collect(),
+        }
+    }
+
+    fn failed(&self, operation: &str, e: actix_web::Error) -> HttpResponse {
+        log::error!("Failed to {} {}: {}", operation, self.name, e);
+        AdminxError::InternalError.error_response()
+    }
+
+    /// The permitted fields of a form payload
+    fn fields(&self, payload: Value) -> Result<Document, HttpResponse> {
+        let Value::Object(map) = payload else {
+            return Ok(Document::new());
+        };
+        let permitted: Map<String, Value> = map.into_iter().filter(|(key, _)| self.permitted.contains(key.as_str())).collect();
+        mongodb::bson::to_document(&permitted).map_err(|_| AdminxError::BadRequest("Invalid input data".into()).error_response())
+    }
+
+    async fn list(self, query: String, projection: Option<Document>) -> HttpResponse {
+        let Some(repos) = &self.repos else {
+            return repositories_missing();
+        };
+
+        let opts = parse_query(&query);
+        let per_page = opts.limit.max(1);
+        let total = match repos.records.count(self.collection, opts.filter.clone()).await {
+            Ok(total) => total,
+            Err(e) => return self.failed("count", e),
+        };
+
+        let query = RecordQuery { sort: opts.sort, skip: opts.skip, limit: Some(per_page as i64), projection };
+        match repos.records.find(self.collection, opts.filter, query).await {
+            Ok(data) => HttpResponse::Ok().json(PaginatedResponse { data, total, page: (opts.skip / per_page) + 1, per_page }),
+            Err(e) => self.failed("list", e),
+        }
+    }
+
+    async fn get(self, id: String, projection: Option<Document>) -> HttpResponse {
+        let Some(repos) = &self.repos else {
+            return repositories_missing();
+        };
+        let Ok(id) = ObjectId::parse_str(&id) else {
+            return AdminxError::BadRequest("Invalid ID format".into()).error_response();
+        };
+
+        let query = RecordQuery { limit: Some(1), projection, ..RecordQuery::default() };
+        match repos.records.find(self.collection, doc! { "_id": id }, query).await {
+            Ok(records) => match records.into_iter().next() {
+                Some(record) => HttpResponse::Ok().json(record),
+                None => AdminxError::NotFound.error_response(),
+            },
+            Err(e) => self.failed("get", e),
+        }
+    }
+
+    async fn insert(self, payload: Value) -> HttpResponse {
+        let Some(repos) = &self.repos else {
+            return repositories_missing();
+        };
+        let mut record = match self.fields(payload) {
+            Ok(record) => record,
+            Err(response) => return response,
+        };
+
+        let now = BsonDateTime::now();
+        record.insert("created_at", now);
+        record.insert("updated_at", now);
+        if self.permitted.contains("deleted") && !record.contains_key("deleted") {
+            record.insert("deleted", false);
+        }
+
+        match repos.records.insert_one(self.collection, record).await {
+            Ok(id) => HttpResponse::Created().json(json!({
+                "success": true,
+                "message": format!("{} created successfully", self.name),
+                "id": id
+            })),
+            Err(e) => self.failed("create", e),
+        }
+    }
+
+    async fn set(self, id: String, payload: Value) -> HttpResponse {
+        let Some(repos) = &self.repos else {
+            return repositories_missing();
+        };
+        let Ok(id) = ObjectId::parse_str(&id) else {
+            return AdminxError::BadRequest("Invalid ID format".into()).error_response();
+        };
+        let mut fields = match self.fields(payload) {
+            Ok(fields) => fields,
+            Err(response) => return response,
+        };
+        fields.insert("updated_at", BsonDateTime::now());
+
+        match repos.records.update_one(self.collection, doc! { "_id": id }, doc! { "$set": fields }).await {
+            Ok(counts) if counts.modified_count > 0 => HttpResponse::Ok().json(json!({
+                "success": true,
+                "message": format!("{} updated successfully", self.name),
+                "modified_count": counts.modified_count
+            })),
+            Ok(_) => AdminxError::NotFound.error_response(),
+            Err(e) => self.failed("update", e),
+        }
+    }
+
+    /// Soft deletes when the resource has a `deleted` field, otherwise removes the record
+    async fn remove(self, id: String) -> HttpResponse {
+        let Some(repos) = &self.repos else {
+            return repositories_missing();
+        };
+        let Ok(id) = ObjectId::parse_str(&id) else {
+            return AdminxError::BadRequest("Invalid ID format".into()).error_response();
+        };
+
+        if self.permitted.contains("deleted") {
+            let update = doc! { "$set": { "deleted": true, "updated_at": BsonDateTime::now() } };
+            return match repos.records.update_one(self.collection, doc! { "_id": id }, update).await {
+                Ok(counts) if counts.modified_count > 0 => HttpResponse::Ok().json(json!({
+                    "success": true,
+                    "message": format!("{} deleted successfully", self.name),
+                    "soft_delete": true,
+                    "modified_count": counts.modified_count
+                })),
+                Ok(_) => AdminxError::NotFound.error_response(),
+                Err(e) => self.failed("delete", e),
+            };
+        }
+
+        match repos.records.delete_one(self.collection, doc! { "_id": id }).await {
+            Ok(deleted_count) if deleted_count > 0 => HttpResponse::Ok().json(json!({
+                "success": true,
+                "message": format!("{} deleted successfully", self.name),
+                "soft_delete": false,
+                "deleted_count": deleted_count
+            })),
+            Ok(_) => AdminxError::NotFound.error_response(),
+            Err(e) => self.failed("delete", e),
+        }
+    }
+}
+
+fn repositories_missing() -> HttpResponse {
+    HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}))
+}
+
+/// Runs the resource's upload handler on each non-empty file, adding what it returns to the form
+async fn upload(
+    resource: &dyn AdmixResource,
+    mut form_data: HashMap<String, String>,
+    files: HashMap<String, (String, Vec<u8>)>,
+) -> Result<HashMap<String, String>, AdminxError> {
+    for (field_name, (filename, file_data)) in files {
+        if file_data.is_empty() {
+            continue;
+        }
+        match resource.process_file_upload(&field_name, &file_data, &filename).await {
+            Ok(uploaded) => form_data.extend(uploaded),
+            Err(e) => {
+                log::error!("File upload failed for field {}: {:?}", field_name, e);
+                return Err(e);
+            }
+        }
+    }
+    Ok(form_data)
+}
+
+fn upload_failed(e: AdminxError) -> HttpResponse {
+    HttpResponse::BadRequest().json(json!({ "error": format!("File upload failed: {:?}", e) }))
+}
+
+pub fn list(resource: &dyn AdmixResource, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+    list_with(resource, req, query, None)
+}
+
+/// `list` returning only the fields `projection` lets through
+pub fn list_with(resource: &dyn AdmixResource, req: &HttpRequest, query: String, projection: Option<Document>) -> BoxFuture<'static, HttpResponse> {
+    Box::pin(Target::of(resource, req).list(query, projection))
+}
+
+pub fn get(resource: &dyn AdmixResource, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+    get_with(resource, req, id, None)
+}
+
+/// `get` returning only the fields `projection` lets through
+pub fn get_with(resource: &dyn AdmixResource, req: &HttpRequest, id: String, projection: Option<Document>) -> BoxFuture<'static, HttpResponse> {
+    Box::pin(Target::of(resource, req).get(id, projection))
+}
+
+pub fn create(resource: &dyn AdmixResource, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+    Box::pin(Target::of(resource, req).insert(payload))
+}
+
+pub fn update(resource: &dyn AdmixResource, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+    Box::pin(Target::of(resource, req).set(id, payload))
+}
+
+pub fn delete(resource: &dyn AdmixResource, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+    Box::pin(Target::of(resource, req).remove(id))
+}
+
+// The AdminX *_with_files go through `create`/`update` with a synthetic request, which
+// has no repositories; these keep the real one.
+pub fn create_with_files(
+    resource: &dyn AdmixResource,
+    req: &HttpRequest,
+    form_data: HashMap<String, String>,
+    files: HashMap<String, (String, Vec<u8>)>,
+) -> BoxFuture<'static, HttpResponse> {
+    let target = Target::of(resource, req);
+    let resource = resource.clone_box();
+    Box::pin(async move {
+        match upload(resource.as_ref(), form_data, files).await {
+            Ok(form_data) => target.insert(convert_form_data_to_json(form_data)).await,
+            Err(e) => upload_failed(e),
+        }
+    })
+}
+
+pub fn update_with_files(
+    resource: &dyn AdmixResource,
+    req: &HttpRequest,
+    id: String,
+    form_data: HashMap<String, String>,
+    files: HashMap<String, (String, Vec<u8>)>,
+) -> BoxFuture<'static, HttpResponse> {
+    let target = Target::of(resource, req);
+    let resource = resource.clone_box();
+    Box::pin(async move {
+        match upload(resource.as_ref(), form_data, files).await {
+            Ok(form_data) => target.set(id, convert_form_data_to_json(form_data)).await,
+            Err(e) => upload_failed(e),
+        }
+    })
+}
+/*----- END CRUD -----*/
+
+
+/*----- START PAGES -----*/
+/// Columns of the list page: the resource's list structure, or its permitted fields
+fn list_columns(resource: &dyn AdmixResource) -> Vec<String> {
+    let declared = resource.list_structure()
+        .and_then(|structure| structure.get("columns").and_then(Value::as_array).cloned())
+        .map(|columns| columns.iter().filter_map(|column| column.get("field").and_then(Value::as_str)).map(str::to_string).collect());
+
+    declared.unwrap_or_else(|| {
+        std::iter::once("id".to_string())
+            .chain(resource.permit_keys().into_iter().filter(|field| !["_id", "created_at", "updated_at"].contains(field)).map(str::to_string))
+            .chain(std::iter::once("created_at".to_string()))
+            .collect()
+    })
+}
+
+/// A value as the stock list and view pages show it
+fn display(value: &Bson) -> String {
+    match value {
+        Bson::Null | Bson::Undefined => String::new(),
+        Bson::String(text) => text.clone(),
+        Bson::ObjectId(id) => id.to_hex(),
+        Bson::Boolean(flag) => flag.to_string(),
+        Bson::Int32(number) => number.to_string(),
+        Bson::Int64(number) => number.to_string(),
+        Bson::Double(number) => number.to_string(),
+        Bson::DateTime(date) => date.to_chrono().format("%Y-%m-%d %H:%M:%S").to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// A value as the JSON export writes it
+fn exported(value: &Bson) -> Value {
+    match value {
+        Bson::ObjectId(id) => Value::String(id.to_hex()),
+        Bson::DateTime(date) => Value::String(date.to_chrono().to_rfc3339()),
+        other => other.clone().into_relaxed_extjson(),
+    }
+}
+
+fn record_id(record: &Document) -> Option<String> {
+    record.get_object_id("_id").ok().map(|id| id.to_hex())
+}
+
+/// Headers, rows and pagination of a list page, filtered by the resource's declared filters
+pub async fn list_data(
+    repos: &Repositories,
+    resource: &dyn AdmixResource,
+    params: &HashMap<String, String>,
+) -> Result<(Vec<String>, Vec<Map<String, Value>>, Value), PageError> {
+    let number = |key: &str| params.get(key).and_then(|value| value.parse::<u64>().ok()).filter(|value| *value > 0);
+    let page = number("page").unwrap_or(1);
+    let per_page = number("per_page").unwrap_or(DEFAULT_PER_PAGE);
+
+    let filter = list_filter(resource, params);
+    let total = repos.records.count(resource.collection_name(), filter.clone()).await.map_err(|e| e.to_string())?;
+    let query = RecordQuery {
+        sort: Some(doc! { "created_at": -1 }),
+        skip: (page - 1) * per_page,
+        limit: Some(per_page as i64),
+        projection: None,
+    };
+    let records = repos.records.find(resource.collection_name(), filter, query).await.map_err(|e| e.to_string())?;
+
+    let columns = list_columns(resource);
+    let rows = records.iter()
+        .map(|record| {
+            let mut row = Map::new();
+            if let Some(id) = record_id(record) {
+                row.insert("id".to_string(), Value::String(id));
+            }
+            for column in columns.iter().filter(|column| *column != "id") {
+                let cell = record.get(column).map(display).unwrap_or_else(|| "N/A".to_string());
+                row.insert(column.clone(), Value::String(cell));
+            }
+            row
+        })
+        .collect();
+
+    let total_pages = total.div_ceil(per_page);
+    let mut filter_params: Vec<String> = params.iter()
+        .filter(|(key, value)| *key != "page" && !value.is_empty())
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect();
+    filter_params.sort();
+    let pagination = json!({
+        "current": page,
+        "total": total_pages,
+        "prev": (page > 1).then(|| page - 1),
+        "next": (page < total_pages).then(|| page + 1),
+        "filter_params": filter_params.iter().map(|param| format!("&{}", param)).collect::<String>()
+    });
+
+    Ok((columns, rows, pagination))
+}
+
+/// The permitted fields and timestamps of one record, for the view and edit pages
+pub async fn item_data(repos: &Repositories, resource: &dyn AdmixResource, id: &str) -> Result<Map<String, Value>, PageError> {
+    let id = ObjectId::parse_str(id).map_err(|e| format!("Invalid ObjectId: {}", e))?;
+    let record = repos.records.find_one(resource.collection_name(), doc! { "_id": id }).await
+        .map_err(|e| e.to_string())?
+        .ok_or("Document not found")?;
+
+    let mut item = Map::new();
+    if let Some(id) = record_id(&record) {
+        item.insert("id".to_string(), Value::String(id));
+    }
+    for field in resource.permit_keys().into_iter().chain(["created_at", "updated_at"]) {
+        if let Some(value) = record.get(field) {
+            item.insert(field.to_string(), Value::String(display(value)));
+        }
+    }
+    Ok(item)
+}
+
+/// GET /adminx/{base_path}/list?download=json
+/// The filtered list as JSON: the current page, or every match with `complete=true`
+pub async fn export_json(repos: &Repositories, resource: &dyn AdmixResource, params: &HashMap<String, String>) -> Result<HttpResponse, PageError> {
+    let query = export_query(params);
+    let complete = query.limit.is_none();
+    let (skip, per_page) = (query.skip, query.limit.unwrap_or_default() as u64);
+    let records = repos.records.find(resource.collection_name(), list_filter(resource, params), query).await
+        .map_err(|e| e.to_string())?;
+
+    let data: Vec<Value> = records.iter()
+        .map(|record| {
+            let mut item = Map::new();
+            if let Some(id) = record_id(record) {
+                item.insert("id".to_string(), Value::String(id));
+            }
+            for field in resource.permit_keys().into_iter().chain(["created_at", "updated_at"]) {
+                if let Some(value) = record.get(field) {
+                    item.insert(field.to_string(), exported(value));
+                }
+            }
+            Value::Object(item)
+        })
+        .collect();
+
+    let now = Utc::now();
+    let (export, filename) = if complete {
+        (
+            json!({ "data": data, "total": data.len(), "exported_at": now.to_rfc3339(), "resource": resource.resource_name(), "export_type": "complete" }),
+            format!("{}_{}_complete.json", resource.resource_name(), now.format("%Y%m%d_%H%M%S")),
+        )
+    } else {
+        let page = skip / per_page.max(1) + 1;
+        (
+            json!({
+                "data": data,
+                "total": data.len(),
+                "exported_at": now.to_rfc3339(),
+                "resource": resource.resource_name(),
+                "export_type": "paginated",
+                "page": page,
+                "per_page": per_page
+            }),
+            format!("{}_page{}_{}.json", resource.resource_name(), page, now.format("%Y%m%d_%H%M%S")),
+        )
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/json")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", filename)))
+        .body(serde_json::to_string_pretty(&export)?))
+}
+/*----- END PAGES -----*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, http::StatusCode, test};
+    use crate::admin::resources::contact_resource::ContactResource;
+
+    // The resource routes AdminX registers, over an in-memory store
+    fn contacts_app(repos: web::Data<Repositories>) -> App<impl actix_web::dev::ServiceFactory<
+        actix_web::dev::ServiceRequest,
+        Config = (),
+        Response = actix_web::dev::ServiceResponse,
+        Error = actix_web::Error,
+        InitError = (),
+    >> {
+        App::new()
+            .app_data(repos)
+            .route("/contacts", web::get().to(|req: HttpRequest| ContactResource::new().list(&req, req.query_string().to_string())))
+            .route("/contacts", web::post().to(|req: HttpRequest, body: web::Json<Value>| ContactResource::new().create(&req, body.into_inner())))
+            .route("/contacts/{id}", web::get().to(|req: HttpRequest, id: web::Path<String>| ContactResource::new().get(&req, id.into_inner())))
+            .route("/contacts/{id}", web::put().to(|req: HttpRequest, id: web::Path<String>, body: web::Json<Value>| {
+                ContactResource::new().update(&req, id.into_inner(), body.into_inner())
+            }))
+            .route("/contacts/{id}", web::delete().to(|req: HttpRequest, id: web::Path<String>| ContactResource::new().delete(&req, id.into_inner())))
+    }
+
+    #[actix_web::test]
+    async fn crud_goes_through_the_record_repository() {
+        let repos = web::Data::new(Repositories::in_memory());
+        let app = test::init_service(contacts_app(repos.clone())).await;
+
+        let created: Value = test::call_and_read_body_json(&app, test::TestRequest::post()
+            .uri("/contacts")
+            .set_json(json!({ "first_name": "Ada", "email": "ada@example.com", "not_a_field": "dropped" }))
+            .to_request()).await;
+        let id = created["id"]["$oid"].as_str().expect("inserted id").to_string();
+
+        let record = repos.records.find_one("Contacts", doc! { "_id": ObjectId::parse_str(&id).unwrap() }).await.unwrap().expect("stored");
+        assert_eq!(record.get_str("first_name").unwrap(), "Ada");
+        assert!(!record.contains_key("not_a_field"));
+        assert!(!record.get_bool("deleted").unwrap());
+
+        let response = test::call_service(&app, test::TestRequest::put()
+            .uri(&format!("/contacts/{}", id))
+            .set_json(json!({ "first_name": "Grace" }))
+            .to_request()).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let fetched: Value = test::call_and_read_body_json(&app, test::TestRequest::get().uri(&format!("/contacts/{}", id)).to_request()).await;
+        assert_eq!(fetched["first_name"], "Grace");
+
+        let listed: Value = test::call_and_read_body_json(&app, test::TestRequest::get().uri("/contacts").to_request()).await;
+        assert_eq!(listed["total"], 1);
+
+        let deleted: Value = test::call_and_read_body_json(&app, test::TestRequest::delete().uri(&format!("/contacts/{}", id)).to_request()).await;
+        assert_eq!(deleted["soft_delete"], true);
+        let record = repos.records.find_one("Contacts", doc! { "_id": ObjectId::parse_str(&id).unwrap() }).await.unwrap().expect("kept");
+        assert!(record.get_bool("deleted").unwrap());
+    }
+
+    #[actix_web::test]
+    async fn unknown_and_malformed_ids_are_rejected() {
+        let app = test::init_service(contacts_app(web::Data::new(Repositories::in_memory()))).await;
+
+        let response = test::call_service(&app, test::TestRequest::get().uri("/contacts/not-an-id").to_request()).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let missing = ObjectId::new().to_hex();
+        let response = test::call_service(&app, test::TestRequest::put()
+            .uri(&format!("/contacts/{}", missing))
+            .set_json(json!({ "first_name": "Nobody" }))
+            .to_request()).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn crud_without_repositories_fails() {
+        let req = test::TestRequest::default().to_http_request();
+        let response = ContactResource::new().list(&req, String::new()).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[actix_web::test]
+    async fn list_data_pages_and_stringifies_rows() {
+        let repos = Repositories::in_memory();
+        for name in ["Ada", "Grace", "Linus"] {
+            repos.records.insert_one("Contacts", doc! { "first_name": name, "deleted": false, "created_at": BsonDateTime::now() }).await.unwrap();
+        }
+
+        let params = HashMap::from([("per_page".to_string(), "2".to_string()), ("page".to_string(), "2".to_string())]);
+        let (headers, rows, pagination) = list_data(&repos, &ContactResource::new(), &params).await.expect("list data");
+
+        assert_eq!(headers, list_columns(&ContactResource::new()));
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].contains_key("id"));
+        assert_eq!(pagination["current"], 2);
+        assert_eq!(pagination["total"], 2);
+        assert_eq!(pagination["prev"], 1);
+        assert_eq!(pagination["filter_params"], "&per_page=2");
+
+        let id = rows[0]["id"].as_str().unwrap();
+        let item = item_data(&repos, &ContactResource::new(), id).await.expect("item data");
+        assert_eq!(item["first_name"], rows[0]["first_name"]);
+    }
+}
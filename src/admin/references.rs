@@ -0,0 +1,314 @@
+// src/admin/references.rs
+//
+// `#[adminx(reference = "users")]` on an ObjectId field makes it a reference: the form
+// gets a searchable picker, list and view pages show the referenced record's label
+// instead of a raw id, and saves pointing at a missing or deleted record are rejected.
+use std::collections::HashMap;
+
+use actix_web::{web, Error, HttpResponse};
+use futures::future::BoxFuture;
+use mongodb::bson::{doc, oid::ObjectId, Bson, Document};
+use serde_json::{json, Value};
+
+use crate::repositories::{Repositories, record_repository::RecordQuery};
+
+/// `field_type` of reference fields. The stock AdminX form renders unknown types as a
+/// text input, which `picker_html` then turns into an autocomplete.
+pub const REFERENCE_WIDGET: &str = "reference";
+
+const SEARCH_LIMIT: i64 = 20;
+
+
+/*----- START TARGETS -----*/
+/// A collection reference fields can point at. `label_fields` are joined to label a
+/// record, `detail_field` is added in parentheses to tell namesakes apart.
+pub struct ReferenceTarget {
+    pub name: &'static str,
+    pub collection: &'static str,
+    label_fields: &'static [&'static str],
+    detail_field: Option<&'static str>,
+}
+
+const REFERENCE_TARGETS: [ReferenceTarget; 4] = [
+    ReferenceTarget {
+        name: "users",
+        collection: "users",
+        label_fields: &["first_name", "last_name"],
+        detail_field: Some("email"),
+    },
+    ReferenceTarget {
+        name: "contacts",
+        collection: "Contacts",
+        label_fields: &["first_name", "last_name"],
+        detail_field: Some("email"),
+    },
+    ReferenceTarget {
+        name: "events",
+        collection: "events",
+        label_fields: &["title"],
+        detail_field: None,
+    },
+    ReferenceTarget {
+        name: "event_attendees",
+        collection: "event_attendees",
+        label_fields: &["first_name", "last_name"],
+        detail_field: Some("email"),
+    },
+];
+
+pub fn target(name: &str) -> Option<&'static ReferenceTarget> {
+    REFERENCE_TARGETS.iter().find(|target| target.name == name)
+}
+
+impl ReferenceTarget {
+    fn search_fields(&self) -> impl Iterator<Item = &'static str> {
+        self.label_fields.iter().copied().chain(self.detail_field)
+    }
+
+    fn projection(&self) -> Document {
+        self.search_fields().map(|field| (field.to_string(), Bson::Int32(1))).collect()
+    }
+
+    /// "Jane Doe (jane@example.com)", falling back to the id when nothing is set
+    pub fn label(&self, record: &Document) -> String {
+        let text = |field: &str| record.get_str(field).ok().map(str::trim).filter(|value| !value.is_empty());
+
+        let name = self.label_fields.iter().filter_map(|field| text(field)).collect::<Vec<_>>().join(" ");
+        let detail = self.detail_field.and_then(text);
+        match (name.is_empty(), detail) {
+            (false, Some(detail)) => format!("{} ({})", name, detail),
+            (false, None) => name,
+            (true, Some(detail)) => detail.to_string(),
+            (true, None) => record.get_object_id("_id").map(|id| id.to_hex()).unwrap_or_default(),
+        }
+    }
+}
+/*----- END TARGETS -----*/
+
+
+/*----- START PICKER -----*/
+/// Autocomplete for a reference input, delivered through the field's `help_text`
+/// (AdminX renders it unescaped). It attaches a datalist that is filled from
+/// `GET /adminx/references/{target}?q=` as the admin types.
+pub fn picker_html(field: &str, target: &str) -> String {
+    format!(
+        r#"<datalist id="{field}-references"></datalist>
+<script>
+(function () {{
+  var input = document.getElementById("{field}");
+  var list = document.getElementById("{field}-references");
+  if (!input || !list) return;
+  input.value = input.value.replace(/^ObjectId\("([0-9a-f]{{24}})"\)$/, "$1");
+  input.setAttribute("list", list.id);
+  input.setAttribute("autocomplete", "off");
+  input.placeholder = "Search {target}...";
+  var timer;
+  input.addEventListener("input", function () {{
+    clearTimeout(timer);
+    timer = setTimeout(function () {{
+      fetch("/adminx/references/{target}?q=" + encodeURIComponent(input.value))
+        .then(function (res) {{ return res.ok ? res.json() : []; }})
+        .then(function (items) {{
+          list.innerHTML = "";
+          items.forEach(function (item) {{
+            var option = document.createElement("option");
+            option.value = item.value;
+            option.textContent = item.label;
+            list.appendChild(option);
+          }});
+        }});
+    }}, 250);
+  }});
+}})();
+</script>"#
+    )
+}
+
+/// Up to `SEARCH_LIMIT` live records of `target` whose label fields contain `query`,
+/// or the record itself when `query` is an id
+pub async fn search(repos: &Repositories, target: &ReferenceTarget, query: &str) -> Result<Vec<Value>, Error> {
+    let query = query.trim();
+    let mut filter = doc! { "deleted": { "$ne": true } };
+    if let Some(id) = parse_id(query) {
+        filter.insert("_id", id);
+    } else if !query.is_empty() {
+        let pattern = regex::escape(query);
+        let any_of: Vec<Document> = target.search_fields()
+            .map(|field| doc! { field: { "$regex": &pattern, "$options": "i" } })
+            .collect();
+        filter.insert("$or", any_of);
+    }
+
+    let query = RecordQuery {
+        sort: Some(doc! { "updated_at": -1 }),
+        limit: Some(SEARCH_LIMIT),
+        projection: Some(target.projection()),
+        ..RecordQuery::default()
+    };
+    let records = repos.records.find(target.collection, filter, query).await?;
+
+    Ok(records.iter()
+        .filter_map(|record| {
+            let id = record.get_object_id("_id").ok()?;
+            Some(json!({ "value": id.to_hex(), "label": target.label(record) }))
+        })
+        .collect())
+}
+
+/// Accepts a bare hex id or the `ObjectId("...")` form AdminX pre-fills edit inputs with
+fn parse_id(value: &str) -> Option<ObjectId> {
+    let value = value.trim();
+    let value = value.strip_prefix("ObjectId(\"").and_then(|v| v.strip_suffix("\")")).unwrap_or(value);
+    ObjectId::parse_str(value).ok()
+}
+/*----- END PICKER -----*/
+
+
+/*----- START SAVE CHECKS -----*/
+/// Reference fields of a resource, read from the `reference` key its form fields carry
+pub fn reference_fields(form: Option<&Value>) -> Vec<(String, &'static ReferenceTarget)> {
+    let groups = form.and_then(|form| form.get("groups")).and_then(Value::as_array);
+    groups.into_iter()
+        .flatten()
+        .filter_map(|group| group.get("fields").and_then(Value::as_array))
+        .flatten()
+        .filter_map(|field| {
+            let name = field.get("name")?.as_str()?;
+            let target = target(field.get("reference")?.as_str()?)?;
+            Some((name.to_string(), target))
+        })
+        .collect()
+}
+
+/// Checks a write's reference fields before letting it through
+pub struct ReferenceCheck {
+    pending: Vec<(String, &'static ReferenceTarget, ObjectId)>,
+    invalid: Option<String>,
+}
+
+/// Rewrites the payload's reference fields into ObjectIds (`{"$oid"}`, which BSON
+/// stores as a real ObjectId) and blank ones into null, and collects what to verify
+pub fn prepare(form: Option<Value>, mut payload: Value) -> (Value, ReferenceCheck) {
+    let mut check = ReferenceCheck { pending: Vec::new(), invalid: None };
+    let Some(map) = payload.as_object_mut() else {
+        return (payload, check);
+    };
+
+    for (field, target) in reference_fields(form.as_ref()) {
+        let Some(raw) = map.get(&field).and_then(Value::as_str).map(str::to_string) else {
+            continue;
+        };
+        if raw.trim().is_empty() {
+            map.insert(field, Value::Null);
+            continue;
+        }
+        match parse_id(&raw) {
+            Some(id) => {
+                map.insert(field.clone(), json!({ "$oid": id.to_hex() }));
+                check.pending.push((field, target, id));
+            }
+            None => {
+                check.invalid.get_or_insert(field);
+            }
+        }
+    }
+
+    (payload, check)
+}
+
+impl ReferenceCheck {
+    /// Runs `write` only if every referenced record exists and is not deleted
+    pub fn guard(self, repos: Option<web::Data<Repositories>>, write: BoxFuture<'static, HttpResponse>) -> BoxFuture<'static, HttpResponse> {
+        Box::pin(async move {
+            if let Some(field) = self.invalid {
+                return HttpResponse::BadRequest().json(json!({
+                    "error": "invalid_reference",
+                    "message": format!("{} is not a valid id", field),
+                }));
+            }
+            if self.pending.is_empty() {
+                return write.await;
+            }
+            let Some(repos) = repos else {
+                return HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}));
+            };
+
+            for (field, target, id) in self.pending {
+                let found = repos.records
+                    .find_one(target.collection, doc! { "_id": id, "deleted": { "$ne": true } })
+                    .await;
+                match found {
+                    Ok(Some(_)) => {}
+                    Ok(None) => {
+                        return HttpResponse::BadRequest().json(json!({
+                            "error": "invalid_reference",
+                            "message": format!("{} does not point to an existing {} record", field, target.name),
+                        }));
+                    }
+                    Err(e) => {
+                        return HttpResponse::InternalServerError().json(json!({
+                            "error": "reference_lookup_failed",
+                            "message": e.to_string(),
+                        }));
+                    }
+                }
+            }
+
+            write.await
+        })
+    }
+}
+/*----- END SAVE CHECKS -----*/
+
+
+/*----- START LABELS -----*/
+/// Labels of the records `references` point at, per record id and field, resolved with
+/// one query per reference field. Ids stored as hex strings (older AdminX saves) are
+/// read as ObjectIds.
+pub async fn resolve_labels(
+    repos: &Repositories,
+    collection: &str,
+    references: &[(String, &'static ReferenceTarget)],
+    ids: Vec<ObjectId>,
+) -> Result<HashMap<String, HashMap<String, String>>, Error> {
+    if references.is_empty() || ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let projection: Document = references.iter().map(|(field, _)| (field.clone(), Bson::Int32(1))).collect();
+    let query = RecordQuery { projection: Some(projection), ..RecordQuery::default() };
+    let records = repos.records.find(collection, doc! { "_id": { "$in": ids } }, query).await?;
+
+    let mut labels: HashMap<String, HashMap<String, String>> = HashMap::new();
+    for (field, target) in references {
+        let pointers: Vec<(String, ObjectId)> = records.iter()
+            .filter_map(|record| {
+                let id = record.get_object_id("_id").ok()?;
+                let reference = match record.get(field)? {
+                    Bson::ObjectId(reference) => *reference,
+                    Bson::String(reference) => parse_id(reference)?,
+                    _ => return None,
+                };
+                Some((id.to_hex(), reference))
+            })
+            .collect();
+        if pointers.is_empty() {
+            continue;
+        }
+
+        let referenced_ids: Vec<ObjectId> = pointers.iter().map(|(_, reference)| *reference).collect();
+        let query = RecordQuery { projection: Some(target.projection()), ..RecordQuery::default() };
+        let referenced = repos.records.find(target.collection, doc! { "_id": { "$in": referenced_ids } }, query).await?;
+        let referenced: HashMap<ObjectId, String> = referenced.iter()
+            .filter_map(|record| Some((record.get_object_id("_id").ok()?, target.label(record))))
+            .collect();
+
+        for (id, reference) in pointers {
+            if let Some(label) = referenced.get(&reference) {
+                labels.entry(id).or_default().insert(field.clone(), label.clone());
+            }
+        }
+    }
+    Ok(labels)
+}
+/*----- END LABELS -----*/
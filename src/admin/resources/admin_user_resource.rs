@@ -0,0 +1,282 @@
+// src/admin/resources/admin_user_resource.rs
+use crate::admin::bulk_actions::BulkActions;
+use crate::admin::model_schema::ModelSchema;
+use crate::admin::records;
+use crate::db::mongo::get_collection;
+use crate::models::admin_user::AdminUser;
+use crate::repositories::Repositories;
+use crate::requests::structures::admin_user_structure::{CreateAdminUserRequest, UpdateAdminUserRequest};
+use crate::services::admin_user_service;
+use actix_session::SessionExt;
+use actix_web::{web, HttpRequest, HttpResponse};
+use adminx::{AdmixResource, AdminxConfig};
+use adminx::utils::auth::extract_claims_from_session;
+use async_trait::async_trait;
+use futures::{future::BoxFuture, FutureExt};
+use once_cell::sync::Lazy;
+use mongodb::{Collection, bson::{doc, Document, oid::ObjectId}};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+
+#[derive(Debug, Clone)]
+pub struct AdminUserResource;
+
+static ADMIN_USER_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<AdminUser>);
+
+/* --------------------------------- Helpers -------------------------------- */
+fn parse_oid_opt(s: &str) -> Option<ObjectId> {
+    ObjectId::parse_str(s).ok()
+}
+
+/// Hashes never leave the collection, not even to the JSON endpoints
+fn without_password_hash() -> Document {
+    doc! { "password_hash": 0 }
+}
+
+/// What a resource call needs from the request, taken before its `Send` future starts.
+/// Decoding the session token never actually awaits, so it is resolved inline.
+fn request_context(req: &HttpRequest) -> Option<(web::Data<Repositories>, String)> {
+    let repos = Repositories::from_request(req)?;
+    let config = req.app_data::<web::Data<AdminxConfig>>()?;
+    let claims = extract_claims_from_session(&req.get_session(), config).now_or_never()?.ok()?;
+    Some((repos, claims.sub))
+}
+
+fn unauthorized() -> BoxFuture<'static, HttpResponse> {
+    Box::pin(async { HttpResponse::Unauthorized().json(json!({"error":"unauthorized"})) })
+}
+
+/// Form values arrive as strings, and an empty one means the field was left alone
+fn parse_form<T: DeserializeOwned>(payload: Value) -> Result<T, HttpResponse> {
+    let payload = match payload {
+        Value::Object(map) => Value::Object(map.into_iter().filter(|(_, value)| value != "").collect()),
+        other => other,
+    };
+    serde_json::from_value(payload)
+        .map_err(|e| HttpResponse::BadRequest().json(json!({"error":"invalid_payload","message":e.to_string()})))
+}
+
+/// The generated form, plus a password field: set when adding an admin user, and
+/// resets theirs when filled in on edit. The email is fixed once created, since the
+/// operator's authenticator is enrolled under it.
+fn admin_user_form() -> Value {
+    let mut form = ADMIN_USER_SCHEMA.form_structure();
+    if let Some(groups) = form["groups"].as_array_mut() {
+        for field in groups.iter_mut().filter_map(|group| group["fields"].as_array_mut()).flatten() {
+            if field["name"] == "email" {
+                field["help_text"] = json!("Cannot be changed after the admin user is added.");
+            }
+        }
+        groups.push(json!({
+            "title": "Password",
+            "fields": [{
+                "name": "password",
+                "field_type": "password",
+                "label": "Password",
+                "value": "",
+                "required": false,
+                "options": [],
+                "help_text": "Required for a new admin user. When editing, leave empty to keep the current password; a new one has to be changed at the next sign-in.",
+            }],
+        }));
+    }
+    form
+}
+
+#[async_trait]
+impl AdmixResource for AdminUserResource {
+    // ===========================
+    // REQUIRED IMPLEMENTATIONS
+    // ===========================
+    fn new() -> Self {
+        AdminUserResource
+    }
+
+    fn resource_name(&self) -> &'static str {
+        "Admin Users"
+    }
+
+    fn base_path(&self) -> &'static str {
+        "admin_users"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "admin_users"
+    }
+
+    fn get_collection(&self) -> Collection<Document> {
+        get_collection::<Document>("admin_users")
+    }
+
+    fn clone_box(&self) -> Box<dyn AdmixResource> {
+        Box::new(Self::new())
+    }
+
+    fn menu_group(&self) -> Option<&'static str> {
+        Some("Settings")
+    }
+
+    fn menu(&self) -> &'static str {
+        "Admin Users"
+    }
+
+    // ===========================
+    // CONFIGURATION OVERRIDES
+    // ===========================
+    // Admins can see who has access and rename themselves; everything else is
+    // checked against the superadmin role in admin_user_service
+    fn allowed_roles(&self) -> Vec<String> {
+        vec!["admin".to_string(), "superadmin".to_string()]
+    }
+
+    fn permit_keys(&self) -> Vec<&'static str> {
+        ADMIN_USER_SCHEMA.permit_keys()
+    }
+
+    // ===========================
+    // CRUD OVERRIDES
+    // ===========================
+    // Same as the AdminX defaults, without the password hash
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        records::list_with(self, req, query, Some(without_password_hash()))
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::get_with(self, req, id, Some(without_password_hash()))
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        let Some((repos, admin_id)) = request_context(req) else {
+            return unauthorized();
+        };
+        Box::pin(async move {
+            let request: CreateAdminUserRequest = match parse_form(payload) {
+                Ok(request) => request,
+                Err(response) => return response,
+            };
+            // Matched right away: actix errors are not Send, so none may live across an await
+            let actor = match admin_user_service::acting_admin(&repos, &admin_id).await {
+                Ok(actor) => actor,
+                Err(e) => return e.error_response(),
+            };
+            match admin_user_service::create(&repos, &actor, request).await {
+                Ok(admin) => HttpResponse::Created().json(json!({
+                    "success": true,
+                    "message": "Admin user created successfully",
+                    "id": admin.id.map(|id| id.to_hex()),
+                })),
+                Err(e) => e.error_response(),
+            }
+        })
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        let Some((repos, admin_id)) = request_context(req) else {
+            return unauthorized();
+        };
+        Box::pin(async move {
+            let Some(id) = parse_oid_opt(&id) else {
+                return HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"}));
+            };
+            let request: UpdateAdminUserRequest = match parse_form(payload) {
+                Ok(request) => request,
+                Err(response) => return response,
+            };
+            // Matched right away: actix errors are not Send, so none may live across an await
+            let actor = match admin_user_service::acting_admin(&repos, &admin_id).await {
+                Ok(actor) => actor,
+                Err(e) => return e.error_response(),
+            };
+            match admin_user_service::update(&repos, &actor, id, request).await {
+                Ok(()) => HttpResponse::Ok().json(json!({ "success": true, "message": "Admin user updated successfully" })),
+                Err(e) => e.error_response(),
+            }
+        })
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        let Some((repos, admin_id)) = request_context(req) else {
+            return unauthorized();
+        };
+        Box::pin(async move {
+            let Some(id) = parse_oid_opt(&id) else {
+                return HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"}));
+            };
+            // Matched right away: actix errors are not Send, so none may live across an await
+            let actor = match admin_user_service::acting_admin(&repos, &admin_id).await {
+                Ok(actor) => actor,
+                Err(e) => return e.error_response(),
+            };
+            match admin_user_service::delete(&repos, &actor, id).await {
+                Ok(()) => HttpResponse::Ok().json(json!({ "success": true, "message": "Admin user deleted successfully" })),
+                Err(e) => e.error_response(),
+            }
+        })
+    }
+
+    // ===========================
+    // UI STRUCTURE OVERRIDES (Optional)
+    // ===========================
+    fn form_structure(&self) -> Option<Value> {
+        Some(admin_user_form())
+    }
+
+    fn list_structure(&self) -> Option<Value> {
+        Some(ADMIN_USER_SCHEMA.list_structure(&["view", "edit", "delete"]))
+    }
+
+    fn view_structure(&self) -> Option<Value> {
+        Some(ADMIN_USER_SCHEMA.view_structure())
+    }
+
+    fn filters(&self) -> Option<Value> {
+        Some(ADMIN_USER_SCHEMA.filters())
+    }
+
+    // ===========================
+    // CUSTOM ACTIONS (Optional)
+    // ===========================
+    fn custom_actions(&self) -> Vec<adminx::actions::CustomAction> {
+        vec![
+            adminx::actions::CustomAction {
+                name: "reset_two_factor",
+                method: "POST",
+                handler: |req, _path, _body| {
+                    let id = req.match_info().get("id").and_then(parse_oid_opt);
+                    let Some(id) = id else {
+                        return Box::pin(async { HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
+                    };
+                    Box::pin(async move {
+                        let Some((repos, admin_id)) = request_context(&req) else {
+                            return HttpResponse::Unauthorized().json(json!({"error":"unauthorized"}));
+                        };
+                        let reset = match admin_user_service::acting_admin(&repos, &admin_id).await {
+                            Ok(actor) => admin_user_service::reset_two_factor(&repos, &actor, id).await,
+                            Err(e) => Err(e),
+                        };
+                        match reset {
+                            Ok(true) => HttpResponse::Ok().json(json!({
+                                "success": true,
+                                "message": "Two-factor authentication reset; a new authenticator is set up at the next sign-in",
+                            })),
+                            Ok(false) => HttpResponse::Ok().json(json!({
+                                "success": true,
+                                "message": "Two-factor authentication was not set up",
+                            })),
+                            Err(e) => e.error_response()
+                        }
+                    })
+                },
+                ui: Some(adminx::actions::ActionUi {
+                    label: Some("Reset Two-Factor".into()),
+                    confirm: Some("Remove this admin user's authenticator and recovery codes?".into()),
+                    fields: None,
+                }),
+            },
+        ]
+    }
+}
+
+// Admin users are added and removed one at a time, through admin_user_service
+impl BulkActions for AdminUserResource {}
@@ -0,0 +1,182 @@
+// src/admin/resources/audit_log_resource.rs
+use crate::admin::bulk_actions::BulkActions;
+use crate::db::mongo::get_collection;
+use actix_web::{HttpRequest, HttpResponse};
+use adminx::AdmixResource;
+use adminx::menu::MenuAction;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use mongodb::{Collection, bson::Document};
+use serde_json::{json, Value};
+
+
+#[derive(Debug, Clone)]
+pub struct AuditLogResource;
+
+/* --------------------------------- Helpers -------------------------------- */
+// AdminX registers create/edit/delete routes for every resource; audit entries are
+// only ever written by the `Audited` wrapper.
+fn read_only() -> BoxFuture<'static, HttpResponse> {
+    Box::pin(async { HttpResponse::Forbidden().json(json!({"error":"audit_logs_are_read_only"})) })
+}
+
+#[async_trait]
+impl AdmixResource for AuditLogResource {
+    // ===========================
+    // REQUIRED IMPLEMENTATIONS
+    // ===========================
+    fn new() -> Self {
+        AuditLogResource
+    }
+
+    fn resource_name(&self) -> &'static str {
+        "Audit Logs"
+    }
+
+    fn base_path(&self) -> &'static str {
+        "audit_logs"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "audit_logs"
+    }
+
+    fn get_collection(&self) -> Collection<Document> {
+        get_collection::<Document>("audit_logs")
+    }
+
+    fn clone_box(&self) -> Box<dyn AdmixResource> {
+        Box::new(Self::new())
+    }
+
+    fn menu_group(&self) -> Option<&'static str> {
+        Some("Settings")
+    }
+
+    fn menu(&self) -> &'static str {
+        "Audit Logs"
+    }
+
+    // ===========================
+    // CONFIGURATION OVERRIDES
+    // ===========================
+    fn allowed_roles(&self) -> Vec<String> {
+        vec!["admin".to_string(), "superadmin".to_string()]
+    }
+
+    fn allowed_actions(&self) -> Option<Vec<MenuAction>> {
+        Some(vec![MenuAction::List, MenuAction::View])
+    }
+
+    // Also the list filter whitelist
+    fn permit_keys(&self) -> Vec<&'static str> {
+        vec!["actor", "resource", "action", "document_id", "changes", "before", "after", "created_at"]
+    }
+
+    fn create(&self, _req: &HttpRequest, _payload: Value) -> BoxFuture<'static, HttpResponse> {
+        read_only()
+    }
+
+    fn update(&self, _req: &HttpRequest, _id: String, _payload: Value) -> BoxFuture<'static, HttpResponse> {
+        read_only()
+    }
+
+    fn delete(&self, _req: &HttpRequest, _id: String) -> BoxFuture<'static, HttpResponse> {
+        read_only()
+    }
+
+    // ===========================
+    // UI STRUCTURE OVERRIDES (Optional)
+    // ===========================
+    fn list_structure(&self) -> Option<Value> {
+        Some(json!({
+            "columns": [
+                {
+                    "field": "created_at",
+                    "label": "When",
+                    "type": "datetime",
+                    "sortable": true
+                },
+                {
+                    "field": "actor",
+                    "label": "Actor",
+                    "sortable": true
+                },
+                {
+                    "field": "resource",
+                    "label": "Resource",
+                    "sortable": true
+                },
+                {
+                    "field": "action",
+                    "label": "Action",
+                    "sortable": true
+                },
+                {
+                    "field": "document_id",
+                    "label": "Document"
+                }
+            ],
+            "actions": ["view"]
+        }))
+    }
+
+    fn view_structure(&self) -> Option<Value> {
+        Some(json!({
+            "sections": [
+                {
+                    "title": "Change",
+                    "fields": [
+                        { "field": "actor", "label": "Actor" },
+                        { "field": "resource", "label": "Resource" },
+                        { "field": "action", "label": "Action" },
+                        { "field": "document_id", "label": "Document ID" },
+                        { "field": "created_at", "label": "When", "type": "datetime" }
+                    ]
+                },
+                {
+                    "title": "Diff",
+                    "fields": [
+                        { "field": "changes", "label": "Changed Fields" },
+                        { "field": "before", "label": "Before" },
+                        { "field": "after", "label": "After" }
+                    ]
+                }
+            ]
+        }))
+    }
+
+    fn filters(&self) -> Option<Value> {
+        // Resources are registered before any request, so the registry is complete here
+        let resources: Vec<Value> = adminx::registry::all_resources()
+            .iter()
+            .map(|resource| json!({ "value": resource.resource_name(), "label": resource.menu() }))
+            .collect();
+
+        Some(json!({
+            "filters": [
+                {
+                    "field": "resource",
+                    "type": "select",
+                    "label": "Resource",
+                    "options": resources
+                },
+                {
+                    "field": "actor",
+                    "type": "text",
+                    "label": "Actor",
+                    "placeholder": "Admin email..."
+                },
+                {
+                    "field": "created_at",
+                    "type": "date_range",
+                    "label": "Date",
+                    "placeholder": "Select date range..."
+                },
+            ]
+        }))
+    }
+}
+
+// Entries are read-only, so nothing to run in bulk; the override is for the CSV export
+impl BulkActions for AuditLogResource {}
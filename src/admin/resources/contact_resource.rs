@@ -1,14 +1,25 @@
 // src/admin/resources/user_resource.rs
+use crate::admin::bulk_actions::{BulkAction, BulkActions, BulkOperation};
+use crate::admin::model_schema::ModelSchema;
+use crate::admin::options::enum_options;
+use crate::admin::records;
+use crate::enums::common_enums::StatusEnum;
 use crate::db::mongo::get_collection;
+use crate::models::contact::Contact;
+use actix_web::{HttpRequest, HttpResponse};
 use adminx::AdmixResource;
 use async_trait::async_trait;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
 use mongodb::{Collection, bson::Document};
-use serde_json::{json, Value};
+use serde_json::Value;
 
 
 #[derive(Debug, Clone)]
 pub struct ContactResource;
 
+static CONTACT_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<Contact>);
+
 #[async_trait]
 impl AdmixResource for ContactResource {
     // ===========================
@@ -54,162 +65,62 @@ impl AdmixResource for ContactResource {
     }
 
     fn permit_keys(&self) -> Vec<&'static str> {
-        vec!["first_name", "email", "status", "deleted"]
+        CONTACT_SCHEMA.permit_keys()
+    }
+
+    // ===========================
+    // CRUD OVERRIDES
+    // ===========================
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        records::list(self, req, query)
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::get(self, req, id)
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::create(self, req, payload)
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::update(self, req, id, payload)
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::delete(self, req, id)
     }
 
     // ===========================
     // UI STRUCTURE OVERRIDES (Optional)
     // ===========================
     fn form_structure(&self) -> Option<Value> {
-        // Using manual form structure for better control
-        Some(json!({
-            "groups": [
-                {
-                    "title": "User Details",
-                    "fields": [
-                        {
-                            "name": "name",
-                            "field_type": "text",
-                            "label": "Full Name",
-                            "value": "",
-                            "options": null
-                        },
-                        {
-                            "name": "email",
-                            "field_type": "email", 
-                            "label": "Email Address",
-                            "value": "",
-                            "options": null
-                        }
-                    ]
-                }
-            ]
-        }))
+        Some(CONTACT_SCHEMA.form_structure())
     }
 
     fn list_structure(&self) -> Option<Value> {
-        Some(json!({
-            "columns": [
-                {
-                    "field": "first_name",
-                    "label": "First Name",
-                    "sortable": true
-                },
-                {
-                    "field": "email", 
-                    "label": "Email",
-                    "sortable": true
-                },
-                {
-                    "field": "status", 
-                    "label": "Status",
-                    "sortable": true
-                },
-                {
-                    "field": "created_at",
-                    "label": "Created At",
-                    "type": "datetime",
-                    "sortable": true
-                }
-            ],
-            "actions": ["view", "edit", "delete"]
-        }))
+        Some(CONTACT_SCHEMA.list_structure(&["view", "edit", "delete"]))
     }
 
     fn view_structure(&self) -> Option<Value> {
-        Some(json!({
-            "sections": [
-                {
-                    "title": "User Information",
-                    "fields": [
-                        {
-                            "field": "first_name",
-                            "label": "First Name"
-                        },
-                        {
-                            "field": "last_name",
-                            "label": "Last Name"
-                        },
-                        {
-                            "field": "email",
-                            "label": "Email"
-                        },
-                        {
-                            "field": "phone_number",
-                            "label": "Phone number"
-                        },
-                        {
-                            "field": "status",
-                            "label": "Status"
-                        },
-                        {
-                            "field": "created_at",
-                            "label": "Created AT"
-                        },
-                        {
-                            "field": "updated_at",
-                            "label": "Update AT"
-                        },
-                    ]
-                },
-                {
-                    "title": "System Information",
-                    "fields": [
-                        {
-                            "field": "_id",
-                            "label": "Contact ID"
-                        },
-                        {
-                            "field": "created_at",
-                            "label": "Created At",
-                            "type": "datetime"
-                        },
-                        {
-                            "field": "updated_at", 
-                            "label": "Updated At",
-                            "type": "datetime"
-                        }
-                    ]
-                }
-            ]
-        }))
+        Some(CONTACT_SCHEMA.view_structure())
     }
 
     fn filters(&self) -> Option<Value> {
-        Some(json!({
-            "filters": [
-                // ===========================
-                // TEXT FILTERS (like your example)
-                // ===========================
-                {
-                    "field": "phone_number",
-                    "type": "text",
-                    "label": "Phone number",
-                    "placeholder": "Search by Phone..."
-                },
-                {
-                    "field": "email",
-                    "type": "text", 
-                    "label": "Email",
-                    "placeholder": "Search by email..."
-                },
-                
-                // ===========================
-                // DATE RANGE FILTERS
-                // ===========================
-                {
-                    "field": "created_at",
-                    "type": "date_range",
-                    "label": "Created Date",
-                    "placeholder": "Select date range..."
-                },
-                {
-                    "field": "updated_at",
-                    "type": "date_range",
-                    "label": "Updated Date",
-                    "placeholder": "Select date range..."
-                },
-            ]
-        }))
-    }
-}
\ No newline at end of file
+        Some(CONTACT_SCHEMA.filters())
+    }
+}
+
+impl BulkActions for ContactResource {
+    fn bulk_actions() -> Vec<BulkAction> {
+        vec![
+            BulkAction {
+                name: "set_status",
+                label: "Set status",
+                operation: BulkOperation::Choose { field: "status", options: enum_options::<StatusEnum> },
+            },
+            BulkAction { name: "delete", label: "Delete", operation: BulkOperation::SoftDelete },
+            BulkAction { name: "restore", label: "Restore", operation: BulkOperation::Restore },
+        ]
+    }
+}
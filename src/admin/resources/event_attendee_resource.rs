@@ -1,67 +1,46 @@
 // src/admin/resources/event_attendee_resource.rs
+use crate::admin::bulk_actions::{BulkAction, BulkActions, BulkOperation};
+use crate::admin::model_schema::ModelSchema;
+use crate::admin::records;
 use crate::db::mongo::get_collection;
+use crate::models::event_attendee::EventAttendee;
+use crate::repositories::Repositories;
+use crate::services::attendance_service::{cancel_registration, reinstate_registration};
+use actix_web::{HttpRequest, HttpResponse};
 use adminx::AdmixResource;
 use async_trait::async_trait;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
 use mongodb::{
     Collection,
     bson::{doc, Document, oid::ObjectId},
 };
 use serde_json::{json, Value};
-use convert_case::{Casing, Case};
 
 use crate::enums::common_enums::StatusEnum;
-use crate::requests::enums::event_enums::RegistrationTypeEnum;
 
 #[derive(Debug, Clone)]
 pub struct EventAttendeeResource;
 
-/* ----------------------------- Options Builder ----------------------------- */
-pub struct AttendeeOptions;
-
-impl AttendeeOptions {
-    // StatusEnum में #[serde(rename_all="lowercase")] है → value को lowercase देना चाहिए
-    pub fn registration_status_options() -> Vec<Value> {
-        let vals = [
-            "initial","active","inactive","pending","finished","accepted",
-            "declined","expired","blocked","archived",
-        ];
-        vals.into_iter()
-            .map(|v| json!({"value": v, "label": v.to_case(Case::Title)}))
-            .collect()
-    }
-
-    // RegistrationTypeEnum में rename नहीं है → variants as-is
-    pub fn registration_type_options() -> Vec<Value> {
-        let vals = [
-            "Initial","Required","Optional","NotRequired","Open","Invited","InviteOnly","Paid","Waitlist",
-        ];
-        vals.into_iter()
-            .map(|v| json!({"value": v, "label": v}))
-            .collect()
-    }
-
-    pub fn boolean_options() -> Vec<Value> {
-        vec![
-            json!({ "value": true,  "label": "True"  }),
-            json!({ "value": false, "label": "False" }),
-        ]
-    }
-}
+static ATTENDEE_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<EventAttendee>);
 
 /* --------------------------------- Helpers -------------------------------- */
 fn parse_oid_opt(s: &str) -> Option<ObjectId> {
     ObjectId::parse_str(s).ok()
 }
 
-async fn update_set_by_id(set_doc: Document, id: &ObjectId) -> Result<u64, actix_web::HttpResponse> {
-    let coll = get_collection::<Document>("event_attendees");
-    coll.update_one(
+fn repositories_missing() -> actix_web::HttpResponse {
+    actix_web::HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}))
+}
+
+async fn update_set_by_id(repos: &Repositories, set_doc: Document, id: &ObjectId) -> Result<u64, actix_web::HttpResponse> {
+    repos.records.update_one(
+        "event_attendees",
         doc!{"_id": id, "deleted": { "$ne": true }},
         doc!{
             "$set": set_doc,
             "$currentDate": { "updated_at": true }
         },
-        None
     )
     .await
     .map(|res| res.modified_count)
@@ -91,198 +70,43 @@ impl AdmixResource for EventAttendeeResource {
     }
 
     fn permit_keys(&self) -> Vec<&'static str> {
-        vec![
-            "event_id","user_id","email","phone","first_name","last_name","company","designation",
-            "registration_status","registration_type","registration_date",
-            "check_in_date","check_out_date",
-            "dietary_requirements","accessibility_needs","emergency_contact","notes",
-            "payment_status","payment_amount","payment_date","payment_reference",
-            "deleted"
-        ]
+        ATTENDEE_SCHEMA.permit_keys()
+    }
+
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        records::list(self, req, query)
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::get(self, req, id)
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::create(self, req, payload)
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::update(self, req, id, payload)
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::delete(self, req, id)
     }
 
     fn form_structure(&self) -> Option<Value> {
-        Some(json!({
-            "groups": [
-                {
-                    "title": "Links",
-                    "fields": [
-                        { "name": "event_id", "field_type": "text", "label": "Event ID (ObjectId)" },
-                        { "name": "user_id",  "field_type": "text", "label": "User ID (ObjectId)" }
-                    ]
-                },
-                {
-                    "title": "Contact",
-                    "fields": [
-                        { "name": "first_name", "field_type": "text",  "label": "First Name" },
-                        { "name": "last_name",  "field_type": "text",  "label": "Last Name" },
-                        { "name": "email",      "field_type": "email", "label": "Email" },
-                        { "name": "phone",      "field_type": "text",  "label": "Phone" },
-                        { "name": "company",    "field_type": "text",  "label": "Company" },
-                        { "name": "designation","field_type": "text",  "label": "Designation" }
-                    ]
-                },
-                {
-                    "title": "Registration",
-                    "fields": [
-                        { "name": "registration_status", "field_type": "select",   "label": "Registration Status", "options": AttendeeOptions::registration_status_options(), "required": true },
-                        { "name": "registration_type",   "field_type": "select",   "label": "Registration Type",   "options": AttendeeOptions::registration_type_options(),   "required": true },
-                        { "name": "registration_date",   "field_type": "datetime", "label": "Registration Date" }
-                    ]
-                },
-                {
-                    "title": "Attendance",
-                    "fields": [
-                        { "name": "check_in_date",  "field_type": "datetime", "label": "Check-in Date" },
-                        { "name": "check_out_date", "field_type": "datetime", "label": "Check-out Date" }
-                    ]
-                },
-                {
-                    "title": "Payment",
-                    "fields": [
-                        { "name": "payment_status",    "field_type": "boolean",  "label": "Paid?", "options": AttendeeOptions::boolean_options() },
-                        { "name": "payment_amount",    "field_type": "number",   "label": "Amount" },
-                        { "name": "payment_date",      "field_type": "datetime", "label": "Payment Date" },
-                        { "name": "payment_reference", "field_type": "text",     "label": "Payment Reference" }
-                    ]
-                },
-                {
-                    "title": "Other",
-                    "fields": [
-                        { "name": "dietary_requirements",  "field_type": "text", "label": "Dietary Requirements" },
-                        { "name": "accessibility_needs",   "field_type": "text", "label": "Accessibility Needs" },
-                        { "name": "emergency_contact",     "field_type": "text", "label": "Emergency Contact" },
-                        { "name": "notes",                 "field_type": "editor_text", "label": "Notes" }
-                    ]
-                },
-                {
-                    "title": "System",
-                    "fields": [
-                        { "name": "deleted", "field_type": "boolean", "label": "Deleted?", "options": AttendeeOptions::boolean_options() }
-                    ]
-                }
-            ]
-        }))
+        Some(ATTENDEE_SCHEMA.form_structure())
     }
 
     fn list_structure(&self) -> Option<Value> {
-        Some(json!({
-            "columns": [
-                { "field": "first_name", "label": "First", "sortable": true },
-                { "field": "last_name",  "label": "Last",  "sortable": true },
-                { "field": "email",      "label": "Email", "sortable": true },
-                { "field": "phone",      "label": "Phone", "sortable": true },
-                { "field": "registration_status", "label": "Reg. Status", "sortable": true },
-                { "field": "registration_type",   "label": "Reg. Type",   "sortable": true },
-                { "field": "payment_status", "label": "Paid", "sortable": true },
-                { "field": "payment_amount", "label": "Amount", "type": "number", "sortable": true },
-                { "field": "check_in_date",  "label": "Check-in",  "type": "datetime", "sortable": true },
-                { "field": "check_out_date", "label": "Check-out", "type": "datetime", "sortable": true },
-                { "field": "created_at", "label": "Created", "type": "datetime", "sortable": true },
-                { "field": "updated_at", "label": "Updated", "type": "datetime", "sortable": true }
-            ],
-            "actions": ["view", "edit", "delete"]
-        }))
+        Some(ATTENDEE_SCHEMA.list_structure(&["view", "edit", "delete"]))
     }
 
     fn view_structure(&self) -> Option<Value> {
-        Some(json!({
-            "sections": [
-                {
-                    "title": "Links",
-                    "fields": [
-                        { "field": "_id",      "label": "Attendee ID" },
-                        { "field": "event_id", "label": "Event ID" },
-                        { "field": "user_id",  "label": "User ID" }
-                    ]
-                },
-                {
-                    "title": "Contact",
-                    "fields": [
-                        { "field": "first_name", "label": "First Name" },
-                        { "field": "last_name",  "label": "Last Name" },
-                        { "field": "email",      "label": "Email" },
-                        { "field": "phone",      "label": "Phone" },
-                        { "field": "company",    "label": "Company" },
-                        { "field": "designation","label": "Designation" }
-                    ]
-                },
-                {
-                    "title": "Registration",
-                    "fields": [
-                        { "field": "registration_status", "label": "Status" },
-                        { "field": "registration_type",   "label": "Type" },
-                        { "field": "registration_date",   "label": "Date", "type": "datetime" }
-                    ]
-                },
-                {
-                    "title": "Attendance",
-                    "fields": [
-                        { "field": "check_in_date",  "label": "Check-in",  "type": "datetime" },
-                        { "field": "check_out_date", "label": "Check-out", "type": "datetime" }
-                    ]
-                },
-                {
-                    "title": "Payment",
-                    "fields": [
-                        { "field": "payment_status",    "label": "Paid", "type": "boolean" },
-                        { "field": "payment_amount",    "label": "Amount" },
-                        { "field": "payment_date",      "label": "Date", "type": "datetime" },
-                        { "field": "payment_reference", "label": "Reference" }
-                    ]
-                },
-                {
-                    "title": "Other",
-                    "fields": [
-                        { "field": "dietary_requirements", "label": "Dietary Requirements" },
-                        { "field": "accessibility_needs",  "label": "Accessibility Needs" },
-                        { "field": "emergency_contact",    "label": "Emergency Contact" },
-                        { "field": "notes",                "label": "Notes" }
-                    ]
-                },
-                {
-                    "title": "System",
-                    "fields": [
-                        { "field": "deleted",     "label": "Deleted", "type": "boolean" },
-                        { "field": "created_at",  "label": "Created At", "type": "datetime" },
-                        { "field": "updated_at",  "label": "Updated At", "type": "datetime" }
-                    ]
-                }
-            ]
-        }))
+        Some(ATTENDEE_SCHEMA.view_structure())
     }
 
     fn filters(&self) -> Option<Value> {
-        Some(json!({
-            "filters": [
-                { "field": "event_id", "type": "text", "label": "Event ID" },
-                { "field": "user_id",  "type": "text", "label": "User ID" },
-                { "field": "email",    "type": "text", "label": "Email" },
-                { "field": "phone",    "type": "text", "label": "Phone" },
-                { "field": "first_name", "type": "text", "label": "First Name" },
-                { "field": "last_name",  "type": "text", "label": "Last Name" },
-                { "field": "company",    "type": "text", "label": "Company" },
-                { "field": "designation","type": "text", "label": "Designation" },
-                { "field": "payment_reference","type": "text", "label": "Payment Ref" },
-
-                { "field": "registration_status", "type": "select", "label": "Reg. Status",
-                  "options": AttendeeOptions::registration_status_options() },
-                { "field": "registration_type", "type": "select", "label": "Reg. Type",
-                  "options": AttendeeOptions::registration_type_options() },
-
-                { "field": "payment_status", "type": "boolean", "label": "Paid?",
-                  "options": AttendeeOptions::boolean_options() },
-                { "field": "deleted", "type": "boolean", "label": "Deleted?",
-                  "options": AttendeeOptions::boolean_options() },
-
-                { "field": "registration_date", "type": "date_range", "label": "Registration Date" },
-                { "field": "check_in_date",     "type": "date_range", "label": "Check-in Date" },
-                { "field": "check_out_date",    "type": "date_range", "label": "Check-out Date" },
-                { "field": "payment_date",      "type": "date_range", "label": "Payment Date" },
-                { "field": "created_at",        "type": "date_range", "label": "Created Date" },
-                { "field": "updated_at",        "type": "date_range", "label": "Updated Date" }
-            ]
-        }))
+        Some(ATTENDEE_SCHEMA.filters())
     }
 
     fn custom_actions(&self) -> Vec<adminx::actions::CustomAction> {
@@ -295,16 +119,12 @@ impl AdmixResource for EventAttendeeResource {
                     let Some(id) = id else {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
                     };
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        let coll = get_collection::<Document>("event_attendees");
-                        let res = coll.update_one(
-                            doc!{"_id": &id, "deleted": { "$ne": true }},
-                            doc!{ "$currentDate": { "check_in_date": true, "updated_at": true } },
-                            None
-                        ).await;
-                        match res {
-                            Ok(r) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": r.modified_count })),
-                            Err(e) => actix_web::HttpResponse::InternalServerError().json(json!({"error":"db_update_failed","message":e.to_string()}))
+                        let Some(repos) = repos else { return repositories_missing(); };
+                        match repos.attendees.check_in(id).await {
+                            Ok(()) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": 1 })),
+                            Err(e) => e.error_response()
                         }
                     })
                 },
@@ -318,16 +138,12 @@ impl AdmixResource for EventAttendeeResource {
                     let Some(id) = id else {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
                     };
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        let coll = get_collection::<Document>("event_attendees");
-                        let res = coll.update_one(
-                            doc!{"_id": &id, "deleted": { "$ne": true }},
-                            doc!{ "$currentDate": { "check_out_date": true, "updated_at": true } },
-                            None
-                        ).await;
-                        match res {
-                            Ok(r) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": r.modified_count })),
-                            Err(e) => actix_web::HttpResponse::InternalServerError().json(json!({"error":"db_update_failed","message":e.to_string()}))
+                        let Some(repos) = repos else { return repositories_missing(); };
+                        match repos.attendees.check_out(id).await {
+                            Ok(()) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": 1 })),
+                            Err(e) => e.error_response()
                         }
                     })
                 },
@@ -341,10 +157,13 @@ impl AdmixResource for EventAttendeeResource {
                     let Some(id) = id else {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
                     };
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        match update_set_by_id(doc!{ "deleted": true, "registration_status": "inactive" }, &id).await {
-                            Ok(modified) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": modified })),
-                            Err(resp) => resp
+                        let Some(repos) = repos else { return repositories_missing(); };
+                        // Goes through the attendance service so the seat is released and the waitlist promoted
+                        match cancel_registration(&repos, id, None).await {
+                            Ok(()) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": 1 })),
+                            Err(e) => e.error_response()
                         }
                     })
                 },
@@ -358,28 +177,17 @@ impl AdmixResource for EventAttendeeResource {
                     let Some(id) = id else {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
                     };
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        match update_set_by_id(doc!{ "deleted": false, "registration_status": "active" }, &id).await {
-                            Ok(modified) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": modified })),
-                            Err(resp) => resp
-                        }
-                    })
-                },
-                ui: None,
-            },
-            adminx::actions::CustomAction {
-                name: "set_registration_status",
-                method: "POST",
-                handler: |req, _path, body| {
-                    let id = req.match_info().get("id").and_then(parse_oid_opt);
-                    let Some(id) = id else {
-                        return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
-                    };
-                    let st = body.get("registration_status").and_then(|v| v.as_str()).unwrap_or("initial").to_string();
-                    Box::pin(async move {
-                        match update_set_by_id(doc!{ "registration_status": st }, &id).await {
-                            Ok(modified) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": modified })),
-                            Err(resp) => resp
+                        let Some(repos) = repos else { return repositories_missing(); };
+                        // Takes a seat again, or a waitlist place, through the attendance service
+                        match reinstate_registration(&repos, id).await {
+                            Ok(attendee) => actix_web::HttpResponse::Ok().json(json!({
+                                "success": true,
+                                "modified": 1,
+                                "waitlisted": attendee.registration_status == StatusEnum::Pending,
+                            })),
+                            Err(e) => e.error_response()
                         }
                     })
                 },
@@ -394,8 +202,9 @@ impl AdmixResource for EventAttendeeResource {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
                     };
                     let on = body.get("payment_status").and_then(|v| v.as_bool()).unwrap_or(true);
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        let coll = get_collection::<Document>("event_attendees");
+                        let Some(repos) = repos else { return repositories_missing(); };
                         let update = if on {
                             doc!{
                                 "$set": { "payment_status": true },
@@ -407,7 +216,7 @@ impl AdmixResource for EventAttendeeResource {
                                 "$currentDate": { "updated_at": true }
                             }
                         };
-                        match coll.update_one(doc!{"_id": &id, "deleted": { "$ne": true }}, update, None).await {
+                        match repos.records.update_one("event_attendees", doc!{"_id": &id, "deleted": { "$ne": true }}, update).await {
                             Ok(r) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "payment_status": on, "modified": r.modified_count })),
                             Err(e) => actix_web::HttpResponse::InternalServerError().json(json!({"error":"db_update_failed","message":e.to_string()}))
                         }
@@ -426,8 +235,10 @@ impl AdmixResource for EventAttendeeResource {
                     let amount = body.get("payment_amount").and_then(|v| v.as_f64());
                     let reference = body.get("payment_reference").and_then(|v| v.as_str()).map(|s| s.to_string());
                     let stamp_now = body.get("stamp_now").and_then(|v| v.as_bool()).unwrap_or(false);
+                    let repos = Repositories::from_request(&req);
 
                     Box::pin(async move {
+                        let Some(repos) = repos else { return repositories_missing(); };
                         let mut set_doc = Document::new();
                         if let Some(a) = amount { set_doc.insert("payment_amount", a); }
                         if let Some(r) = reference { set_doc.insert("payment_reference", r); }
@@ -436,7 +247,6 @@ impl AdmixResource for EventAttendeeResource {
                             return actix_web::HttpResponse::BadRequest().json(json!({"error":"no_fields_to_update"}));
                         }
 
-                        let coll = get_collection::<Document>("event_attendees");
                         let mut update = doc!{ "$set": set_doc };
                         if stamp_now {
                             update.insert("$currentDate", doc!{ "payment_date": true, "updated_at": true });
@@ -444,7 +254,7 @@ impl AdmixResource for EventAttendeeResource {
                             update.insert("$currentDate", doc!{ "updated_at": true });
                         }
 
-                        match coll.update_one(doc!{"_id": &id, "deleted": { "$ne": true }}, update, None).await {
+                        match repos.records.update_one("event_attendees", doc!{"_id": &id, "deleted": { "$ne": true }}, update).await {
                             Ok(r) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": r.modified_count })),
                             Err(e) => actix_web::HttpResponse::InternalServerError().json(json!({"error":"db_update_failed","message":e.to_string()}))
                         }
@@ -464,8 +274,10 @@ impl AdmixResource for EventAttendeeResource {
                     let Some(user_oid) = parse_oid_opt(user_id) else {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_user_id"})) });
                     };
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        match update_set_by_id(doc!{ "user_id": user_oid }, &id).await {
+                        let Some(repos) = repos else { return repositories_missing(); };
+                        match update_set_by_id(&repos, doc!{ "user_id": user_oid }, &id).await {
                             Ok(modified) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": modified })),
                             Err(resp) => resp
                         }
@@ -485,8 +297,10 @@ impl AdmixResource for EventAttendeeResource {
                     let Some(event_oid) = parse_oid_opt(event_id) else {
                         return Box::pin(async { actix_web::HttpResponse::BadRequest().json(json!({"error":"invalid_event_id"})) });
                     };
+                    let repos = Repositories::from_request(&req);
                     Box::pin(async move {
-                        match update_set_by_id(doc!{ "event_id": event_oid }, &id).await {
+                        let Some(repos) = repos else { return repositories_missing(); };
+                        match update_set_by_id(&repos, doc!{ "event_id": event_oid }, &id).await {
                             Ok(modified) => actix_web::HttpResponse::Ok().json(json!({ "success": true, "modified": modified })),
                             Err(resp) => resp
                         }
@@ -497,3 +311,13 @@ impl AdmixResource for EventAttendeeResource {
         ]
     }
 }
+
+// No bulk delete or status change: both take or free seats, which has to go through
+// the attendance service to keep the event's head count and waitlist right
+impl BulkActions for EventAttendeeResource {
+    fn bulk_actions() -> Vec<BulkAction> {
+        vec![
+            BulkAction { name: "check_in", label: "Check in", operation: BulkOperation::Stamp { field: "check_in_date" } },
+        ]
+    }
+}
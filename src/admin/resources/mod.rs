@@ -4,4 +4,7 @@ pub mod config_resource;
 pub mod picture_resource;
 pub mod contact_resource;
 pub mod event_attendee_resource;
-pub mod event_resource;
\ No newline at end of file
+pub mod event_resource;
+pub mod audit_log_resource;
+pub mod sent_email_resource;
+pub mod admin_user_resource;
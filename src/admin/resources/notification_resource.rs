@@ -1,13 +1,22 @@
 // src/admin/resources/notification_resource.rs - Fixed Version
+use crate::admin::bulk_actions::{BulkAction, BulkActions, BulkOperation};
+use crate::admin::model_schema::ModelSchema;
+use crate::admin::records;
 use crate::db::mongo::get_collection;
+use crate::models::notification::Notification;
+use actix_web::{HttpRequest, HttpResponse};
 use adminx::AdmixResource;
 use async_trait::async_trait;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
 use mongodb::{Collection, bson::Document};
-use serde_json::{json, Value};
+use serde_json::Value;
 
 #[derive(Debug, Clone)]
 pub struct NotificationResource;
 
+static NOTIFICATION_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<Notification>);
+
 #[async_trait]
 impl AdmixResource for NotificationResource {
     // ===========================
@@ -51,137 +60,57 @@ impl AdmixResource for NotificationResource {
 
     // FIXED: Make permit_keys match the actual fields you want to use
     fn permit_keys(&self) -> Vec<&'static str> {
-        vec!["title", "message"]
+        NOTIFICATION_SCHEMA.permit_keys()
+    }
+
+    // ===========================
+    // CRUD OVERRIDES
+    // ===========================
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        records::list(self, req, query)
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::get(self, req, id)
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::create(self, req, payload)
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::update(self, req, id, payload)
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::delete(self, req, id)
     }
 
     // ===========================
     // UI STRUCTURE OVERRIDES (Optional)
     // ===========================
     fn form_structure(&self) -> Option<Value> {
-        Some(json!({
-            "groups": [
-                {
-                    "title": "Notification Details",
-                    "fields": [
-                        {
-                            "name": "title",
-                            "field_type": "text",
-                            "label": "Title",
-                            "value": "",
-                            "required": true,  // FIXED: Added required field
-                            "options": null
-                        },
-                        {
-                            "name": "message",
-                            "field_type": "textarea", 
-                            "label": "Description",
-                            "value": "",
-                            "required": true,  // FIXED: Added required field
-                            "options": null
-                        }
-                    ]
-                }
-            ]
-        }))
+        Some(NOTIFICATION_SCHEMA.form_structure())
     }
 
     fn list_structure(&self) -> Option<Value> {
-        Some(json!({
-            "columns": [
-                {
-                    "field": "title",
-                    "label": "Title",
-                    "sortable": true
-                },
-                {
-                    "field": "message", 
-                    "label": "Message",
-                    "sortable": true
-                },
-                {
-                    "field": "created_at",
-                    "label": "Created At",
-                    "type": "datetime",
-                    "sortable": true
-                },
-                {
-                    "field": "updated_at",
-                    "label": "Updated At",
-                    "type": "datetime",
-                    "sortable": true
-                }
-            ],
-            "actions": ["view", "edit", "delete"]
-        }))
+        Some(NOTIFICATION_SCHEMA.list_structure(&["view", "edit", "delete"]))
     }
 
     fn view_structure(&self) -> Option<Value> {
-        Some(json!({
-            "sections": [
-                {
-                    "title": "Notification Information",
-                    "fields": [
-                        {
-                            "field": "title",
-                            "label": "Title"
-                        },
-                        {
-                            "field": "message",
-                            "label": "Description"
-                        }
-                    ]
-                },
-                {
-                    "title": "System Information",
-                    "fields": [
-                        {
-                            "field": "id",  // FIXED: Use "id" instead of "_id" for templates
-                            "label": "Notification ID"
-                        },
-                        {
-                            "field": "created_at",
-                            "label": "Created At",
-                            "type": "datetime"
-                        },
-                        {
-                            "field": "updated_at", 
-                            "label": "Updated At",
-                            "type": "datetime"
-                        }
-                    ]
-                }
-            ]
-        }))
+        Some(NOTIFICATION_SCHEMA.view_structure())
     }
 
     fn filters(&self) -> Option<Value> {
-        Some(json!({
-            "filters": [
-                // ===========================
-                // TEXT FILTERS
-                // ===========================
-                {
-                    "field": "title",
-                    "type": "text",
-                    "label": "Title",
-                    "placeholder": "Search by title..."
-                },
-                // ===========================
-                // DATE RANGE FILTERS
-                // ===========================
-                {
-                    "field": "created_at",
-                    "type": "date_range",
-                    "label": "Created Date",
-                    "placeholder": "Select date range..."
-                },
-                {
-                    "field": "updated_at",
-                    "type": "date_range",
-                    "label": "Updated Date",
-                    "placeholder": "Select date range..."
-                }
-            ]
-        }))
-    }
-}
\ No newline at end of file
+        Some(NOTIFICATION_SCHEMA.filters())
+    }
+}
+
+impl BulkActions for NotificationResource {
+    fn bulk_actions() -> Vec<BulkAction> {
+        vec![
+            BulkAction { name: "delete", label: "Delete", operation: BulkOperation::SoftDelete },
+            BulkAction { name: "restore", label: "Restore", operation: BulkOperation::Restore },
+        ]
+    }
+}
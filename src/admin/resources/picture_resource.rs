@@ -1,45 +1,25 @@
 // src/admin/resources/image_resource.rs
+use crate::admin::bulk_actions::BulkActions;
+use crate::admin::model_schema::ModelSchema;
+use crate::admin::options::boolean_options;
+use crate::admin::records;
 use crate::db::mongo::get_collection;
+use crate::models::picture::Picture;
+use actix_web::{HttpRequest, HttpResponse};
 use adminx::{AdmixResource, error::AdminxError};
 use async_trait::async_trait;
-use crate::models::picture::PictureStatus;
+use once_cell::sync::Lazy;
 use mongodb::{Collection, bson::Document};
 use serde_json::{json, Value};
-use crate::enums::{
-    common_enums::{
-        StatusEnum,
-    }
-};
 use futures::future::BoxFuture;
 use std::collections::HashMap;
-use convert_case::{Case, Casing};
-use strum::IntoEnumIterator;
 use crate::utilities::s3_utility::{upload_image_to_s3};
 
-pub struct PictureOptions;
-
-impl PictureOptions {
-    pub fn statuses_options() -> Vec<Value> {
-        let mut options = vec![];
-        for variant in PictureStatus::iter() {
-            let value = serde_json::to_string(&variant).unwrap().replace('"', "");
-            let label = value.to_case(Case::Title);
-            options.push(json!({ "value": value, "label": label }));
-        }
-        options
-    }
-
-    pub fn boolean_options() -> Vec<Value> {
-        vec![
-            json!({ "value": "true",  "label": "True"  }),
-            json!({ "value": "false", "label": "False" }),
-        ]
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct PictureResource;
 
+static PICTURE_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<Picture>);
+
 #[async_trait]
 impl AdmixResource for PictureResource {
     // ===========================
@@ -97,7 +77,7 @@ impl AdmixResource for PictureResource {
     }
     
     fn permit_keys(&self) -> Vec<&'static str> {
-        vec!["title", "image_file", "status"]
+        PICTURE_SCHEMA.permit_keys()
     }
     
     // FIXED: Remove 'async' keyword and correct method signature
@@ -120,7 +100,7 @@ impl AdmixResource for PictureResource {
             match upload_image_to_s3(unique_filename.clone(), file_data).await {
                 Ok(public_url) => {
                     let mut urls = HashMap::new();
-                    urls.insert("image_url".to_string(), public_url);
+                    urls.insert("url".to_string(), public_url);
                     
                     tracing::info!("File uploaded successfully to S3: {}", unique_filename);
                     Ok(urls)
@@ -135,6 +115,48 @@ impl AdmixResource for PictureResource {
 
     
 
+    // ===========================
+    // CRUD OVERRIDES
+    // ===========================
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        records::list(self, req, query)
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::get(self, req, id)
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::create(self, req, payload)
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::update(self, req, id, payload)
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::delete(self, req, id)
+    }
+
+    fn create_with_files(
+        &self,
+        req: &HttpRequest,
+        form_data: HashMap<String, String>,
+        files: HashMap<String, (String, Vec<u8>)>,
+    ) -> BoxFuture<'static, HttpResponse> {
+        records::create_with_files(self, req, form_data, files)
+    }
+
+    fn update_with_files(
+        &self,
+        req: &HttpRequest,
+        id: String,
+        form_data: HashMap<String, String>,
+        files: HashMap<String, (String, Vec<u8>)>,
+    ) -> BoxFuture<'static, HttpResponse> {
+        records::update_with_files(self, req, id, form_data, files)
+    }
+
     // ===========================
     // UI STRUCTURE OVERRIDES
     // ===========================
@@ -165,7 +187,7 @@ impl AdmixResource for PictureResource {
                             "label": "Mark as Deleted",
                             "value": "false",
                             "required": false,
-                            "options": PictureOptions::boolean_options(),
+                            "options": boolean_options(),
                             "help_text": "Mark this image as deleted (soft delete)"
                         }
                     ]
@@ -175,120 +197,16 @@ impl AdmixResource for PictureResource {
     }
 
     fn list_structure(&self) -> Option<Value> {
-        Some(json!({
-            "columns": [
-                {
-                    "field": "title",
-                    "label": "Title",
-                    "sortable": true
-                },
-                {
-                    "field": "url", 
-                    "label": "Image URL",
-                    "sortable": false,
-                    "type": "url"
-                },
-                {
-                    "field": "status",
-                    "label": "Status",
-                    "sortable": true,
-                    "type": "badge"
-                },
-                {
-                    "field": "deleted",
-                    "label": "Deleted",
-                    "sortable": true,
-                    "type": "boolean"
-                },
-                {
-                    "field": "created_at",
-                    "label": "Created At",
-                    "type": "datetime",
-                    "sortable": true
-                }
-            ],
-            "actions": ["view", "edit", "delete"]
-        }))
+        Some(PICTURE_SCHEMA.list_structure(&["view", "edit", "delete"]))
     }
 
     fn view_structure(&self) -> Option<Value> {
-        Some(json!({
-            "sections": [
-                {
-                    "title": "Image Information",
-                    "fields": [
-                        {
-                            "field": "title",
-                            "label": "Title"
-                        },
-                        {
-                            "field": "url",
-                            "label": "Image URL",
-                            "type": "url"
-                        },
-                        {
-                            "field": "status",
-                            "label": "Status",
-                            "type": "badge"
-                        },
-                        {
-                            "field": "deleted",
-                            "label": "Deleted",
-                            "type": "boolean"
-                        }
-                    ]
-                },
-                {
-                    "title": "System Information",
-                    "fields": [
-                        {
-                            "field": "_id",
-                            "label": "Image ID"
-                        },
-                        {
-                            "field": "created_at",
-                            "label": "Created At",
-                            "type": "datetime"
-                        },
-                        {
-                            "field": "updated_at", 
-                            "label": "Updated At",
-                            "type": "datetime"
-                        }
-                    ]
-                }
-            ]
-        }))
+        Some(PICTURE_SCHEMA.view_structure())
     }
 
     fn filters(&self) -> Option<Value> {
-        Some(json!({
-            "title": "Image Filters",
-            "filters": [
-                {
-                    "field": "title",
-                    "type": "text",
-                    "label": "Title",
-                    "placeholder": "Search by title..."
-                },
-                {
-                    "field": "status",
-                    "type": "select",
-                    "label": "Status",
-                    "options": PictureOptions::statuses_options(),
-                },
-                {
-                    "field": "deleted",
-                    "type": "boolean",
-                    "label": "Show Deleted",
-                    "options": PictureOptions::boolean_options(),
-                },
-                {
-                    "field": "created_at",
-                    "type": "date_range",
-                    "label": "Created Date"
-                }
-            ]
-        }))
+        Some(PICTURE_SCHEMA.filters())
     }
-}
\ No newline at end of file
+}
+
+impl BulkActions for PictureResource {}
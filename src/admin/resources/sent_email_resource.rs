@@ -0,0 +1,145 @@
+// src/admin/resources/sent_email_resource.rs
+use crate::admin::bulk_actions::BulkActions;
+use crate::admin::model_schema::ModelSchema;
+use crate::db::mongo::get_collection;
+use crate::models::sent_email::SentEmail;
+use crate::repositories::Repositories;
+use crate::services::mail_service;
+use actix_web::{HttpRequest, HttpResponse};
+use adminx::AdmixResource;
+use adminx::menu::MenuAction;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use mongodb::{Collection, bson::{Document, oid::ObjectId}};
+use serde_json::{json, Value};
+
+
+#[derive(Debug, Clone)]
+pub struct SentEmailResource;
+
+static SENT_EMAIL_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<SentEmail>);
+
+/* --------------------------------- Helpers -------------------------------- */
+// Emails are only written by the mail service; the log can be read and failed
+// emails retried, nothing else.
+fn read_only() -> BoxFuture<'static, HttpResponse> {
+    Box::pin(async { HttpResponse::Forbidden().json(json!({"error":"sent_emails_are_read_only"})) })
+}
+
+#[async_trait]
+impl AdmixResource for SentEmailResource {
+    // ===========================
+    // REQUIRED IMPLEMENTATIONS
+    // ===========================
+    fn new() -> Self {
+        SentEmailResource
+    }
+
+    fn resource_name(&self) -> &'static str {
+        "Sent Emails"
+    }
+
+    fn base_path(&self) -> &'static str {
+        "sent_emails"
+    }
+
+    fn collection_name(&self) -> &'static str {
+        "sent_emails"
+    }
+
+    fn get_collection(&self) -> Collection<Document> {
+        get_collection::<Document>("sent_emails")
+    }
+
+    fn clone_box(&self) -> Box<dyn AdmixResource> {
+        Box::new(Self::new())
+    }
+
+    fn menu_group(&self) -> Option<&'static str> {
+        Some("Settings")
+    }
+
+    fn menu(&self) -> &'static str {
+        "Sent Emails"
+    }
+
+    // ===========================
+    // CONFIGURATION OVERRIDES
+    // ===========================
+    fn allowed_roles(&self) -> Vec<String> {
+        vec!["admin".to_string(), "superadmin".to_string()]
+    }
+
+    fn allowed_actions(&self) -> Option<Vec<MenuAction>> {
+        Some(vec![MenuAction::List, MenuAction::View])
+    }
+
+    fn permit_keys(&self) -> Vec<&'static str> {
+        SENT_EMAIL_SCHEMA.permit_keys()
+    }
+
+    fn create(&self, _req: &HttpRequest, _payload: Value) -> BoxFuture<'static, HttpResponse> {
+        read_only()
+    }
+
+    fn update(&self, _req: &HttpRequest, _id: String, _payload: Value) -> BoxFuture<'static, HttpResponse> {
+        read_only()
+    }
+
+    fn delete(&self, _req: &HttpRequest, _id: String) -> BoxFuture<'static, HttpResponse> {
+        read_only()
+    }
+
+    // ===========================
+    // UI STRUCTURE OVERRIDES (Optional)
+    // ===========================
+    fn list_structure(&self) -> Option<Value> {
+        Some(SENT_EMAIL_SCHEMA.list_structure(&["view"]))
+    }
+
+    fn view_structure(&self) -> Option<Value> {
+        Some(SENT_EMAIL_SCHEMA.view_structure())
+    }
+
+    fn filters(&self) -> Option<Value> {
+        Some(SENT_EMAIL_SCHEMA.filters())
+    }
+
+    // ===========================
+    // CUSTOM ACTIONS (Optional)
+    // ===========================
+    fn custom_actions(&self) -> Vec<adminx::actions::CustomAction> {
+        vec![
+            adminx::actions::CustomAction {
+                name: "retry",
+                method: "POST",
+                handler: |req, _path, _body| {
+                    let id = req.match_info().get("id").and_then(|id| ObjectId::parse_str(id).ok());
+                    let Some(id) = id else {
+                        return Box::pin(async { HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
+                    };
+                    Box::pin(async move {
+                        let Some(repos) = Repositories::from_request(&req) else {
+                            return HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}));
+                        };
+
+                        match mail_service::retry(&repos, id).await {
+                            Ok(true) => HttpResponse::Ok().json(json!({ "success": true, "message": "Email queued for another attempt" })),
+                            Ok(false) => HttpResponse::Conflict().json(json!({"error":"only_failed_emails_can_be_retried"})),
+                            Err(e) => e.error_response()
+                        }
+                    })
+                },
+                ui: Some(adminx::actions::ActionUi {
+                    label: Some("Retry".into()),
+                    confirm: Some("Send this email again?".into()),
+                    fields: None,
+                }),
+            },
+        ]
+    }
+}
+
+// The log is read-only, so nothing to run in bulk; the override is for the CSV export
+impl BulkActions for SentEmailResource {}
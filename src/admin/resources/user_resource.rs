@@ -1,14 +1,44 @@
 // src/admin/resources/user_resource.rs
+use crate::admin::bulk_actions::{BulkAction, BulkActions, BulkOperation};
+use crate::admin::model_schema::ModelSchema;
+use crate::admin::options::enum_options;
+use crate::admin::records;
 use crate::db::mongo::get_collection;
-use adminx::AdmixResource;
+use crate::enums::common_enums::BanTypeEnum;
+use crate::models::user::User;
+use crate::repositories::Repositories;
+use crate::requests::structures::user_structure::BanUserRequest;
+use crate::requests::validators::open_validator::validate_params;
+use crate::services::{auth_service, ban_service};
+use actix_session::SessionExt;
+use actix_web::{web, HttpRequest, HttpResponse};
+use adminx::{AdmixResource, AdminxConfig};
+use adminx::utils::auth::extract_claims_from_session;
 use async_trait::async_trait;
-use mongodb::{Collection, bson::Document};
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use mongodb::{Collection, bson::{Document, oid::ObjectId}};
 use serde_json::{json, Value};
 
 
 #[derive(Debug, Clone)]
 pub struct UserResource;
 
+static USER_SCHEMA: Lazy<ModelSchema> = Lazy::new(ModelSchema::of::<User>);
+
+/* --------------------------------- Helpers -------------------------------- */
+fn parse_oid_opt(s: &str) -> Option<ObjectId> {
+    ObjectId::parse_str(s).ok()
+}
+
+/// Email of the AdminX user behind the request, recorded on bans
+async fn admin_email(req: &HttpRequest) -> Option<String> {
+    let config = req.app_data::<web::Data<AdminxConfig>>()?;
+    extract_claims_from_session(&req.get_session(), config).await
+        .ok()
+        .map(|claims| claims.email)
+}
+
 #[async_trait]
 impl AdmixResource for UserResource {
     // ===========================
@@ -54,162 +84,192 @@ impl AdmixResource for UserResource {
     }
 
     fn permit_keys(&self) -> Vec<&'static str> {
-        vec!["first_name", "email", "status", "deleted"]
+        USER_SCHEMA.permit_keys()
+    }
+
+    // ===========================
+    // CRUD OVERRIDES
+    // ===========================
+    fn list(&self, req: &HttpRequest, query: String) -> BoxFuture<'static, HttpResponse> {
+        records::list(self, req, query)
+    }
+
+    fn get(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::get(self, req, id)
+    }
+
+    fn create(&self, req: &HttpRequest, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::create(self, req, payload)
+    }
+
+    fn update(&self, req: &HttpRequest, id: String, payload: Value) -> BoxFuture<'static, HttpResponse> {
+        records::update(self, req, id, payload)
+    }
+
+    fn delete(&self, req: &HttpRequest, id: String) -> BoxFuture<'static, HttpResponse> {
+        records::delete(self, req, id)
     }
 
     // ===========================
     // UI STRUCTURE OVERRIDES (Optional)
     // ===========================
     fn form_structure(&self) -> Option<Value> {
-        // Using manual form structure for better control
-        Some(json!({
-            "groups": [
-                {
-                    "title": "User Details",
-                    "fields": [
-                        {
-                            "name": "name",
-                            "field_type": "text",
-                            "label": "Full Name",
-                            "value": "",
-                            "options": null
-                        },
-                        {
-                            "name": "email",
-                            "field_type": "email", 
-                            "label": "Email Address",
-                            "value": "",
-                            "options": null
-                        }
-                    ]
-                }
-            ]
-        }))
+        Some(USER_SCHEMA.form_structure())
     }
 
     fn list_structure(&self) -> Option<Value> {
-        Some(json!({
-            "columns": [
-                {
-                    "field": "first_name",
-                    "label": "First Name",
-                    "sortable": true
-                },
-                {
-                    "field": "email", 
-                    "label": "Email",
-                    "sortable": true
-                },
-                {
-                    "field": "status", 
-                    "label": "Status",
-                    "sortable": true
-                },
-                {
-                    "field": "created_at",
-                    "label": "Created At",
-                    "type": "datetime",
-                    "sortable": true
-                }
-            ],
-            "actions": ["view", "edit", "delete"]
-        }))
+        Some(USER_SCHEMA.list_structure(&["view", "edit", "delete"]))
     }
 
     fn view_structure(&self) -> Option<Value> {
-        Some(json!({
-            "sections": [
-                {
-                    "title": "User Information",
-                    "fields": [
-                        {
-                            "field": "first_name",
-                            "label": "First Name"
-                        },
-                        {
-                            "field": "last_name",
-                            "label": "Last Name"
-                        },
-                        {
-                            "field": "email",
-                            "label": "Email"
-                        },
-                        {
-                            "field": "phone_number",
-                            "label": "Phone number"
-                        },
-                        {
-                            "field": "status",
-                            "label": "Status"
+        Some(USER_SCHEMA.view_structure())
+    }
+
+    fn filters(&self) -> Option<Value> {
+        Some(USER_SCHEMA.filters())
+    }
+
+    // ===========================
+    // CUSTOM ACTIONS (Optional)
+    // ===========================
+    fn custom_actions(&self) -> Vec<adminx::actions::CustomAction> {
+        vec![
+            adminx::actions::CustomAction {
+                name: "ban",
+                method: "POST",
+                handler: |req, _path, body| {
+                    let id = req.match_info().get("id").and_then(parse_oid_opt);
+                    let Some(id) = id else {
+                        return Box::pin(async { HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
+                    };
+                    Box::pin(async move {
+                        let Some(repos) = Repositories::from_request(&req) else {
+                            return HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}));
+                        };
+                        let Some(issued_by) = admin_email(&req).await else {
+                            return HttpResponse::Unauthorized().json(json!({"error":"unauthorized"}));
+                        };
+                        let payload: BanUserRequest = match serde_json::from_value(body) {
+                            Ok(payload) => payload,
+                            Err(e) => return HttpResponse::BadRequest().json(json!({"error":"invalid_payload","message":e.to_string()})),
+                        };
+                        if let Err(e) = validate_params(&payload).await {
+                            return e.error_response();
+                        }
+
+                        match ban_service::ban_user(&repos, id, &payload, &issued_by).await {
+                            Ok(ban) => HttpResponse::Ok().json(json!({
+                                "success": true,
+                                "message": "User has been banned",
+                                "ban_type": ban.ban_type,
+                                "expires_at": ban.expires_at.map(|at| at.to_chrono().to_rfc3339()),
+                            })),
+                            Err(e) => e.error_response()
+                        }
+                    })
+                },
+                ui: Some(adminx::actions::ActionUi {
+                    label: Some("Ban User".into()),
+                    confirm: Some("Are you sure you want to ban this user?".into()),
+                    fields: Some(vec![
+                        adminx::actions::ActionField {
+                            name: "reason".into(),
+                            label: Some("Reason".into()),
+                            field_type: "text".into(),
+                            required: Some(true),
+                            options: None,
                         },
-                        {
-                            "field": "created_at",
-                            "label": "Created AT"
+                        adminx::actions::ActionField {
+                            name: "duration".into(),
+                            label: Some("Duration (days)".into()),
+                            field_type: "number".into(),
+                            required: Some(false),
+                            options: None,
                         },
-                        {
-                            "field": "updated_at",
-                            "label": "Update AT"
+                        adminx::actions::ActionField {
+                            name: "ban_type".into(),
+                            label: Some("Ban Type".into()),
+                            field_type: "select".into(),
+                            required: Some(true),
+                            options: Some(enum_options::<BanTypeEnum>()),
                         },
-                    ]
+                    ]),
+                }),
+            },
+            adminx::actions::CustomAction {
+                name: "unban",
+                method: "POST",
+                handler: |req, _path, body| {
+                    let id = req.match_info().get("id").and_then(parse_oid_opt);
+                    let Some(id) = id else {
+                        return Box::pin(async { HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
+                    };
+                    let reason = body.get("reason").and_then(|v| v.as_str())
+                        .filter(|v| !v.trim().is_empty())
+                        .unwrap_or("manual")
+                        .to_string();
+                    Box::pin(async move {
+                        let Some(repos) = Repositories::from_request(&req) else {
+                            return HttpResponse::InternalServerError().json(json!({"error":"repositories_unavailable"}));
+                        };
+                        let Some(lifted_by) = admin_email(&req).await else {
+                            return HttpResponse::Unauthorized().json(json!({"error":"unauthorized"}));
+                        };
+
+                        match ban_service::unban_user(&repos, id, &lifted_by, &reason).await {
+                            Ok(()) => HttpResponse::Ok().json(json!({ "success": true, "message": "User has been unbanned" })),
+                            Err(e) => e.error_response()
+                        }
+                    })
                 },
-                {
-                    "title": "System Information",
-                    "fields": [
-                        {
-                            "field": "_id",
-                            "label": "User ID"
-                        },
-                        {
-                            "field": "created_at",
-                            "label": "Created At",
-                            "type": "datetime"
+                ui: Some(adminx::actions::ActionUi {
+                    label: Some("Unban User".into()),
+                    confirm: Some("Lift this user's ban?".into()),
+                    fields: Some(vec![
+                        adminx::actions::ActionField {
+                            name: "reason".into(),
+                            label: Some("Reason".into()),
+                            field_type: "text".into(),
+                            required: Some(false),
+                            options: None,
                         },
-                        {
-                            "field": "updated_at", 
-                            "label": "Updated At",
-                            "type": "datetime"
+                    ]),
+                }),
+            },
+            adminx::actions::CustomAction {
+                name: "sign_out_everywhere",
+                method: "POST",
+                handler: |req, _path, _body| {
+                    let id = req.match_info().get("id").and_then(parse_oid_opt);
+                    let Some(id) = id else {
+                        return Box::pin(async { HttpResponse::BadRequest().json(json!({"error":"invalid_object_id"})) });
+                    };
+                    Box::pin(async move {
+                        match auth_service::end_all_sessions(id).await {
+                            Ok(sessions) => HttpResponse::Ok().json(json!({
+                                "success": true,
+                                "message": format!("Signed out of {} session(s)", sessions),
+                            })),
+                            Err(e) => e.error_response()
                         }
-                    ]
-                }
-            ]
-        }))
+                    })
+                },
+                ui: Some(adminx::actions::ActionUi {
+                    label: Some("Sign Out All Devices".into()),
+                    confirm: Some("Sign this user out on every device?".into()),
+                    fields: None,
+                }),
+            },
+        ]
     }
+}
 
-    fn filters(&self) -> Option<Value> {
-        Some(json!({
-            "filters": [
-                // ===========================
-                // TEXT FILTERS (like your example)
-                // ===========================
-                {
-                    "field": "phone_number",
-                    "type": "text",
-                    "label": "Phone number",
-                    "placeholder": "Search by Phone..."
-                },
-                {
-                    "field": "email",
-                    "type": "text", 
-                    "label": "Email",
-                    "placeholder": "Search by email..."
-                },
-                
-                // ===========================
-                // DATE RANGE FILTERS
-                // ===========================
-                {
-                    "field": "created_at",
-                    "type": "date_range",
-                    "label": "Created Date",
-                    "placeholder": "Select date range..."
-                },
-                {
-                    "field": "updated_at",
-                    "type": "date_range",
-                    "label": "Updated Date",
-                    "placeholder": "Select date range..."
-                },
-            ]
-        }))
+// No bulk status change: blocking a user goes through the ban service
+impl BulkActions for UserResource {
+    fn bulk_actions() -> Vec<BulkAction> {
+        vec![
+            BulkAction { name: "delete", label: "Delete", operation: BulkOperation::SoftDelete },
+            BulkAction { name: "restore", label: "Restore", operation: BulkOperation::Restore },
+        ]
     }
-}
\ No newline at end of file
+}
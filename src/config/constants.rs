@@ -13,4 +13,23 @@ pub const EMAIL_GENERAL_LABEL: &str = "Srotas Space";
 
 
 pub static OTP_EXPIRY: usize = 300; // 5 minutes
-pub static JWT_EXPIRY_SECONDS: i64 = 2628000; // 1 Month
+pub static OTP_RATE_WINDOW_SECONDS: usize = 900; // 15 minutes
+pub static OTP_MAX_REQUESTS: i64 = 5; // codes per identifier per window
+pub static OTP_MAX_IP_REQUESTS: i64 = 20; // codes per client IP per window
+pub static OTP_MAX_FAILURES: i64 = 5; // wrong codes per identifier before lockout
+pub static OTP_MAX_IP_FAILURES: i64 = 20; // wrong codes per client IP before lockout
+pub static OTP_LOCKOUT_SECONDS: usize = 900; // 15 minutes
+pub static JWT_EXPIRY_SECONDS: i64 = 2628000; // 1 Month, sessions and refresh tokens
+pub static ACCESS_TOKEN_EXPIRY_SECONDS: i64 = 900; // 15 minutes
+pub static CONTACT_QR_EXPIRY_SECONDS: i64 = 86400; // 1 day, then the app fetches a new code
+pub static INVITATION_EXPIRY_JOB_SECONDS: u64 = 900; // 15 minutes
+pub static INVITATION_RATE_WINDOW_SECONDS: usize = 3600; // 1 hour
+pub static INVITATION_MAX_RECIPIENTS: i64 = 200; // recipients invited per organizer per window
+pub static BAN_EXPIRY_JOB_SECONDS: u64 = 300; // 5 minutes
+pub static EMAIL_DELIVERY_JOB_SECONDS: u64 = 60; // 1 minute
+pub static EVENT_REMINDER_JOB_SECONDS: u64 = 900; // 15 minutes
+pub static EVENT_REMINDER_LEAD_SECONDS: i64 = 86400; // 1 day
+pub static TOTP_PERIOD_SECONDS: u64 = 30;
+pub static TOTP_RECOVERY_CODES: usize = 10;
+pub static TOTP_MAX_FAILURES: i64 = 5; // wrong codes per operator before lockout
+pub static ADMIN_LOGIN_PENDING_SECONDS: i64 = 600; // 10 minutes to finish the TOTP step
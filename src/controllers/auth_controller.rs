@@ -0,0 +1,177 @@
+// src/controllers/auth_controller.rs
+
+use std::net::IpAddr;
+
+use actix_web::{web, Error, HttpRequest, HttpResponse, http::StatusCode};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::config::env_vars::get_custom_env;
+use crate::libs::custom_library::success_response;
+use crate::models::user::User;
+use crate::repositories::Repositories;
+use crate::middlewares::auth_middleware::AuthUser;
+use crate::requests::{
+    structures::open_structure::{OTPRequest, RefreshTokenRequest, VerifyOTP},
+    validators::open_validator::validate_params,
+};
+use crate::services::auth_service::{self, TokenPair};
+
+
+/// Proxies allowed to say who the client is, from `TRUSTED_PROXIES` (comma separated IPs)
+static TRUSTED_PROXIES: Lazy<Vec<IpAddr>> = Lazy::new(|| {
+    get_custom_env("TRUSTED_PROXIES", "")
+        .split(',')
+        .filter_map(|ip| ip.trim().parse().ok())
+        .collect()
+});
+
+/// The address limits are counted against: the connecting peer, unless it is a trusted
+/// proxy, in which case the nearest `X-Forwarded-For` hop that is not one. Forwarding
+/// headers from anyone else are ignored, so a client cannot pick a fresh address per request.
+fn client_ip(req: &HttpRequest) -> String {
+    resolve_client_ip(req, &TRUSTED_PROXIES).map_or_else(|| "unknown".to_string(), |ip| ip.to_string())
+}
+
+fn resolve_client_ip(req: &HttpRequest, trusted: &[IpAddr]) -> Option<IpAddr> {
+    let peer = req.peer_addr()?.ip();
+    if !trusted.contains(&peer) {
+        return Some(peer);
+    }
+
+    let forwarded: Vec<IpAddr> = req.headers().get_all("x-forwarded-for")
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|hop| hop.trim().parse().ok())
+        .collect::<Option<Vec<IpAddr>>>()
+        .unwrap_or_default();
+    Some(forwarded.into_iter().rev().find(|hop| !trusted.contains(hop)).unwrap_or(peer))
+}
+
+/// The signed-in user as returned to the apps; tokens and secrets stay server-side
+pub fn account_json(user: &User) -> Value {
+    json!({
+        "id": user.id.map(|id| id.to_hex()),
+        "first_name": user.first_name,
+        "last_name": user.last_name,
+        "email": user.email,
+        "phone_number": user.phone_number,
+        "ccode": user.ccode,
+        "username": user.username,
+        "status": user.status,
+        "onboard": user.onboard,
+    })
+}
+
+fn tokens_json(tokens: &TokenPair) -> Value {
+    json!({
+        "access_token": tokens.access.token,
+        "token_type": "Bearer",
+        "expires_in": tokens.access.expires_in,
+        "refresh_token": tokens.refresh_token,
+        "refresh_expires_in": tokens.refresh_expires_in,
+    })
+}
+
+/// POST /api/v1/auth/otp
+/// Sends a sign-in code to an email address or phone number
+pub async fn request_otp(
+    req: HttpRequest,
+    repos: web::Data<Repositories>,
+    payload: web::Json<OTPRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let expires_in = auth_service::request_otp(&repos, &payload, &client_ip(&req)).await?;
+    Ok(success_response(StatusCode::ACCEPTED, "Code sent", json!({
+        "expires_in": expires_in,
+    })))
+}
+
+/// POST /api/v1/auth/otp/verify
+/// Exchanges a sign-in code for an access token, creating the account on first sign-in
+pub async fn verify_otp(
+    req: HttpRequest,
+    repos: web::Data<Repositories>,
+    payload: web::Json<VerifyOTP>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let sign_in = auth_service::verify_otp(&repos, &payload, &client_ip(&req)).await?;
+    let mut data = tokens_json(&sign_in.tokens);
+    data["new_user"] = json!(sign_in.new_user);
+    data["user"] = account_json(&sign_in.user);
+    Ok(success_response(StatusCode::OK, "Signed in", data))
+}
+
+/// POST /api/v1/auth/refresh
+/// Trades a refresh token for a new token pair; the old refresh token stops working
+pub async fn refresh_token(
+    repos: web::Data<Repositories>,
+    payload: web::Json<RefreshTokenRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let tokens = auth_service::refresh_session(&repos, &payload.refresh_token).await?;
+    Ok(success_response(StatusCode::OK, "Token refreshed", tokens_json(&tokens)))
+}
+
+/// GET /api/v1/auth/me
+pub async fn get_me(AuthUser(auth): AuthUser) -> Result<HttpResponse, Error> {
+    Ok(success_response(StatusCode::OK, "Account fetched", account_json(&auth.user)))
+}
+
+/// POST /api/v1/auth/logout
+/// Ends the session of the token used, on this device only
+pub async fn logout(AuthUser(auth): AuthUser) -> Result<HttpResponse, Error> {
+    auth_service::end_session(auth.user_id()?, &auth.claims.sid).await?;
+    Ok(success_response(StatusCode::OK, "Signed out", json!({})))
+}
+
+/// POST /api/v1/auth/logout/all
+/// Ends every session of the user, this one included
+pub async fn logout_all(AuthUser(auth): AuthUser) -> Result<HttpResponse, Error> {
+    let sessions = auth_service::end_all_sessions(auth.user_id()?).await?;
+    Ok(success_response(StatusCode::OK, "Signed out on all devices", json!({
+        "sessions": sessions,
+    })))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    const PROXY: &str = "10.0.0.1";
+
+    fn request(peer: &str, forwarded_for: Option<&str>) -> HttpRequest {
+        let mut request = TestRequest::default().peer_addr(format!("{}:4000", peer).parse().unwrap());
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.insert_header(("X-Forwarded-For", forwarded_for));
+        }
+        request.to_http_request()
+    }
+
+    fn resolved(peer: &str, forwarded_for: Option<&str>) -> String {
+        let trusted = [PROXY.parse().unwrap()];
+        resolve_client_ip(&request(peer, forwarded_for), &trusted).unwrap().to_string()
+    }
+
+    #[test]
+    fn forwarding_headers_from_untrusted_peers_are_ignored() {
+        assert_eq!(resolved("203.0.113.7", Some("198.51.100.1")), "203.0.113.7");
+    }
+
+    #[test]
+    fn trusted_proxies_name_the_nearest_untrusted_hop() {
+        assert_eq!(resolved(PROXY, Some("198.51.100.1, 203.0.113.9")), "203.0.113.9");
+        assert_eq!(resolved(PROXY, Some("198.51.100.1, 10.0.0.1")), "198.51.100.1");
+    }
+
+    #[test]
+    fn trusted_proxy_without_a_usable_header_counts_as_the_client() {
+        assert_eq!(resolved(PROXY, None), PROXY);
+        assert_eq!(resolved(PROXY, Some("not-an-ip")), PROXY);
+    }
+}
@@ -0,0 +1,73 @@
+// src/controllers/contact_controller.rs
+
+use actix_web::{web, Error, HttpResponse, http::StatusCode};
+use serde_json::json;
+
+use crate::enums::common_enums::ScanRQCodeStruct;
+use crate::middlewares::auth_middleware::AuthUser;
+use crate::repositories::Repositories;
+use crate::requests::{
+    structures::contact_structure::{ContactPathInfo, VCardQuery},
+    validators::open_validator::validate_params,
+};
+use crate::libs::custom_library::success_response;
+use crate::services::contact_exchange_service;
+use crate::utilities::bason_utility::{parse_object_id, to_api_json};
+use crate::utilities::vcard_utility::{VCard, VCardVersion};
+use crate::handle_custom_error;
+
+
+/// GET /api/v1/contacts/{id}/vcard
+/// Only for the user who saved the contact
+pub async fn get_contact_vcard(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<ContactPathInfo>,
+    query: web::Query<VCardQuery>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*query).await?;
+
+    let contact_id = parse_object_id(&path.id, "contact id")?;
+    let contact = repos.contacts.find_by_id(contact_id).await?;
+    if contact.parent_user_id != Some(auth.user_id()?) {
+        handle_custom_error!(not_found, 404, "Contact not found");
+    }
+    let card = VCard::from(&contact);
+    Ok(vcard_response(&card, query.version.unwrap_or_default()))
+}
+
+/// POST /api/v1/contacts/scan
+/// Saves the person behind a scanned user QR code as a contact of the signed-in user
+pub async fn scan_qr_code(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    payload: web::Json<ScanRQCodeStruct>,
+) -> Result<HttpResponse, Error> {
+    let Some(code) = payload.code.as_deref().filter(|code| !code.trim().is_empty()) else {
+        handle_custom_error!(bad_request, 400, "code is required");
+    };
+    let scanner_id = auth.user_id()?;
+    let event_id = payload.event_id.as_deref()
+        .filter(|event_id| !event_id.trim().is_empty())
+        .map(|event_id| parse_object_id(event_id, "event_id"))
+        .transpose()?;
+
+    let (contact, created) = contact_exchange_service::scan_user_qr(&repos, scanner_id, code, event_id).await?;
+    let (status, message) = if created {
+        (StatusCode::CREATED, "Contact added")
+    } else {
+        (StatusCode::OK, "Contact already saved")
+    };
+    Ok(success_response(status, message, json!({
+        "contact": to_api_json(&contact)?,
+        "created": created,
+    })))
+}
+
+/// A vCard as a download
+pub fn vcard_response(card: &VCard, version: VCardVersion) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/vcard; charset=utf-8")
+        .insert_header(("Content-Disposition", format!("attachment; filename=\"{}\"", card.file_name())))
+        .body(card.render(version))
+}
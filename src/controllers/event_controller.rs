@@ -0,0 +1,190 @@
+// src/controllers/event_controller.rs
+
+use actix_web::{web, Error, HttpResponse, http::StatusCode};
+use mongodb::{bson::oid::ObjectId, Database};
+use serde_json::{json, Value};
+
+use crate::middlewares::auth_middleware::AuthUser;
+use crate::models::event::Event;
+use crate::repositories::Repositories;
+use crate::requests::{
+    structures::event_structure::{
+        EventRequestBody,
+        EventRequestFilters,
+        EventPathInfo,
+        JoinEventRequest,
+        LeaveEventRequest,
+        EventAnalyticsRequest,
+        EventSearchRequest,
+    },
+    structures::contact_structure::QrQuery,
+    validators::open_validator::validate_params,
+};
+use crate::libs::custom_library::success_response;
+use crate::controllers::user_controller::qr_response;
+use crate::services::{attendance_service, analytics_service};
+use crate::utilities::bason_utility::{parse_object_id, to_api_json};
+use crate::utilities::qr_utility::QrPayload;
+use crate::handle_custom_error;
+
+
+/// The event, when the signed-in user organizes it
+async fn organized_event(repos: &Repositories, event_id: ObjectId, user_id: ObjectId) -> Result<Event, Error> {
+    let event = repos.events.find_by_id(event_id).await?;
+    if event.user_id != user_id {
+        handle_custom_error!(forbidden, 403, "Only the organizer can change this event");
+    }
+    Ok(event)
+}
+
+/// POST /api/v1/events
+/// The signed-in user becomes the organizer
+pub async fn create_event(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    payload: web::Json<EventRequestBody>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let event = repos.events.create(&Event::from_request(&payload, auth.user_id()?)).await?;
+    Ok(success_response(StatusCode::CREATED, "Event created", to_api_json(&event)?))
+}
+
+/// GET /api/v1/events/{id}
+pub async fn get_event(
+    repos: web::Data<Repositories>,
+    path: web::Path<EventPathInfo>,
+) -> Result<HttpResponse, Error> {
+    let event_id = parse_object_id(&path.id, "event id")?;
+    let event = repos.events.find_by_id(event_id).await?;
+    Ok(success_response(StatusCode::OK, "Event fetched", to_api_json(&event)?))
+}
+
+/// GET /api/v1/events
+pub async fn list_events(
+    repos: web::Data<Repositories>,
+    query: web::Query<EventRequestFilters>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*query).await?;
+
+    let (events, total) = repos.events.list(&query).await?;
+    let data = events.iter()
+        .map(to_api_json)
+        .collect::<Result<Vec<Value>, Error>>()?;
+
+    Ok(success_response(StatusCode::OK, "Events fetched", json!({
+        "events": data,
+        "total": total,
+        "page": query.page.unwrap_or(crate::config::constants::DEFAULT_PAGE),
+        "limit": query.limit.unwrap_or(crate::config::constants::DEFAULT_LIMIT),
+    })))
+}
+
+/// POST /api/v1/events/search
+pub async fn search_events(
+    repos: web::Data<Repositories>,
+    payload: web::Json<EventSearchRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let (events, total) = repos.events.search(&payload).await?;
+    let data = events.iter()
+        .map(|(event, distance_km)| {
+            let mut data = to_api_json(event)?;
+            if let Some(distance_km) = distance_km {
+                data["distance_km"] = json!(distance_km);
+            }
+            Ok(data)
+        })
+        .collect::<Result<Vec<Value>, Error>>()?;
+
+    Ok(success_response(StatusCode::OK, "Events fetched", json!({
+        "events": data,
+        "total": total,
+        "page": payload.page.unwrap_or(crate::config::constants::DEFAULT_PAGE as u32),
+        "limit": payload.limit.unwrap_or(crate::config::constants::DEFAULT_LIMIT as u32),
+    })))
+}
+
+/// PUT /api/v1/events/{id}
+pub async fn update_event(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<EventPathInfo>,
+    payload: web::Json<EventRequestBody>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let event_id = parse_object_id(&path.id, "event id")?;
+    organized_event(&repos, event_id, auth.user_id()?).await?;
+    let event = repos.events.update(event_id, &payload).await?;
+    Ok(success_response(StatusCode::OK, "Event updated", to_api_json(&event)?))
+}
+
+/// DELETE /api/v1/events/{id}
+pub async fn delete_event(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<EventPathInfo>,
+) -> Result<HttpResponse, Error> {
+    let event_id = parse_object_id(&path.id, "event id")?;
+    organized_event(&repos, event_id, auth.user_id()?).await?;
+    repos.events.soft_delete(event_id).await?;
+    Ok(success_response(StatusCode::OK, "Event deleted", json!({ "id": path.id })))
+}
+
+/// POST /api/v1/events/{id}/join
+/// Registers the signed-in user
+pub async fn join_event(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<EventPathInfo>,
+    payload: web::Json<JoinEventRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let event_id = parse_object_id(&path.id, "event id")?;
+    let attendee = attendance_service::join_event(&repos, event_id, auth.user_id()?, &payload).await?;
+    let message = if attendee.waitlisted_at.is_some() { "Added to waitlist" } else { "Joined event" };
+    Ok(success_response(StatusCode::CREATED, message, to_api_json(&attendee)?))
+}
+
+/// POST /api/v1/events/{id}/leave
+/// Cancels the signed-in user's registration
+pub async fn leave_event(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<EventPathInfo>,
+    payload: web::Json<LeaveEventRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let event_id = parse_object_id(&path.id, "event id")?;
+    attendance_service::leave_event(&repos, event_id, auth.user_id()?, &payload).await?;
+    Ok(success_response(StatusCode::OK, "Left event", json!({ "event_id": path.id })))
+}
+
+/// GET /api/v1/events/{id}/qr
+/// The code attendees scan to open the event
+pub async fn get_event_qr(
+    repos: web::Data<Repositories>,
+    path: web::Path<EventPathInfo>,
+    query: web::Query<QrQuery>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*query).await?;
+
+    let event_id = parse_object_id(&path.id, "event id")?;
+    repos.events.find_by_id(event_id).await?;
+    qr_response(&QrPayload::Event(event_id), &query)
+}
+
+/// POST /api/v1/events/analytics
+pub async fn event_analytics(
+    db: web::Data<Database>,
+    payload: web::Json<EventAnalyticsRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let report = analytics_service::event_analytics(&db, &payload).await?;
+    Ok(success_response(StatusCode::OK, "Event analytics fetched", report))
+}
@@ -0,0 +1,76 @@
+// src/controllers/invitation_controller.rs
+
+use actix_web::{web, Error, HttpResponse, http::StatusCode};
+use serde_json::{json, Value};
+
+use crate::middlewares::auth_middleware::AuthUser;
+use crate::repositories::Repositories;
+use crate::requests::{
+    structures::event_structure::{
+        EventInvitationRequest,
+        InvitationResponseRequest,
+        InvitationPathInfo,
+    },
+    validators::open_validator::validate_params,
+};
+use crate::libs::custom_library::success_response;
+use crate::services::invitation_service;
+use crate::utilities::bason_utility::to_api_json;
+
+
+/// POST /api/v1/invitations
+/// Only the event's organizer can invite
+pub async fn create_invitations(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    payload: web::Json<EventInvitationRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let invitations = invitation_service::create_invitations(&repos, auth.user_id()?, &payload).await?;
+    let data = invitations.iter()
+        .map(to_api_json)
+        .collect::<Result<Vec<Value>, Error>>()?;
+
+    Ok(success_response(StatusCode::CREATED, "Invitations created", json!({
+        "invitations": data,
+        "total": invitations.len(),
+    })))
+}
+
+/// GET /api/v1/invitations/{token}
+pub async fn get_invitation(
+    repos: web::Data<Repositories>,
+    path: web::Path<InvitationPathInfo>,
+) -> Result<HttpResponse, Error> {
+    let invitation = repos.invitations.find_by_token(&path.id).await?;
+    Ok(success_response(StatusCode::OK, "Invitation fetched", to_api_json(&invitation)?))
+}
+
+/// POST /api/v1/invitations/{token}/accept
+pub async fn accept_invitation(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<InvitationPathInfo>,
+    payload: web::Json<InvitationResponseRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let (attendee, guest) = invitation_service::accept_invitation(&repos, &path.id, &auth.user, &payload).await?;
+    let guest = guest.as_ref().map(to_api_json).transpose()?;
+
+    Ok(success_response(StatusCode::OK, "Invitation accepted", json!({
+        "attendee": to_api_json(&attendee)?,
+        "plus_one": guest,
+    })))
+}
+
+/// POST /api/v1/invitations/{token}/decline
+pub async fn decline_invitation(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<InvitationPathInfo>,
+) -> Result<HttpResponse, Error> {
+    invitation_service::decline_invitation(&repos, &path.id, &auth.user).await?;
+    Ok(success_response(StatusCode::OK, "Invitation declined", json!({ "token": path.id })))
+}
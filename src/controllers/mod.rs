@@ -0,0 +1,7 @@
+pub mod auth_controller;
+pub mod contact_controller;
+pub mod event_controller;
+pub mod invitation_controller;
+pub mod search_controller;
+pub mod support_controller;
+pub mod user_controller;
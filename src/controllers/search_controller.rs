@@ -0,0 +1,23 @@
+// src/controllers/search_controller.rs
+
+use actix_web::{web, Error, HttpResponse, http::StatusCode};
+use mongodb::Database;
+
+use crate::requests::{
+    structures::search_structure::SearchRequest,
+    validators::open_validator::validate_params,
+};
+use crate::libs::custom_library::success_response;
+use crate::services::search_service;
+
+
+/// GET /api/v1/search?query=...&collections=events,contacts
+pub async fn search(
+    db: web::Data<Database>,
+    query: web::Query<SearchRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*query).await?;
+
+    let results = search_service::search(&db, &query).await?;
+    Ok(success_response(StatusCode::OK, "Search results fetched", results))
+}
@@ -0,0 +1,25 @@
+// src/controllers/support_controller.rs
+
+use actix_web::{web, Error, HttpResponse, http::StatusCode};
+use serde_json::json;
+
+use crate::enums::common_enums::SupportRequest;
+use crate::libs::custom_library::success_response;
+use crate::repositories::Repositories;
+use crate::requests::validators::open_validator::validate_params;
+use crate::services::mail_service;
+
+
+/// POST /api/v1/support
+/// Emails the ticket to the support inbox; it is delivered in the background
+pub async fn create_support_ticket(
+    repos: web::Data<Repositories>,
+    payload: web::Json<SupportRequest>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*payload).await?;
+
+    let email = mail_service::open_support_ticket(&repos, &payload).await?;
+    Ok(success_response(StatusCode::ACCEPTED, "Support ticket received", json!({
+        "ticket_id": email.id.map(|id| id.to_hex()),
+    })))
+}
@@ -0,0 +1,76 @@
+// src/controllers/user_controller.rs
+
+use actix_web::{web, Error, HttpResponse};
+use mongodb::bson::oid::ObjectId;
+
+use crate::controllers::contact_controller::vcard_response;
+use crate::enums::common_enums::QRRequest;
+use crate::middlewares::auth_middleware::AuthUser;
+use crate::models::user::User;
+use crate::repositories::Repositories;
+use crate::requests::{
+    structures::contact_structure::{QrQuery, VCardQuery},
+    validators::open_validator::validate_params,
+};
+use crate::utilities::bason_utility::parse_object_id;
+use crate::utilities::qr_utility::{render_qr, QrPayload, DEFAULT_QR_SIZE};
+use crate::utilities::vcard_utility::VCard;
+use crate::handle_custom_error;
+
+
+/// GET /api/v1/users/{user_id}/vcard
+/// The signed-in user's own card, or that of someone they saved as a contact
+pub async fn get_user_vcard(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<QRRequest>,
+    query: web::Query<VCardQuery>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*query).await?;
+
+    let viewer_id = auth.user_id()?;
+    let user_id = parse_object_id(&path.user_id, "user id")?;
+    if user_id != viewer_id && repos.contacts.find_for_user(viewer_id, user_id).await?.is_none() {
+        handle_custom_error!(not_found, 404, "User not found");
+    }
+
+    let user = shareable_user(&repos, user_id).await?;
+    Ok(vcard_response(&VCard::from(&user), query.version.unwrap_or_default()))
+}
+
+/// GET /api/v1/users/{user_id}/qr
+/// The code others scan to save the user as a contact; only the user gets theirs
+pub async fn get_user_qr(
+    AuthUser(auth): AuthUser,
+    repos: web::Data<Repositories>,
+    path: web::Path<QRRequest>,
+    query: web::Query<QrQuery>,
+) -> Result<HttpResponse, Error> {
+    validate_params(&*query).await?;
+
+    let user_id = parse_object_id(&path.user_id, "user id")?;
+    if user_id != auth.user_id()? {
+        handle_custom_error!(forbidden, 403, "You can only get your own QR code");
+    }
+    shareable_user(&repos, user_id).await?;
+    qr_response(&QrPayload::User(user_id), &query)
+}
+
+/// Live, unlocked users only; a locked account is not shared
+async fn shareable_user(repos: &Repositories, user_id: ObjectId) -> Result<User, Error> {
+    let user = repos.users.find_active_by_id(user_id).await?;
+    if user.locked {
+        handle_custom_error!(not_found, 404, "User not found");
+    }
+    Ok(user)
+}
+
+/// A QR image, inline so clients can show it directly
+pub fn qr_response(payload: &QrPayload, query: &QrQuery) -> Result<HttpResponse, Error> {
+    let format = query.format.unwrap_or_default();
+    let image = render_qr(payload, format, query.size.unwrap_or(DEFAULT_QR_SIZE))?;
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header(("Cache-Control", payload.cache_control()))
+        .body(image))
+}
@@ -0,0 +1,298 @@
+// src/db/indexes.rs
+use log::{info, warn};
+use mongodb::{
+    bson::{doc, Bson, Document},
+    error::{ErrorKind, WriteFailure},
+    options::IndexOptions,
+    Client, Collection, IndexModel,
+};
+use futures::TryStreamExt;
+
+use crate::config::env_vars::get_custom_env;
+
+/// One declared index. `name` is the identity used to diff against the server.
+pub struct IndexSpec {
+    pub collection: &'static str,
+    pub name: &'static str,
+    pub keys: Document,
+    pub unique: bool,
+    pub sparse: bool,
+    pub weights: Option<Document>,
+    pub partial_filter: Option<Document>,
+}
+
+impl IndexSpec {
+    fn new(collection: &'static str, name: &'static str, keys: Document) -> Self {
+        Self { collection, name, keys, unique: false, sparse: false, weights: None, partial_filter: None }
+    }
+
+    fn unique(mut self) -> Self {
+        self.unique = true;
+        self
+    }
+
+    fn sparse(mut self) -> Self {
+        self.sparse = true;
+        self
+    }
+
+    fn weights(mut self, weights: Document) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    fn partial(mut self, filter: Document) -> Self {
+        self.partial_filter = Some(filter);
+        self
+    }
+
+    fn is_text(&self) -> bool {
+        self.keys.values().any(|v| v.as_str() == Some("text"))
+    }
+
+    fn to_model(&self) -> IndexModel {
+        let options = IndexOptions::builder()
+            .name(Some(self.name.to_string()))
+            .unique(self.unique.then_some(true))
+            .sparse(self.sparse.then_some(true))
+            .weights(self.weights.clone())
+            .partial_filter_expression(self.partial_filter.clone())
+            .build();
+
+        IndexModel::builder().keys(self.keys.clone()).options(options).build()
+    }
+
+    /// Whether an index already on the server matches this declaration
+    fn matches(&self, existing: &IndexModel) -> bool {
+        let options = existing.options.as_ref();
+        let flag = |value: Option<bool>| value.unwrap_or(false);
+
+        // Text indexes are stored as `{ _fts: "text", _ftsx: 1 }`, so compare their weights instead
+        let same_keys = if self.is_text() {
+            let weight = |value: &Bson| match value {
+                Bson::Int32(v) => *v as i64,
+                Bson::Int64(v) => *v,
+                Bson::Double(v) => *v as i64,
+                _ => 0,
+            };
+            let declared: Vec<(String, i64)> = {
+                let mut w: Vec<_> = self.keys.keys()
+                    .map(|field| (field.clone(), self.weights.as_ref().and_then(|w| w.get(field)).map(weight).unwrap_or(1)))
+                    .collect();
+                w.sort();
+                w
+            };
+            let actual: Vec<(String, i64)> = {
+                let mut w: Vec<_> = options.and_then(|o| o.weights.as_ref())
+                    .map(|w| w.iter().map(|(field, value)| (field.clone(), weight(value))).collect())
+                    .unwrap_or_default();
+                w.sort();
+                w
+            };
+            declared == actual
+        } else {
+            normalize(&self.keys) == normalize(&existing.keys)
+        };
+
+        same_keys
+            && flag(options.and_then(|o| o.unique)) == self.unique
+            && flag(options.and_then(|o| o.sparse)) == self.sparse
+            && options.and_then(|o| o.partial_filter_expression.clone()) == self.partial_filter
+    }
+}
+
+/// Index directions come back as Int32 or Double depending on who created them
+fn normalize(keys: &Document) -> Vec<(String, String)> {
+    keys.iter()
+        .map(|(field, value)| {
+            let value = match value {
+                Bson::Int32(v) => v.to_string(),
+                Bson::Int64(v) => v.to_string(),
+                Bson::Double(v) => (*v as i64).to_string(),
+                other => other.to_string(),
+            };
+            (field.clone(), value)
+        })
+        .collect()
+}
+
+/// Every index the application relies on. Add new ones here; `MaintainMongoIndex`
+/// creates anything missing at startup and reports what is no longer declared.
+pub fn declared_indexes() -> Vec<IndexSpec> {
+    vec![
+        /*----- users -----*/
+        // Email: Unique, absent on accounts created by a phone sign-in
+        IndexSpec::new("users", "user_email_unique", doc! { "email": 1 }).unique().sparse(),
+        // Unique BUT Optional (Nullable)
+        IndexSpec::new("users", "user_official_email", doc! { "official_email": 1 }).unique().sparse(),
+        IndexSpec::new("users", "user_phone_unique", doc! { "phone_number": 1 }).unique().sparse(),
+        IndexSpec::new("users", "user_deleted_index", doc! { "deleted": 1 }),
+        IndexSpec::new("users", "user_text_search", doc! {
+            "first_name": "text", "last_name": "text", "username": "text", "email": "text", "company_name": "text"
+        })
+        .weights(doc! { "first_name": 10, "last_name": 10, "username": 8, "email": 5, "company_name": 3 }),
+
+        /*----- user_bans -----*/
+        IndexSpec::new("user_bans", "user_ban_history_index", doc! { "user_id": 1, "created_at": -1 }),
+        IndexSpec::new("user_bans", "user_ban_expiry_index", doc! { "active": 1, "expires_at": 1 }),
+
+        /*----- audit_logs -----*/
+        IndexSpec::new("audit_logs", "audit_log_resource_index", doc! { "resource": 1, "created_at": -1 }),
+        IndexSpec::new("audit_logs", "audit_log_actor_index", doc! { "actor": 1, "created_at": -1 }),
+        IndexSpec::new("audit_logs", "audit_log_document_index", doc! { "document_id": 1, "created_at": -1 }),
+
+        /*----- events -----*/
+        IndexSpec::new("events", "event_geo_location_2dsphere", doc! { "geo_location": "2dsphere" }),
+        IndexSpec::new("events", "event_text_search", doc! {
+            "title": "text", "description": "text", "tags": "text", "organizer_name": "text"
+        })
+        .weights(doc! { "title": 10, "tags": 5, "organizer_name": 3, "description": 1 }),
+        IndexSpec::new("events", "event_user_id_index", doc! { "user_id": 1, "deleted": 1 }),
+        IndexSpec::new("events", "event_listing_index", doc! { "deleted": 1, "status": 1, "event_date": 1 }),
+        IndexSpec::new("events", "event_category_index", doc! { "category": 1, "event_date": 1 }),
+        IndexSpec::new("events", "event_reminder_index", doc! { "reminder_sent_at": 1, "start_time": 1 }),
+
+        /*----- event_attendees -----*/
+        // One live registration per user and event; guests (no user_id) and cancelled rows are exempt
+        IndexSpec::new("event_attendees", "event_attendee_event_user_unique", doc! { "event_id": 1, "user_id": 1 })
+            .unique()
+            .partial(doc! { "user_id": { "$type": "objectId" }, "deleted": false }),
+        IndexSpec::new("event_attendees", "event_attendee_waitlist_index", doc! { "event_id": 1, "registration_status": 1, "waitlisted_at": 1 }),
+        IndexSpec::new("event_attendees", "event_attendee_plus_one_index", doc! { "plus_one_of": 1 }).sparse(),
+
+        /*----- invitations -----*/
+        IndexSpec::new("invitations", "invitation_token_unique", doc! { "token": 1 }).unique().sparse(),
+        IndexSpec::new("invitations", "invitation_event_status_index", doc! { "event_id": 1, "status": 1 }),
+        IndexSpec::new("invitations", "invitation_expiry_index", doc! { "status": 1, "expires_at": 1 }),
+
+        /*----- Contacts -----*/
+        IndexSpec::new("Contacts", "contact_user_parent_index", doc! { "user_id": 1, "parent_user_id": 1 }),
+        IndexSpec::new("Contacts", "contact_parent_deleted_index", doc! { "parent_user_id": 1, "deleted": 1 }),
+        IndexSpec::new("Contacts", "contact_text_search", doc! {
+            "first_name": "text", "last_name": "text", "company_name": "text", "email": "text"
+        })
+        .weights(doc! { "first_name": 10, "last_name": 10, "company_name": 5, "email": 5 }),
+
+        /*----- notifications -----*/
+        IndexSpec::new("notifications", "notification_user_index", doc! { "user_id": 1, "deleted": 1, "created_at": -1 }),
+
+        /*----- configs -----*/
+        IndexSpec::new("configs", "config_key_unique", doc! { "key": 1 }).unique(),
+
+        /*----- pictures -----*/
+        IndexSpec::new("pictures", "picture_deleted_status_index", doc! { "deleted": 1, "status": 1 }),
+
+        /*----- sent_emails -----*/
+        // The delivery queue: due emails are claimed by status and next attempt
+        IndexSpec::new("sent_emails", "sent_email_queue_index", doc! { "status": 1, "next_attempt_at": 1 }),
+        IndexSpec::new("sent_emails", "sent_email_recipient_index", doc! { "to": 1, "created_at": -1 }),
+
+        /*----- admin_two_factors -----*/
+        IndexSpec::new("admin_two_factors", "admin_two_factor_email_unique", doc! { "email": 1 }).unique(),
+
+        /*----- admin_users -----*/
+        IndexSpec::new("admin_users", "admin_user_email_unique", doc! { "email": 1 }).unique(),
+
+        /*----- images -----*/
+        IndexSpec::new("images", "image_model_index", doc! { "model_type": 1, "model_id": 1 }),
+    ]
+}
+
+/// Outcome of a maintenance run, logged at startup
+#[derive(Debug, Default)]
+pub struct IndexReport {
+    pub created: Vec<String>,
+    pub unchanged: Vec<String>,
+    pub stale: Vec<String>,
+    pub dropped: Vec<String>,
+}
+
+pub struct MaintainMongoIndex;
+
+impl MaintainMongoIndex {
+    /// Diff `declared_indexes()` against the server. Missing indexes are created; indexes
+    /// that are no longer declared (or whose definition changed) are only reported, unless
+    /// `MONGO_DROP_STALE_INDEXES=true`, in which case they are dropped (and recreated).
+    pub async fn maintain(client: &Client, mongo_database_name: &str) -> mongodb::error::Result<IndexReport> {
+        let db = client.database(mongo_database_name);
+        let drop_stale = get_custom_env("MONGO_DROP_STALE_INDEXES", "false") == "true";
+        let declared = declared_indexes();
+        let mut report = IndexReport::default();
+
+        let mut collections: Vec<&str> = Vec::new();
+        for spec in &declared {
+            if !collections.contains(&spec.collection) {
+                collections.push(spec.collection);
+            }
+        }
+
+        for collection_name in collections {
+            let collection: Collection<Document> = db.collection(collection_name);
+            let specs: Vec<&IndexSpec> = declared.iter().filter(|spec| spec.collection == collection_name).collect();
+
+            // A collection that doesn't exist yet has no indexes
+            let existing: Vec<IndexModel> = match collection.list_indexes(None).await {
+                Ok(cursor) => cursor.try_collect().await?,
+                Err(e) if matches!(*e.kind, ErrorKind::Command(ref c) if c.code == 26) => Vec::new(),
+                Err(e) => return Err(e),
+            };
+
+            for index in &existing {
+                let Some(name) = index.options.as_ref().and_then(|o| o.name.clone()) else { continue };
+                if name == "_id_" {
+                    continue;
+                }
+
+                let qualified = format!("{}.{}", collection_name, name);
+                match specs.iter().find(|spec| spec.name == name) {
+                    Some(spec) if spec.matches(index) => report.unchanged.push(qualified),
+                    Some(_) if drop_stale => {
+                        collection.drop_index(name.as_str(), None).await?;
+                        report.dropped.push(qualified);
+                    }
+                    Some(_) => warn!("Index {} differs from its declaration; set MONGO_DROP_STALE_INDEXES=true to rebuild it", qualified),
+                    None if drop_stale => {
+                        collection.drop_index(name.as_str(), None).await?;
+                        report.dropped.push(qualified);
+                    }
+                    None => report.stale.push(qualified),
+                }
+            }
+
+            for spec in specs {
+                let qualified = format!("{}.{}", collection_name, spec.name);
+                if report.unchanged.contains(&qualified) {
+                    continue;
+                }
+                // Changed definitions are left alone unless they were dropped above
+                let on_server = existing.iter().any(|index| index.options.as_ref().and_then(|o| o.name.as_deref()) == Some(spec.name));
+                if on_server && !report.dropped.contains(&qualified) {
+                    continue;
+                }
+
+                collection.create_index(spec.to_model(), None).await?;
+                report.created.push(qualified);
+            }
+        }
+
+        info!(
+            "Mongo indexes: {} created, {} unchanged, {} dropped, {} stale",
+            report.created.len(), report.unchanged.len(), report.dropped.len(), report.stale.len()
+        );
+        for stale in &report.stale {
+            warn!("Undeclared index {} (set MONGO_DROP_STALE_INDEXES=true to drop it)", stale);
+        }
+
+        Ok(report)
+    }
+}
+
+/// True for an insert/update rejected by a unique index. findAndModify upserts
+/// report it as a command error rather than a write error.
+pub fn is_duplicate_key_error(error: &mongodb::error::Error) -> bool {
+    match *error.kind {
+        ErrorKind::Write(WriteFailure::WriteError(ref e)) => e.code == 11000,
+        ErrorKind::Command(ref e) => e.code == 11000,
+        _ => false,
+    }
+}
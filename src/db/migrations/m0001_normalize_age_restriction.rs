@@ -0,0 +1,78 @@
+// src/db/migrations/m0001_normalize_age_restriction.rs
+//
+// `Event.age_restriction` is free text while AdminX offers `AgeRestrictionEnum` variant
+// names. Rewrite the common spellings ("18+", "all ages", "kids", ...) to those names and
+// keep the original in `age_restriction_legacy` so the step can be reverted. Values that
+// match nothing are left alone; they are the enum's `Custom` case.
+use anyhow::Result;
+use futures::future::BoxFuture;
+use mongodb::{bson::{doc, Bson}, Database};
+
+use super::Migration;
+
+pub fn migration() -> Migration {
+    Migration {
+        id: "0001_normalize_age_restriction",
+        description: "Map free-text Event.age_restriction values to AgeRestrictionEnum names",
+        up,
+        down: Some(down),
+    }
+}
+
+fn canonical(value: &str) -> Option<&'static str> {
+    let key: String = value.to_lowercase().chars().filter(|c| c.is_alphanumeric() || *c == '+').collect();
+    let name = match key.as_str() {
+        "allages" | "all" | "everyone" | "any" | "general" => "AllAges",
+        "adultsonly" | "adults" | "adult" | "18+" | "18plus" | "21+" | "21plus" => "AdultsOnly",
+        "childrenonly" | "children" | "child" | "kids" => "ChildrenOnly",
+        "teenagers" | "teenager" | "teens" | "teen" | "13+" => "Teenagers",
+        "seniors" | "senior" | "60+" | "65+" => "Seniors",
+        "initial" | "none" => "Initial",
+        _ => return None,
+    };
+    (name != value).then_some(name)
+}
+
+fn up(db: &Database, dry_run: bool) -> BoxFuture<'_, Result<u64>> {
+    Box::pin(async move {
+        let events = db.collection::<mongodb::bson::Document>("events");
+        let values = events.distinct("age_restriction", doc! { "age_restriction": { "$type": "string" } }, None).await?;
+
+        let mut affected = 0;
+        for value in values {
+            let Bson::String(value) = value else { continue };
+            let Some(name) = canonical(&value) else { continue };
+
+            let filter = doc! { "age_restriction": &value };
+            if dry_run {
+                affected += events.count_documents(filter, None).await?;
+                continue;
+            }
+            let update = doc! { "$set": { "age_restriction": name, "age_restriction_legacy": &value } };
+            affected += events.update_many(filter, update, None).await?.modified_count;
+        }
+
+        Ok(affected)
+    })
+}
+
+fn down(db: &Database, dry_run: bool) -> BoxFuture<'_, Result<u64>> {
+    Box::pin(async move {
+        let events = db.collection::<mongodb::bson::Document>("events");
+        let filter = doc! { "age_restriction_legacy": { "$exists": true } };
+        if dry_run {
+            return Ok(events.count_documents(filter, None).await?);
+        }
+
+        let mut affected = 0;
+        for value in events.distinct("age_restriction_legacy", filter, None).await? {
+            let update = doc! {
+                "$set": { "age_restriction": &value },
+                "$unset": { "age_restriction_legacy": "" }
+            };
+            affected += events.update_many(doc! { "age_restriction_legacy": value }, update, None).await?.modified_count;
+        }
+
+        Ok(affected)
+    })
+}
@@ -0,0 +1,87 @@
+// src/db/migrations/m0002_normalize_enum_casing.rs
+//
+// AdminX forms have written enum values in whatever casing their options used
+// ("Active" vs "active", "published" vs "Published"), which the models then fail to
+// deserialize. Rewrite every stored value to the exact string serde expects. Not
+// reversible: the old mixed casing carries no information worth restoring.
+use anyhow::Result;
+use convert_case::{Case, Casing};
+use futures::future::BoxFuture;
+use mongodb::{bson::{doc, Bson, Document}, Database};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::enums::common_enums::{GenderEnum, OnboardEnum, StatusEnum};
+use crate::requests::enums::event_enums::{
+    ApprovalStatusEnum,
+    EventCategoryEnum,
+    EventStatusEnum,
+    EventTypeEnum,
+    PaymentTypeEnum,
+    RegistrationTypeEnum,
+};
+use super::Migration;
+
+pub fn migration() -> Migration {
+    Migration {
+        id: "0002_normalize_enum_casing",
+        description: "Rewrite enum fields to the casing their serde representation uses",
+        up,
+        down: None,
+    }
+}
+
+/// The serialized form of `E` that `value` was meant to be, if it differs from `value`
+fn canonical<E: DeserializeOwned + Serialize>(value: &str) -> Option<String> {
+    [value.to_case(Case::Pascal), value.to_lowercase(), value.to_case(Case::Snake)]
+        .into_iter()
+        .find_map(|candidate| serde_json::from_value::<E>(serde_json::Value::String(candidate)).ok())
+        .and_then(|variant| serde_json::to_value(variant).ok())
+        .and_then(|serialized| serialized.as_str().map(str::to_string))
+        .filter(|serialized| serialized != value)
+}
+
+type Normalizer = fn(&str) -> Option<String>;
+
+const FIELDS: &[(&str, &str, Normalizer)] = &[
+    ("events", "status", canonical::<EventStatusEnum>),
+    ("events", "approval_status", canonical::<ApprovalStatusEnum>),
+    ("events", "category", canonical::<EventCategoryEnum>),
+    ("events", "event_type", canonical::<EventTypeEnum>),
+    ("events", "registration_type", canonical::<RegistrationTypeEnum>),
+    ("events", "payment_type", canonical::<PaymentTypeEnum>),
+    ("event_attendees", "registration_status", canonical::<StatusEnum>),
+    ("event_attendees", "registration_type", canonical::<RegistrationTypeEnum>),
+    ("users", "status", canonical::<StatusEnum>),
+    ("users", "gender", canonical::<GenderEnum>),
+    ("users", "onboard", canonical::<OnboardEnum>),
+    ("Contacts", "status", canonical::<StatusEnum>),
+    ("Contacts", "gender", canonical::<GenderEnum>),
+    ("pictures", "status", canonical::<StatusEnum>),
+    ("invitations", "status", canonical::<StatusEnum>),
+];
+
+fn up(db: &Database, dry_run: bool) -> BoxFuture<'_, Result<u64>> {
+    Box::pin(async move {
+        let mut affected = 0;
+
+        for (collection_name, field, normalize) in FIELDS {
+            let collection = db.collection::<Document>(collection_name);
+            let values = collection.distinct(*field, doc! { *field: { "$type": "string" } }, None).await?;
+
+            for value in values {
+                let Bson::String(value) = value else { continue };
+                let Some(canonical) = normalize(&value) else { continue };
+
+                let filter = doc! { *field: &value };
+                if dry_run {
+                    affected += collection.count_documents(filter, None).await?;
+                    continue;
+                }
+                let update = doc! { "$set": { *field: canonical } };
+                affected += collection.update_many(filter, update, None).await?.modified_count;
+            }
+        }
+
+        Ok(affected)
+    })
+}
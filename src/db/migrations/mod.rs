@@ -0,0 +1,216 @@
+// src/db/migrations/mod.rs
+//
+// Versioned data migrations. Each migration is a pair of Rust functions registered in
+// `registry()`; applied ids are stored in the `migrations` collection so every
+// environment runs them exactly once, in id order.
+//
+//   xard-be migrate status
+//   xard-be migrate up [--dry-run]
+//   xard-be migrate down [--steps N] [--dry-run]
+mod m0001_normalize_age_restriction;
+mod m0002_normalize_enum_casing;
+
+use anyhow::{anyhow, bail, Result};
+use futures::{future::BoxFuture, TryStreamExt};
+use log::info;
+use mongodb::{
+    bson::{doc, DateTime as BsonDateTime, Document},
+    options::FindOptions,
+    Database,
+};
+
+const MIGRATIONS_COLLECTION: &str = "migrations";
+
+/// A migration step. Returns the number of documents changed (or that would change on a dry run).
+pub type MigrationFn = fn(&Database, bool) -> BoxFuture<'_, Result<u64>>;
+
+pub struct Migration {
+    pub id: &'static str,
+    pub description: &'static str,
+    pub up: MigrationFn,
+    // None for migrations that cannot be reverted
+    pub down: Option<MigrationFn>,
+}
+
+/// All migrations, oldest first. Ids must sort in the order they should run.
+pub fn registry() -> Vec<Migration> {
+    vec![
+        m0001_normalize_age_restriction::migration(),
+        m0002_normalize_enum_casing::migration(),
+    ]
+}
+
+/// Registered migrations not applied yet, in registry order
+fn pending(registry: Vec<Migration>, applied: &[String]) -> Vec<Migration> {
+    registry.into_iter().filter(|m| !applied.iter().any(|id| id == m.id)).collect()
+}
+
+/// The last `steps` applied migrations, newest first. Fails before anything is reverted
+/// when one of them is unknown or irreversible.
+fn to_revert<'r>(registry: &'r [Migration], applied: &[String], steps: usize) -> Result<Vec<&'r Migration>> {
+    applied.iter().rev().take(steps)
+        .map(|id| {
+            let Some(migration) = registry.iter().find(|m| m.id == id) else {
+                bail!("applied migration {} is not in the registry", id);
+            };
+            if migration.down.is_none() {
+                bail!("migration {} cannot be reverted", id);
+            }
+            Ok(migration)
+        })
+        .collect()
+}
+
+pub struct MigrationRunner<'a> {
+    db: &'a Database,
+    dry_run: bool,
+}
+
+impl<'a> MigrationRunner<'a> {
+    pub fn new(db: &'a Database, dry_run: bool) -> Self {
+        Self { db, dry_run }
+    }
+
+    /// Applied migration ids, oldest first
+    pub async fn applied(&self) -> Result<Vec<String>> {
+        let find_options = FindOptions::builder().sort(doc! { "_id": 1 }).build();
+        let records: Vec<Document> = self.db.collection::<Document>(MIGRATIONS_COLLECTION)
+            .find(None, find_options).await?
+            .try_collect().await?;
+
+        Ok(records.iter().filter_map(|r| r.get_str("_id").ok().map(str::to_string)).collect())
+    }
+
+    pub async fn status(&self) -> Result<Vec<(&'static str, &'static str, bool)>> {
+        let applied = self.applied().await?;
+        Ok(registry().into_iter()
+            .map(|m| (m.id, m.description, applied.iter().any(|id| id == m.id)))
+            .collect())
+    }
+
+    /// Apply every pending migration in order; returns the ids that ran
+    pub async fn up(&self) -> Result<Vec<&'static str>> {
+        let applied = self.applied().await?;
+        let mut ran = Vec::new();
+
+        for migration in pending(registry(), &applied) {
+            let affected = (migration.up)(self.db, self.dry_run).await
+                .map_err(|e| anyhow!("migration {} failed: {}", migration.id, e))?;
+            info!("{} {} ({} documents)", if self.dry_run { "Would apply" } else { "Applied" }, migration.id, affected);
+
+            if !self.dry_run {
+                self.db.collection::<Document>(MIGRATIONS_COLLECTION).insert_one(doc! {
+                    "_id": migration.id,
+                    "description": migration.description,
+                    "affected": affected as i64,
+                    "applied_at": BsonDateTime::now(),
+                }, None).await?;
+            }
+            ran.push(migration.id);
+        }
+
+        Ok(ran)
+    }
+
+    /// Revert the last `steps` applied migrations, newest first
+    pub async fn down(&self, steps: usize) -> Result<Vec<&'static str>> {
+        let applied = self.applied().await?;
+        let registry = registry();
+        let mut reverted = Vec::new();
+
+        for migration in to_revert(&registry, &applied, steps)? {
+            let Some(down) = migration.down else { continue };
+
+            let affected = down(self.db, self.dry_run).await
+                .map_err(|e| anyhow!("reverting {} failed: {}", migration.id, e))?;
+            info!("{} {} ({} documents)", if self.dry_run { "Would revert" } else { "Reverted" }, migration.id, affected);
+
+            if !self.dry_run {
+                self.db.collection::<Document>(MIGRATIONS_COLLECTION)
+                    .delete_one(doc! { "_id": migration.id }, None).await?;
+            }
+            reverted.push(migration.id);
+        }
+
+        Ok(reverted)
+    }
+}
+
+/// Entry point for `xard-be migrate ...`; `args` are the arguments after `migrate`
+pub async fn run_cli(db: &Database, args: &[String]) -> Result<()> {
+    let dry_run = args.iter().any(|a| a == "--dry-run");
+    let steps = match args.iter().position(|a| a == "--steps") {
+        Some(i) => args.get(i + 1).and_then(|v| v.parse().ok()).ok_or_else(|| anyhow!("--steps expects a number"))?,
+        None => 1,
+    };
+    let runner = MigrationRunner::new(db, dry_run);
+
+    match args.first().map(String::as_str) {
+        Some("status") | None => {
+            for (id, description, applied) in runner.status().await? {
+                println!("{} {}  {}", if applied { "[x]" } else { "[ ]" }, id, description);
+            }
+        }
+        Some("up") => {
+            let ran = runner.up().await?;
+            println!("{} migration(s) {}", ran.len(), if dry_run { "pending (dry run)" } else { "applied" });
+        }
+        Some("down") => {
+            let reverted = runner.down(steps).await?;
+            println!("{} migration(s) {}", reverted.len(), if dry_run { "would be reverted (dry run)" } else { "reverted" });
+        }
+        Some(other) => bail!("unknown migrate command '{}', expected status, up or down", other),
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop(_db: &Database, _dry_run: bool) -> BoxFuture<'_, Result<u64>> {
+        Box::pin(async { Ok(0) })
+    }
+
+    fn migration(id: &'static str, reversible: bool) -> Migration {
+        Migration { id, description: "", up: noop, down: reversible.then_some(noop as MigrationFn) }
+    }
+
+    fn applied(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn registry_ids_are_unique_and_sorted() {
+        let ids: Vec<&str> = registry().iter().map(|m| m.id).collect();
+        let mut sorted = ids.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(ids, sorted);
+    }
+
+    #[test]
+    fn pending_keeps_registry_order_and_skips_applied() {
+        let registry = vec![migration("m0001", true), migration("m0002", true), migration("m0003", true)];
+        let ids: Vec<&str> = pending(registry, &applied(&["m0002"])).iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["m0001", "m0003"]);
+    }
+
+    #[test]
+    fn revert_goes_newest_first() {
+        let registry = vec![migration("m0001", true), migration("m0002", true), migration("m0003", true)];
+        let ids: Vec<&str> = to_revert(&registry, &applied(&["m0001", "m0002", "m0003"]), 2).unwrap()
+            .iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec!["m0003", "m0002"]);
+    }
+
+    #[test]
+    fn revert_refuses_unknown_or_irreversible_migrations_up_front() {
+        let registry = vec![migration("m0001", false), migration("m0002", true)];
+        assert!(to_revert(&registry, &applied(&["m0001", "m0002"]), 2).is_err());
+        assert!(to_revert(&registry, &applied(&["m0002", "m0009"]), 1).is_err());
+        assert_eq!(to_revert(&registry, &applied(&["m0001", "m0002"]), 1).unwrap().len(), 1);
+    }
+}
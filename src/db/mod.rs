@@ -1 +1,3 @@
 pub mod mongo;
+pub mod indexes;
+pub mod migrations;
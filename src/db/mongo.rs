@@ -1,15 +1,15 @@
 // src/db/mongo.rs
-use mongodb::{bson::doc, Client, options::IndexOptions, options::ClientOptions, Database, Collection, IndexModel};
+use mongodb::{Client, options::ClientOptions, Database, Collection};
 use std::env;
-use crate::models::{user::User};
+use crate::db::indexes::MaintainMongoIndex;
 use log::{info};
 use once_cell::sync::OnceCell;
 
 static DB_INSTANCE: OnceCell<Database> = OnceCell::new();
 static DB_CLIENT: OnceCell<Client> = OnceCell::new();
 
-/// ✅ Initializes MongoDB and sets global DB instance
-pub async fn init_mongo_client() -> Database {
+/// Connects to MongoDB without touching indexes or the global instances
+pub async fn connect_mongo() -> (Client, Database) {
     let mongo_uri = env::var("MONGO_URI").expect("MONGO_URI must be set");
     let mongo_database_name = env::var("MONGO_DATABASE_NAME").expect("MONGO_DATABASE_NAME must be set");
     
@@ -24,13 +24,18 @@ pub async fn init_mongo_client() -> Database {
     
     info!("✅ Mongo client initialized: {}", mongo_uri);
 
+    (client, db)
+}
+
+/// ✅ Initializes MongoDB and sets global DB instance
+pub async fn init_mongo_client() -> Database {
+    let mongo_database_name = env::var("MONGO_DATABASE_NAME").expect("MONGO_DATABASE_NAME must be set");
+    let (client, db) = connect_mongo().await;
+
     init_indexes_and_uniqness(&client, &mongo_database_name)
         .await
         .expect("Index creation failed");
     
-    // Uncomment when ready to maintain indexes
-    // MaintainMongoIndex::maintain(&client, &mongo_database_name).await.expect("Index creation failed");
-    
     // Set global instances
     DB_INSTANCE.set(db.clone()).expect("Failed to set DB_INSTANCE");
     DB_CLIENT.set(client.clone()).expect("Failed to set DB_CLIENT");
@@ -54,7 +59,8 @@ pub fn get_db() -> Database {
         .clone()
 }
 
-/// Returns a typed collection from the database
+/// Returns a typed collection from the database. Only for `AdmixResource::get_collection`
+/// and raw AdminX updates; application code goes through `crate::repositories`.
 pub fn get_collection<T>(name: &str) -> Collection<T>
 where
     T: serde::de::DeserializeOwned + Unpin + Send + Sync,
@@ -65,68 +71,8 @@ where
 
 
 
+/// Brings every collection's indexes in line with `db::indexes::declared_indexes()`
 pub async fn init_indexes_and_uniqness(client: &Client, mongo_database_name: &str) -> mongodb::error::Result<()> {
-    let db = client.database(mongo_database_name); // change if your DB name differs
-
-    /*-----------------------------------------------------
-    Start User
-    -----------------------------------------------------*/
-    let user_collection: Collection<User> = db.collection("users");
-
-    // Email: Unique AND Required (Non-null)
-    let email_index = IndexModel::builder()
-        .keys(doc! { "email": 1 })
-        .options(
-            IndexOptions::builder()
-                .unique(true)
-                .sparse(false)
-                .name(Some("user_email_unique".to_string()))
-                .build()
-        )
-        .build();
-
-    // Unique BUT Optional (Nullable)
-    let official_email_index = IndexModel::builder()
-    .keys(doc! { "official_email": 1 })
-    .options(
-        IndexOptions::builder()
-            .unique(true)
-            .sparse(true)
-            .name(Some("user_official_email".to_string()))
-            .build()
-    )
-    .build();
-
-    // Phone Number: Unique BUT Optional (Nullable)
-    let phone_index = IndexModel::builder()
-        .keys(doc! { "phone_number": 1 })
-        .options(
-        IndexOptions::builder()
-            .unique(true)
-            .sparse(true)
-            .name(Some("user_phone_unique".to_string()))
-            .build()
-        )
-        .build();
-
-    // Deleted: filter index (already present)
-    let user_deleted_index = IndexModel::builder()
-        .keys(doc! { "deleted": 1 })
-        .options(
-            IndexOptions::builder()
-                .unique(false)
-                .name(Some("user_deleted_index".to_string()))
-                .build()
-        )
-        .build();
-
-    user_collection.create_index(email_index, None).await?;
-    user_collection.create_index(phone_index, None).await?;
-    user_collection.create_index(official_email_index, None).await?;
-    user_collection.create_index(user_deleted_index, None).await?;
-    /*-----------------------------------------------------
-    END User
-    -----------------------------------------------------*/
-
+    MaintainMongoIndex::maintain(client, mongo_database_name).await?;
     Ok(())
 }
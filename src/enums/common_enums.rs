@@ -6,8 +6,12 @@ use serde_with::serde_as;
 use serde_json::Value;
 use serde_with::DisplayFromStr;
 use strum_macros::EnumIter;
+use schemars::JsonSchema;
+use validator::Validate;
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+use crate::libs::custom_validators::validate_email;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum GenderEnum {
     Initial,
@@ -43,7 +47,7 @@ where
 }
 
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum StatusEnum {
     Active,
@@ -60,7 +64,7 @@ pub enum StatusEnum {
 }
 
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum OnboardEnum {
     Completed,
@@ -68,6 +72,59 @@ pub enum OnboardEnum {
 }
 
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+#[serde(rename_all = "lowercase")]
+pub enum BanTypeEnum {
+    Temporary,
+    Permanent,
+}
+
+/// Which template an outgoing email was rendered from
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, EnumIter, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailKindEnum {
+    Invitation,
+    RegistrationConfirmation,
+    EventReminder,
+    OtpCode,
+    SupportTicket,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, EnumIter, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailStatusEnum {
+    Queued,
+    Sending,
+    Sent,
+    Failed,
+}
+
+/// Role of an AdminX operator; resources list the roles they admit in `allowed_roles`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, EnumIter, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminRoleEnum {
+    Admin,
+    Superadmin,
+}
+
+impl AdminRoleEnum {
+    /// The role as AdminX tokens and `allowed_roles` spell it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdminRoleEnum::Admin => "admin",
+            AdminRoleEnum::Superadmin => "superadmin",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, EnumIter, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AdminStatusEnum {
+    Active,
+    Inactive,
+}
+
+
 impl Default for GenderEnum {
     fn default() -> Self {
         GenderEnum::Initial
@@ -114,7 +171,8 @@ pub struct FetchNotificationRequest {
 }
 
 
-#[derive(Deserialize)]
+/// Path of the user vCard and QR endpoints
+#[derive(Debug, Deserialize)]
 pub struct QRRequest {
     pub user_id: String,
 }
@@ -126,10 +184,16 @@ pub struct InviteRequest {
 }
 
 
-#[derive(Deserialize)]
+/// Body of a support ticket. Replies go to `email`, or else to the account email of `user_id`.
+#[derive(Debug, Deserialize, Validate)]
 pub struct SupportRequest {
+    #[validate(length(min = 1, max = 200))]
     pub subject: String,
+    #[validate(length(min = 1, max = 5000))]
     pub description: String,
+    #[validate(custom = "validate_email")]
+    pub email: Option<String>,
+    pub user_id: Option<String>,
 }
 
 
@@ -141,8 +205,9 @@ pub struct LinkedInCallback {
 }
 
 
+/// Body of a QR scan: the signed-in user scanned `code`, at the event `event_id` if given
 #[derive(Debug, serde::Deserialize)]
 pub struct ScanRQCodeStruct {
-    pub user_id: Option<String>,
     pub event_id: Option<String>,
+    pub code: Option<String>,
 }
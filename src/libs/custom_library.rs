@@ -78,4 +78,14 @@ pub async fn get_country_code_from_request(req: HttpRequest) -> Result<serde_jso
         .map_or_else(|| "IN".to_string(), |geo| geo["country"].as_str().unwrap_or("IN").to_string());
 
     Ok(json!({ "country_code": country_code }))
-}
\ No newline at end of file
+}
+
+/// Builds a success response with the same envelope as `ErrorResponseBody`
+pub fn success_response(status: actix_web::http::StatusCode, message: &str, data: Value) -> actix_web::HttpResponse {
+    actix_web::HttpResponse::build(status).json(json!({
+        "code": status.as_u16(),
+        "message": message,
+        "status": status.as_u16(),
+        "data": data,
+    }))
+}
@@ -25,3 +25,45 @@ pub fn is_today_or_later(dt: BsonDateTime) -> bool {
 
     input_millis >= today_millis
 }
+
+
+// Parses a query date given as RFC3339 or `YYYY-MM-DD` (start or end of that day)
+pub fn parse_date_param(value: &str, end_of_day: bool) -> Option<BsonDateTime> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(BsonDateTime::from_chrono(dt.with_timezone(&chrono::Utc)));
+    }
+
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+    let time = if end_of_day {
+        date.and_hms_milli_opt(23, 59, 59, 999)?
+    } else {
+        date.and_hms_opt(0, 0, 0)?
+    };
+    Some(BsonDateTime::from_chrono(time.and_utc()))
+}
+
+/// Named date windows accepted by search endpoints
+pub const DATE_RANGE_PRESETS: [&str; 7] = ["today", "tomorrow", "this_week", "this_weekend", "next_week", "this_month", "next_month"];
+
+// Resolves a named date window (UTC, weeks start on Monday) to inclusive start/end bounds
+pub fn date_range_preset(name: &str) -> Option<(BsonDateTime, BsonDateTime)> {
+    let today = chrono::Utc::now().date_naive();
+    let week_start = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+    let month_start = today.with_day(1)?;
+    let next_month_start = month_start.checked_add_months(chrono::Months::new(1))?;
+
+    let (first, last) = match name {
+        "today" => (today, today),
+        "tomorrow" => (today.succ_opt()?, today.succ_opt()?),
+        "this_week" => (week_start, week_start + chrono::Duration::days(6)),
+        "this_weekend" => (week_start + chrono::Duration::days(5), week_start + chrono::Duration::days(6)),
+        "next_week" => (week_start + chrono::Duration::days(7), week_start + chrono::Duration::days(13)),
+        "this_month" => (month_start, next_month_start.pred_opt()?),
+        "next_month" => (next_month_start, next_month_start.checked_add_months(chrono::Months::new(1))?.pred_opt()?),
+        _ => return None,
+    };
+
+    let start = first.and_hms_opt(0, 0, 0)?.and_utc();
+    let end = last.and_hms_milli_opt(23, 59, 59, 999)?.and_utc();
+    Some((BsonDateTime::from_chrono(start), BsonDateTime::from_chrono(end)))
+}
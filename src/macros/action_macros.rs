@@ -0,0 +1,34 @@
+// src/macros/action_macros.rs
+
+/// AdminX custom action setting a boolean field, e.g.
+/// `toggle_action!("toggle_public", "events", "is_public")`.
+/// Collection and field must be literals: the handler is a non-capturing closure.
+#[macro_export]
+macro_rules! toggle_action {
+    ($name:expr, $collection:expr, $field:expr) => {
+        adminx::actions::CustomAction {
+            name: $name,
+            method: "POST",
+            handler: |req, _path, body| {
+                Box::pin($crate::admin::field_actions::toggle_field(req, body, $collection, $field))
+            },
+            ui: Some($crate::admin::field_actions::toggle_ui($field)),
+        }
+    };
+}
+
+/// AdminX custom action setting an enum field to one of `$enum`'s variants, e.g.
+/// `set_enum_action!("set_status", "events", "status", EventStatusEnum)`.
+#[macro_export]
+macro_rules! set_enum_action {
+    ($name:expr, $collection:expr, $field:expr, $enum:ty) => {
+        adminx::actions::CustomAction {
+            name: $name,
+            method: "POST",
+            handler: |req, _path, body| {
+                Box::pin($crate::admin::field_actions::set_enum_field::<$enum>(req, body, $collection, $field))
+            },
+            ui: Some($crate::admin::field_actions::enum_ui::<$enum>($field)),
+        }
+    };
+}
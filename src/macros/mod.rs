@@ -1,3 +1,4 @@
 pub mod global_error_macros;
 pub mod handle_custom_error;
-pub mod field_check_macros;
\ No newline at end of file
+pub mod field_check_macros;
+pub mod action_macros;
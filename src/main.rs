@@ -10,6 +10,10 @@ mod enums;
 mod admin;
 mod macros;
 mod requests;
+mod controllers;
+mod routes;
+mod repositories;
+mod middlewares;
 
 
 use dotenv::dotenv;
@@ -25,7 +29,10 @@ use env_logger::Env;
 use actix_web_prom::PrometheusMetricsBuilder;
 use actix_files::Files;
 use crate::services::redis_service::init_redis;
+use crate::services::mail_service::init_mailer;
+use crate::services::sms_service::init_sms;
 use crate::admin::initializer::AdminxInitializer;
+use crate::repositories::Repositories;
 
 
 
@@ -52,6 +59,13 @@ async fn main() -> std::io::Result<()> {
     env_logger::Builder::from_env(Env::default().default_filter_or("debug")).init();
     info!("Logging Initialized");
 
+    // `xard-be migrate <status|up|down> [--dry-run] [--steps N]` runs data migrations and exits
+    let args: Vec<String> = env::args().collect();
+    if args.get(1).map(String::as_str) == Some("migrate") {
+        let (_client, db) = crate::db::mongo::connect_mongo().await;
+        return crate::db::migrations::run_cli(&db, &args[2..]).await.map_err(std::io::Error::other);
+    }
+
     let prometheus = PrometheusMetricsBuilder::new("api")
         .endpoint("/metrics") // Expose Prometheus metrics at /metrics
         .build()
@@ -59,11 +73,23 @@ async fn main() -> std::io::Result<()> {
 
     // Initialize Redis and Wrap in `web::Data`
     init_redis().await;
+    init_mailer();
+    init_sms();
     
     let db: Database = init_mongo_client().await;
     let db_data = web::Data::new(db.clone()); // Wrap DB in `web::Data`
+    let repositories = web::Data::new(Repositories::mongo(&db));
     println!("Database initialized......");
 
+    crate::services::invitation_service::spawn_expiry_job(repositories.get_ref().clone());
+    crate::services::ban_service::spawn_expiry_job(repositories.get_ref().clone());
+    crate::services::mail_service::spawn_delivery_job(repositories.get_ref().clone());
+    crate::services::mail_service::spawn_reminder_job(repositories.get_ref().clone());
+
+    if let Err(e) = crate::services::admin_user_service::bootstrap_superadmin(repositories.get_ref()).await {
+        error!("Failed to bootstrap the superadmin: {}", e);
+    }
+
     // Initialize AdminX components using the initializer
     let adminx_config = AdminxInitializer::initialize(db.clone()).await;
 
@@ -75,9 +101,15 @@ async fn main() -> std::io::Result<()> {
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(adminx_config.clone()))
+            .app_data(db_data.clone())
+            .app_data(repositories.clone())
             .wrap(Logger::default())
             .wrap(prometheus.clone())
+            // Registered before the session middleware, so it runs inside it and can read the session
+            .wrap(actix_web::middleware::from_fn(crate::middlewares::admin_two_factor_middleware::require_admin_two_factor))
             .wrap(AdminxInitializer::get_session_middleware(&adminx_config))
+            .service(crate::routes::api_v1_routes())
+            .configure(AdminxInitializer::configure_pages)
             .service(AdminxInitializer::get_routes_service())
     })
     .bind(server_address)?
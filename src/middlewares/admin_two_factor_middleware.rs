@@ -0,0 +1,112 @@
+// src/middlewares/admin_two_factor_middleware.rs
+//
+// AdminX only knows about its session token. The login override stores that token once
+// the TOTP step is passed, together with `TWO_FACTOR_SESSION_KEY`; a token without the
+// flag came from somewhere else (e.g. an AdminX login route we do not shadow) and is
+// thrown away before any AdminX page sees it. An operator whose password was reset also
+// carries `PASSWORD_CHANGE_SESSION_KEY`, and is kept on the password page until they
+// pick a new one.
+//
+// The token's operator is also looked up on every request, so deactivating or
+// demoting an operator ends their session at once instead of when the token expires.
+use actix_session::{Session, SessionExt};
+use actix_web::{
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::StatusCode,
+    middleware::Next,
+    web,
+    Error,
+    HttpResponse,
+};
+use adminx::{AdminxConfig, utils::auth::extract_claims_from_session};
+use log::{error, warn};
+
+use crate::repositories::Repositories;
+use crate::services::admin_user_service;
+
+/// Session key of the AdminX JWT
+pub const ADMIN_TOKEN_SESSION_KEY: &str = "admintoken";
+
+/// Session flag set once the operator passed the TOTP step
+pub const TWO_FACTOR_SESSION_KEY: &str = "adminx_two_factor";
+
+/// Session flag set while the operator still has to replace a reset password
+pub const PASSWORD_CHANGE_SESSION_KEY: &str = "adminx_password_change";
+
+/// Where an operator with a reset password is sent
+pub const PASSWORD_CHANGE_PATH: &str = "/adminx/account/password";
+
+/// Paths reachable without a completed sign-in; everything else under `/adminx` is guarded
+fn is_open_path(path: &str) -> bool {
+    path == "/adminx/login"
+        || path == "/adminx/logout"
+        || path == "/adminx/api/login"
+        || path == "/adminx/2fa"
+        || path.starts_with("/adminx/2fa/")
+}
+
+fn to_login() -> HttpResponse {
+    HttpResponse::Found().append_header(("Location", "/adminx/login")).finish()
+}
+
+/// Whether the session's token still matches an active operator with the same role.
+/// Errors other than a missing or refused operator become a response, leaving the session be.
+async fn session_operator_is_current(req: &ServiceRequest, session: &Session) -> Result<bool, HttpResponse> {
+    let (Some(config), Some(repos)) = (req.app_data::<web::Data<AdminxConfig>>(), req.app_data::<web::Data<Repositories>>()) else {
+        error!("AdminX config or repositories are not registered, refusing AdminX requests");
+        return Err(HttpResponse::InternalServerError().finish());
+    };
+    let Ok(claims) = extract_claims_from_session(session, config).await else {
+        return Ok(false);
+    };
+
+    match admin_user_service::acting_admin(repos, &claims.sub).await {
+        Ok(admin) => Ok(admin.role.as_str() == claims.role),
+        Err(e) if e.as_response_error().status_code() == StatusCode::UNAUTHORIZED => Ok(false),
+        Err(e) => {
+            error!("Could not check the AdminX operator: {}", e);
+            Err(HttpResponse::ServiceUnavailable().finish())
+        }
+    }
+}
+
+pub async fn require_admin_two_factor(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let path = req.path();
+    if (path == "/adminx" || path.starts_with("/adminx/")) && !is_open_path(path) {
+        let session = req.get_session();
+        let has_token = session.get::<String>(ADMIN_TOKEN_SESSION_KEY).ok().flatten().is_some();
+        let passed = session.get::<bool>(TWO_FACTOR_SESSION_KEY).ok().flatten() == Some(true);
+
+        if has_token && !passed {
+            warn!("Dropping an AdminX session without a two-factor step on {}", path);
+            session.purge();
+            return Ok(req.into_response(to_login()).map_into_right_body());
+        }
+
+        if has_token {
+            match session_operator_is_current(&req, &session).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    warn!("Dropping an AdminX session whose operator was deactivated, demoted or removed");
+                    session.purge();
+                    return Ok(req.into_response(to_login()).map_into_right_body());
+                }
+                Err(response) => return Ok(req.into_response(response).map_into_right_body()),
+            }
+        }
+
+        let must_change = session.get::<bool>(PASSWORD_CHANGE_SESSION_KEY).ok().flatten() == Some(true);
+        if must_change && path != PASSWORD_CHANGE_PATH {
+            let response = HttpResponse::Found()
+                .append_header(("Location", PASSWORD_CHANGE_PATH))
+                .finish();
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+    }
+
+    next.call(req).await.map(ServiceResponse::map_into_left_body)
+}
@@ -0,0 +1,54 @@
+// src/middlewares/auth_middleware.rs
+//
+// Bearer-token authentication for the public API. Wrap a scope with `require_auth()`
+// and its handlers can take an `AuthUser` argument for the signed-in user.
+
+use std::future::{ready, Ready};
+
+use actix_web::{dev::{Payload, ServiceRequest}, Error, FromRequest, HttpMessage, HttpRequest};
+use actix_web_httpauth::{extractors::bearer::BearerAuth, middleware::HttpAuthentication};
+use futures::future::LocalBoxFuture;
+
+use crate::repositories::Repositories;
+use crate::services::auth_service::{self, AuthenticatedUser};
+use crate::custom_error_expression;
+
+type Validated = Result<ServiceRequest, (Error, ServiceRequest)>;
+type BearerValidator = fn(ServiceRequest, BearerAuth) -> LocalBoxFuture<'static, Validated>;
+
+/// Verifies the bearer token and stores the user it belongs to in the request extensions
+async fn validate_bearer(req: ServiceRequest, credentials: BearerAuth) -> Validated {
+    let Some(repos) = Repositories::from_request(req.request()) else {
+        return Err((custom_error_expression!(internal_error, 500, "Repositories unavailable").into(), req));
+    };
+
+    match auth_service::authenticate(&repos, credentials.token()).await {
+        Ok(authenticated) => {
+            req.extensions_mut().insert(authenticated);
+            Ok(req)
+        }
+        Err(e) => Err((e, req)),
+    }
+}
+
+/// Rejects requests without a valid access token of a live, unlocked account
+pub fn require_auth() -> HttpAuthentication<BearerAuth, BearerValidator> {
+    HttpAuthentication::bearer(|req, credentials| Box::pin(validate_bearer(req, credentials)))
+}
+
+/// The signed-in user, for handlers behind `require_auth()`
+pub struct AuthUser(pub AuthenticatedUser);
+
+impl FromRequest for AuthUser {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(
+            req.extensions().get::<AuthenticatedUser>()
+                .cloned()
+                .map(AuthUser)
+                .ok_or_else(|| custom_error_expression!(unauthorized, 401, "Sign in required").into()),
+        )
+    }
+}
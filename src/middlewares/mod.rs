@@ -0,0 +1,3 @@
+// src/middlewares/mod.rs
+pub mod auth_middleware;
+pub mod admin_two_factor_middleware;
@@ -0,0 +1,30 @@
+// models/admin_two_factor.rs
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+use serde::{Deserialize, Serialize};
+
+/// TOTP enrolment of an AdminX operator, keyed by their login email. Until
+/// `confirmed_at` is set the secret is only a pending enrolment.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AdminTwoFactor {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub email: String,
+
+    // The TOTP seed, encrypted with crypto_utility
+    pub secret: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_at: Option<BsonDateTime>,
+
+    // Last accepted time step, so a code cannot be replayed within its window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_step: Option<i64>,
+
+    // SHA-256 hashes of the unused recovery codes
+    #[serde(default)]
+    pub recovery_codes: Vec<String>,
+
+    pub created_at: BsonDateTime,
+    pub updated_at: BsonDateTime,
+}
@@ -0,0 +1,60 @@
+// models/admin_user.rs
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
+
+use crate::enums::common_enums::{AdminRoleEnum, AdminStatusEnum};
+
+/// An AdminX operator. Signs in with email and password (an argon2id hash in PHC
+/// format) plus a TOTP code, and gets the resources its role is allowed on.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, AdminxModel)]
+pub struct AdminUser {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Admin ID", group = "System")]
+    pub id: Option<ObjectId>,
+
+    #[adminx(group = "Account", list, filter)]
+    pub name: String,
+
+    #[adminx(group = "Account", list, filter)]
+    pub email: String,
+
+    #[adminx(group = "Access", list, filter)]
+    pub role: AdminRoleEnum,
+
+    #[adminx(group = "Access", list, filter)]
+    pub status: AdminStatusEnum,
+
+    #[adminx(skip)]
+    pub password_hash: String,
+
+    // Set by a reset; the operator picks a new password right after signing in
+    #[serde(default)]
+    #[adminx(label = "Must Change Password", group = "Access", readonly, list)]
+    pub must_change_password: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Password Changed", group = "System", readonly)]
+    pub password_changed_at: Option<BsonDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Last Login", group = "System", readonly, list)]
+    pub last_login_at: Option<BsonDateTime>,
+
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
+    pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System")]
+    pub updated_at: BsonDateTime,
+}
+
+impl AdminUser {
+    pub fn is_active(&self) -> bool {
+        self.status == AdminStatusEnum::Active
+    }
+}
@@ -0,0 +1,36 @@
+// models/audit_log.rs
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime, Document};
+use serde::{Deserialize, Serialize};
+
+/// One change made through AdminX. Written by the `Audited` resource wrapper after a
+/// create, update, delete or custom action succeeds; never edited afterwards.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditLog {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    // Email of the AdminX user, None when the session could not be read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+
+    // AdmixResource::resource_name, e.g. "Users"
+    pub resource: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub document_id: Option<String>,
+
+    // "create", "update", "delete" or the custom action name
+    pub action: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Document>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Document>,
+
+    // Changed fields only: { field: { from, to } }
+    #[serde(default)]
+    pub changes: Document,
+
+    pub created_at: BsonDateTime,
+}
@@ -8,9 +8,12 @@ use serde::{Deserialize, Serialize};
 use serde_json::{self, Value};
 use crate::services::redis_service::{get_redis_connection};
 use strum_macros::EnumIter;
+use schemars::JsonSchema;
+
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 
 /// **Enum for Config Status**
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfigStatus {
     Active,
@@ -27,7 +30,7 @@ impl ToString for ConfigStatus {
 }
 
 /// **Enum for Config Data Type**
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ConfigDataType {
     Json,
@@ -44,18 +47,32 @@ impl ToString for ConfigDataType {
 }
 
 /// **Config Model**
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema, AdminxModel)]
 pub struct Config {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "ID", group = "System")]
     pub id: Option<ObjectId>,
 
+    #[adminx(list, filter)]
     pub key: String,
+    #[adminx(widget = "editor")]
     pub data: Option<Value>, // Can be string or JSON
+    #[adminx(list)]
     pub data_type: ConfigDataType, // ✅ New
 
+    #[adminx(list, filter)]
     pub status: ConfigStatus,
 
+    #[serde(default)]
+    #[adminx(list, filter)]
+    pub deleted: bool,
+
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub updated_at: BsonDateTime,
 }
 
@@ -73,6 +90,7 @@ impl Config {
             data,
             data_type,
             status,
+            deleted: false,
             created_at: BsonDateTime::now(),
             updated_at: BsonDateTime::now(),
         }
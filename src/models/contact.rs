@@ -1,6 +1,8 @@
 // models/contact.rs
 use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 use validator::{Validate};
 use crate::enums::{
     common_enums::{
@@ -20,92 +22,120 @@ use crate::libs::custom_validators::{
 };
 
 
-#[derive(Serialize, Deserialize, Debug, Clone, Validate)]
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema, AdminxModel)]
 pub struct Contact {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Contact ID", group = "System")]
     pub id: Option<ObjectId>,
     
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_object_required")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "User", group = "Links", reference = "users", filter)]
     pub user_id: Option<ObjectId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_object_required")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Owner", group = "Links", reference = "users", list, filter)]
     pub parent_user_id: Option<ObjectId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_alpha_only")]
+    #[adminx(group = "Profile", list, filter)]
     pub first_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_alpha_only")]
+    #[adminx(group = "Profile", list)]
     pub last_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_email")]
+    #[adminx(group = "Profile", widget = "email", list, filter)]
     pub email: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_username")]
+    #[adminx(group = "Profile")]
     pub username: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_email")]
+    #[adminx(group = "Profile", widget = "email")]
     pub official_email: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_phone")]
+    #[adminx(group = "Profile", widget = "tel", filter)]
     pub phone_number: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Profile")]
     pub age: Option<u8>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default, deserialize_with = "deserialize_gender")]
+    #[adminx(group = "Profile")]
     pub gender: Option<GenderEnum>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Work")]
     pub designation: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Work", list, filter)]
     pub company_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Work", widget = "textarea")]
     pub company_address: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_website")]
+    #[adminx(group = "Work", widget = "url")]
     pub website: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Notes", widget = "textarea")]
     pub description: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Notes", widget = "textarea")]
     pub notes: Option<String>,
 
+    #[adminx(group = "Status", list, filter)]
     pub status: StatusEnum,
 
     #[serde(default)]
+    #[adminx(group = "Status", filter)]
     pub favorite: bool,
 
     #[serde(default)]
+    #[adminx(group = "Status", filter)]
     pub deleted: bool,
 
     #[serde(default)]
+    #[adminx(group = "Status", filter)]
     pub guest: bool,
 
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", filter)]
     pub updated_at: BsonDateTime,
 }
 
@@ -2,13 +2,10 @@
 
 
 use actix_web::Error;
-use mongodb::{
-    Database,
-    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
-    options::{FindOptions}
-};
-use futures::{TryStreamExt, StreamExt};
+use mongodb::bson::{doc, oid::ObjectId, DateTime as BsonDateTime, Document};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 use serde_json::Value;
 use validator::{Validate};
 
@@ -46,7 +43,12 @@ use crate::requests::{
             validate_textual
         }
     },
+    structures::event_structure::{
+        EventRequestBody,
+        EventRequestFilters,
+    },
 };
+use crate::libs::general_library::parse_date_param;
 
 use crate::utilities::bason_utility::convert_to_bson;
 use crate::{
@@ -56,140 +58,225 @@ use crate::{
 };
 use anyhow::{Error as AnyhowError};
 
-#[derive(Serialize, Deserialize, Debug, Clone, Validate)]
+/// GeoJSON `Point`; coordinates are stored longitude first, as Mongo expects
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct GeoPoint {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub coordinates: Vec<f64>,
+}
+
+impl GeoPoint {
+    pub fn new(latitude: f64, longitude: f64) -> Self {
+        Self {
+            kind: "Point".to_string(),
+            coordinates: vec![longitude, latitude],
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema, AdminxModel)]
 pub struct Event {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Event ID", group = "System")]
     pub id: Option<ObjectId>,
 
+    #[schemars(with = "ObjectIdSchema")]
+    #[adminx(label = "Owner", group = "Core", reference = "users", list)]
     pub user_id: ObjectId,
 
     #[validate(custom = "validate_textual")]
+    #[adminx(group = "Core", list, filter)]
     pub title: String,
 
     #[validate(custom = "validate_textual")]
+    #[adminx(group = "Core", widget = "editor")]
     pub description: String,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Location & Mode")]
     pub location: Option<String>,
 
     #[validate(custom = "validate_textual")]
+    #[adminx(group = "Location & Mode")]
     pub address: String,
 
+    // GeoJSON point backing the `2dsphere` index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(skip)]
+    pub geo_location: Option<GeoPoint>,
+
     // Event date and time fields
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Schedule")]
     pub event_date: BsonDateTime,
 
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Schedule")]
     pub start_time: BsonDateTime,
 
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Schedule")]
     pub end_time: BsonDateTime,
 
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Schedule", list, filter)]
     pub start_date: BsonDateTime,
 
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Schedule", list, filter)]
     pub end_date: BsonDateTime,
 
     // Media and images
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Image URL", group = "Core", widget = "url")]
     pub image: Option<String>,
 
     // Event status and approval
+    #[adminx(group = "Status & System", list, filter)]
     pub status: EventStatusEnum,
+    #[adminx(group = "Status & System", list, filter)]
     pub approval_status: ApprovalStatusEnum,
 
     // Event properties
     #[serde(default)]
+    #[adminx(label = "Public?", group = "Location & Mode", list, filter)]
     pub is_public: bool,
 
     #[serde(default)]
+    #[adminx(label = "Virtual?", group = "Location & Mode", list, filter)]
     pub is_virtual: bool,
 
     #[serde(default)]
+    #[adminx(label = "Paid Event?", group = "Pricing & Capacity", list, filter)]
     pub is_paid: bool,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Pricing & Capacity", list, filter)]
     pub price: Option<f64>,
 
     // Attendee management
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Pricing & Capacity", filter)]
     pub max_attendees: Option<u32>,
 
     #[serde(default)]
+    #[adminx(group = "Pricing & Capacity", readonly, filter)]
     pub current_attendees: u32,
 
     #[serde(default)]
+    #[adminx(label = "Attendees", group = "Pricing & Capacity", readonly, list)]
     pub attendees_count: u32,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<Vec<ObjectIdSchema>>")]
+    #[adminx(skip)]
     pub attendees: Option<Vec<ObjectId>>,
 
     // Creator information
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Status & System")]
     pub created_by: Option<String>,
 
     // Timestamps
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Status & System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "Status & System", list, filter)]
     pub updated_at: BsonDateTime,
 
     // Categorization
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Tags (comma-separated)", group = "Core", widget = "text")]
     pub tags: Option<Vec<String>>,
 
+    #[adminx(group = "Core", list, filter)]
     pub category: EventCategoryEnum,
 
     // Organizer information
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_alpha_only")]
+    #[adminx(group = "Organizer")]
     pub organizer_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_email")]
+    #[adminx(group = "Organizer", widget = "email")]
     pub organizer_email: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_phone")]
+    #[adminx(group = "Organizer", widget = "tel")]
     pub organizer_phone: Option<String>,
 
     // Registration and access
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_website")]
+    #[adminx(label = "Registration URL", group = "Access & Registration", widget = "url")]
     pub registration_url: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_website")]
+    #[adminx(group = "Location & Mode", widget = "url")]
     pub meeting_link: Option<String>,
 
     #[serde(default)]
+    #[adminx(label = "Requires Registration?", group = "Access & Registration", filter)]
     pub requires_registration: bool,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(group = "Schedule")]
     pub registration_deadline: Option<BsonDateTime>,
 
     // Event requirements
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<AgeRestrictionEnum>")]
+    #[adminx(group = "Policies & Notes")]
     pub age_restriction: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DressCodeEnum>")]
+    #[adminx(group = "Policies & Notes")]
     pub dress_code: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_textual")]
+    #[adminx(group = "Policies & Notes", widget = "editor_text")]
     pub special_instructions: Option<String>,
 
     // Custom fields for extensibility
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Custom Fields (JSON)", group = "Status & System")]
     pub custom_fields: Option<Value>,
 
     // Additional enums for better type safety
+    #[adminx(group = "Access & Registration", filter)]
     pub event_type: EventTypeEnum,
+    #[adminx(group = "Access & Registration", filter)]
     pub registration_type: RegistrationTypeEnum,
+    #[adminx(group = "Pricing & Capacity", filter)]
     pub payment_type: PaymentTypeEnum,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "QR Code", group = "Status & System")]
     pub qr_code: Option<String>,
 
+    // Set by the reminder job once the attendees' reminder emails are queued
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Reminder Sent", group = "Status & System", readonly)]
+    pub reminder_sent_at: Option<BsonDateTime>,
+
     // System fields
     #[serde(default)]
+    #[adminx(label = "Deleted?", group = "Status & System", list, filter)]
     pub deleted: bool,
 
     #[serde(default)]
+    #[adminx(label = "Locked?", group = "Status & System", list, filter)]
     pub locked: bool,
 }
 
@@ -202,6 +289,7 @@ impl Default for Event {
             description: String::new(),
             location: None,
             address: String::new(),
+            geo_location: None,
             event_date: BsonDateTime::now(),
             start_time: BsonDateTime::now(),
             end_time: BsonDateTime::now(),
@@ -238,9 +326,182 @@ impl Default for Event {
             registration_type: RegistrationTypeEnum::Initial,
             payment_type: PaymentTypeEnum::Initial,
             qr_code: None,
+            reminder_sent_at: None,
             deleted: false,
             locked: false,
         }
     }
 }
 
+
+
+impl Event {
+    /// Build a new event owned by `user_id` from an API request body
+    pub fn from_request(body: &EventRequestBody, user_id: ObjectId) -> Self {
+        let defaults = Event::default();
+        Self {
+            user_id,
+            title: body.title.clone(),
+            description: body.description.clone(),
+            location: body.location.clone(),
+            address: body.address.clone(),
+            geo_location: body.latitude.zip(body.longitude).map(|(lat, lng)| GeoPoint::new(lat, lng)),
+            event_date: BsonDateTime::from_chrono(body.event_date),
+            start_time: BsonDateTime::from_chrono(body.start_time),
+            end_time: BsonDateTime::from_chrono(body.end_time),
+            start_date: BsonDateTime::from_chrono(body.start_date),
+            end_date: BsonDateTime::from_chrono(body.end_date),
+            image: body.image.clone(),
+            status: body.status.clone().unwrap_or(EventStatusEnum::Draft),
+            approval_status: body.approval_status.clone().unwrap_or(ApprovalStatusEnum::Pending),
+            is_public: body.is_public.unwrap_or(defaults.is_public),
+            is_virtual: body.is_virtual.unwrap_or(defaults.is_virtual),
+            is_paid: body.is_paid.unwrap_or(defaults.is_paid),
+            price: body.price,
+            max_attendees: body.max_attendees,
+            attendees: Some(Vec::new()),
+            created_by: Some(user_id.to_hex()),
+            tags: body.tags.clone(),
+            category: body.category.clone().unwrap_or_default(),
+            organizer_name: body.organizer_name.clone(),
+            organizer_email: body.organizer_email.clone(),
+            organizer_phone: body.organizer_phone.clone(),
+            registration_url: body.registration_url.clone(),
+            meeting_link: body.meeting_link.clone(),
+            requires_registration: body.requires_registration.unwrap_or(defaults.requires_registration),
+            registration_deadline: body.registration_deadline.map(BsonDateTime::from_chrono),
+            age_restriction: body.age_restriction.clone(),
+            dress_code: body.dress_code.clone(),
+            special_instructions: body.special_instructions.clone(),
+            custom_fields: body.custom_fields.clone(),
+            event_type: body.event_type.clone().unwrap_or_default(),
+            registration_type: body.registration_type.clone().unwrap_or_default(),
+            payment_type: body.payment_type.clone().unwrap_or_default(),
+            ..defaults
+        }
+    }
+
+    /// `$set` document for an API update; optional fields left out of the body are kept as-is
+    pub(crate) fn update_document(body: &EventRequestBody) -> Result<Document, Error> {
+        let mut set = doc! {
+            "title": &body.title,
+            "description": &body.description,
+            "address": &body.address,
+            "event_date": BsonDateTime::from_chrono(body.event_date),
+            "start_time": BsonDateTime::from_chrono(body.start_time),
+            "end_time": BsonDateTime::from_chrono(body.end_time),
+            "start_date": BsonDateTime::from_chrono(body.start_date),
+            "end_date": BsonDateTime::from_chrono(body.end_date),
+        };
+
+        if let Some(v) = &body.location { set.insert("location", v); }
+        if let Some((lat, lng)) = body.latitude.zip(body.longitude) {
+            set.insert("geo_location", convert_to_bson(&GeoPoint::new(lat, lng))?);
+        }
+        if let Some(v) = &body.image { set.insert("image", v); }
+        if let Some(v) = &body.status { set.insert("status", convert_to_bson(v)?); }
+        if let Some(v) = &body.approval_status { set.insert("approval_status", convert_to_bson(v)?); }
+        if let Some(v) = body.is_public { set.insert("is_public", v); }
+        if let Some(v) = body.is_virtual { set.insert("is_virtual", v); }
+        if let Some(v) = body.is_paid { set.insert("is_paid", v); }
+        if let Some(v) = body.price { set.insert("price", v); }
+        if let Some(v) = body.max_attendees { set.insert("max_attendees", v as i64); }
+        if let Some(v) = &body.tags { set.insert("tags", v); }
+        if let Some(v) = &body.category { set.insert("category", convert_to_bson(v)?); }
+        if let Some(v) = &body.organizer_name { set.insert("organizer_name", v); }
+        if let Some(v) = &body.organizer_email { set.insert("organizer_email", v); }
+        if let Some(v) = &body.organizer_phone { set.insert("organizer_phone", v); }
+        if let Some(v) = &body.registration_url { set.insert("registration_url", v); }
+        if let Some(v) = &body.meeting_link { set.insert("meeting_link", v); }
+        if let Some(v) = body.requires_registration { set.insert("requires_registration", v); }
+        if let Some(v) = body.registration_deadline { set.insert("registration_deadline", BsonDateTime::from_chrono(v)); }
+        if let Some(v) = &body.age_restriction { set.insert("age_restriction", v); }
+        if let Some(v) = &body.dress_code { set.insert("dress_code", v); }
+        if let Some(v) = &body.special_instructions { set.insert("special_instructions", v); }
+        if let Some(v) = &body.custom_fields { set.insert("custom_fields", convert_to_bson(v)?); }
+        if let Some(v) = &body.event_type { set.insert("event_type", convert_to_bson(v)?); }
+        if let Some(v) = &body.registration_type { set.insert("registration_type", convert_to_bson(v)?); }
+        if let Some(v) = &body.payment_type { set.insert("payment_type", convert_to_bson(v)?); }
+
+        Ok(set)
+    }
+
+    /// Mongo filter for the public list endpoint; soft-deleted events are never returned
+    pub(crate) fn filter_document(filters: &EventRequestFilters) -> Result<Document, Error> {
+        let mut filter = doc! { "deleted": false };
+
+        if let Some(query) = filters.query.as_deref().filter(|q| !q.trim().is_empty()) {
+            let pattern = regex::escape(query.trim());
+            filter.insert("$or", vec![
+                doc! { "title": { "$regex": &pattern, "$options": "i" } },
+                doc! { "description": { "$regex": &pattern, "$options": "i" } },
+                doc! { "address": { "$regex": &pattern, "$options": "i" } },
+                doc! { "location": { "$regex": &pattern, "$options": "i" } },
+            ]);
+        }
+
+        if let Some(v) = &filters.category { filter.insert("category", convert_to_bson(v)?); }
+        if let Some(v) = &filters.event_type { filter.insert("event_type", convert_to_bson(v)?); }
+        if let Some(v) = &filters.status { filter.insert("status", convert_to_bson(v)?); }
+        if let Some(v) = &filters.approval_status { filter.insert("approval_status", convert_to_bson(v)?); }
+        if let Some(v) = filters.is_public { filter.insert("is_public", v); }
+        if let Some(v) = filters.is_virtual { filter.insert("is_virtual", v); }
+        if let Some(v) = filters.is_paid { filter.insert("is_paid", v); }
+        if let Some(v) = filters.requires_registration { filter.insert("requires_registration", v); }
+        if let Some(v) = &filters.age_restriction { filter.insert("age_restriction", v); }
+        if let Some(v) = &filters.dress_code { filter.insert("dress_code", v); }
+        if let Some(v) = &filters.created_by { filter.insert("created_by", v); }
+
+        if let Some(location) = &filters.location {
+            filter.insert("location", doc! { "$regex": regex::escape(location), "$options": "i" });
+        }
+        if let Some(organizer) = &filters.organizer {
+            filter.insert("organizer_name", doc! { "$regex": regex::escape(organizer), "$options": "i" });
+        }
+
+        if let Some(user_id) = &filters.user_id {
+            let user_id = ObjectId::parse_str(user_id)
+                .map_err(|_| custom_error_expression!(bad_request, 400, "Invalid user_id"))?;
+            filter.insert("user_id", user_id);
+        }
+
+        let mut event_date = Document::new();
+        if let Some(from) = &filters.date_from {
+            match parse_date_param(from, false) {
+                Some(dt) => { event_date.insert("$gte", dt); }
+                None => handle_custom_error!(bad_request, 400, "Invalid date_from"),
+            }
+        }
+        if let Some(to) = &filters.date_to {
+            match parse_date_param(to, true) {
+                Some(dt) => { event_date.insert("$lte", dt); }
+                None => handle_custom_error!(bad_request, 400, "Invalid date_to"),
+            }
+        }
+        if !event_date.is_empty() {
+            filter.insert("event_date", event_date);
+        }
+
+        let mut max_attendees = Document::new();
+        if let Some(min) = filters.max_attendees_min { max_attendees.insert("$gte", min as i64); }
+        if let Some(max) = filters.max_attendees_max { max_attendees.insert("$lte", max as i64); }
+        if !max_attendees.is_empty() {
+            filter.insert("max_attendees", max_attendees);
+        }
+
+        let mut price = Document::new();
+        if let Some(min) = filters.price_min { price.insert("$gte", min); }
+        if let Some(max) = filters.price_max { price.insert("$lte", max); }
+        if !price.is_empty() {
+            filter.insert("price", price);
+        }
+
+        if let Some(tags) = filters.tags.as_ref().filter(|t| !t.is_empty()) {
+            filter.insert("tags", doc! { "$in": tags });
+        } else if let Some(has_tags) = filters.has_tags {
+            filter.insert("tags.0", doc! { "$exists": has_tags });
+        }
+
+        Ok(filter)
+    }
+}
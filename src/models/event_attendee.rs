@@ -1,27 +1,21 @@
 // /Users/xsm/Documents/workspace/XARD/xard-be/src/models/event_attendee.rs
 
-use actix_web::Error;
-use mongodb::{
-    Database,
-    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
-    options::{FindOptions}
-};
-use futures::{TryStreamExt, StreamExt};
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 use validator::{Validate};
 
 use crate::enums::{
     common_enums::StatusEnum,
 };
 use crate::libs::custom_validators::{
+    validate_alpha_only,
     validate_email,
     validate_phone,
 };
-use crate::utilities::bason_utility::convert_to_bson;
 use crate::{
     handle_global_error,
-    custom_error_expression,
-    handle_custom_error,
 };
 use anyhow::{Error as AnyhowError};
 
@@ -34,83 +28,141 @@ use crate::requests::enums::{
 use strum_macros::EnumIter;
 
 
-#[derive(Serialize, Deserialize, Debug, Clone, Validate)]
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema, AdminxModel)]
 pub struct EventAttendee {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Attendee ID", group = "Links")]
     pub id: Option<ObjectId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Event", group = "Links", reference = "events", list, filter)]
     pub event_id: Option<ObjectId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "User", group = "Links", reference = "users", filter)]
     pub user_id: Option<ObjectId>,
 
     // Attendee information
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_email")]
+    #[adminx(group = "Contact", widget = "email", list, filter)]
     pub email: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_phone")]
+    #[adminx(group = "Contact", widget = "tel", list, filter)]
     pub phone: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_alpha_only")]
+    #[adminx(label = "First", group = "Contact", list, filter)]
     pub first_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(custom = "validate_alpha_only")]
+    #[adminx(label = "Last", group = "Contact", list, filter)]
     pub last_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Contact", filter)]
     pub company: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Contact", filter)]
     pub designation: Option<String>,
 
     // Registration details
+    // Seats follow it, so it only changes through the attendance service
+    #[adminx(label = "Reg. Status", group = "Registration", readonly, list, filter)]
     pub registration_status: StatusEnum,
+    #[adminx(label = "Reg. Type", group = "Registration", list, filter)]
     pub registration_type: RegistrationTypeEnum,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(group = "Registration", filter)]
     pub registration_date: Option<BsonDateTime>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Check-in", group = "Attendance", list, filter)]
     pub check_in_date: Option<BsonDateTime>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Check-out", group = "Attendance", list, filter)]
     pub check_out_date: Option<BsonDateTime>,
 
     // Additional information
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Other")]
     pub dietary_requirements: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Other")]
     pub accessibility_needs: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Other")]
     pub emergency_contact: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Other", widget = "editor_text")]
     pub notes: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Registration")]
+    pub cancellation_reason: Option<String>,
+
+    // Waitlist
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(group = "Registration", filter)]
+    pub waitlisted_at: Option<BsonDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(group = "Registration")]
+    pub promoted_at: Option<BsonDateTime>,
+
+    // Set on guests registered as someone's plus one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Plus One Of", group = "Links", reference = "event_attendees")]
+    pub plus_one_of: Option<ObjectId>,
+
     // Payment information
     #[serde(default)]
+    #[adminx(label = "Paid?", group = "Payment", list, filter)]
     pub payment_status: bool,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Amount", group = "Payment", list)]
     pub payment_amount: Option<f64>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(group = "Payment", filter)]
     pub payment_date: Option<BsonDateTime>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Payment Ref", group = "Payment", filter)]
     pub payment_reference: Option<String>,
 
     // Timestamps
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub updated_at: BsonDateTime,
 
     // System fields
     #[serde(default)]
+    #[adminx(label = "Deleted?", group = "System", filter)]
     pub deleted: bool,
 }
 
@@ -135,6 +187,10 @@ impl Default for EventAttendee {
             accessibility_needs: None,
             emergency_contact: None,
             notes: None,
+            cancellation_reason: None,
+            waitlisted_at: None,
+            promoted_at: None,
+            plus_one_of: None,
             payment_status: false,
             payment_amount: None,
             payment_date: None,
@@ -145,182 +201,3 @@ impl Default for EventAttendee {
         }
     }
 }
-
-impl EventAttendee {
-    /// Register for an event
-    pub async fn register_for_event(&self, db: &Database) -> Result<Self, Error> {
-        let collection = db.collection::<EventAttendee>("event_attendees");
-        
-        let mut attendee = self.clone();
-        attendee.registration_date = Some(BsonDateTime::now());
-        attendee.created_at = BsonDateTime::now();
-        attendee.updated_at = BsonDateTime::now();
-        
-        let insert_result = collection.insert_one(&attendee, None).await
-            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
-        
-        attendee.id = insert_result.inserted_id.as_object_id();
-        Ok(attendee)
-    }
-
-    /// Check in to an event
-    pub async fn check_in(&self, db: &Database) -> Result<(), Error> {
-        let attendee_id = match self.id {
-            Some(id) => id,
-            None => return Err(handle_custom_error!(bad_request, 400, "Missing attendee ID".to_string())),
-        };
-
-        let collection = db.collection::<EventAttendee>("event_attendees");
-        let filter = doc! { "_id": attendee_id, "deleted": false };
-        let update = doc! {
-            "$set": {
-                "check_in_date": BsonDateTime::now()
-            },
-            "$currentDate": {
-                "updated_at": true
-            }
-        };
-
-        let update_result = collection.update_one(filter, update, None).await
-            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
-
-        if update_result.matched_count == 0 {
-            return Err(handle_custom_error!(not_found, 404, "Attendee not found".to_string()));
-        }
-
-        Ok(())
-    }
-
-    /// Check out of an event
-    pub async fn check_out(&self, db: &Database) -> Result<(), Error> {
-        let attendee_id = match self.id {
-            Some(id) => id,
-            None => return Err(handle_custom_error!(bad_request, 400, "Missing attendee ID".to_string())),
-        };
-
-        let collection = db.collection::<EventAttendee>("event_attendees");
-        let filter = doc! { "_id": attendee_id, "deleted": false };
-        let update = doc! {
-            "$set": {
-                "check_out_date": BsonDateTime::now()
-            },
-            "$currentDate": {
-                "updated_at": true
-            }
-        };
-
-        let update_result = collection.update_one(filter, update, None).await
-            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
-
-        if update_result.matched_count == 0 {
-            return Err(handle_custom_error!(not_found, 404, "Attendee not found".to_string()));
-        }
-
-        Ok(())
-    }
-
-    /// Get attendees for a specific event
-    pub async fn get_event_attendees(
-        db: &Database,
-        event_id: ObjectId,
-        page: u64,
-        limit: i64,
-    ) -> Result<Vec<Self>, Error> {
-        let collection = db.collection::<EventAttendee>("event_attendees");
-        let skip = (page - 1) * limit as u64;
-
-        let filter = doc! {
-            "event_id": event_id,
-            "deleted": false
-        };
-
-        let find_options = FindOptions::builder()
-            .sort(doc! { "created_at": -1 })
-            .skip(skip)
-            .limit(limit)
-            .build();
-
-        let mut cursor = collection.find(filter, find_options).await
-            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
-
-        let mut attendees = Vec::new();
-        while let Some(result) = cursor.next().await {
-            match result {
-                Ok(attendee) => attendees.push(attendee),
-                Err(e) => return Err(handle_custom_error!(internal_error, 500, e.to_string())),
-            }
-        }
-
-        Ok(attendees)
-    }
-
-    /// Cancel attendance
-    pub async fn cancel_attendance(&self, db: &Database) -> Result<(), Error> {
-        let attendee_id = match self.id {
-            Some(id) => id,
-            None => return Err(handle_custom_error!(bad_request, 400, "Missing attendee ID".to_string())),
-        };
-
-        let collection = db.collection::<EventAttendee>("event_attendees");
-        let filter = doc! { "_id": attendee_id };
-        let update = doc! {
-            "$set": {
-                "deleted": true,
-                "registration_status": convert_to_bson(&StatusEnum::Inactive)?
-            },
-            "$currentDate": {
-                "updated_at": true
-            }
-        };
-
-        let update_result = collection.update_one(filter, update, None).await
-            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
-
-        if update_result.matched_count == 0 {
-            return Err(handle_custom_error!(not_found, 404, "Attendee not found".to_string()));
-        }
-
-        Ok(())
-    }
-
-    /// Update attendee information
-    pub async fn update_attendee(&self, db: &Database) -> Result<Self, Error> {
-        let attendee_id = match self.id {
-            Some(id) => id,
-            None => return Err(handle_custom_error!(bad_request, 400, "Missing attendee ID".to_string())),
-        };
-
-        let collection = db.collection::<EventAttendee>("event_attendees");
-        let filter = doc! { "_id": attendee_id, "deleted": false };
-        
-        let update_doc = doc! {
-            "$set": {
-                "email": &self.email,
-                "phone": &self.phone,
-                "first_name": &self.first_name,
-                "last_name": &self.last_name,
-                "company": &self.company,
-                "designation": &self.designation,
-                "dietary_requirements": &self.dietary_requirements,
-                "accessibility_needs": &self.accessibility_needs,
-                "emergency_contact": &self.emergency_contact,
-                "notes": &self.notes,
-                "payment_status": self.payment_status,
-                "payment_amount": &self.payment_amount,
-                "payment_reference": &self.payment_reference,
-            },
-            "$currentDate": {
-                "updated_at": true
-            }
-        };
-
-        let update_result = collection.update_one(filter, update_doc, None).await
-            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
-
-        if update_result.matched_count == 0 {
-            return Err(handle_custom_error!(not_found, 404, "Attendee not found".to_string()));
-        }
-
-        Ok(self.clone())
-    }
-}
@@ -2,13 +2,57 @@
 use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::enums::common_enums::StatusEnum;
+
+fn default_invitation_status() -> StatusEnum {
+    StatusEnum::Pending
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Invitation {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
     pub id: Option<ObjectId>,
     pub user_id: Option<ObjectId>, // ✅ Foreign key reference to User model
     pub email: Option<String>,
     pub message: Option<String>,
+
+    // Event invitations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<ObjectId>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+
+    #[serde(default = "default_invitation_status")]
+    pub status: StatusEnum,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invite_type: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<BsonDateTime>,
+
+    #[serde(default)]
+    pub requires_response: bool,
+
+    #[serde(default)]
+    pub allow_plus_one: bool,
+
+    #[serde(default)]
+    pub plus_one: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plus_one_name: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub responded_at: Option<BsonDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendee_id: Option<ObjectId>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x_platform: Option<String>,
+
     pub created_at: BsonDateTime,
     pub updated_at: BsonDateTime,
 }
@@ -21,8 +65,35 @@ impl Invitation {
             user_id,
             email,
             message: Some(message.unwrap_or_else(|| "Join Xard!".to_string())),
+            event_id: None,
+            token: None,
+            status: StatusEnum::Pending,
+            invite_type: None,
+            expires_at: None,
+            requires_response: false,
+            allow_plus_one: false,
+            plus_one: false,
+            plus_one_name: None,
+            responded_at: None,
+            attendee_id: None,
+            x_platform: None,
             created_at: BsonDateTime::now(),
             updated_at: BsonDateTime::now(),
         }
     }
+
+    /// Creates a pending invitation to an event with a fresh, unguessable token.
+    pub fn for_event(event_id: ObjectId, user_id: Option<ObjectId>, email: Option<String>, message: Option<String>) -> Self {
+        Invitation {
+            event_id: Some(event_id),
+            token: Some(nanoid::nanoid!(32)),
+            message,
+            ..Invitation::new(user_id, email, None)
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at.timestamp_millis() <= BsonDateTime::now().timestamp_millis())
+    }
 }
@@ -1,10 +1,15 @@
 // models/mod.rs
 pub mod user;
+pub mod user_ban;
+pub mod audit_log;
 pub mod contact;
 pub mod invitation;
 pub mod config;
 pub mod notification;
 pub mod picture;
+pub mod sent_email;
+pub mod admin_two_factor;
+pub mod admin_user;
 
 pub mod event_attendee;
 pub mod event;
\ No newline at end of file
@@ -1,22 +1,41 @@
 // models/notification.rs
 use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 use serde_json::Value;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, AdminxModel)]
 pub struct Notification {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Notification ID", group = "System")]
     pub id: Option<ObjectId>,
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "User", group = "Notification", reference = "users", list, filter)]
     pub user_id: Option<ObjectId>, // ✅ Foreign key reference to User Notification
+    #[adminx(group = "Notification", list, filter)]
     pub title: Option<String>,
+    #[adminx(label = "Type", group = "Notification", filter)]
     pub notify_type: Option<String>,
+    #[adminx(group = "Notification", widget = "textarea", list)]
     pub message: Option<String>, // ✅ "success" or "failed"
+    #[adminx(group = "Notification")]
     pub destination: Option<String>,
+    #[adminx(group = "Notification")]
     pub extras: Option<Value>,
+    #[adminx(group = "System", filter)]
     pub deleted: bool,
+    #[adminx(label = "Platform", group = "System", readonly)]
     pub x_platform: Option<String>, // ✅ Stores `X-PLATFORM` header info
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub updated_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", readonly)]
     pub sent_at: BsonDateTime,
 }
 
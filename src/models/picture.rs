@@ -1,14 +1,11 @@
 use mongodb::{bson::{doc, oid::ObjectId, DateTime as BsonDateTime}};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 use strum_macros::EnumIter;
-use crate::enums::{
-    common_enums::{
-        StatusEnum,
-    }
-};
 
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, EnumIter, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum PictureStatus {
     Active,
@@ -26,24 +23,34 @@ impl ToString for PictureStatus {
 
 
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, AdminxModel)]
 pub struct Picture {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Picture ID", group = "System")]
     pub id: Option<ObjectId>,
 
     /// Picture URL or file path
+    #[adminx(label = "Image URL", widget = "url", readonly, list)]
     pub url: String,
 
     /// Optional Picture metadata
+    #[adminx(list, filter)]
     pub title: Option<String>,
 
     #[serde(default)]
+    #[adminx(list, filter)]
     pub deleted: bool,
 
-    pub status: StatusEnum,
+    #[adminx(list, filter)]
+    pub status: PictureStatus,
 
     /// Timestamps
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", filter)]
     pub updated_at: BsonDateTime,
 }
 
@@ -54,7 +61,7 @@ impl Default for Picture {
             id: None,
             url: String::new(),
             title: None,
-            status: StatusEnum::Active,
+            status: PictureStatus::Active,
             deleted: false,
             created_at: BsonDateTime::now(),
             updated_at: BsonDateTime::now(),
@@ -0,0 +1,108 @@
+// models/sent_email.rs
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
+
+use crate::enums::common_enums::{EmailKindEnum, EmailStatusEnum};
+
+/// One outgoing email. Written rendered when it is queued, so retries resend exactly
+/// what was first attempted and the log shows what went out.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, AdminxModel)]
+pub struct SentEmail {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Email ID", group = "System")]
+    pub id: Option<ObjectId>,
+
+    #[adminx(group = "Message", list, filter)]
+    pub kind: EmailKindEnum,
+
+    #[adminx(label = "To", group = "Message", list, filter)]
+    pub to: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Reply To", group = "Message")]
+    pub reply_to: Option<String>,
+
+    #[adminx(group = "Message", list, filter)]
+    pub subject: String,
+
+    // Cleared once a sensitive email (OTP codes) is sent or given up on
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "HTML Body", group = "Message", widget = "textarea")]
+    pub html_body: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Text Body", group = "Message", widget = "textarea")]
+    pub text_body: Option<String>,
+
+    #[serde(default)]
+    #[adminx(group = "Message")]
+    pub sensitive: bool,
+
+    // Invitation, attendee or event the email is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "Related Record", group = "Message", filter)]
+    pub related_id: Option<ObjectId>,
+
+    #[adminx(group = "Delivery", list, filter)]
+    pub status: EmailStatusEnum,
+
+    #[serde(default)]
+    #[adminx(group = "Delivery", list)]
+    pub attempts: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Last Error", group = "Delivery", widget = "textarea")]
+    pub last_error: Option<String>,
+
+    // When the next attempt is due; while sending, when the attempt counts as abandoned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Next Attempt", group = "Delivery")]
+    pub next_attempt_at: Option<BsonDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<DateTimeSchema>")]
+    #[adminx(label = "Sent At", group = "Delivery", list, filter)]
+    pub sent_at: Option<BsonDateTime>,
+
+    // MailTransport::name of the transport that delivered it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Delivery", filter)]
+    pub transport: Option<String>,
+
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
+    pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System")]
+    pub updated_at: BsonDateTime,
+}
+
+impl SentEmail {
+    /// A rendered email waiting for its first attempt
+    pub fn queued(kind: EmailKindEnum, to: String, subject: String, html_body: String, text_body: String) -> Self {
+        SentEmail {
+            id: None,
+            kind,
+            to,
+            reply_to: None,
+            subject,
+            html_body: Some(html_body),
+            text_body: Some(text_body),
+            sensitive: false,
+            related_id: None,
+            status: EmailStatusEnum::Queued,
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: Some(BsonDateTime::now()),
+            sent_at: None,
+            transport: None,
+            created_at: BsonDateTime::now(),
+            updated_at: BsonDateTime::now(),
+        }
+    }
+}
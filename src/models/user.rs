@@ -1,12 +1,12 @@
 // models/user.rs
-use actix_web::{Error};
 use mongodb::{
-    Database,
     bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
     options::{FindOptions}
 };
 use futures::{TryStreamExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use crate::admin::model_schema::{AdminxModel, DateTimeSchema, ObjectIdSchema};
 use serde_json::Value;
 use validator::{Validate};
 use crate::enums::{
@@ -28,103 +28,133 @@ use crate::libs::custom_validators::{
 use crate::utilities::bason_utility::convert_to_bson;
 use crate::{
     handle_global_error,
-    custom_error_expression,
     handle_custom_error,
 };
 use anyhow::{Error as AnyhowError};
 
-#[derive(Serialize, Deserialize, Debug, Clone, Validate)]
+#[derive(Serialize, Deserialize, Debug, Clone, Validate, JsonSchema, AdminxModel)]
 pub struct User {
     #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    #[schemars(with = "Option<ObjectIdSchema>")]
+    #[adminx(label = "User ID", group = "System")]
     pub id: Option<ObjectId>,
 
     #[validate(required)]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_alpha_only")]
+    #[adminx(group = "Profile", list, filter)]
     pub first_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_alpha_only")]
+    #[adminx(group = "Profile", list)]
     pub last_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_email")]
+    #[adminx(group = "Profile", widget = "email", list, filter)]
     pub email: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_email")]
+    #[adminx(group = "Profile", widget = "email")]
     pub official_email: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(required)]
     #[validate(custom = "validate_phone")]
+    #[adminx(group = "Profile", widget = "tel", filter)]
     pub phone_number: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_username")]
+    #[adminx(group = "Profile", filter)]
     pub username: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "Country Code", group = "Profile")]
     pub ccode: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Profile")]
     pub age: Option<u8>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(group = "Profile")]
     pub gender: Option<GenderEnum>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_alphanumeric")]
+    #[adminx(group = "Profile", widget = "textarea")]
     pub description: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(label = "LinkedIn", group = "Work", widget = "url")]
     pub linkedin: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(skip)]
     pub linkedin_datum: Option<Value>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_alphanumeric")]
+    #[adminx(group = "Work")]
     pub designation: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[adminx(skip)]
     pub firebase_token: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Work", filter)]
     pub company_name: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Work", widget = "textarea")]
     pub company_address: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_website")]
+    #[adminx(group = "Work", widget = "url")]
     pub website: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(skip)]
     pub protection_secret: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(label = "Platform", group = "System", readonly)]
     pub x_platform: Option<String>, // ✅ Store X-PLATFORM header info
+    #[adminx(group = "Account", list, filter)]
     pub status: StatusEnum,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(custom = "validate_message")]
+    #[adminx(group = "Account", widget = "textarea")]
     pub notes: Option<String>,
+    #[adminx(label = "Social User ID", group = "System", readonly)]
     pub social_user_id: Option<String>,
 
     #[serde(default)]
+    #[adminx(group = "Account", filter)]
     pub deleted: bool,
     #[serde(default)]
+    #[adminx(group = "Account", readonly, list, filter)]
     pub locked: bool,
 
+    #[adminx(group = "Account")]
     pub onboard: OnboardEnum,
 
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", list, filter)]
     pub created_at: BsonDateTime,
+    #[schemars(with = "DateTimeSchema")]
+    #[adminx(group = "System", filter)]
     pub updated_at: BsonDateTime,
 }
 
@@ -174,4 +204,4 @@ impl User {
             (None, None)       => String::new(),
         };
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,45 @@
+// models/user_ban.rs
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::enums::common_enums::{BanTypeEnum, StatusEnum};
+
+/// One ban issued from AdminX. Bans are never deleted: lifting one (manually or on
+/// expiry) flips `active` and records who lifted it, so the collection is the history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserBan {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: ObjectId,
+    pub reason: String,
+    pub ban_type: BanTypeEnum,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_days: Option<i64>,
+
+    // Only temporary bans expire
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<BsonDateTime>,
+
+    // Email of the AdminX user that issued the ban
+    pub issued_by: String,
+
+    // User status before the ban, restored when it is lifted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<StatusEnum>,
+
+    #[serde(default)]
+    pub active: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifted_at: Option<BsonDateTime>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifted_by: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lift_reason: Option<String>,
+
+    pub created_at: BsonDateTime,
+    pub updated_at: BsonDateTime,
+}
@@ -0,0 +1,154 @@
+// src/repositories/admin_user_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, Document, DateTime as BsonDateTime},
+};
+
+use crate::db::indexes::is_duplicate_key_error;
+use crate::enums::common_enums::{AdminRoleEnum, AdminStatusEnum};
+use crate::models::admin_user::AdminUser;
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+#[async_trait]
+pub trait AdminUserRepository: Send + Sync {
+    async fn find_by_id(&self, id: ObjectId) -> Result<Option<AdminUser>, Error>;
+
+    /// The operator signing in with this (lowercased) email
+    async fn find_by_email(&self, email: &str) -> Result<Option<AdminUser>, Error>;
+
+    /// 409 when the email is taken
+    async fn insert(&self, admin: &AdminUser) -> Result<AdminUser, Error>;
+
+    /// Sets whichever of the fields are given; 404 when the operator is missing
+    async fn update_account(
+        &self,
+        id: ObjectId,
+        name: Option<&str>,
+        role: Option<AdminRoleEnum>,
+        status: Option<AdminStatusEnum>,
+    ) -> Result<(), Error>;
+
+    async fn set_password(&self, id: ObjectId, password_hash: &str, must_change_password: bool) -> Result<(), Error>;
+
+    async fn record_login(&self, id: ObjectId) -> Result<(), Error>;
+
+    async fn count_active_superadmins(&self) -> Result<u64, Error>;
+
+    /// 404 when the operator is missing
+    async fn delete(&self, id: ObjectId) -> Result<(), Error>;
+}
+
+pub struct MongoAdminUserRepository {
+    collection: Collection<AdminUser>,
+}
+
+impl MongoAdminUserRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<AdminUser>("admin_users") }
+    }
+
+    async fn set(&self, id: ObjectId, fields: Document) -> Result<(), Error> {
+        let update = doc! {
+            "$set": fields,
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(doc! { "_id": id }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if update_result.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Admin user not found");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AdminUserRepository for MongoAdminUserRepository {
+    async fn find_by_id(&self, id: ObjectId) -> Result<Option<AdminUser>, Error> {
+        self.collection.find_one(doc! { "_id": id }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<AdminUser>, Error> {
+        self.collection.find_one(doc! { "email": email }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn insert(&self, admin: &AdminUser) -> Result<AdminUser, Error> {
+        let mut admin = admin.clone();
+        admin.created_at = BsonDateTime::now();
+        admin.updated_at = BsonDateTime::now();
+
+        let insert_result = match self.collection.insert_one(&admin, None).await {
+            Ok(insert_result) => insert_result,
+            Err(e) if is_duplicate_key_error(&e) => {
+                handle_custom_error!(conflict, 409, "An admin user with this email already exists");
+            }
+            Err(e) => return Err(custom_error_expression!(internal_error, 500, e.to_string()).into()),
+        };
+
+        admin.id = insert_result.inserted_id.as_object_id();
+        Ok(admin)
+    }
+
+    async fn update_account(
+        &self,
+        id: ObjectId,
+        name: Option<&str>,
+        role: Option<AdminRoleEnum>,
+        status: Option<AdminStatusEnum>,
+    ) -> Result<(), Error> {
+        let mut fields = Document::new();
+        if let Some(name) = name {
+            fields.insert("name", name);
+        }
+        if let Some(role) = role {
+            fields.insert("role", convert_to_bson(&role)?);
+        }
+        if let Some(status) = status {
+            fields.insert("status", convert_to_bson(&status)?);
+        }
+        self.set(id, fields).await
+    }
+
+    async fn set_password(&self, id: ObjectId, password_hash: &str, must_change_password: bool) -> Result<(), Error> {
+        self.set(id, doc! {
+            "password_hash": password_hash,
+            "must_change_password": must_change_password,
+            "password_changed_at": BsonDateTime::now()
+        }).await
+    }
+
+    async fn record_login(&self, id: ObjectId) -> Result<(), Error> {
+        self.set(id, doc! { "last_login_at": BsonDateTime::now() }).await
+    }
+
+    async fn count_active_superadmins(&self) -> Result<u64, Error> {
+        let filter = doc! {
+            "role": convert_to_bson(&AdminRoleEnum::Superadmin)?,
+            "status": convert_to_bson(&AdminStatusEnum::Active)?
+        };
+        self.collection.count_documents(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn delete(&self, id: ObjectId) -> Result<(), Error> {
+        let delete_result = self.collection.delete_one(doc! { "_id": id }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if delete_result.deleted_count == 0 {
+            handle_custom_error!(not_found, 404, "Admin user not found");
+        }
+        Ok(())
+    }
+}
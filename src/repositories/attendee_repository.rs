@@ -0,0 +1,310 @@
+// src/repositories/attendee_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
+    options::{FindOneAndUpdateOptions, FindOneOptions, FindOptions, ReturnDocument},
+};
+use serde::Serialize;
+
+use crate::db::indexes::is_duplicate_key_error;
+use crate::repositories::contact_repository::email_or_phone_filter;
+use crate::enums::common_enums::StatusEnum;
+use crate::models::event_attendee::EventAttendee;
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+/// Live (not cancelled) attendees of one event. Waitlisted attendees are not counted
+/// as registered.
+#[derive(Debug, Clone, Copy, Default, Serialize)]
+pub struct AttendeeCounts {
+    pub total: u64,
+    pub registered: u64,
+    pub checked_in: u64,
+    pub waitlisted: u64,
+}
+
+#[async_trait]
+pub trait AttendeeRepository: Send + Sync {
+    /// Insert a registration; a second live registration of the same user is a 409
+    async fn insert(&self, attendee: &EventAttendee) -> Result<EventAttendee, Error>;
+
+    /// A registration, cancelled or not
+    async fn find_by_id(&self, attendee_id: ObjectId) -> Result<Option<EventAttendee>, Error>;
+
+    /// The live registration of a user for an event
+    async fn find_registration(&self, event_id: ObjectId, user_id: ObjectId) -> Result<Option<EventAttendee>, Error>;
+
+    /// Stamp `check_in_date`; 404 when the attendee is missing or cancelled
+    async fn check_in(&self, attendee_id: ObjectId) -> Result<(), Error>;
+
+    /// Stamp `check_out_date`; 404 when the attendee is missing or cancelled
+    async fn check_out(&self, attendee_id: ObjectId) -> Result<(), Error>;
+
+    /// Soft delete a registration, returning it as it was before the update.
+    /// `None` when nothing live matched.
+    async fn mark_cancelled(&self, attendee_id: ObjectId, reason: Option<String>) -> Result<Option<EventAttendee>, Error>;
+
+    /// Bring a cancelled registration back, holding a seat or at the end of the waitlist.
+    /// `None` when it is not cancelled (anymore).
+    async fn restore(&self, attendee_id: ObjectId, waitlisted: bool) -> Result<Option<EventAttendee>, Error>;
+
+    /// One page of an event's live attendees, newest registration first
+    async fn list_for_event(&self, event_id: ObjectId, page: u64, per_page: i64) -> Result<Vec<EventAttendee>, Error>;
+
+    /// Every attendee holding a seat at the event, waitlisted ones excluded
+    async fn registered_for_event(&self, event_id: ObjectId) -> Result<Vec<EventAttendee>, Error>;
+
+    async fn counts_for_event(&self, event_id: ObjectId) -> Result<AttendeeCounts, Error>;
+
+    /// Live attendees of an event with one of the emails (case-insensitive) or phone numbers
+    async fn find_matching(&self, event_id: ObjectId, emails: &[String], phones: &[String]) -> Result<Vec<EventAttendee>, Error>;
+
+    /// Ids of the live guests registered as `host_id`'s plus one
+    async fn plus_ones_of(&self, host_id: ObjectId) -> Result<Vec<ObjectId>, Error>;
+
+    /// The longest-waiting attendee still on the event's waitlist
+    async fn next_waitlisted(&self, event_id: ObjectId) -> Result<Option<EventAttendee>, Error>;
+
+    /// Move a waitlisted attendee to active. Returns false when they left the queue meanwhile.
+    async fn promote(&self, attendee_id: ObjectId) -> Result<bool, Error>;
+}
+
+pub struct MongoAttendeeRepository {
+    collection: Collection<EventAttendee>,
+}
+
+impl MongoAttendeeRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<EventAttendee>("event_attendees") }
+    }
+
+    async fn stamp(&self, attendee_id: ObjectId, field: &str) -> Result<(), Error> {
+        let filter = doc! { "_id": attendee_id, "deleted": { "$ne": true } };
+        let update = doc! {
+            "$set": {
+                field: BsonDateTime::now()
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if update_result.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Attendee not found");
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AttendeeRepository for MongoAttendeeRepository {
+    async fn insert(&self, attendee: &EventAttendee) -> Result<EventAttendee, Error> {
+        let mut attendee = attendee.clone();
+        attendee.registration_date = Some(BsonDateTime::now());
+        attendee.created_at = BsonDateTime::now();
+        attendee.updated_at = BsonDateTime::now();
+
+        let insert_result = self.collection.insert_one(&attendee, None).await
+            .map_err(|e| if is_duplicate_key_error(&e) {
+                custom_error_expression!(conflict, 409, "Already registered for this event")
+            } else {
+                custom_error_expression!(internal_error, 500, e.to_string())
+            })?;
+
+        attendee.id = insert_result.inserted_id.as_object_id();
+        Ok(attendee)
+    }
+
+    async fn find_by_id(&self, attendee_id: ObjectId) -> Result<Option<EventAttendee>, Error> {
+        self.collection.find_one(doc! { "_id": attendee_id }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn find_registration(&self, event_id: ObjectId, user_id: ObjectId) -> Result<Option<EventAttendee>, Error> {
+        let filter = doc! {
+            "event_id": event_id,
+            "user_id": user_id,
+            "deleted": false
+        };
+
+        self.collection.find_one(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn check_in(&self, attendee_id: ObjectId) -> Result<(), Error> {
+        self.stamp(attendee_id, "check_in_date").await
+    }
+
+    async fn check_out(&self, attendee_id: ObjectId) -> Result<(), Error> {
+        self.stamp(attendee_id, "check_out_date").await
+    }
+
+    async fn mark_cancelled(&self, attendee_id: ObjectId, reason: Option<String>) -> Result<Option<EventAttendee>, Error> {
+        let filter = doc! { "_id": attendee_id, "deleted": { "$ne": true } };
+        let update = doc! {
+            "$set": {
+                "deleted": true,
+                "registration_status": convert_to_bson(&StatusEnum::Inactive)?,
+                "cancellation_reason": reason
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        // Returns the document as it was before the update
+        self.collection.find_one_and_update(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn restore(&self, attendee_id: ObjectId, waitlisted: bool) -> Result<Option<EventAttendee>, Error> {
+        let filter = doc! { "_id": attendee_id, "deleted": true };
+        let status = if waitlisted { StatusEnum::Pending } else { StatusEnum::Active };
+        let update = doc! {
+            "$set": {
+                "deleted": false,
+                "registration_status": convert_to_bson(&status)?,
+                "waitlisted_at": waitlisted.then(BsonDateTime::now),
+                "promoted_at": null,
+                "cancellation_reason": null
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection.find_one_and_update(filter, update, options).await
+            .map_err(|e| if is_duplicate_key_error(&e) {
+                custom_error_expression!(conflict, 409, "Already registered for this event").into()
+            } else {
+                custom_error_expression!(internal_error, 500, e.to_string()).into()
+            })
+    }
+
+    async fn list_for_event(&self, event_id: ObjectId, page: u64, per_page: i64) -> Result<Vec<EventAttendee>, Error> {
+        let filter = doc! { "event_id": event_id, "deleted": { "$ne": true } };
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1, "_id": -1 })
+            .skip(page.saturating_sub(1) * per_page as u64)
+            .limit(per_page)
+            .build();
+
+        self.collection.find(filter, options).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn registered_for_event(&self, event_id: ObjectId) -> Result<Vec<EventAttendee>, Error> {
+        let filter = doc! {
+            "event_id": event_id,
+            "deleted": { "$ne": true },
+            "registration_status": convert_to_bson(&StatusEnum::Active)?
+        };
+
+        self.collection.find(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn counts_for_event(&self, event_id: ObjectId) -> Result<AttendeeCounts, Error> {
+        let live = doc! { "event_id": event_id, "deleted": { "$ne": true } };
+        let mut waitlisted = live.clone();
+        waitlisted.extend(doc! {
+            "registration_status": convert_to_bson(&StatusEnum::Pending)?,
+            "waitlisted_at": { "$ne": null },
+            "promoted_at": null
+        });
+        let mut checked_in = live.clone();
+        checked_in.insert("check_in_date", doc! { "$ne": null });
+
+        let count = |filter| async {
+            self.collection.count_documents(filter, None).await
+                .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))
+        };
+        let total = count(live).await?;
+        let waitlisted = count(waitlisted).await?;
+        let checked_in = count(checked_in).await?;
+
+        Ok(AttendeeCounts {
+            total,
+            registered: total.saturating_sub(waitlisted),
+            checked_in,
+            waitlisted,
+        })
+    }
+
+    async fn find_matching(&self, event_id: ObjectId, emails: &[String], phones: &[String]) -> Result<Vec<EventAttendee>, Error> {
+        let Some(any_of) = email_or_phone_filter("email", "phone", emails, phones) else {
+            return Ok(Vec::new());
+        };
+        let filter = doc! { "event_id": event_id, "deleted": { "$ne": true }, "$or": any_of };
+
+        self.collection.find(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn plus_ones_of(&self, host_id: ObjectId) -> Result<Vec<ObjectId>, Error> {
+        let guests: Vec<EventAttendee> = self.collection.find(doc! { "plus_one_of": host_id, "deleted": { "$ne": true } }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(guests.into_iter().filter_map(|guest| guest.id).collect())
+    }
+
+    async fn next_waitlisted(&self, event_id: ObjectId) -> Result<Option<EventAttendee>, Error> {
+        let filter = doc! {
+            "event_id": event_id,
+            "deleted": { "$ne": true },
+            "registration_status": convert_to_bson(&StatusEnum::Pending)?,
+            "waitlisted_at": { "$ne": null },
+            "promoted_at": null
+        };
+        let options = FindOneOptions::builder()
+            .sort(doc! { "waitlisted_at": 1, "_id": 1 })
+            .build();
+
+        self.collection.find_one(filter, options).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn promote(&self, attendee_id: ObjectId) -> Result<bool, Error> {
+        let filter = doc! {
+            "_id": attendee_id,
+            "deleted": { "$ne": true },
+            "registration_status": convert_to_bson(&StatusEnum::Pending)?
+        };
+        let update = doc! {
+            "$set": {
+                "registration_status": convert_to_bson(&StatusEnum::Active)?,
+                "promoted_at": BsonDateTime::now()
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(update_result.modified_count == 1)
+    }
+}
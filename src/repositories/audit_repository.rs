@@ -0,0 +1,32 @@
+// src/repositories/audit_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::{Collection, Database};
+
+use crate::models::audit_log::AuditLog;
+use crate::custom_error_expression;
+
+/// Append-only: entries are read back through the AdminX `AuditLogResource`
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn insert(&self, entry: &AuditLog) -> Result<(), Error>;
+}
+
+pub struct MongoAuditRepository {
+    collection: Collection<AuditLog>,
+}
+
+impl MongoAuditRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<AuditLog>("audit_logs") }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for MongoAuditRepository {
+    async fn insert(&self, entry: &AuditLog) -> Result<(), Error> {
+        self.collection.insert_one(entry, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(())
+    }
+}
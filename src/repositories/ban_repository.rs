@@ -0,0 +1,110 @@
+// src/repositories/ban_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
+    options::FindOptions,
+};
+
+use crate::models::user_ban::UserBan;
+use crate::custom_error_expression;
+
+#[async_trait]
+pub trait BanRepository: Send + Sync {
+    async fn insert(&self, ban: &UserBan) -> Result<UserBan, Error>;
+
+    /// The ban currently in force for a user, if any
+    async fn find_active(&self, user_id: ObjectId) -> Result<Option<UserBan>, Error>;
+
+    /// Deactivate the user's active bans, returning how many were lifted
+    async fn lift(&self, user_id: ObjectId, lifted_by: &str, lift_reason: &str) -> Result<u64, Error>;
+
+    /// Every ban of a user, newest first
+    async fn history(&self, user_id: ObjectId) -> Result<Vec<UserBan>, Error>;
+
+    /// Users whose temporary ban has run out
+    async fn expired_user_ids(&self) -> Result<Vec<ObjectId>, Error>;
+}
+
+pub struct MongoBanRepository {
+    collection: Collection<UserBan>,
+}
+
+impl MongoBanRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<UserBan>("user_bans") }
+    }
+}
+
+#[async_trait]
+impl BanRepository for MongoBanRepository {
+    async fn insert(&self, ban: &UserBan) -> Result<UserBan, Error> {
+        let mut ban = ban.clone();
+        ban.created_at = BsonDateTime::now();
+        ban.updated_at = BsonDateTime::now();
+
+        let insert_result = self.collection.insert_one(&ban, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        ban.id = insert_result.inserted_id.as_object_id();
+        Ok(ban)
+    }
+
+    async fn find_active(&self, user_id: ObjectId) -> Result<Option<UserBan>, Error> {
+        self.collection.find_one(doc! { "user_id": user_id, "active": true }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn lift(&self, user_id: ObjectId, lifted_by: &str, lift_reason: &str) -> Result<u64, Error> {
+        let update = doc! {
+            "$set": {
+                "active": false,
+                "lifted_at": BsonDateTime::now(),
+                "lifted_by": lifted_by,
+                "lift_reason": lift_reason
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_many(doc! { "user_id": user_id, "active": true }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(update_result.modified_count)
+    }
+
+    async fn history(&self, user_id: ObjectId) -> Result<Vec<UserBan>, Error> {
+        let options = FindOptions::builder()
+            .sort(doc! { "created_at": -1 })
+            .build();
+
+        self.collection.find(doc! { "user_id": user_id }, options).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn expired_user_ids(&self) -> Result<Vec<ObjectId>, Error> {
+        let filter = doc! {
+            "active": true,
+            "expires_at": { "$lte": BsonDateTime::now() }
+        };
+
+        let bans: Vec<UserBan> = self.collection.find(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        let mut user_ids: Vec<ObjectId> = Vec::new();
+        for ban in bans {
+            if !user_ids.contains(&ban.user_id) {
+                user_ids.push(ban.user_id);
+            }
+        }
+        Ok(user_ids)
+    }
+}
@@ -0,0 +1,93 @@
+// src/repositories/contact_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document, Regex},
+};
+
+use crate::models::contact::Contact;
+use crate::custom_error_expression;
+
+#[async_trait]
+pub trait ContactRepository: Send + Sync {
+    async fn insert(&self, contact: &Contact) -> Result<Contact, Error>;
+
+    /// A live contact; 404 when missing or deleted
+    async fn find_by_id(&self, contact_id: ObjectId) -> Result<Contact, Error>;
+
+    /// `owner_id`'s live contact for the user `user_id`, if they already saved one
+    async fn find_for_user(&self, owner_id: ObjectId, user_id: ObjectId) -> Result<Option<Contact>, Error>;
+
+    /// Live contacts of `owner_id` with one of the emails (case-insensitive) or phone numbers
+    async fn find_matching(&self, owner_id: ObjectId, emails: &[String], phones: &[String]) -> Result<Vec<Contact>, Error>;
+}
+
+pub struct MongoContactRepository {
+    collection: Collection<Contact>,
+}
+
+impl MongoContactRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<Contact>("Contacts") }
+    }
+}
+
+/// `$or` matching any of the emails (case-insensitive) in `email_field` or phones in `phone_field`
+pub fn email_or_phone_filter(email_field: &str, phone_field: &str, emails: &[String], phones: &[String]) -> Option<Vec<Document>> {
+    let mut any_of = Vec::new();
+    if !emails.is_empty() {
+        let patterns: Vec<Bson> = emails.iter()
+            .map(|email| Bson::RegularExpression(Regex {
+                pattern: format!("^{}$", regex::escape(email)),
+                options: "i".to_string(),
+            }))
+            .collect();
+        any_of.push(doc! { email_field: { "$in": patterns } });
+    }
+    if !phones.is_empty() {
+        any_of.push(doc! { phone_field: { "$in": phones } });
+    }
+    (!any_of.is_empty()).then_some(any_of)
+}
+
+#[async_trait]
+impl ContactRepository for MongoContactRepository {
+    async fn insert(&self, contact: &Contact) -> Result<Contact, Error> {
+        let mut contact = contact.clone();
+        contact.created_at = BsonDateTime::now();
+        contact.updated_at = BsonDateTime::now();
+
+        let insert_result = self.collection.insert_one(&contact, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        contact.id = insert_result.inserted_id.as_object_id();
+        Ok(contact)
+    }
+
+    async fn find_by_id(&self, contact_id: ObjectId) -> Result<Contact, Error> {
+        self.collection.find_one(doc! { "_id": contact_id, "deleted": { "$ne": true } }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .ok_or_else(|| custom_error_expression!(not_found, 404, "Contact not found").into())
+    }
+
+    async fn find_for_user(&self, owner_id: ObjectId, user_id: ObjectId) -> Result<Option<Contact>, Error> {
+        let filter = doc! { "parent_user_id": owner_id, "user_id": user_id, "deleted": { "$ne": true } };
+        self.collection.find_one(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn find_matching(&self, owner_id: ObjectId, emails: &[String], phones: &[String]) -> Result<Vec<Contact>, Error> {
+        let Some(any_of) = email_or_phone_filter("email", "phone_number", emails, phones) else {
+            return Ok(Vec::new());
+        };
+        let filter = doc! { "parent_user_id": owner_id, "deleted": { "$ne": true }, "$or": any_of };
+
+        self.collection.find(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+}
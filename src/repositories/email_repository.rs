@@ -0,0 +1,172 @@
+// src/repositories/email_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
+    options::{FindOneAndUpdateOptions, ReturnDocument},
+};
+
+use crate::enums::common_enums::EmailStatusEnum;
+use crate::models::sent_email::SentEmail;
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::custom_error_expression;
+
+/// The `sent_emails` collection doubles as the delivery queue: queued and abandoned
+/// emails are claimed one at a time, so several workers never send the same email.
+#[async_trait]
+pub trait EmailRepository: Send + Sync {
+    async fn insert(&self, email: &SentEmail) -> Result<SentEmail, Error>;
+
+    /// Takes an email due for an attempt (`id` only, when given), counting the attempt and
+    /// leasing it until `lease_until`. `None` when nothing is due.
+    async fn claim(&self, id: Option<ObjectId>, lease_until: BsonDateTime) -> Result<Option<SentEmail>, Error>;
+
+    /// Records a delivery; `redact` drops the bodies
+    async fn mark_sent(&self, id: ObjectId, transport: &str, redact: bool) -> Result<(), Error>;
+
+    /// Records a failed attempt. It is retried at `retry_at`, or given up on when `None`.
+    async fn mark_failed(&self, id: ObjectId, error: &str, retry_at: Option<BsonDateTime>, redact: bool) -> Result<(), Error>;
+
+    /// Puts a failed email back in the queue with fresh attempts. False when it had not failed.
+    async fn requeue(&self, id: ObjectId) -> Result<bool, Error>;
+}
+
+pub struct MongoEmailRepository {
+    collection: Collection<SentEmail>,
+}
+
+impl MongoEmailRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<SentEmail>("sent_emails") }
+    }
+}
+
+#[async_trait]
+impl EmailRepository for MongoEmailRepository {
+    async fn insert(&self, email: &SentEmail) -> Result<SentEmail, Error> {
+        let mut email = email.clone();
+        email.created_at = BsonDateTime::now();
+        email.updated_at = BsonDateTime::now();
+
+        let insert_result = self.collection.insert_one(&email, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        email.id = insert_result.inserted_id.as_object_id();
+        Ok(email)
+    }
+
+    async fn claim(&self, id: Option<ObjectId>, lease_until: BsonDateTime) -> Result<Option<SentEmail>, Error> {
+        // A `sending` email past its lease was abandoned mid-attempt (e.g. by a restart)
+        let mut filter = doc! {
+            "status": { "$in": [convert_to_bson(&EmailStatusEnum::Queued)?, convert_to_bson(&EmailStatusEnum::Sending)?] },
+            "next_attempt_at": { "$lte": BsonDateTime::now() }
+        };
+        if let Some(id) = id {
+            filter.insert("_id", id);
+        }
+        let update = doc! {
+            "$set": {
+                "status": convert_to_bson(&EmailStatusEnum::Sending)?,
+                "next_attempt_at": lease_until
+            },
+            "$inc": {
+                "attempts": 1
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .sort(doc! { "next_attempt_at": 1, "_id": 1 })
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection.find_one_and_update(filter, update, options).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn mark_sent(&self, id: ObjectId, transport: &str, redact: bool) -> Result<(), Error> {
+        let mut update = doc! {
+            "$set": {
+                "status": convert_to_bson(&EmailStatusEnum::Sent)?,
+                "sent_at": BsonDateTime::now(),
+                "transport": transport
+            },
+            "$unset": {
+                "next_attempt_at": "",
+                "last_error": ""
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+        if redact {
+            update.get_document_mut("$unset").map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+                .extend(doc! { "html_body": "", "text_body": "" });
+        }
+
+        self.collection.update_one(doc! { "_id": id }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: ObjectId, error: &str, retry_at: Option<BsonDateTime>, redact: bool) -> Result<(), Error> {
+        let update = match retry_at {
+            Some(retry_at) => doc! {
+                "$set": {
+                    "status": convert_to_bson(&EmailStatusEnum::Queued)?,
+                    "next_attempt_at": retry_at,
+                    "last_error": error
+                },
+                "$currentDate": {
+                    "updated_at": true
+                }
+            },
+            None => {
+                let mut unset = doc! { "next_attempt_at": "" };
+                if redact {
+                    unset.extend(doc! { "html_body": "", "text_body": "" });
+                }
+                doc! {
+                    "$set": {
+                        "status": convert_to_bson(&EmailStatusEnum::Failed)?,
+                        "last_error": error
+                    },
+                    "$unset": unset,
+                    "$currentDate": {
+                        "updated_at": true
+                    }
+                }
+            }
+        };
+
+        self.collection.update_one(doc! { "_id": id }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(())
+    }
+
+    async fn requeue(&self, id: ObjectId) -> Result<bool, Error> {
+        // A redacted email has nothing left to send
+        let filter = doc! {
+            "_id": id,
+            "status": convert_to_bson(&EmailStatusEnum::Failed)?,
+            "html_body": { "$exists": true }
+        };
+        let update = doc! {
+            "$set": {
+                "status": convert_to_bson(&EmailStatusEnum::Queued)?,
+                "attempts": 0,
+                "next_attempt_at": BsonDateTime::now()
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(update_result.modified_count == 1)
+    }
+}
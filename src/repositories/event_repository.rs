@@ -0,0 +1,361 @@
+// src/repositories/event_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, Bson, Document},
+    options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument},
+};
+
+use crate::config::constants::{DEFAULT_LIMIT, DEFAULT_PAGE, MAX_LIMIT};
+use crate::libs::general_library::{date_range_preset, parse_date_param};
+use crate::models::event::{Event, GeoPoint};
+use crate::requests::enums::event_enums::EventStatusEnum;
+use crate::requests::structures::event_structure::{
+    EventRequestBody,
+    EventRequestFilters,
+    EventSearchRequest,
+};
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+#[async_trait]
+pub trait EventRepository: Send + Sync {
+    /// Insert a new event and return it with its id
+    async fn create(&self, event: &Event) -> Result<Event, Error>;
+
+    /// A single non-deleted event; 404 when missing
+    async fn find_by_id(&self, event_id: ObjectId) -> Result<Event, Error>;
+
+    /// The requested page of events plus the total match count
+    async fn list(&self, filters: &EventRequestFilters) -> Result<(Vec<Event>, u64), Error>;
+
+    /// Advanced search; each event comes with its distance in km for geo searches
+    async fn search(&self, request: &EventSearchRequest) -> Result<(Vec<(Event, Option<f64>)>, u64), Error>;
+
+    async fn update(&self, event_id: ObjectId, body: &EventRequestBody) -> Result<Event, Error>;
+
+    async fn soft_delete(&self, event_id: ObjectId) -> Result<(), Error>;
+
+    /// Atomically take a seat: only succeeds while the event is open, unlocked, below
+    /// `max_attendees` and (for identified users) the user doesn't already hold a seat.
+    async fn claim_seat(&self, event_id: ObjectId, user_id: Option<ObjectId>) -> Result<bool, Error>;
+
+    /// Give back a seat taken with `claim_seat`
+    async fn release_seat(&self, event_id: ObjectId, user_id: Option<ObjectId>) -> Result<(), Error>;
+
+    /// Takes one live event starting before `starts_before` whose reminders have not gone
+    /// out yet, stamping `reminder_sent_at` so it is only taken once
+    async fn claim_reminder(&self, starts_before: mongodb::bson::DateTime) -> Result<Option<Event>, Error>;
+}
+
+pub struct MongoEventRepository {
+    collection: Collection<Event>,
+}
+
+impl MongoEventRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<Event>("events") }
+    }
+}
+
+#[async_trait]
+impl EventRepository for MongoEventRepository {
+    async fn create(&self, event: &Event) -> Result<Event, Error> {
+        let mut event = event.clone();
+        event.created_at = mongodb::bson::DateTime::now();
+        event.updated_at = mongodb::bson::DateTime::now();
+
+        let insert_result = self.collection.insert_one(&event, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        event.id = insert_result.inserted_id.as_object_id();
+        Ok(event)
+    }
+
+    async fn find_by_id(&self, event_id: ObjectId) -> Result<Event, Error> {
+        self.collection.find_one(doc! { "_id": event_id, "deleted": false }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .ok_or_else(|| custom_error_expression!(not_found, 404, "Event not found").into())
+    }
+
+    async fn list(&self, filters: &EventRequestFilters) -> Result<(Vec<Event>, u64), Error> {
+        let filter = Event::filter_document(filters)?;
+
+        let page = filters.page.unwrap_or(DEFAULT_PAGE).max(1);
+        let limit = filters.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let sort_by = filters.sort_by.as_deref().unwrap_or("created_at");
+        let sort_order = if filters.sort_order.as_deref() == Some("asc") { 1 } else { -1 };
+
+        let total = self.collection.count_documents(filter.clone(), None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        let find_options = FindOptions::builder()
+            .sort(doc! { sort_by: sort_order, "_id": sort_order })
+            .skip(((page - 1) * limit) as u64)
+            .limit(limit)
+            .build();
+
+        let events: Vec<Event> = self.collection.find(filter, find_options).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok((events, total))
+    }
+
+    async fn search(&self, request: &EventSearchRequest) -> Result<(Vec<(Event, Option<f64>)>, u64), Error> {
+        let filter = search_filter(request)?;
+
+        let page = request.page.unwrap_or(DEFAULT_PAGE as u32).max(1) as i64;
+        let limit = (request.limit.unwrap_or(DEFAULT_LIMIT as u32) as i64).clamp(1, MAX_LIMIT);
+
+        let mut pipeline = Vec::new();
+        let is_geo = match (request.latitude, request.longitude) {
+            (Some(latitude), Some(longitude)) => {
+                let mut geo_near = doc! {
+                    "near": convert_to_bson(&GeoPoint::new(latitude, longitude))?,
+                    "distanceField": "distance",
+                    "key": "geo_location",
+                    "spherical": true,
+                    "query": filter,
+                };
+                if let Some(radius_km) = request.radius_km {
+                    geo_near.insert("maxDistance", radius_km * 1000.0);
+                }
+                pipeline.push(doc! { "$geoNear": geo_near });
+                true
+            }
+            _ => {
+                pipeline.push(doc! { "$match": filter });
+                false
+            }
+        };
+
+        let sort = search_sort(request, is_geo);
+
+        pipeline.push(doc! {
+            "$facet": {
+                "items": [
+                    { "$sort": sort },
+                    { "$skip": (page - 1) * limit },
+                    { "$limit": limit }
+                ],
+                "total": [
+                    { "$count": "count" }
+                ]
+            }
+        });
+
+        let mut cursor = self.collection.aggregate(pipeline, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        let result = match cursor.next().await {
+            Some(result) => result.map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?,
+            None => return Ok((Vec::new(), 0)),
+        };
+
+        let total = result.get_array("total").ok()
+            .and_then(|total| total.first())
+            .and_then(|count| count.as_document())
+            .and_then(|count| match count.get("count") {
+                Some(Bson::Int32(v)) => Some(*v as u64),
+                Some(Bson::Int64(v)) => Some(*v as u64),
+                _ => None,
+            })
+            .unwrap_or(0);
+
+        let mut events = Vec::new();
+        for item in result.get_array("items").cloned().unwrap_or_default() {
+            let Bson::Document(item) = item else { continue };
+            let distance_km = item.get_f64("distance").ok().map(|meters| (meters / 10.0).round() / 100.0);
+            let event: Event = mongodb::bson::from_document(item)
+                .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+            events.push((event, distance_km));
+        }
+
+        Ok((events, total))
+    }
+
+    async fn update(&self, event_id: ObjectId, body: &EventRequestBody) -> Result<Event, Error> {
+        let update = doc! {
+            "$set": Event::update_document(body)?,
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(doc! { "_id": event_id, "deleted": false }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if update_result.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Event not found");
+        }
+
+        self.find_by_id(event_id).await
+    }
+
+    async fn soft_delete(&self, event_id: ObjectId) -> Result<(), Error> {
+        let update = doc! {
+            "$set": {
+                "deleted": true
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(doc! { "_id": event_id, "deleted": false }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if update_result.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Event not found");
+        }
+
+        Ok(())
+    }
+
+    async fn claim_seat(&self, event_id: ObjectId, user_id: Option<ObjectId>) -> Result<bool, Error> {
+        let mut filter = doc! {
+            "_id": event_id,
+            "deleted": false,
+            "locked": false,
+            "$or": [
+                { "max_attendees": null },
+                { "$expr": { "$lt": ["$current_attendees", "$max_attendees"] } }
+            ]
+        };
+        let mut update = doc! {
+            "$inc": {
+                "current_attendees": 1,
+                "attendees_count": 1
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        if let Some(user_id) = user_id {
+            filter.insert("attendees", doc! { "$ne": user_id });
+            update.insert("$addToSet", doc! { "attendees": user_id });
+        }
+
+        let update_result = self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(update_result.modified_count == 1)
+    }
+
+    async fn release_seat(&self, event_id: ObjectId, user_id: Option<ObjectId>) -> Result<(), Error> {
+        let mut filter = doc! {
+            "_id": event_id,
+            "current_attendees": { "$gt": 0 }
+        };
+        let mut update = doc! {
+            "$inc": {
+                "current_attendees": -1,
+                "attendees_count": -1
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        if let Some(user_id) = user_id {
+            filter.insert("attendees", user_id);
+            update.insert("$pull", doc! { "attendees": user_id });
+        }
+
+        self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn claim_reminder(&self, starts_before: mongodb::bson::DateTime) -> Result<Option<Event>, Error> {
+        let filter = doc! {
+            "deleted": false,
+            "locked": false,
+            "status": { "$nin": [convert_to_bson(&EventStatusEnum::Cancelled)?, convert_to_bson(&EventStatusEnum::Completed)?] },
+            "start_time": { "$gt": mongodb::bson::DateTime::now(), "$lte": starts_before },
+            "reminder_sent_at": null
+        };
+        let update = doc! {
+            "$currentDate": {
+                "reminder_sent_at": true,
+                "updated_at": true
+            }
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .sort(doc! { "start_time": 1 })
+            .return_document(ReturnDocument::After)
+            .build();
+
+        self.collection.find_one_and_update(filter, update, options).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+}
+
+/// Filter of an advanced search: the list filters, the search term and the date window
+pub(crate) fn search_filter(request: &EventSearchRequest) -> Result<Document, Error> {
+    let mut filter = match &request.filters {
+        Some(filters) => Event::filter_document(filters)?,
+        None => doc! { "deleted": false },
+    };
+
+    if let Some(term) = request.search_term.as_deref().filter(|t| !t.trim().is_empty()) {
+        let pattern = regex::escape(term.trim());
+        let text_match = doc! { "$or": [
+            { "title": { "$regex": &pattern, "$options": "i" } },
+            { "description": { "$regex": &pattern, "$options": "i" } },
+            { "tags": { "$regex": &pattern, "$options": "i" } },
+            { "organizer_name": { "$regex": &pattern, "$options": "i" } },
+        ] };
+        // `filters.query` may already own `$or`
+        filter.insert("$and", vec![text_match]);
+    }
+
+    // A date_range overrides filters.date_from / filters.date_to
+    let (start, end) = search_window(request)?;
+    let mut event_date = Document::new();
+    if let Some(start) = start { event_date.insert("$gte", start); }
+    if let Some(end) = end { event_date.insert("$lte", end); }
+    if !event_date.is_empty() {
+        filter.insert("event_date", event_date);
+    }
+
+    Ok(filter)
+}
+
+/// Order of an advanced search; geo searches default to nearest first
+pub(crate) fn search_sort(request: &EventSearchRequest, is_geo: bool) -> Document {
+    let sort_order = if request.sort_order.as_deref() == Some("desc") { -1 } else { 1 };
+    match request.sort_by.as_deref() {
+        Some("distance") | None if is_geo => doc! { "distance": sort_order, "_id": 1 },
+        Some(sort_by) => doc! { sort_by: sort_order, "_id": sort_order },
+        None => doc! { "event_date": 1, "_id": 1 },
+    }
+}
+
+/// `event_date` bounds of a search: a named `date_range` preset, or the custom start/end dates
+fn search_window(request: &EventSearchRequest) -> Result<(Option<mongodb::bson::DateTime>, Option<mongodb::bson::DateTime>), Error> {
+    match request.date_range.as_deref() {
+        Some("custom") | None => {
+            let start = request.start_date.as_deref().map(|v| parse_date_param(v, false).ok_or("start_date")).transpose();
+            let end = request.end_date.as_deref().map(|v| parse_date_param(v, true).ok_or("end_date")).transpose();
+            match (start, end) {
+                (Ok(start), Ok(end)) => Ok((start, end)),
+                (Err(field), _) | (_, Err(field)) => handle_custom_error!(bad_request, 400, format!("Invalid {}", field)),
+            }
+        }
+        Some(preset) => match date_range_preset(preset) {
+            Some((start, end)) => Ok((Some(start), Some(end))),
+            None => handle_custom_error!(bad_request, 400, "Invalid date_range"),
+        },
+    }
+}
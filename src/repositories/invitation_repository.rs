@@ -0,0 +1,176 @@
+// src/repositories/invitation_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
+};
+
+use crate::enums::common_enums::StatusEnum;
+use crate::models::invitation::Invitation;
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+#[async_trait]
+pub trait InvitationRepository: Send + Sync {
+    /// Insert a batch of invitations, returning them with their ids
+    async fn insert_many(&self, invitations: Vec<Invitation>) -> Result<Vec<Invitation>, Error>;
+
+    /// An event invitation by its token; 404 when missing
+    async fn find_by_token(&self, token: &str) -> Result<Invitation, Error>;
+
+    /// A still usable (pending or accepted) invitation for a user, by id or email
+    async fn find_open_for_user(&self, event_id: ObjectId, user_id: ObjectId, email: Option<&str>) -> Result<Option<Invitation>, Error>;
+
+    /// Recipients (user ids and lowercased emails) that already hold a pending or accepted invitation
+    async fn existing_recipients(&self, event_id: ObjectId) -> Result<(Vec<ObjectId>, Vec<String>), Error>;
+
+    /// Record the recipient's answer. Only pending invitations can be answered.
+    async fn respond(
+        &self,
+        invitation: &Invitation,
+        status: StatusEnum,
+        user_id: Option<ObjectId>,
+        attendee_id: Option<ObjectId>,
+        plus_one_name: Option<String>,
+    ) -> Result<(), Error>;
+
+    /// Flip every pending invitation past its `expires_at` to expired
+    async fn expire_pending(&self) -> Result<u64, Error>;
+}
+
+pub struct MongoInvitationRepository {
+    collection: Collection<Invitation>,
+}
+
+impl MongoInvitationRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<Invitation>("invitations") }
+    }
+
+    /// Pending and accepted invitations of an event matching `filter`, expired ones excluded
+    async fn live(&self, mut filter: mongodb::bson::Document) -> Result<Vec<Invitation>, Error> {
+        filter.insert("status", doc! { "$in": [convert_to_bson(&StatusEnum::Pending)?, convert_to_bson(&StatusEnum::Accepted)?] });
+
+        let invitations: Vec<Invitation> = self.collection.find(filter, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .try_collect().await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(invitations.into_iter()
+            .filter(|invitation| !(invitation.status == StatusEnum::Pending && invitation.is_expired()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for MongoInvitationRepository {
+    async fn insert_many(&self, invitations: Vec<Invitation>) -> Result<Vec<Invitation>, Error> {
+        if invitations.is_empty() {
+            return Ok(invitations);
+        }
+
+        let insert_result = self.collection.insert_many(&invitations, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(invitations.into_iter()
+            .enumerate()
+            .map(|(index, mut invitation)| {
+                invitation.id = insert_result.inserted_ids.get(&index).and_then(|id| id.as_object_id());
+                invitation
+            })
+            .collect())
+    }
+
+    async fn find_by_token(&self, token: &str) -> Result<Invitation, Error> {
+        self.collection.find_one(doc! { "token": token }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .ok_or_else(|| custom_error_expression!(not_found, 404, "Invitation not found").into())
+    }
+
+    async fn find_open_for_user(&self, event_id: ObjectId, user_id: ObjectId, email: Option<&str>) -> Result<Option<Invitation>, Error> {
+        let mut recipients = vec![doc! { "user_id": user_id }];
+        if let Some(email) = email {
+            recipients.push(doc! { "email": email.to_lowercase() });
+        }
+
+        let invitations = self.live(doc! { "event_id": event_id, "$or": recipients }).await?;
+        Ok(invitations.into_iter().next())
+    }
+
+    async fn existing_recipients(&self, event_id: ObjectId) -> Result<(Vec<ObjectId>, Vec<String>), Error> {
+        let (mut user_ids, mut emails) = (Vec::new(), Vec::new());
+        for invitation in self.live(doc! { "event_id": event_id }).await? {
+            if let Some(user_id) = invitation.user_id { user_ids.push(user_id); }
+            if let Some(email) = invitation.email { emails.push(email.to_lowercase()); }
+        }
+
+        Ok((user_ids, emails))
+    }
+
+    async fn respond(
+        &self,
+        invitation: &Invitation,
+        status: StatusEnum,
+        user_id: Option<ObjectId>,
+        attendee_id: Option<ObjectId>,
+        plus_one_name: Option<String>,
+    ) -> Result<(), Error> {
+        let invitation_id = match invitation.id {
+            Some(id) => id,
+            None => handle_custom_error!(bad_request, 400, "Missing invitation ID"),
+        };
+
+        let filter = doc! {
+            "_id": invitation_id,
+            "status": convert_to_bson(&StatusEnum::Pending)?
+        };
+        let update = doc! {
+            "$set": {
+                "status": convert_to_bson(&status)?,
+                "user_id": user_id.or(invitation.user_id),
+                "attendee_id": attendee_id,
+                "plus_one": plus_one_name.is_some(),
+                "plus_one_name": plus_one_name,
+                "responded_at": BsonDateTime::now()
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if update_result.matched_count == 0 {
+            handle_custom_error!(conflict, 409, "Invitation has already been answered");
+        }
+
+        Ok(())
+    }
+
+    async fn expire_pending(&self) -> Result<u64, Error> {
+        let filter = doc! {
+            "status": convert_to_bson(&StatusEnum::Pending)?,
+            "expires_at": { "$lte": BsonDateTime::now() }
+        };
+        let update = doc! {
+            "$set": {
+                "status": convert_to_bson(&StatusEnum::Expired)?
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_many(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        Ok(update_result.modified_count)
+    }
+}
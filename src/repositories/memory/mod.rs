@@ -0,0 +1,927 @@
+// src/repositories/memory/mod.rs
+//
+// Every repository over one shared `MemoryStore`, for tests that run services, handlers
+// and AdminX pages without a Mongo server. The typed repositories apply the filters and
+// updates of their Mongo counterparts to the same documents the record repository
+// (and so AdminX) reads and writes.
+pub mod store;
+
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::config::constants::{DEFAULT_LIMIT, DEFAULT_PAGE, MAX_LIMIT};
+use crate::enums::common_enums::{AdminRoleEnum, AdminStatusEnum, EmailStatusEnum, StatusEnum};
+use crate::models::{
+    admin_two_factor::AdminTwoFactor,
+    admin_user::AdminUser,
+    audit_log::AuditLog,
+    contact::Contact,
+    event::Event,
+    event_attendee::EventAttendee,
+    invitation::Invitation,
+    sent_email::SentEmail,
+    user::User,
+    user_ban::UserBan,
+};
+use crate::repositories::{
+    admin_user_repository::AdminUserRepository,
+    attendee_repository::{AttendeeCounts, AttendeeRepository},
+    audit_repository::AuditRepository,
+    ban_repository::BanRepository,
+    contact_repository::{email_or_phone_filter, ContactRepository},
+    email_repository::EmailRepository,
+    event_repository::{search_filter, search_sort, EventRepository},
+    invitation_repository::InvitationRepository,
+    record_repository::{RecordQuery, RecordRepository, UpdateCounts},
+    two_factor_repository::TwoFactorRepository,
+    user_repository::UserRepository,
+};
+use crate::requests::enums::event_enums::EventStatusEnum;
+use crate::requests::structures::event_structure::{EventRequestBody, EventRequestFilters, EventSearchRequest};
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+use store::{DuplicateKey, MemoryStore, Modify};
+
+/// Mean earth radius `$geoNear` uses for spherical distances, in meters
+const EARTH_RADIUS_METERS: f64 = 6_378_100.0;
+
+
+/*----- START HELPERS -----*/
+fn internal_error(message: impl ToString) -> Error {
+    custom_error_expression!(internal_error, 500, message.to_string()).into()
+}
+
+fn duplicate_key(_: DuplicateKey) -> Error {
+    internal_error("E11000 duplicate key error")
+}
+
+fn to_document<T: Serialize>(value: &T) -> Result<Document, Error> {
+    mongodb::bson::to_document(value).map_err(internal_error)
+}
+
+fn from_document<T: DeserializeOwned>(document: Document) -> Result<T, Error> {
+    mongodb::bson::from_document(document).map_err(internal_error)
+}
+
+fn from_documents<T: DeserializeOwned>(documents: Vec<Document>) -> Result<Vec<T>, Error> {
+    documents.into_iter().map(from_document).collect()
+}
+
+fn live_and(mut filter: Document) -> Document {
+    filter.insert("deleted", doc! { "$ne": true });
+    filter
+}
+
+fn touched(mut update: Document) -> Document {
+    update.insert("$currentDate", doc! { "updated_at": true });
+    update
+}
+/*----- END HELPERS -----*/
+
+
+/*----- START RECORDS -----*/
+pub struct InMemoryRecordRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl RecordRepository for InMemoryRecordRepository {
+    async fn find_one(&self, collection: &str, filter: Document) -> Result<Option<Document>, Error> {
+        Ok(self.store.find_one(collection, &filter, None))
+    }
+
+    async fn find(&self, collection: &str, filter: Document, query: RecordQuery) -> Result<Vec<Document>, Error> {
+        Ok(self.store.find(collection, &filter, &query))
+    }
+
+    async fn count(&self, collection: &str, filter: Document) -> Result<u64, Error> {
+        Ok(self.store.count(collection, &filter))
+    }
+
+    async fn insert_one(&self, collection: &str, record: Document) -> Result<Bson, Error> {
+        self.store.insert(collection, record).map_err(duplicate_key)
+    }
+
+    async fn update_one(&self, collection: &str, filter: Document, update: Document) -> Result<UpdateCounts, Error> {
+        self.store.update(collection, &filter, &update, false).map_err(duplicate_key)
+    }
+
+    async fn delete_one(&self, collection: &str, filter: Document) -> Result<u64, Error> {
+        Ok(self.store.delete(collection, &filter, false))
+    }
+}
+/*----- END RECORDS -----*/
+
+
+/*----- START EVENTS -----*/
+pub struct InMemoryEventRepository {
+    store: MemoryStore,
+}
+
+/// Great-circle distance in meters between a `GeoPoint` document and a latitude/longitude
+fn distance_meters(point: &Document, latitude: f64, longitude: f64) -> Option<f64> {
+    let coordinates = point.get_array("coordinates").ok()?;
+    let (lng, lat) = (coordinates.first()?.as_f64()?, coordinates.get(1)?.as_f64()?);
+    let (phi1, phi2) = (lat.to_radians(), latitude.to_radians());
+    let half_chord = ((phi2 - phi1) / 2.0).sin().powi(2)
+        + phi1.cos() * phi2.cos() * ((longitude - lng).to_radians() / 2.0).sin().powi(2);
+    Some(2.0 * EARTH_RADIUS_METERS * half_chord.sqrt().asin())
+}
+
+#[async_trait]
+impl EventRepository for InMemoryEventRepository {
+    async fn create(&self, event: &Event) -> Result<Event, Error> {
+        let mut event = event.clone();
+        event.created_at = BsonDateTime::now();
+        event.updated_at = BsonDateTime::now();
+        event.id = self.store.insert("events", to_document(&event)?).map_err(duplicate_key)?.as_object_id();
+        Ok(event)
+    }
+
+    async fn find_by_id(&self, event_id: ObjectId) -> Result<Event, Error> {
+        match self.store.find_one("events", &doc! { "_id": event_id, "deleted": false }, None) {
+            Some(event) => from_document(event),
+            None => handle_custom_error!(not_found, 404, "Event not found"),
+        }
+    }
+
+    async fn list(&self, filters: &EventRequestFilters) -> Result<(Vec<Event>, u64), Error> {
+        let filter = Event::filter_document(filters)?;
+        let page = filters.page.unwrap_or(DEFAULT_PAGE).max(1);
+        let limit = filters.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+        let sort_by = filters.sort_by.as_deref().unwrap_or("created_at");
+        let sort_order = if filters.sort_order.as_deref() == Some("asc") { 1 } else { -1 };
+
+        let query = RecordQuery {
+            sort: Some(doc! { sort_by: sort_order, "_id": sort_order }),
+            skip: ((page - 1) * limit) as u64,
+            limit: Some(limit),
+            projection: None,
+        };
+        let total = self.store.count("events", &filter);
+        Ok((from_documents(self.store.find("events", &filter, &query))?, total))
+    }
+
+    async fn search(&self, request: &EventSearchRequest) -> Result<(Vec<(Event, Option<f64>)>, u64), Error> {
+        let filter = search_filter(request)?;
+        let page = request.page.unwrap_or(DEFAULT_PAGE as u32).max(1) as usize;
+        let limit = (request.limit.unwrap_or(DEFAULT_LIMIT as u32) as i64).clamp(1, MAX_LIMIT) as usize;
+
+        let mut events = self.store.find("events", &filter, &RecordQuery::default());
+        let origin = request.latitude.zip(request.longitude);
+        if let Some((latitude, longitude)) = origin {
+            events = events.into_iter()
+                .filter_map(|mut event| {
+                    let distance = distance_meters(event.get_document("geo_location").ok()?, latitude, longitude)?;
+                    if request.radius_km.is_some_and(|radius_km| distance > radius_km * 1000.0) {
+                        return None;
+                    }
+                    event.insert("distance", distance);
+                    Some(event)
+                })
+                .collect();
+        }
+        let sort = search_sort(request, origin.is_some());
+        events.sort_by(|a, b| store::compare_by(a, b, &sort));
+
+        let total = events.len() as u64;
+        let mut page_events = Vec::new();
+        for event in events.into_iter().skip((page - 1) * limit).take(limit) {
+            let distance_km = event.get_f64("distance").ok().map(|meters| (meters / 10.0).round() / 100.0);
+            page_events.push((from_document(event)?, distance_km));
+        }
+        Ok((page_events, total))
+    }
+
+    async fn update(&self, event_id: ObjectId, body: &EventRequestBody) -> Result<Event, Error> {
+        let update = touched(doc! { "$set": Event::update_document(body)? });
+        let counts = self.store.update("events", &doc! { "_id": event_id, "deleted": false }, &update, false).map_err(duplicate_key)?;
+        if counts.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Event not found");
+        }
+        self.find_by_id(event_id).await
+    }
+
+    async fn soft_delete(&self, event_id: ObjectId) -> Result<(), Error> {
+        let update = touched(doc! { "$set": { "deleted": true } });
+        let counts = self.store.update("events", &doc! { "_id": event_id, "deleted": false }, &update, false).map_err(duplicate_key)?;
+        if counts.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Event not found");
+        }
+        Ok(())
+    }
+
+    async fn claim_seat(&self, event_id: ObjectId, user_id: Option<ObjectId>) -> Result<bool, Error> {
+        let mut filter = doc! {
+            "_id": event_id,
+            "deleted": false,
+            "locked": false,
+            "$or": [
+                { "max_attendees": null },
+                { "$expr": { "$lt": ["$current_attendees", "$max_attendees"] } }
+            ]
+        };
+        let mut update = touched(doc! { "$inc": { "current_attendees": 1, "attendees_count": 1 } });
+        if let Some(user_id) = user_id {
+            filter.insert("attendees", doc! { "$ne": user_id });
+            update.insert("$addToSet", doc! { "attendees": user_id });
+        }
+
+        let counts = self.store.update("events", &filter, &update, false).map_err(duplicate_key)?;
+        Ok(counts.modified_count == 1)
+    }
+
+    async fn release_seat(&self, event_id: ObjectId, user_id: Option<ObjectId>) -> Result<(), Error> {
+        let mut filter = doc! { "_id": event_id, "current_attendees": { "$gt": 0 } };
+        let mut update = touched(doc! { "$inc": { "current_attendees": -1, "attendees_count": -1 } });
+        if let Some(user_id) = user_id {
+            filter.insert("attendees", user_id);
+            update.insert("$pull", doc! { "attendees": user_id });
+        }
+
+        self.store.update("events", &filter, &update, false).map_err(duplicate_key)?;
+        Ok(())
+    }
+
+    async fn claim_reminder(&self, starts_before: BsonDateTime) -> Result<Option<Event>, Error> {
+        let filter = doc! {
+            "deleted": false,
+            "locked": false,
+            "status": { "$nin": [convert_to_bson(&EventStatusEnum::Cancelled)?, convert_to_bson(&EventStatusEnum::Completed)?] },
+            "start_time": { "$gt": BsonDateTime::now(), "$lte": starts_before },
+            "reminder_sent_at": null
+        };
+        let update = doc! { "$currentDate": { "reminder_sent_at": true, "updated_at": true } };
+        let modify = Modify { sort: Some(doc! { "start_time": 1 }), return_after: true, ..Modify::default() };
+
+        self.store.find_one_and_update("events", &filter, &update, modify).map_err(duplicate_key)?
+            .map(from_document)
+            .transpose()
+    }
+}
+/*----- END EVENTS -----*/
+
+
+/*----- START ATTENDEES -----*/
+pub struct InMemoryAttendeeRepository {
+    store: MemoryStore,
+}
+
+fn already_registered(_: DuplicateKey) -> Error {
+    custom_error_expression!(conflict, 409, "Already registered for this event").into()
+}
+
+impl InMemoryAttendeeRepository {
+    fn stamp(&self, attendee_id: ObjectId, field: &str) -> Result<(), Error> {
+        let update = touched(doc! { "$set": { field: BsonDateTime::now() } });
+        let counts = self.store.update("event_attendees", &live_and(doc! { "_id": attendee_id }), &update, false).map_err(duplicate_key)?;
+        if counts.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Attendee not found");
+        }
+        Ok(())
+    }
+
+    fn waitlist_filter(event_id: ObjectId) -> Result<Document, Error> {
+        Ok(live_and(doc! {
+            "event_id": event_id,
+            "registration_status": convert_to_bson(&StatusEnum::Pending)?,
+            "waitlisted_at": { "$ne": null },
+            "promoted_at": null
+        }))
+    }
+}
+
+#[async_trait]
+impl AttendeeRepository for InMemoryAttendeeRepository {
+    async fn insert(&self, attendee: &EventAttendee) -> Result<EventAttendee, Error> {
+        let mut attendee = attendee.clone();
+        attendee.registration_date = Some(BsonDateTime::now());
+        attendee.created_at = BsonDateTime::now();
+        attendee.updated_at = BsonDateTime::now();
+        attendee.id = self.store.insert("event_attendees", to_document(&attendee)?).map_err(already_registered)?.as_object_id();
+        Ok(attendee)
+    }
+
+    async fn find_by_id(&self, attendee_id: ObjectId) -> Result<Option<EventAttendee>, Error> {
+        self.store.find_one("event_attendees", &doc! { "_id": attendee_id }, None).map(from_document).transpose()
+    }
+
+    async fn find_registration(&self, event_id: ObjectId, user_id: ObjectId) -> Result<Option<EventAttendee>, Error> {
+        let filter = doc! { "event_id": event_id, "user_id": user_id, "deleted": false };
+        self.store.find_one("event_attendees", &filter, None).map(from_document).transpose()
+    }
+
+    async fn check_in(&self, attendee_id: ObjectId) -> Result<(), Error> {
+        self.stamp(attendee_id, "check_in_date")
+    }
+
+    async fn check_out(&self, attendee_id: ObjectId) -> Result<(), Error> {
+        self.stamp(attendee_id, "check_out_date")
+    }
+
+    async fn mark_cancelled(&self, attendee_id: ObjectId, reason: Option<String>) -> Result<Option<EventAttendee>, Error> {
+        let update = touched(doc! {
+            "$set": {
+                "deleted": true,
+                "registration_status": convert_to_bson(&StatusEnum::Inactive)?,
+                "cancellation_reason": reason
+            }
+        });
+        self.store.find_one_and_update("event_attendees", &live_and(doc! { "_id": attendee_id }), &update, Modify::default())
+            .map_err(duplicate_key)?
+            .map(from_document)
+            .transpose()
+    }
+
+    async fn restore(&self, attendee_id: ObjectId, waitlisted: bool) -> Result<Option<EventAttendee>, Error> {
+        let status = if waitlisted { StatusEnum::Pending } else { StatusEnum::Active };
+        let update = touched(doc! {
+            "$set": {
+                "deleted": false,
+                "registration_status": convert_to_bson(&status)?,
+                "waitlisted_at": waitlisted.then(BsonDateTime::now),
+                "promoted_at": null,
+                "cancellation_reason": null
+            }
+        });
+        let modify = Modify { return_after: true, ..Modify::default() };
+        self.store.find_one_and_update("event_attendees", &doc! { "_id": attendee_id, "deleted": true }, &update, modify)
+            .map_err(already_registered)?
+            .map(from_document)
+            .transpose()
+    }
+
+    async fn list_for_event(&self, event_id: ObjectId, page: u64, per_page: i64) -> Result<Vec<EventAttendee>, Error> {
+        let query = RecordQuery {
+            sort: Some(doc! { "created_at": -1, "_id": -1 }),
+            skip: page.saturating_sub(1) * per_page as u64,
+            limit: Some(per_page),
+            projection: None,
+        };
+        from_documents(self.store.find("event_attendees", &live_and(doc! { "event_id": event_id }), &query))
+    }
+
+    async fn registered_for_event(&self, event_id: ObjectId) -> Result<Vec<EventAttendee>, Error> {
+        let filter = live_and(doc! { "event_id": event_id, "registration_status": convert_to_bson(&StatusEnum::Active)? });
+        from_documents(self.store.find("event_attendees", &filter, &RecordQuery::default()))
+    }
+
+    async fn counts_for_event(&self, event_id: ObjectId) -> Result<AttendeeCounts, Error> {
+        let live = live_and(doc! { "event_id": event_id });
+        let total = self.store.count("event_attendees", &live);
+        let waitlisted = self.store.count("event_attendees", &Self::waitlist_filter(event_id)?);
+        let checked_in = self.store.count("event_attendees", &live_and(doc! { "event_id": event_id, "check_in_date": { "$ne": null } }));
+
+        Ok(AttendeeCounts {
+            total,
+            registered: total.saturating_sub(waitlisted),
+            checked_in,
+            waitlisted,
+        })
+    }
+
+    async fn find_matching(&self, event_id: ObjectId, emails: &[String], phones: &[String]) -> Result<Vec<EventAttendee>, Error> {
+        let Some(any_of) = email_or_phone_filter("email", "phone", emails, phones) else {
+            return Ok(Vec::new());
+        };
+        let filter = live_and(doc! { "event_id": event_id, "$or": any_of });
+        from_documents(self.store.find("event_attendees", &filter, &RecordQuery::default()))
+    }
+
+    async fn plus_ones_of(&self, host_id: ObjectId) -> Result<Vec<ObjectId>, Error> {
+        let guests: Vec<EventAttendee> = from_documents(self.store.find("event_attendees", &live_and(doc! { "plus_one_of": host_id }), &RecordQuery::default()))?;
+        Ok(guests.into_iter().filter_map(|guest| guest.id).collect())
+    }
+
+    async fn next_waitlisted(&self, event_id: ObjectId) -> Result<Option<EventAttendee>, Error> {
+        self.store.find_one("event_attendees", &Self::waitlist_filter(event_id)?, Some(doc! { "waitlisted_at": 1, "_id": 1 }))
+            .map(from_document)
+            .transpose()
+    }
+
+    async fn promote(&self, attendee_id: ObjectId) -> Result<bool, Error> {
+        let filter = live_and(doc! { "_id": attendee_id, "registration_status": convert_to_bson(&StatusEnum::Pending)? });
+        let update = touched(doc! {
+            "$set": {
+                "registration_status": convert_to_bson(&StatusEnum::Active)?,
+                "promoted_at": BsonDateTime::now()
+            }
+        });
+        let counts = self.store.update("event_attendees", &filter, &update, false).map_err(duplicate_key)?;
+        Ok(counts.modified_count == 1)
+    }
+}
+/*----- END ATTENDEES -----*/
+
+
+/*----- START INVITATIONS -----*/
+pub struct InMemoryInvitationRepository {
+    store: MemoryStore,
+}
+
+impl InMemoryInvitationRepository {
+    fn live(&self, mut filter: Document) -> Result<Vec<Invitation>, Error> {
+        filter.insert("status", doc! { "$in": [convert_to_bson(&StatusEnum::Pending)?, convert_to_bson(&StatusEnum::Accepted)?] });
+        let invitations: Vec<Invitation> = from_documents(self.store.find("invitations", &filter, &RecordQuery::default()))?;
+        Ok(invitations.into_iter()
+            .filter(|invitation| !(invitation.status == StatusEnum::Pending && invitation.is_expired()))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl InvitationRepository for InMemoryInvitationRepository {
+    async fn insert_many(&self, invitations: Vec<Invitation>) -> Result<Vec<Invitation>, Error> {
+        let mut inserted = Vec::with_capacity(invitations.len());
+        for mut invitation in invitations {
+            invitation.id = self.store.insert("invitations", to_document(&invitation)?).map_err(duplicate_key)?.as_object_id();
+            inserted.push(invitation);
+        }
+        Ok(inserted)
+    }
+
+    async fn find_by_token(&self, token: &str) -> Result<Invitation, Error> {
+        match self.store.find_one("invitations", &doc! { "token": token }, None) {
+            Some(invitation) => from_document(invitation),
+            None => handle_custom_error!(not_found, 404, "Invitation not found"),
+        }
+    }
+
+    async fn find_open_for_user(&self, event_id: ObjectId, user_id: ObjectId, email: Option<&str>) -> Result<Option<Invitation>, Error> {
+        let mut recipients = vec![doc! { "user_id": user_id }];
+        if let Some(email) = email {
+            recipients.push(doc! { "email": email.to_lowercase() });
+        }
+        Ok(self.live(doc! { "event_id": event_id, "$or": recipients })?.into_iter().next())
+    }
+
+    async fn existing_recipients(&self, event_id: ObjectId) -> Result<(Vec<ObjectId>, Vec<String>), Error> {
+        let (mut user_ids, mut emails) = (Vec::new(), Vec::new());
+        for invitation in self.live(doc! { "event_id": event_id })? {
+            if let Some(user_id) = invitation.user_id { user_ids.push(user_id); }
+            if let Some(email) = invitation.email { emails.push(email.to_lowercase()); }
+        }
+        Ok((user_ids, emails))
+    }
+
+    async fn respond(
+        &self,
+        invitation: &Invitation,
+        status: StatusEnum,
+        user_id: Option<ObjectId>,
+        attendee_id: Option<ObjectId>,
+        plus_one_name: Option<String>,
+    ) -> Result<(), Error> {
+        let Some(invitation_id) = invitation.id else {
+            handle_custom_error!(bad_request, 400, "Missing invitation ID");
+        };
+
+        let filter = doc! { "_id": invitation_id, "status": convert_to_bson(&StatusEnum::Pending)? };
+        let update = touched(doc! {
+            "$set": {
+                "status": convert_to_bson(&status)?,
+                "user_id": user_id.or(invitation.user_id),
+                "attendee_id": attendee_id,
+                "plus_one": plus_one_name.is_some(),
+                "plus_one_name": plus_one_name,
+                "responded_at": BsonDateTime::now()
+            }
+        });
+        let counts = self.store.update("invitations", &filter, &update, false).map_err(duplicate_key)?;
+        if counts.matched_count == 0 {
+            handle_custom_error!(conflict, 409, "Invitation has already been answered");
+        }
+        Ok(())
+    }
+
+    async fn expire_pending(&self) -> Result<u64, Error> {
+        let filter = doc! { "status": convert_to_bson(&StatusEnum::Pending)?, "expires_at": { "$lte": BsonDateTime::now() } };
+        let update = touched(doc! { "$set": { "status": convert_to_bson(&StatusEnum::Expired)? } });
+        Ok(self.store.update("invitations", &filter, &update, true).map_err(duplicate_key)?.modified_count)
+    }
+}
+/*----- END INVITATIONS -----*/
+
+
+/*----- START USERS -----*/
+pub struct InMemoryUserRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_active_by_id(&self, user_id: ObjectId) -> Result<User, Error> {
+        match self.store.find_one("users", &doc! { "_id": user_id, "deleted": false }, None) {
+            Some(user) => from_document(user),
+            None => handle_custom_error!(not_found, 404, "User not found"),
+        }
+    }
+
+    async fn set_lock(&self, user_id: ObjectId, locked: bool, status: StatusEnum) -> Result<(), Error> {
+        let update = touched(doc! { "$set": { "locked": locked, "status": convert_to_bson(&status)? } });
+        let counts = self.store.update("users", &doc! { "_id": user_id }, &update, false).map_err(duplicate_key)?;
+        if counts.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "User not found");
+        }
+        Ok(())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, Error> {
+        self.store.find_one("users", &doc! { "email": email, "deleted": false }, None).map(from_document).transpose()
+    }
+
+    async fn find_by_phone(&self, phone_number: &str) -> Result<Option<User>, Error> {
+        self.store.find_one("users", &doc! { "phone_number": phone_number, "deleted": false }, None).map(from_document).transpose()
+    }
+
+    async fn insert(&self, user: &User) -> Result<User, Error> {
+        let mut user = user.clone();
+        user.created_at = BsonDateTime::now();
+        user.updated_at = BsonDateTime::now();
+        user.id = self.store.insert("users", to_document(&user)?).map_err(duplicate_key)?.as_object_id();
+        Ok(user)
+    }
+
+    async fn set_firebase_token(&self, user_id: ObjectId, firebase_token: &str) -> Result<(), Error> {
+        let update = touched(doc! { "$set": { "firebase_token": firebase_token } });
+        self.store.update("users", &doc! { "_id": user_id }, &update, false).map_err(duplicate_key)?;
+        Ok(())
+    }
+}
+/*----- END USERS -----*/
+
+
+/*----- START BANS -----*/
+pub struct InMemoryBanRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl BanRepository for InMemoryBanRepository {
+    async fn insert(&self, ban: &UserBan) -> Result<UserBan, Error> {
+        let mut ban = ban.clone();
+        ban.created_at = BsonDateTime::now();
+        ban.updated_at = BsonDateTime::now();
+        ban.id = self.store.insert("user_bans", to_document(&ban)?).map_err(duplicate_key)?.as_object_id();
+        Ok(ban)
+    }
+
+    async fn find_active(&self, user_id: ObjectId) -> Result<Option<UserBan>, Error> {
+        self.store.find_one("user_bans", &doc! { "user_id": user_id, "active": true }, None).map(from_document).transpose()
+    }
+
+    async fn lift(&self, user_id: ObjectId, lifted_by: &str, lift_reason: &str) -> Result<u64, Error> {
+        let update = touched(doc! {
+            "$set": {
+                "active": false,
+                "lifted_at": BsonDateTime::now(),
+                "lifted_by": lifted_by,
+                "lift_reason": lift_reason
+            }
+        });
+        let counts = self.store.update("user_bans", &doc! { "user_id": user_id, "active": true }, &update, true).map_err(duplicate_key)?;
+        Ok(counts.modified_count)
+    }
+
+    async fn history(&self, user_id: ObjectId) -> Result<Vec<UserBan>, Error> {
+        let query = RecordQuery { sort: Some(doc! { "created_at": -1 }), ..RecordQuery::default() };
+        from_documents(self.store.find("user_bans", &doc! { "user_id": user_id }, &query))
+    }
+
+    async fn expired_user_ids(&self) -> Result<Vec<ObjectId>, Error> {
+        let filter = doc! { "active": true, "expires_at": { "$lte": BsonDateTime::now() } };
+        let bans: Vec<UserBan> = from_documents(self.store.find("user_bans", &filter, &RecordQuery::default()))?;
+
+        let mut user_ids: Vec<ObjectId> = Vec::new();
+        for ban in bans {
+            if !user_ids.contains(&ban.user_id) {
+                user_ids.push(ban.user_id);
+            }
+        }
+        Ok(user_ids)
+    }
+}
+/*----- END BANS -----*/
+
+
+/*----- START AUDIT LOGS -----*/
+pub struct InMemoryAuditRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryAuditRepository {
+    async fn insert(&self, entry: &AuditLog) -> Result<(), Error> {
+        self.store.insert("audit_logs", to_document(entry)?).map_err(duplicate_key)?;
+        Ok(())
+    }
+}
+/*----- END AUDIT LOGS -----*/
+
+
+/*----- START CONTACTS -----*/
+pub struct InMemoryContactRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl ContactRepository for InMemoryContactRepository {
+    async fn insert(&self, contact: &Contact) -> Result<Contact, Error> {
+        let mut contact = contact.clone();
+        contact.created_at = BsonDateTime::now();
+        contact.updated_at = BsonDateTime::now();
+        contact.id = self.store.insert("Contacts", to_document(&contact)?).map_err(duplicate_key)?.as_object_id();
+        Ok(contact)
+    }
+
+    async fn find_by_id(&self, contact_id: ObjectId) -> Result<Contact, Error> {
+        match self.store.find_one("Contacts", &live_and(doc! { "_id": contact_id }), None) {
+            Some(contact) => from_document(contact),
+            None => handle_custom_error!(not_found, 404, "Contact not found"),
+        }
+    }
+
+    async fn find_for_user(&self, owner_id: ObjectId, user_id: ObjectId) -> Result<Option<Contact>, Error> {
+        let filter = live_and(doc! { "parent_user_id": owner_id, "user_id": user_id });
+        self.store.find_one("Contacts", &filter, None).map(from_document).transpose()
+    }
+
+    async fn find_matching(&self, owner_id: ObjectId, emails: &[String], phones: &[String]) -> Result<Vec<Contact>, Error> {
+        let Some(any_of) = email_or_phone_filter("email", "phone_number", emails, phones) else {
+            return Ok(Vec::new());
+        };
+        let filter = live_and(doc! { "parent_user_id": owner_id, "$or": any_of });
+        from_documents(self.store.find("Contacts", &filter, &RecordQuery::default()))
+    }
+}
+/*----- END CONTACTS -----*/
+
+
+/*----- START EMAILS -----*/
+pub struct InMemoryEmailRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl EmailRepository for InMemoryEmailRepository {
+    async fn insert(&self, email: &SentEmail) -> Result<SentEmail, Error> {
+        let mut email = email.clone();
+        email.created_at = BsonDateTime::now();
+        email.updated_at = BsonDateTime::now();
+        email.id = self.store.insert("sent_emails", to_document(&email)?).map_err(duplicate_key)?.as_object_id();
+        Ok(email)
+    }
+
+    async fn claim(&self, id: Option<ObjectId>, lease_until: BsonDateTime) -> Result<Option<SentEmail>, Error> {
+        let mut filter = doc! {
+            "status": { "$in": [convert_to_bson(&EmailStatusEnum::Queued)?, convert_to_bson(&EmailStatusEnum::Sending)?] },
+            "next_attempt_at": { "$lte": BsonDateTime::now() }
+        };
+        if let Some(id) = id {
+            filter.insert("_id", id);
+        }
+        let update = touched(doc! {
+            "$set": { "status": convert_to_bson(&EmailStatusEnum::Sending)?, "next_attempt_at": lease_until },
+            "$inc": { "attempts": 1 }
+        });
+        let modify = Modify { sort: Some(doc! { "next_attempt_at": 1, "_id": 1 }), return_after: true, ..Modify::default() };
+
+        self.store.find_one_and_update("sent_emails", &filter, &update, modify).map_err(duplicate_key)?
+            .map(from_document)
+            .transpose()
+    }
+
+    async fn mark_sent(&self, id: ObjectId, transport: &str, redact: bool) -> Result<(), Error> {
+        let mut unset = doc! { "next_attempt_at": "", "last_error": "" };
+        if redact {
+            unset.extend(doc! { "html_body": "", "text_body": "" });
+        }
+        let update = touched(doc! {
+            "$set": {
+                "status": convert_to_bson(&EmailStatusEnum::Sent)?,
+                "sent_at": BsonDateTime::now(),
+                "transport": transport
+            },
+            "$unset": unset
+        });
+        self.store.update("sent_emails", &doc! { "_id": id }, &update, false).map_err(duplicate_key)?;
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: ObjectId, error: &str, retry_at: Option<BsonDateTime>, redact: bool) -> Result<(), Error> {
+        let update = match retry_at {
+            Some(retry_at) => touched(doc! {
+                "$set": {
+                    "status": convert_to_bson(&EmailStatusEnum::Queued)?,
+                    "next_attempt_at": retry_at,
+                    "last_error": error
+                }
+            }),
+            None => {
+                let mut unset = doc! { "next_attempt_at": "" };
+                if redact {
+                    unset.extend(doc! { "html_body": "", "text_body": "" });
+                }
+                touched(doc! {
+                    "$set": { "status": convert_to_bson(&EmailStatusEnum::Failed)?, "last_error": error },
+                    "$unset": unset
+                })
+            }
+        };
+        self.store.update("sent_emails", &doc! { "_id": id }, &update, false).map_err(duplicate_key)?;
+        Ok(())
+    }
+
+    async fn requeue(&self, id: ObjectId) -> Result<bool, Error> {
+        let filter = doc! {
+            "_id": id,
+            "status": convert_to_bson(&EmailStatusEnum::Failed)?,
+            "html_body": { "$exists": true }
+        };
+        let update = touched(doc! {
+            "$set": {
+                "status": convert_to_bson(&EmailStatusEnum::Queued)?,
+                "attempts": 0,
+                "next_attempt_at": BsonDateTime::now()
+            }
+        });
+        Ok(self.store.update("sent_emails", &filter, &update, false).map_err(duplicate_key)?.modified_count == 1)
+    }
+}
+/*----- END EMAILS -----*/
+
+
+/*----- START TWO FACTOR -----*/
+pub struct InMemoryTwoFactorRepository {
+    store: MemoryStore,
+}
+
+#[async_trait]
+impl TwoFactorRepository for InMemoryTwoFactorRepository {
+    async fn find_by_email(&self, email: &str) -> Result<Option<AdminTwoFactor>, Error> {
+        self.store.find_one("admin_two_factors", &doc! { "email": email }, None).map(from_document).transpose()
+    }
+
+    async fn start_enrolment(&self, email: &str, secret: &str) -> Result<Option<AdminTwoFactor>, Error> {
+        let update = touched(doc! {
+            "$set": { "secret": secret, "recovery_codes": [] },
+            "$unset": { "last_used_step": "" },
+            "$setOnInsert": { "email": email, "created_at": BsonDateTime::now() }
+        });
+        let modify = Modify { upsert: true, return_after: true, ..Modify::default() };
+
+        // A confirmed enrolment does not match, so the upsert hits the unique email index
+        match self.store.find_one_and_update("admin_two_factors", &doc! { "email": email, "confirmed_at": null }, &update, modify) {
+            Ok(enrolment) => enrolment.map(from_document).transpose(),
+            Err(DuplicateKey) => Ok(None),
+        }
+    }
+
+    async fn confirm(&self, id: ObjectId, step: i64, recovery_codes: &[String]) -> Result<bool, Error> {
+        let update = touched(doc! {
+            "$set": {
+                "confirmed_at": BsonDateTime::now(),
+                "last_used_step": step,
+                "recovery_codes": recovery_codes
+            }
+        });
+        let counts = self.store.update("admin_two_factors", &doc! { "_id": id, "confirmed_at": null }, &update, false).map_err(duplicate_key)?;
+        Ok(counts.modified_count == 1)
+    }
+
+    async fn use_step(&self, id: ObjectId, step: i64) -> Result<bool, Error> {
+        let filter = doc! {
+            "_id": id,
+            "$or": [
+                { "last_used_step": null },
+                { "last_used_step": { "$lt": step } }
+            ]
+        };
+        let update = touched(doc! { "$set": { "last_used_step": step } });
+        Ok(self.store.update("admin_two_factors", &filter, &update, false).map_err(duplicate_key)?.modified_count == 1)
+    }
+
+    async fn use_recovery_code(&self, id: ObjectId, code_hash: &str) -> Result<bool, Error> {
+        let update = touched(doc! { "$pull": { "recovery_codes": code_hash } });
+        let counts = self.store.update("admin_two_factors", &doc! { "_id": id, "recovery_codes": code_hash }, &update, false).map_err(duplicate_key)?;
+        Ok(counts.modified_count == 1)
+    }
+
+    async fn delete_by_email(&self, email: &str) -> Result<bool, Error> {
+        Ok(self.store.delete("admin_two_factors", &doc! { "email": email }, false) == 1)
+    }
+}
+/*----- END TWO FACTOR -----*/
+
+
+/*----- START ADMIN USERS -----*/
+pub struct InMemoryAdminUserRepository {
+    store: MemoryStore,
+}
+
+impl InMemoryAdminUserRepository {
+    fn set(&self, id: ObjectId, fields: Document) -> Result<(), Error> {
+        let counts = self.store.update("admin_users", &doc! { "_id": id }, &touched(doc! { "$set": fields }), false).map_err(duplicate_key)?;
+        if counts.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "Admin user not found");
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AdminUserRepository for InMemoryAdminUserRepository {
+    async fn find_by_id(&self, id: ObjectId) -> Result<Option<AdminUser>, Error> {
+        self.store.find_one("admin_users", &doc! { "_id": id }, None).map(from_document).transpose()
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<AdminUser>, Error> {
+        self.store.find_one("admin_users", &doc! { "email": email }, None).map(from_document).transpose()
+    }
+
+    async fn insert(&self, admin: &AdminUser) -> Result<AdminUser, Error> {
+        let mut admin = admin.clone();
+        admin.created_at = BsonDateTime::now();
+        admin.updated_at = BsonDateTime::now();
+        match self.store.insert("admin_users", to_document(&admin)?) {
+            Ok(id) => admin.id = id.as_object_id(),
+            Err(DuplicateKey) => handle_custom_error!(conflict, 409, "An admin user with this email already exists"),
+        }
+        Ok(admin)
+    }
+
+    async fn update_account(
+        &self,
+        id: ObjectId,
+        name: Option<&str>,
+        role: Option<AdminRoleEnum>,
+        status: Option<AdminStatusEnum>,
+    ) -> Result<(), Error> {
+        let mut fields = Document::new();
+        if let Some(name) = name {
+            fields.insert("name", name);
+        }
+        if let Some(role) = role {
+            fields.insert("role", convert_to_bson(&role)?);
+        }
+        if let Some(status) = status {
+            fields.insert("status", convert_to_bson(&status)?);
+        }
+        self.set(id, fields)
+    }
+
+    async fn set_password(&self, id: ObjectId, password_hash: &str, must_change_password: bool) -> Result<(), Error> {
+        self.set(id, doc! {
+            "password_hash": password_hash,
+            "must_change_password": must_change_password,
+            "password_changed_at": BsonDateTime::now()
+        })
+    }
+
+    async fn record_login(&self, id: ObjectId) -> Result<(), Error> {
+        self.set(id, doc! { "last_login_at": BsonDateTime::now() })
+    }
+
+    async fn count_active_superadmins(&self) -> Result<u64, Error> {
+        let filter = doc! {
+            "role": convert_to_bson(&AdminRoleEnum::Superadmin)?,
+            "status": convert_to_bson(&AdminStatusEnum::Active)?
+        };
+        Ok(self.store.count("admin_users", &filter))
+    }
+
+    async fn delete(&self, id: ObjectId) -> Result<(), Error> {
+        if self.store.delete("admin_users", &doc! { "_id": id }, false) == 0 {
+            handle_custom_error!(not_found, 404, "Admin user not found");
+        }
+        Ok(())
+    }
+}
+/*----- END ADMIN USERS -----*/
+
+
+/*----- START WIRING -----*/
+/// The in-memory repositories, all over `store`
+pub(super) fn repositories(store: &MemoryStore) -> super::Repositories {
+    use std::sync::Arc;
+
+    super::Repositories {
+        events: Arc::new(InMemoryEventRepository { store: store.clone() }),
+        attendees: Arc::new(InMemoryAttendeeRepository { store: store.clone() }),
+        invitations: Arc::new(InMemoryInvitationRepository { store: store.clone() }),
+        users: Arc::new(InMemoryUserRepository { store: store.clone() }),
+        bans: Arc::new(InMemoryBanRepository { store: store.clone() }),
+        audit_logs: Arc::new(InMemoryAuditRepository { store: store.clone() }),
+        contacts: Arc::new(InMemoryContactRepository { store: store.clone() }),
+        emails: Arc::new(InMemoryEmailRepository { store: store.clone() }),
+        two_factors: Arc::new(InMemoryTwoFactorRepository { store: store.clone() }),
+        admin_users: Arc::new(InMemoryAdminUserRepository { store: store.clone() }),
+        records: Arc::new(InMemoryRecordRepository { store: store.clone() }),
+    }
+}
+/*----- END WIRING -----*/
@@ -0,0 +1,553 @@
+// src/repositories/memory/store.rs
+//
+// Documents by collection name, queried with the subset of the Mongo query and update
+// language the repositories use. Unique indexes from `declared_indexes()` are enforced,
+// so duplicate registrations or emails fail here the way they fail on the server.
+// An operator outside that subset panics: a test must not pass on a half-read filter.
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use mongodb::bson::{oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+
+use crate::db::indexes::{declared_indexes, IndexSpec};
+use crate::repositories::record_repository::{RecordQuery, UpdateCounts};
+
+/// A write rejected by a unique index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateKey;
+
+/// How `find_one_and_update` picks, writes and returns its document
+#[derive(Debug, Clone, Default)]
+pub struct Modify {
+    pub sort: Option<Document>,
+    pub upsert: bool,
+    /// Return the document after the update rather than before it
+    pub return_after: bool,
+}
+
+#[derive(Clone)]
+pub struct MemoryStore {
+    collections: Arc<Mutex<HashMap<String, Vec<Document>>>>,
+    unique_indexes: Arc<Vec<IndexSpec>>,
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self {
+            collections: Arc::default(),
+            unique_indexes: Arc::new(declared_indexes().into_iter().filter(|spec| spec.unique).collect()),
+        }
+    }
+}
+
+
+/*----- START OPERATIONS -----*/
+impl MemoryStore {
+    fn with<T>(&self, collection: &str, f: impl FnOnce(&mut Vec<Document>) -> T) -> T {
+        let mut collections = self.collections.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(collections.entry(collection.to_string()).or_default())
+    }
+
+    pub fn find(&self, collection: &str, filter: &Document, query: &RecordQuery) -> Vec<Document> {
+        self.with(collection, |records| {
+            let mut found: Vec<Document> = records.iter().filter(|record| matches(record, filter)).cloned().collect();
+            if let Some(sort) = &query.sort {
+                found.sort_by(|a, b| compare_by(a, b, sort));
+            }
+            found.into_iter()
+                .skip(query.skip as usize)
+                .take(query.limit.filter(|limit| *limit > 0).map_or(usize::MAX, |limit| limit as usize))
+                .map(|record| match &query.projection {
+                    Some(projection) => project(record, projection),
+                    None => record,
+                })
+                .collect()
+        })
+    }
+
+    pub fn find_one(&self, collection: &str, filter: &Document, sort: Option<Document>) -> Option<Document> {
+        let query = RecordQuery { sort, limit: Some(1), ..RecordQuery::default() };
+        self.find(collection, filter, &query).into_iter().next()
+    }
+
+    pub fn count(&self, collection: &str, filter: &Document) -> u64 {
+        self.with(collection, |records| records.iter().filter(|record| matches(record, filter)).count() as u64)
+    }
+
+    /// Stores the record, giving it an ObjectId when it has no `_id`, and returns the id
+    pub fn insert(&self, collection: &str, mut record: Document) -> Result<Bson, DuplicateKey> {
+        let id = record.get("_id").cloned().unwrap_or_else(|| Bson::ObjectId(ObjectId::new()));
+        record.insert("_id", id.clone());
+
+        self.with(collection, |records| {
+            self.check_unique(collection, records, &record, None)?;
+            records.push(record);
+            Ok(id)
+        })
+    }
+
+    pub fn update(&self, collection: &str, filter: &Document, update: &Document, many: bool) -> Result<UpdateCounts, DuplicateKey> {
+        self.with(collection, |records| {
+            let mut counts = UpdateCounts::default();
+            for index in 0..records.len() {
+                if !matches(&records[index], filter) {
+                    continue;
+                }
+                counts.matched_count += 1;
+
+                let updated = apply_update(&records[index], update, false);
+                if updated != records[index] {
+                    self.check_unique(collection, records, &updated, Some(index))?;
+                    records[index] = updated;
+                    counts.modified_count += 1;
+                }
+                if !many {
+                    break;
+                }
+            }
+            Ok(counts)
+        })
+    }
+
+    pub fn find_one_and_update(&self, collection: &str, filter: &Document, update: &Document, modify: Modify) -> Result<Option<Document>, DuplicateKey> {
+        self.with(collection, |records| {
+            let mut candidates: Vec<usize> = (0..records.len()).filter(|index| matches(&records[*index], filter)).collect();
+            if let Some(sort) = &modify.sort {
+                candidates.sort_by(|a, b| compare_by(&records[*a], &records[*b], sort));
+            }
+
+            match candidates.first() {
+                Some(&index) => {
+                    let before = records[index].clone();
+                    let updated = apply_update(&before, update, false);
+                    self.check_unique(collection, records, &updated, Some(index))?;
+                    records[index] = updated.clone();
+                    Ok(Some(if modify.return_after { updated } else { before }))
+                }
+                None if modify.upsert => {
+                    let mut seed: Document = filter.iter()
+                        .filter(|(key, condition)| !key.starts_with('$') && !is_operator_document(condition))
+                        .map(|(key, condition)| (key.clone(), condition.clone()))
+                        .collect();
+                    seed.insert("_id", ObjectId::new());
+                    let inserted = apply_update(&seed, update, true);
+                    self.check_unique(collection, records, &inserted, None)?;
+                    records.push(inserted.clone());
+                    Ok(modify.return_after.then_some(inserted))
+                }
+                None => Ok(None),
+            }
+        })
+    }
+
+    pub fn delete(&self, collection: &str, filter: &Document, many: bool) -> u64 {
+        self.with(collection, |records| {
+            let mut deleted = 0;
+            records.retain(|record| {
+                let delete = (many || deleted == 0) && matches(record, filter);
+                deleted += delete as u64;
+                !delete
+            });
+            deleted
+        })
+    }
+
+    /// Refuses `record` when a unique index of the collection already holds its key.
+    /// `replacing` is the position of the record being updated, which does not count.
+    fn check_unique(&self, collection: &str, records: &[Document], record: &Document, replacing: Option<usize>) -> Result<(), DuplicateKey> {
+        for spec in self.unique_indexes.iter().filter(|spec| spec.collection == collection) {
+            let Some(key) = index_key(spec, record) else { continue };
+            let taken = records.iter()
+                .enumerate()
+                .filter(|(index, _)| Some(*index) != replacing)
+                .any(|(_, other)| index_key(spec, other).is_some_and(|other_key| other_key.iter().zip(&key).all(|(a, b)| equals(a, b))));
+            if taken {
+                return Err(DuplicateKey);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The values a unique index keys `record` by, or None when the index leaves it out
+fn index_key(spec: &IndexSpec, record: &Document) -> Option<Vec<Bson>> {
+    if spec.sparse && spec.keys.keys().all(|field| lookup(record, field).is_empty()) {
+        return None;
+    }
+    if spec.partial_filter.as_ref().is_some_and(|filter| !matches(record, filter)) {
+        return None;
+    }
+    Some(spec.keys.keys().map(|field| lookup(record, field).into_iter().next().unwrap_or(Bson::Null)).collect())
+}
+/*----- END OPERATIONS -----*/
+
+
+/*----- START QUERIES -----*/
+/// Whether `record` satisfies a Mongo filter
+pub fn matches(record: &Document, filter: &Document) -> bool {
+    filter.iter().all(|(key, condition)| match key.as_str() {
+        "$or" => conditions(condition).iter().any(|filter| matches(record, filter)),
+        "$and" => conditions(condition).iter().all(|filter| matches(record, filter)),
+        "$nor" => !conditions(condition).iter().any(|filter| matches(record, filter)),
+        "$expr" => expression(record, condition),
+        operator if operator.starts_with('$') => panic!("Query operator {} is not supported by the in-memory store", operator),
+        field => field_matches(&lookup(record, field), condition),
+    })
+}
+
+fn conditions(value: &Bson) -> Vec<Document> {
+    value.as_array().into_iter().flatten().filter_map(Bson::as_document).cloned().collect()
+}
+
+fn is_operator_document(value: &Bson) -> bool {
+    value.as_document().and_then(|document| document.keys().next()).is_some_and(|key| key.starts_with('$'))
+}
+
+/// `values` are what the field path resolved to, empty when it is missing
+fn field_matches(values: &[Bson], condition: &Bson) -> bool {
+    let Some(operators) = condition.as_document().filter(|_| is_operator_document(condition)) else {
+        return equals_any(values, condition);
+    };
+
+    operators.iter().all(|(operator, operand)| match operator.as_str() {
+        "$eq" => equals_any(values, operand),
+        "$ne" => !equals_any(values, operand),
+        "$in" => operand.as_array().into_iter().flatten().any(|candidate| equals_any(values, candidate)),
+        "$nin" => !operand.as_array().into_iter().flatten().any(|candidate| equals_any(values, candidate)),
+        "$gt" => compares(values, operand, |ordering| ordering == Ordering::Greater),
+        "$gte" => compares(values, operand, |ordering| ordering != Ordering::Less),
+        "$lt" => compares(values, operand, |ordering| ordering == Ordering::Less),
+        "$lte" => compares(values, operand, |ordering| ordering != Ordering::Greater),
+        "$exists" => operand.as_bool().unwrap_or(true) != values.is_empty(),
+        "$regex" => {
+            let options = operators.get_str("$options").unwrap_or_default();
+            let pattern = match operand {
+                Bson::RegularExpression(regex) => regex.pattern.clone(),
+                other => other.as_str().unwrap_or_default().to_string(),
+            };
+            values.iter().flat_map(elements).any(|value| regex_matches(&pattern, options, &value))
+        }
+        "$options" => true,
+        "$type" => values.iter().any(|value| type_name(value) == operand.as_str().unwrap_or_default()),
+        "$not" => !field_matches(values, operand),
+        other => panic!("Query operator {} is not supported by the in-memory store", other),
+    })
+}
+
+/// A value and, for an array, each of its elements: `{ tags: "x" }` matches `["x", "y"]`
+fn elements(value: &Bson) -> Vec<Bson> {
+    match value {
+        Bson::Array(items) => std::iter::once(value.clone()).chain(items.iter().cloned()).collect(),
+        other => vec![other.clone()],
+    }
+}
+
+fn equals_any(values: &[Bson], expected: &Bson) -> bool {
+    if matches!(expected, Bson::Null) && values.is_empty() {
+        return true;
+    }
+    values.iter().flat_map(elements).any(|value| match expected {
+        Bson::RegularExpression(regex) => regex_matches(&regex.pattern, &regex.options, &value),
+        expected => equals(&value, expected),
+    })
+}
+
+fn compares(values: &[Bson], bound: &Bson, accept: impl Fn(Ordering) -> bool) -> bool {
+    values.iter()
+        .flat_map(elements)
+        .any(|value| type_rank(&value) == type_rank(bound) && accept(compare(&value, bound)))
+}
+
+fn regex_matches(pattern: &str, options: &str, value: &Bson) -> bool {
+    let Some(text) = value.as_str() else { return false };
+    let flags = if options.contains('i') { "(?i)" } else { "" };
+    regex::Regex::new(&format!("{}{}", flags, pattern)).is_ok_and(|regex| regex.is_match(text))
+}
+
+fn type_name(value: &Bson) -> &'static str {
+    match value {
+        Bson::ObjectId(_) => "objectId",
+        Bson::String(_) => "string",
+        Bson::Boolean(_) => "bool",
+        Bson::DateTime(_) => "date",
+        Bson::Null => "null",
+        Bson::Int32(_) => "int",
+        Bson::Int64(_) => "long",
+        Bson::Double(_) => "double",
+        Bson::Array(_) => "array",
+        Bson::Document(_) => "object",
+        _ => "other",
+    }
+}
+
+/// `{ "$lt": ["$current_attendees", "$max_attendees"] }` and the other comparisons
+fn expression(record: &Document, condition: &Bson) -> bool {
+    let Some((operator, operands)) = condition.as_document().and_then(|document| document.iter().next()) else {
+        return false;
+    };
+    let operands: Vec<Bson> = operands.as_array().into_iter().flatten()
+        .map(|operand| match operand.as_str().and_then(|text| text.strip_prefix('$')) {
+            Some(field) => lookup(record, field).into_iter().next().unwrap_or(Bson::Null),
+            None => operand.clone(),
+        })
+        .collect();
+    let [left, right] = operands.as_slice() else {
+        panic!("Expression {} needs two operands in the in-memory store", operator);
+    };
+
+    let ordering = compare(left, right);
+    match operator.as_str() {
+        "$eq" => ordering == Ordering::Equal,
+        "$ne" => ordering != Ordering::Equal,
+        "$lt" => ordering == Ordering::Less,
+        "$lte" => ordering != Ordering::Greater,
+        "$gt" => ordering == Ordering::Greater,
+        "$gte" => ordering != Ordering::Less,
+        other => panic!("Expression {} is not supported by the in-memory store", other),
+    }
+}
+
+/// Every value a dotted path reaches; paths through arrays fan out, numeric segments index
+pub fn lookup(record: &Document, path: &str) -> Vec<Bson> {
+    let mut current = vec![Bson::Document(record.clone())];
+    for segment in path.split('.') {
+        current = current.into_iter()
+            .flat_map(|value| match value {
+                Bson::Document(document) => document.get(segment).cloned().into_iter().collect::<Vec<_>>(),
+                Bson::Array(items) => match segment.parse::<usize>() {
+                    Ok(index) => items.get(index).cloned().into_iter().collect(),
+                    Err(_) => items.iter()
+                        .filter_map(Bson::as_document)
+                        .filter_map(|item| item.get(segment).cloned())
+                        .collect(),
+                },
+                _ => Vec::new(),
+            })
+            .collect();
+    }
+    current
+}
+
+fn project(record: Document, projection: &Document) -> Document {
+    let included = |value: &Bson| matches!(value, Bson::Int32(1) | Bson::Int64(1) | Bson::Boolean(true)) || value.as_f64() == Some(1.0);
+    let inclusive = projection.iter().any(|(field, value)| field != "_id" && included(value));
+    let keep_id = projection.get("_id").is_none_or(included);
+
+    record.into_iter()
+        .filter(|(field, _)| match field.as_str() {
+            "_id" => keep_id,
+            field if inclusive => projection.get(field).is_some_and(included),
+            field => projection.get(field).is_none_or(included),
+        })
+        .collect()
+}
+/*----- END QUERIES -----*/
+
+
+/*----- START ORDERING -----*/
+/// The BSON comparison order between types: null, numbers, strings, objects, arrays,
+/// ids, booleans, dates
+fn type_rank(value: &Bson) -> u8 {
+    match value {
+        Bson::Null | Bson::Undefined => 0,
+        Bson::Int32(_) | Bson::Int64(_) | Bson::Double(_) | Bson::Decimal128(_) => 1,
+        Bson::String(_) | Bson::Symbol(_) => 2,
+        Bson::Document(_) => 3,
+        Bson::Array(_) => 4,
+        Bson::ObjectId(_) => 5,
+        Bson::Boolean(_) => 6,
+        Bson::DateTime(_) => 7,
+        _ => 8,
+    }
+}
+
+fn number(value: &Bson) -> Option<f64> {
+    match value {
+        Bson::Int32(v) => Some(*v as f64),
+        Bson::Int64(v) => Some(*v as f64),
+        Bson::Double(v) => Some(*v),
+        _ => None,
+    }
+}
+
+pub fn compare(a: &Bson, b: &Bson) -> Ordering {
+    match (a, b) {
+        _ if type_rank(a) != type_rank(b) => type_rank(a).cmp(&type_rank(b)),
+        (Bson::String(a), Bson::String(b)) => a.cmp(b),
+        (Bson::ObjectId(a), Bson::ObjectId(b)) => a.cmp(b),
+        (Bson::Boolean(a), Bson::Boolean(b)) => a.cmp(b),
+        (Bson::DateTime(a), Bson::DateTime(b)) => a.cmp(b),
+        (Bson::Array(a), Bson::Array(b)) => a.iter().zip(b).map(|(a, b)| compare(a, b)).find(|o| o.is_ne()).unwrap_or(a.len().cmp(&b.len())),
+        (Bson::Document(a), Bson::Document(b)) => a.iter().zip(b.iter())
+            .map(|((ka, va), (kb, vb))| ka.cmp(kb).then_with(|| compare(va, vb)))
+            .find(|o| o.is_ne())
+            .unwrap_or(a.len().cmp(&b.len())),
+        _ => match (number(a), number(b)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            _ => Ordering::Equal,
+        },
+    }
+}
+
+fn equals(a: &Bson, b: &Bson) -> bool {
+    match (a, b) {
+        _ if number(a).is_some() && number(b).is_some() => number(a) == number(b),
+        (Bson::Document(_), Bson::Document(_)) | (Bson::Array(_), Bson::Array(_)) => compare(a, b) == Ordering::Equal,
+        _ => a == b,
+    }
+}
+
+/// Orders two records by a Mongo sort document; missing fields sort as null
+pub fn compare_by(a: &Document, b: &Document, sort: &Document) -> Ordering {
+    sort.iter()
+        .map(|(field, direction)| {
+            let a = lookup(a, field).into_iter().next().unwrap_or(Bson::Null);
+            let b = lookup(b, field).into_iter().next().unwrap_or(Bson::Null);
+            let ordering = compare(&a, &b);
+            if number(direction).is_some_and(|direction| direction < 0.0) { ordering.reverse() } else { ordering }
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+/*----- END ORDERING -----*/
+
+
+/*----- START UPDATES -----*/
+/// `record` with a Mongo update applied. `inserting` runs `$setOnInsert` too.
+pub fn apply_update(record: &Document, update: &Document, inserting: bool) -> Document {
+    let mut record = record.clone();
+    for (operator, fields) in update {
+        let fields = fields.as_document().cloned().unwrap_or_default();
+        for (path, value) in fields {
+            match operator.as_str() {
+                "$set" => set_path(&mut record, &path, value),
+                "$setOnInsert" if inserting => set_path(&mut record, &path, value),
+                "$setOnInsert" => {}
+                "$unset" => unset_path(&mut record, &path),
+                "$currentDate" => set_path(&mut record, &path, Bson::DateTime(BsonDateTime::now())),
+                "$inc" => {
+                    let current = lookup(&record, &path).into_iter().next();
+                    let sum = match (current.as_ref(), &value) {
+                        (Some(Bson::Int32(a)), Bson::Int32(b)) => Bson::Int32(a + b),
+                        (Some(Bson::Int32(a)), Bson::Int64(b)) | (Some(Bson::Int64(b)), Bson::Int32(a)) => Bson::Int64(*a as i64 + b),
+                        (Some(Bson::Int64(a)), Bson::Int64(b)) => Bson::Int64(a + b),
+                        (None | Some(Bson::Null), value) => value.clone(),
+                        (Some(current), value) => Bson::Double(number(current).unwrap_or(0.0) + number(value).unwrap_or(0.0)),
+                    };
+                    set_path(&mut record, &path, sum);
+                }
+                "$push" | "$addToSet" => {
+                    let mut items = lookup(&record, &path).into_iter().next()
+                        .and_then(|current| current.as_array().cloned())
+                        .unwrap_or_default();
+                    if operator == "$push" || !items.iter().any(|item| equals(item, &value)) {
+                        items.push(value);
+                    }
+                    set_path(&mut record, &path, Bson::Array(items));
+                }
+                "$pull" => {
+                    if let Some(Bson::Array(items)) = lookup(&record, &path).into_iter().next() {
+                        let kept = items.into_iter().filter(|item| !equals(item, &value)).collect();
+                        set_path(&mut record, &path, Bson::Array(kept));
+                    }
+                }
+                other => panic!("Update operator {} is not supported by the in-memory store", other),
+            }
+        }
+    }
+    record
+}
+
+fn set_path(record: &mut Document, path: &str, value: Bson) {
+    match path.split_once('.') {
+        None => {
+            record.insert(path, value);
+        }
+        Some((head, rest)) => {
+            if !matches!(record.get(head), Some(Bson::Document(_))) {
+                record.insert(head, Document::new());
+            }
+            if let Some(Bson::Document(inner)) = record.get_mut(head) {
+                set_path(inner, rest, value);
+            }
+        }
+    }
+}
+
+fn unset_path(record: &mut Document, path: &str) {
+    match path.split_once('.') {
+        None => {
+            record.remove(path);
+        }
+        Some((head, rest)) => {
+            if let Some(Bson::Document(inner)) = record.get_mut(head) {
+                unset_path(inner, rest);
+            }
+        }
+    }
+}
+/*----- END UPDATES -----*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mongodb::bson::doc;
+
+    #[test]
+    fn filters_match_like_mongo() {
+        let record = doc! { "name": "Ada", "age": 36, "tags": ["math", "code"], "deleted": false, "address": { "city": "London" } };
+
+        assert!(matches(&record, &doc! { "age": 36.0, "address.city": "London" }));
+        assert!(matches(&record, &doc! { "tags": "code", "missing": null }));
+        assert!(matches(&record, &doc! { "age": { "$gte": 30, "$lt": 40 }, "deleted": { "$ne": true } }));
+        assert!(matches(&record, &doc! { "$or": [{ "name": "Grace" }, { "tags": { "$in": ["math"] } }] }));
+        assert!(!matches(&record, &doc! { "name": { "$nin": ["Ada"] } }));
+        assert!(matches(&record, &doc! { "$expr": { "$lt": ["$age", 40] } }));
+    }
+
+    #[test]
+    fn updates_apply_operators() {
+        let record = doc! { "count": 1, "tags": ["a"] };
+        let updated = apply_update(&record, &doc! {
+            "$inc": { "count": 2 },
+            "$set": { "nested.flag": true },
+            "$addToSet": { "tags": "a" },
+            "$push": { "log": "created" }
+        }, false);
+
+        assert_eq!(updated.get_i32("count").unwrap(), 3);
+        assert!(updated.get_document("nested").unwrap().get_bool("flag").unwrap());
+        assert_eq!(updated.get_array("tags").unwrap().len(), 1);
+        assert_eq!(updated.get_array("log").unwrap(), &vec![Bson::from("created")]);
+    }
+
+    #[test]
+    fn unique_indexes_reject_duplicates() {
+        let store = MemoryStore::default();
+        store.insert("configs", doc! { "key": "site_name" }).expect("first insert");
+        assert_eq!(store.insert("configs", doc! { "key": "site_name" }), Err(DuplicateKey));
+
+        // Sparse: records without the key never collide
+        store.insert("users", doc! { "first_name": "Ada" }).expect("user without email");
+        store.insert("users", doc! { "first_name": "Grace" }).expect("second user without email");
+
+        let id = store.insert("configs", doc! { "key": "timezone" }).expect("other key");
+        let rename = store.update("configs", &doc! { "_id": id }, &doc! { "$set": { "key": "site_name" } }, false);
+        assert_eq!(rename, Err(DuplicateKey));
+    }
+
+    #[test]
+    fn find_sorts_and_pages() {
+        let store = MemoryStore::default();
+        for rank in [3, 1, 2] {
+            store.insert("events", doc! { "rank": rank }).unwrap();
+        }
+
+        let query = RecordQuery { sort: Some(doc! { "rank": -1 }), skip: 1, limit: Some(1), projection: None };
+        let page = store.find("events", &doc! {}, &query);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].get_i32("rank").unwrap(), 2);
+    }
+}
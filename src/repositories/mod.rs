@@ -0,0 +1,81 @@
+// src/repositories/mod.rs
+//
+// Data access for controllers, services and AdminX custom actions. Callers only see
+// the traits and get them from `web::Data<Repositories>` rather than a global handle;
+// `Repositories::mongo` wires the Mongo implementations and main.rs registers them;
+// tests use `Repositories::in_memory` instead.
+pub mod event_repository;
+pub mod attendee_repository;
+pub mod invitation_repository;
+pub mod user_repository;
+pub mod ban_repository;
+pub mod audit_repository;
+pub mod contact_repository;
+pub mod email_repository;
+pub mod two_factor_repository;
+pub mod admin_user_repository;
+pub mod record_repository;
+#[cfg(test)]
+pub mod memory;
+
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest};
+use mongodb::Database;
+
+use event_repository::{EventRepository, MongoEventRepository};
+use attendee_repository::{AttendeeRepository, MongoAttendeeRepository};
+use invitation_repository::{InvitationRepository, MongoInvitationRepository};
+use user_repository::{UserRepository, MongoUserRepository};
+use ban_repository::{BanRepository, MongoBanRepository};
+use audit_repository::{AuditRepository, MongoAuditRepository};
+use contact_repository::{ContactRepository, MongoContactRepository};
+use email_repository::{EmailRepository, MongoEmailRepository};
+use two_factor_repository::{TwoFactorRepository, MongoTwoFactorRepository};
+use admin_user_repository::{AdminUserRepository, MongoAdminUserRepository};
+use record_repository::{RecordRepository, MongoRecordRepository};
+
+/// Registered once as `web::Data<Repositories>`
+#[derive(Clone)]
+pub struct Repositories {
+    pub events: Arc<dyn EventRepository>,
+    pub attendees: Arc<dyn AttendeeRepository>,
+    pub invitations: Arc<dyn InvitationRepository>,
+    pub users: Arc<dyn UserRepository>,
+    pub bans: Arc<dyn BanRepository>,
+    pub audit_logs: Arc<dyn AuditRepository>,
+    pub contacts: Arc<dyn ContactRepository>,
+    pub emails: Arc<dyn EmailRepository>,
+    pub two_factors: Arc<dyn TwoFactorRepository>,
+    pub admin_users: Arc<dyn AdminUserRepository>,
+    pub records: Arc<dyn RecordRepository>,
+}
+
+impl Repositories {
+    pub fn mongo(db: &Database) -> Self {
+        Self {
+            events: Arc::new(MongoEventRepository::new(db)),
+            attendees: Arc::new(MongoAttendeeRepository::new(db)),
+            invitations: Arc::new(MongoInvitationRepository::new(db)),
+            users: Arc::new(MongoUserRepository::new(db)),
+            bans: Arc::new(MongoBanRepository::new(db)),
+            audit_logs: Arc::new(MongoAuditRepository::new(db)),
+            contacts: Arc::new(MongoContactRepository::new(db)),
+            emails: Arc::new(MongoEmailRepository::new(db)),
+            two_factors: Arc::new(MongoTwoFactorRepository::new(db)),
+            admin_users: Arc::new(MongoAdminUserRepository::new(db)),
+            records: Arc::new(MongoRecordRepository::new(db)),
+        }
+    }
+
+    /// Every repository over one empty in-memory store
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        memory::repositories(&memory::store::MemoryStore::default())
+    }
+
+    /// For AdminX custom actions, which get the raw request instead of extractors
+    pub fn from_request(req: &HttpRequest) -> Option<web::Data<Self>> {
+        req.app_data::<web::Data<Self>>().cloned()
+    }
+}
@@ -0,0 +1,99 @@
+// src/repositories/record_repository.rs
+//
+// Untyped access by collection name, for AdminX tooling that works the same way on
+// any resource (CRUD, lists and exports, field toggles, reference pickers and checks).
+// Everything with business rules goes through the typed repositories instead.
+use actix_web::Error;
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::{
+    Database,
+    bson::{Bson, Document},
+    options::FindOptions,
+};
+
+use crate::custom_error_expression;
+
+/// Order, window and fields of a `find`
+#[derive(Debug, Clone, Default)]
+pub struct RecordQuery {
+    pub sort: Option<Document>,
+    pub skip: u64,
+    pub limit: Option<i64>,
+    pub projection: Option<Document>,
+}
+
+/// How many records an update matched and how many it actually changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UpdateCounts {
+    pub matched_count: u64,
+    pub modified_count: u64,
+}
+
+#[async_trait]
+pub trait RecordRepository: Send + Sync {
+    async fn find_one(&self, collection: &str, filter: Document) -> Result<Option<Document>, Error>;
+
+    async fn find(&self, collection: &str, filter: Document, query: RecordQuery) -> Result<Vec<Document>, Error>;
+
+    async fn count(&self, collection: &str, filter: Document) -> Result<u64, Error>;
+
+    /// Returns the id of the inserted record
+    async fn insert_one(&self, collection: &str, record: Document) -> Result<Bson, Error>;
+
+    async fn update_one(&self, collection: &str, filter: Document, update: Document) -> Result<UpdateCounts, Error>;
+
+    /// Returns how many records were deleted
+    async fn delete_one(&self, collection: &str, filter: Document) -> Result<u64, Error>;
+}
+
+pub struct MongoRecordRepository {
+    db: Database,
+}
+
+impl MongoRecordRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { db: db.clone() }
+    }
+}
+
+fn db_error(e: mongodb::error::Error) -> Error {
+    custom_error_expression!(internal_error, 500, e.to_string()).into()
+}
+
+#[async_trait]
+impl RecordRepository for MongoRecordRepository {
+    async fn find_one(&self, collection: &str, filter: Document) -> Result<Option<Document>, Error> {
+        self.db.collection::<Document>(collection).find_one(filter, None).await.map_err(db_error)
+    }
+
+    async fn find(&self, collection: &str, filter: Document, query: RecordQuery) -> Result<Vec<Document>, Error> {
+        let options = FindOptions::builder()
+            .sort(query.sort)
+            .skip(query.skip)
+            .limit(query.limit)
+            .projection(query.projection)
+            .build();
+        let cursor = self.db.collection::<Document>(collection).find(filter, options).await.map_err(db_error)?;
+        cursor.try_collect().await.map_err(db_error)
+    }
+
+    async fn count(&self, collection: &str, filter: Document) -> Result<u64, Error> {
+        self.db.collection::<Document>(collection).count_documents(filter, None).await.map_err(db_error)
+    }
+
+    async fn insert_one(&self, collection: &str, record: Document) -> Result<Bson, Error> {
+        let insert_result = self.db.collection::<Document>(collection).insert_one(record, None).await.map_err(db_error)?;
+        Ok(insert_result.inserted_id)
+    }
+
+    async fn update_one(&self, collection: &str, filter: Document, update: Document) -> Result<UpdateCounts, Error> {
+        let update_result = self.db.collection::<Document>(collection).update_one(filter, update, None).await.map_err(db_error)?;
+        Ok(UpdateCounts { matched_count: update_result.matched_count, modified_count: update_result.modified_count })
+    }
+
+    async fn delete_one(&self, collection: &str, filter: Document) -> Result<u64, Error> {
+        let delete_result = self.db.collection::<Document>(collection).delete_one(filter, None).await.map_err(db_error)?;
+        Ok(delete_result.deleted_count)
+    }
+}
@@ -0,0 +1,142 @@
+// src/repositories/two_factor_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
+    options::{FindOneAndUpdateOptions, ReturnDocument},
+};
+
+use crate::db::indexes::is_duplicate_key_error;
+use crate::models::admin_two_factor::AdminTwoFactor;
+use crate::custom_error_expression;
+
+#[async_trait]
+pub trait TwoFactorRepository: Send + Sync {
+    async fn find_by_email(&self, email: &str) -> Result<Option<AdminTwoFactor>, Error>;
+
+    /// Stores a new pending secret, replacing an unconfirmed one. `None` when the
+    /// operator is already enrolled.
+    async fn start_enrolment(&self, email: &str, secret: &str) -> Result<Option<AdminTwoFactor>, Error>;
+
+    /// Confirms a pending enrolment with the step of its first code and the recovery code hashes
+    async fn confirm(&self, id: ObjectId, step: i64, recovery_codes: &[String]) -> Result<bool, Error>;
+
+    /// Records an accepted time step. False when it, or a later one, was already used.
+    async fn use_step(&self, id: ObjectId, step: i64) -> Result<bool, Error>;
+
+    /// Removes a recovery code. False when it was not (or no longer) there.
+    async fn use_recovery_code(&self, id: ObjectId, code_hash: &str) -> Result<bool, Error>;
+
+    /// Drops the operator's enrolment, so the next sign-in sets up a new authenticator
+    async fn delete_by_email(&self, email: &str) -> Result<bool, Error>;
+}
+
+pub struct MongoTwoFactorRepository {
+    collection: Collection<AdminTwoFactor>,
+}
+
+impl MongoTwoFactorRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<AdminTwoFactor>("admin_two_factors") }
+    }
+}
+
+#[async_trait]
+impl TwoFactorRepository for MongoTwoFactorRepository {
+    async fn find_by_email(&self, email: &str) -> Result<Option<AdminTwoFactor>, Error> {
+        self.collection.find_one(doc! { "email": email }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn start_enrolment(&self, email: &str, secret: &str) -> Result<Option<AdminTwoFactor>, Error> {
+        let update = doc! {
+            "$set": {
+                "secret": secret,
+                "recovery_codes": []
+            },
+            "$unset": {
+                "last_used_step": ""
+            },
+            "$setOnInsert": {
+                "email": email,
+                "created_at": BsonDateTime::now()
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+        let options = FindOneAndUpdateOptions::builder()
+            .upsert(true)
+            .return_document(ReturnDocument::After)
+            .build();
+
+        // A confirmed enrolment does not match, so the upsert hits the unique email index
+        match self.collection.find_one_and_update(doc! { "email": email, "confirmed_at": null }, update, options).await {
+            Ok(enrolment) => Ok(enrolment),
+            Err(e) if is_duplicate_key_error(&e) => Ok(None),
+            Err(e) => Err(custom_error_expression!(internal_error, 500, e.to_string()).into()),
+        }
+    }
+
+    async fn confirm(&self, id: ObjectId, step: i64, recovery_codes: &[String]) -> Result<bool, Error> {
+        let update = doc! {
+            "$set": {
+                "confirmed_at": BsonDateTime::now(),
+                "last_used_step": step,
+                "recovery_codes": recovery_codes
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(doc! { "_id": id, "confirmed_at": null }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(update_result.modified_count == 1)
+    }
+
+    async fn use_step(&self, id: ObjectId, step: i64) -> Result<bool, Error> {
+        let filter = doc! {
+            "_id": id,
+            "$or": [
+                { "last_used_step": null },
+                { "last_used_step": { "$lt": step } }
+            ]
+        };
+        let update = doc! {
+            "$set": {
+                "last_used_step": step
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(filter, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(update_result.modified_count == 1)
+    }
+
+    async fn use_recovery_code(&self, id: ObjectId, code_hash: &str) -> Result<bool, Error> {
+        let update = doc! {
+            "$pull": {
+                "recovery_codes": code_hash
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(doc! { "_id": id, "recovery_codes": code_hash }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(update_result.modified_count == 1)
+    }
+
+    async fn delete_by_email(&self, email: &str) -> Result<bool, Error> {
+        let delete_result = self.collection.delete_one(doc! { "email": email }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(delete_result.deleted_count == 1)
+    }
+}
@@ -0,0 +1,113 @@
+// src/repositories/user_repository.rs
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::{
+    Collection,
+    Database,
+    bson::{doc, oid::ObjectId, DateTime as BsonDateTime},
+};
+
+use crate::enums::common_enums::StatusEnum;
+use crate::models::user::User;
+use crate::utilities::bason_utility::convert_to_bson;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+#[async_trait]
+pub trait UserRepository: Send + Sync {
+    /// A user that has not been deleted; 404 when missing
+    async fn find_active_by_id(&self, user_id: ObjectId) -> Result<User, Error>;
+
+    /// Set `locked` and `status` together, as bans do
+    async fn set_lock(&self, user_id: ObjectId, locked: bool, status: StatusEnum) -> Result<(), Error>;
+
+    /// The undeleted user signing in with this (lowercased) email
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, Error>;
+
+    /// The undeleted user signing in with this phone number
+    async fn find_by_phone(&self, phone_number: &str) -> Result<Option<User>, Error>;
+
+    async fn insert(&self, user: &User) -> Result<User, Error>;
+
+    /// Stores the device push token sent along with a sign-in
+    async fn set_firebase_token(&self, user_id: ObjectId, firebase_token: &str) -> Result<(), Error>;
+}
+
+pub struct MongoUserRepository {
+    collection: Collection<User>,
+}
+
+impl MongoUserRepository {
+    pub fn new(db: &Database) -> Self {
+        Self { collection: db.collection::<User>("users") }
+    }
+}
+
+#[async_trait]
+impl UserRepository for MongoUserRepository {
+    async fn find_active_by_id(&self, user_id: ObjectId) -> Result<User, Error> {
+        self.collection.find_one(doc! { "_id": user_id, "deleted": false }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+            .ok_or_else(|| custom_error_expression!(not_found, 404, "User not found").into())
+    }
+
+    async fn set_lock(&self, user_id: ObjectId, locked: bool, status: StatusEnum) -> Result<(), Error> {
+        let update = doc! {
+            "$set": {
+                "locked": locked,
+                "status": convert_to_bson(&status)?
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        let update_result = self.collection.update_one(doc! { "_id": user_id }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        if update_result.matched_count == 0 {
+            handle_custom_error!(not_found, 404, "User not found");
+        }
+
+        Ok(())
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, Error> {
+        self.collection.find_one(doc! { "email": email, "deleted": false }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn find_by_phone(&self, phone_number: &str) -> Result<Option<User>, Error> {
+        self.collection.find_one(doc! { "phone_number": phone_number, "deleted": false }, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+    }
+
+    async fn insert(&self, user: &User) -> Result<User, Error> {
+        let mut user = user.clone();
+        user.created_at = BsonDateTime::now();
+        user.updated_at = BsonDateTime::now();
+
+        let insert_result = self.collection.insert_one(&user, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+        user.id = insert_result.inserted_id.as_object_id();
+        Ok(user)
+    }
+
+    async fn set_firebase_token(&self, user_id: ObjectId, firebase_token: &str) -> Result<(), Error> {
+        let update = doc! {
+            "$set": {
+                "firebase_token": firebase_token
+            },
+            "$currentDate": {
+                "updated_at": true
+            }
+        };
+
+        self.collection.update_one(doc! { "_id": user_id }, update, None).await
+            .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+        Ok(())
+    }
+}
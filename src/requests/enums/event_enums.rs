@@ -2,8 +2,10 @@
 
 
 use serde::{Deserialize, Serialize};
+use strum_macros::EnumIter;
+use schemars::JsonSchema;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum EventStatusEnum {
     Draft,
     Published,
@@ -18,7 +20,7 @@ impl Default for EventStatusEnum {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum ApprovalStatusEnum {
     Pending,
     Approved,
@@ -32,7 +34,7 @@ impl Default for ApprovalStatusEnum {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum EventCategoryEnum {
     Business,
     Technology,
@@ -58,7 +60,7 @@ impl Default for EventCategoryEnum {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum EventTypeEnum {
     Physical,
     Virtual,
@@ -72,7 +74,7 @@ impl Default for EventTypeEnum {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum RegistrationTypeEnum {
     Required,
     Optional,
@@ -108,7 +110,7 @@ impl RegistrationTypeEnum {
     }
 } 
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum PaymentTypeEnum {
     Free,
     Paid,
@@ -122,7 +124,7 @@ impl Default for PaymentTypeEnum {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum AgeRestrictionEnum {
     AllAges,
     AdultsOnly,
@@ -139,7 +141,7 @@ impl Default for AgeRestrictionEnum {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum DressCodeEnum {
     Casual,
     BusinessCasual,
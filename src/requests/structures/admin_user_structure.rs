@@ -0,0 +1,57 @@
+// src/requests/structures/admin_user_structure.rs
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::enums::common_enums::{AdminRoleEnum, AdminStatusEnum};
+use crate::libs::custom_validators::validate_email;
+
+/// Password lengths accepted for operators; argon2 takes any length, the cap keeps
+/// hashing a request cheap to refuse
+pub const ADMIN_PASSWORD_MIN_LENGTH: u64 = 12;
+pub const ADMIN_PASSWORD_MAX_LENGTH: u64 = 128;
+
+/// AdminX create form of admin users
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct CreateAdminUserRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+
+    #[validate(custom = "validate_email", length(max = 254))]
+    pub email: String,
+
+    pub role: AdminRoleEnum,
+
+    pub status: Option<AdminStatusEnum>,
+
+    #[validate(length(min = "ADMIN_PASSWORD_MIN_LENGTH", max = "ADMIN_PASSWORD_MAX_LENGTH"))]
+    pub password: String,
+}
+
+/// AdminX edit form of admin users; fields left out stay as they are. A password
+/// resets the operator's, who then has to change it on the next sign-in.
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct UpdateAdminUserRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+
+    pub role: Option<AdminRoleEnum>,
+
+    pub status: Option<AdminStatusEnum>,
+
+    #[validate(length(min = "ADMIN_PASSWORD_MIN_LENGTH", max = "ADMIN_PASSWORD_MAX_LENGTH"))]
+    pub password: Option<String>,
+}
+
+/// The signed-in operator's own password change
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct ChangeAdminPasswordRequest {
+    #[validate(length(min = 1, max = "ADMIN_PASSWORD_MAX_LENGTH"))]
+    pub current_password: String,
+
+    #[validate(length(min = "ADMIN_PASSWORD_MIN_LENGTH", max = "ADMIN_PASSWORD_MAX_LENGTH"))]
+    pub new_password: String,
+
+    #[validate(must_match = "new_password")]
+    pub confirm_password: String,
+}
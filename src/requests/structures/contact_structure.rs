@@ -0,0 +1,27 @@
+// src/requests/structures/contact_structure.rs
+
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::utilities::{qr_utility::QrFormat, vcard_utility::VCardVersion};
+
+#[derive(Deserialize, Debug)]
+pub struct ContactPathInfo {
+    pub id: String,
+}
+
+/// `?version=3.0|4.0`, 4.0 when omitted
+#[derive(Default, Deserialize, Debug, Validate)]
+pub struct VCardQuery {
+    pub version: Option<VCardVersion>,
+}
+
+/// `?format=svg|png&size=`, an SVG of `DEFAULT_QR_SIZE` pixels when omitted
+#[derive(Default, Deserialize, Debug, Validate)]
+pub struct QrQuery {
+    pub format: Option<QrFormat>,
+
+    // Width in pixels
+    #[validate(range(min = 64, max = 2048))]
+    pub size: Option<u32>,
+}
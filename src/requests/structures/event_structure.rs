@@ -13,14 +13,49 @@ use crate::requests::enums::event_enums::{
     PaymentTypeEnum,
     RegistrationTypeEnum,
 };
+use crate::libs::custom_validators::validate_email;
+use crate::libs::general_library::DATE_RANGE_PRESETS;
+use crate::requests::validators::{
+    open_validator::{
+        str_mobgo_object_special_chars,
+        str_max_100_chars,
+        str_max_500_chars,
+        str_max_1000_chars,
+    },
+    event_validators::{
+        validate_event_title,
+        validate_event_description,
+        validate_event_location,
+        validate_event_price,
+        validate_max_attendees,
+        validate_event_tags,
+        validate_event_dates,
+        validate_registration_deadline,
+        validate_organizer_name,
+        validate_age_restriction,
+        validate_dress_code,
+        validate_special_instructions,
+        validate_meeting_link,
+        validate_registration_url,
+    },
+};
 
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
+#[validate(schema(function = "validate_event_request_body"))]
 pub struct EventRequestBody {
     // Required/basic fields
+    #[validate(custom = "validate_event_title")]
     pub title: String,
+    #[validate(custom = "validate_event_description")]
     pub description: String,
+    #[validate(custom = "validate_event_location")]
     pub address: String,
+    #[validate(custom = "validate_event_location")]
     pub location: Option<String>,
+    #[validate(range(min = -90.0, max = 90.0))]
+    pub latitude: Option<f64>,
+    #[validate(range(min = -180.0, max = 180.0))]
+    pub longitude: Option<f64>,
     // Date & time
     pub event_date: DateTime<Utc>,
     pub start_time: DateTime<Utc>,
@@ -43,19 +78,25 @@ pub struct EventRequestBody {
     pub category: Option<EventCategoryEnum>,
 
     // Organizer info
+    #[validate(custom = "validate_organizer_name")]
     pub organizer_name: Option<String>,
     pub organizer_email: Option<String>,
     pub organizer_phone: Option<String>,
 
     // Registration / access
+    #[validate(custom = "validate_registration_url")]
     pub registration_url: Option<String>,
+    #[validate(custom = "validate_meeting_link")]
     pub meeting_link: Option<String>,
     pub requires_registration: Option<bool>,
     pub registration_deadline: Option<DateTime<Utc>>,
 
     // Requirements / misc
+    #[validate(custom = "validate_age_restriction")]
     pub age_restriction: Option<String>,
+    #[validate(custom = "validate_dress_code")]
     pub dress_code: Option<String>,
+    #[validate(custom = "validate_special_instructions")]
     pub special_instructions: Option<String>,
 
     // Extensibility
@@ -70,6 +111,33 @@ pub struct EventRequestBody {
     pub x_platform: Option<String>,
 }
 
+/// Cross-field checks for `EventRequestBody`
+fn validate_event_request_body(body: &EventRequestBody) -> Result<(), ValidationError> {
+    validate_event_dates(&body.start_time, &body.end_time)?;
+
+    if let Some(price) = body.price {
+        validate_event_price(price)?;
+    }
+
+    if let Some(max_attendees) = body.max_attendees {
+        validate_max_attendees(max_attendees)?;
+    }
+
+    if let Some(tags) = &body.tags {
+        validate_event_tags(tags)?;
+    }
+
+    if let Some(deadline) = &body.registration_deadline {
+        validate_registration_deadline(deadline, &body.start_time)?;
+    }
+
+    if body.latitude.is_some() != body.longitude.is_some() {
+        return Err(ValidationError::new("latitude_and_longitude_required_together"));
+    }
+
+    Ok(())
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
 pub struct EventRequestFilters {
     // Search / basic
@@ -107,10 +175,19 @@ pub struct EventRequestFilters {
     pub has_tags: Option<bool>,
 
     // Sorting
+    #[validate(custom = "validate_event_sort_by")]
     pub sort_by: Option<String>,   // "created_at", "event_date", "title", "price", "max_attendees"
+    #[validate(custom = "validate_sort_order")]
     pub sort_order: Option<String>,// "asc", "desc"
 
+    // Pagination
+    #[validate(range(min = 1))]
+    pub page: Option<i64>,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+
     // User-specific
+    #[validate(custom = "str_mobgo_object_special_chars")]
     pub user_id: Option<String>,
     pub created_by: Option<String>,
 
@@ -123,60 +200,127 @@ pub struct EventRequestFilters {
     pub x_platform: Option<String>,
 }
 
-/// Join Event Request Body
+/// Fields events can be sorted by in list endpoints
+pub const EVENT_SORT_FIELDS: [&str; 6] = ["created_at", "event_date", "start_time", "title", "price", "max_attendees"];
+
+fn validate_event_sort_by(sort_by: &str) -> Result<(), ValidationError> {
+    if !EVENT_SORT_FIELDS.contains(&sort_by) {
+        return Err(ValidationError::new("invalid_sort_by"));
+    }
+    Ok(())
+}
+
+fn validate_sort_order(sort_order: &str) -> Result<(), ValidationError> {
+    if sort_order != "asc" && sort_order != "desc" {
+        return Err(ValidationError::new("invalid_sort_order"));
+    }
+    Ok(())
+}
+
+/// Join Event Request Body; the attendee is the signed-in user
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
 pub struct JoinEventRequest {
-    pub user_id: Option<String>,
     pub event_id: Option<String>,
+    #[validate(custom = "str_max_1000_chars")]
     pub notes: Option<String>,
+    #[validate(custom = "str_max_100_chars")]
     pub emergency_contact: Option<String>,
+    #[validate(custom = "str_max_500_chars")]
     pub dietary_requirements: Option<String>,
+    #[validate(custom = "str_max_500_chars")]
     pub accessibility_needs: Option<String>,
 }
 
 
-/// Leave Event Request Body
+/// Leave Event Request Body; the attendee is the signed-in user
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
 pub struct LeaveEventRequest {
+    #[validate(custom = "str_max_500_chars")]
     pub reason: Option<String>,
 }
 
 /// Event Search Request (advanced)
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
+#[validate(schema(function = "validate_event_search_request"))]
 pub struct EventSearchRequest {
     // Text
+    #[validate(custom = "str_max_100_chars")]
     pub search_term: Option<String>,
 
     // Geo
+    #[validate(range(min = -90.0, max = 90.0))]
     pub latitude: Option<f64>,
+    #[validate(range(min = -180.0, max = 180.0))]
     pub longitude: Option<f64>,
+    #[validate(range(min = 0.1, max = 20000.0))]
     pub radius_km: Option<f64>,
 
     // Time
+    #[validate(custom = "validate_date_range")]
     pub date_range: Option<String>, // "today", "tomorrow", "this_week", "this_month", "custom"
     pub start_date: Option<String>,
     pub end_date: Option<String>,
 
     // Advanced filters
+    #[validate]
     pub filters: Option<EventRequestFilters>,
 
     // Pagination
+    #[validate(range(min = 1))]
     pub page: Option<u32>,
+    #[validate(range(min = 1, max = 100))]
     pub limit: Option<u32>,
 
     // Sorting
-    pub sort_by: Option<String>,
+    #[validate(custom = "validate_search_sort_by")]
+    pub sort_by: Option<String>,   // any of EVENT_SORT_FIELDS, or "distance" for geo searches
+    #[validate(custom = "validate_sort_order")]
     pub sort_order: Option<String>,
 }
 
+fn validate_date_range(date_range: &str) -> Result<(), ValidationError> {
+    if date_range != "custom" && !DATE_RANGE_PRESETS.contains(&date_range) {
+        return Err(ValidationError::new("invalid_date_range"));
+    }
+    Ok(())
+}
+
+fn validate_search_sort_by(sort_by: &str) -> Result<(), ValidationError> {
+    if sort_by == "distance" {
+        return Ok(());
+    }
+    validate_event_sort_by(sort_by)
+}
+
+/// Cross-field checks for `EventSearchRequest`
+fn validate_event_search_request(request: &EventSearchRequest) -> Result<(), ValidationError> {
+    let has_point = request.latitude.is_some() && request.longitude.is_some();
+    if request.latitude.is_some() != request.longitude.is_some() {
+        return Err(ValidationError::new("latitude_and_longitude_required_together"));
+    }
+    if request.radius_km.is_some() && !has_point {
+        return Err(ValidationError::new("radius_requires_coordinates"));
+    }
+    if request.sort_by.as_deref() == Some("distance") && !has_point {
+        return Err(ValidationError::new("distance_sort_requires_coordinates"));
+    }
+    if request.date_range.as_deref() == Some("custom") && request.start_date.is_none() && request.end_date.is_none() {
+        return Err(ValidationError::new("custom_date_range_requires_dates"));
+    }
+    Ok(())
+}
+
 /// Event Analytics Request
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
 pub struct EventAnalyticsRequest {
+    #[validate(custom = "validate_analytics_period")]
     pub period: Option<String>, // "day", "week", "month", "quarter", "year"
     pub start_date: Option<String>,
     pub end_date: Option<String>,
 
+    #[validate(custom = "validate_object_id_list")]
     pub event_ids: Option<Vec<String>>,
+    #[validate(custom = "str_mobgo_object_special_chars")]
     pub organizer_id: Option<String>,
     pub category: Option<EventCategoryEnum>,
 
@@ -186,21 +330,37 @@ pub struct EventAnalyticsRequest {
     pub include_geographic_stats: Option<bool>,
 }
 
+/// Buckets supported by analytics time series
+pub const ANALYTICS_PERIODS: [&str; 5] = ["day", "week", "month", "quarter", "year"];
+
+fn validate_analytics_period(period: &str) -> Result<(), ValidationError> {
+    if !ANALYTICS_PERIODS.contains(&period) {
+        return Err(ValidationError::new("invalid_period"));
+    }
+    Ok(())
+}
+
 /// Event Invitation Request
 #[derive(Default, Serialize, Deserialize, Debug, Validate)]
+#[validate(schema(function = "validate_event_invitation_request"))]
 pub struct EventInvitationRequest {
+    #[validate(custom = "str_mobgo_object_special_chars")]
     pub event_id: String,
 
     // Recipients
+    #[validate(custom = "validate_object_id_list")]
     pub user_ids: Option<Vec<String>>,
+    #[validate(custom = "validate_email_list")]
     pub emails: Option<Vec<String>>,
 
     // Details
+    #[validate(custom = "str_max_1000_chars")]
     pub message: Option<String>,
+    #[validate(custom = "validate_invite_type")]
     pub invite_type: Option<String>, // "direct", "bulk", "public"
 
     // Settings
-    pub expires_at: Option<String>,
+    pub expires_at: Option<String>, // RFC3339 or YYYY-MM-DD
     pub requires_response: Option<bool>,
     pub allow_plus_one: Option<bool>,
 
@@ -208,11 +368,44 @@ pub struct EventInvitationRequest {
     pub x_platform: Option<String>,
 }
 
+fn validate_event_invitation_request(body: &EventInvitationRequest) -> Result<(), ValidationError> {
+    let has_users = body.user_ids.as_ref().is_some_and(|ids| !ids.is_empty());
+    let has_emails = body.emails.as_ref().is_some_and(|emails| !emails.is_empty());
+    if !has_users && !has_emails {
+        return Err(ValidationError::new("invitation_recipients_required"));
+    }
+    Ok(())
+}
+
+fn validate_object_id_list(ids: &[String]) -> Result<(), ValidationError> {
+    ids.iter().try_for_each(|id| str_mobgo_object_special_chars(id))
+}
+
+fn validate_email_list(emails: &[String]) -> Result<(), ValidationError> {
+    emails.iter().try_for_each(|email| validate_email(email))
+}
+
+fn validate_invite_type(invite_type: &str) -> Result<(), ValidationError> {
+    if !["direct", "bulk", "public"].contains(&invite_type) {
+        return Err(ValidationError::new("invalid_invite_type"));
+    }
+    Ok(())
+}
+
+/// Accept Invitation Request Body; the recipient is the signed-in user
+#[derive(Default, Serialize, Deserialize, Debug, Validate)]
+pub struct InvitationResponseRequest {
+    pub plus_one: Option<bool>,
+    #[validate(custom = "str_max_100_chars")]
+    pub plus_one_name: Option<String>,
+}
+
 #[derive(Deserialize)]
 pub struct EventPathInfo {
     pub id: String,
 }
 
+/// `id` is the invitation token shared with the recipient
 #[derive(Deserialize)]
 pub struct InvitationPathInfo {
     pub id: String,
@@ -1,2 +1,6 @@
 pub mod open_structure;
 pub mod event_structure;
+pub mod search_structure;
+pub mod user_structure;
+pub mod contact_structure;
+pub mod admin_user_structure;
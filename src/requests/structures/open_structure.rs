@@ -34,6 +34,7 @@ use crate::requests::{
         str_mobgo_object_special_chars,
     }
 };
+use crate::libs::custom_validators::validate_identifier;
 
 
 
@@ -277,8 +278,9 @@ pub struct OAuthCustomRequestStruct {
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Validate)]
 pub struct OTPRequest {
-    #[validate(custom = "str_no_special_chars")]
-    #[validate(custom = "str_max_32_chars")]
+    // An email address or a phone number
+    #[validate(custom = "validate_identifier")]
+    #[validate(length(max = 254))]
     pub identifier: String,
 
     #[validate(custom = "string_no_special_chars")]
@@ -288,8 +290,8 @@ pub struct OTPRequest {
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Validate)]
 pub struct VerifyOTP {
-    #[validate(custom = "str_no_special_chars")]
-    #[validate(custom = "str_max_32_chars")]
+    #[validate(custom = "validate_identifier")]
+    #[validate(length(max = 254))]
     pub identifier: String,
 
     #[validate(custom = "str_otp_special_chars")]
@@ -302,6 +304,14 @@ pub struct VerifyOTP {
 }
 
 
+#[derive(Default, Serialize, Deserialize, Debug, Clone, Validate)]
+pub struct RefreshTokenRequest {
+    #[validate(custom = "str_no_special_chars")]
+    #[validate(custom = "str_max_500_chars")]
+    pub refresh_token: String,
+}
+
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Validate)]
 pub struct UserPath {
     #[validate(custom = "str_mobgo_object_special_chars")]
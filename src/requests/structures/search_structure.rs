@@ -0,0 +1,42 @@
+// src/requests/structures/search_structure.rs
+
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
+
+/// Collections the unified search can be scoped to
+pub const SEARCH_COLLECTIONS: [&str; 3] = ["events", "contacts", "users"];
+
+/// Unified full-text search query
+#[derive(Default, Serialize, Deserialize, Debug, Validate)]
+pub struct SearchRequest {
+    #[validate(required, length(min = 2, max = 100))]
+    pub query: Option<String>,
+
+    // Comma separated subset of SEARCH_COLLECTIONS, all when omitted
+    #[validate(custom = "validate_search_collections")]
+    pub collections: Option<String>,
+
+    // Results per collection
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<i64>,
+}
+
+impl SearchRequest {
+    pub fn collections(&self) -> Vec<&str> {
+        match self.collections.as_deref().filter(|c| !c.trim().is_empty()) {
+            Some(collections) => collections.split(',').map(str::trim).filter(|c| !c.is_empty()).collect(),
+            None => SEARCH_COLLECTIONS.to_vec(),
+        }
+    }
+}
+
+fn validate_search_collections(collections: &str) -> Result<(), ValidationError> {
+    let all_known = collections.split(',')
+        .map(str::trim)
+        .filter(|c| !c.is_empty())
+        .all(|c| SEARCH_COLLECTIONS.contains(&c));
+    if !all_known {
+        return Err(ValidationError::new("invalid_collections"));
+    }
+    Ok(())
+}
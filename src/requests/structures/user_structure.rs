@@ -0,0 +1,87 @@
+// src/requests/structures/user_structure.rs
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use validator::{Validate, ValidationError};
+
+use crate::enums::common_enums::BanTypeEnum;
+
+/// Payload of the AdminX `ban` action on users
+#[derive(Serialize, Deserialize, Debug, Validate)]
+#[validate(schema(function = "validate_ban_request"))]
+pub struct BanUserRequest {
+    #[validate(required, length(min = 3, max = 500))]
+    pub reason: Option<String>,
+
+    pub ban_type: Option<BanTypeEnum>,
+
+    // Days, required for temporary bans
+    #[serde(default, deserialize_with = "deserialize_optional_days")]
+    #[validate(range(min = 1, max = 3650))]
+    pub duration: Option<i64>,
+}
+
+impl BanUserRequest {
+    pub fn ban_type(&self) -> BanTypeEnum {
+        self.ban_type.clone().unwrap_or(BanTypeEnum::Temporary)
+    }
+}
+
+/// AdminX forms post a blank number input as `""` and may send the number as text
+fn deserialize_optional_days<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) if s.trim().is_empty() => Ok(None),
+        Some(Value::String(s)) => s.trim().parse().map(Some)
+            .map_err(|_| serde::de::Error::custom(format!("Invalid duration: {}", s))),
+        Some(Value::Number(n)) => n.as_i64().map(Some)
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid duration: {}", n))),
+        Some(other) => Err(serde::de::Error::custom(format!("Invalid duration: {}", other))),
+    }
+}
+
+fn validate_ban_request(request: &BanUserRequest) -> Result<(), ValidationError> {
+    if request.ban_type() == BanTypeEnum::Temporary && request.duration.is_none() {
+        return Err(ValidationError::new("duration_required_for_temporary_ban"));
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(body: Value) -> BanUserRequest {
+        serde_json::from_value(body).expect("ban payload should deserialize")
+    }
+
+    #[test]
+    fn blank_duration_is_a_permanent_ban() {
+        let request = parse(json!({ "reason": "Spamming attendees", "ban_type": "permanent", "duration": "" }));
+        assert_eq!(request.duration, None);
+        assert!(request.validate().is_ok());
+    }
+
+    #[test]
+    fn duration_may_be_a_number_or_numeric_text() {
+        assert_eq!(parse(json!({ "reason": "Abuse", "duration": 7 })).duration, Some(7));
+        assert_eq!(parse(json!({ "reason": "Abuse", "duration": " 30 " })).duration, Some(30));
+        assert_eq!(parse(json!({ "reason": "Abuse" })).duration, None);
+    }
+
+    #[test]
+    fn non_numeric_duration_is_rejected() {
+        assert!(serde_json::from_value::<BanUserRequest>(json!({ "reason": "Abuse", "duration": "a week" })).is_err());
+    }
+
+    #[test]
+    fn temporary_ban_still_needs_a_duration() {
+        let request = parse(json!({ "reason": "Spamming attendees", "ban_type": "temporary", "duration": "" }));
+        assert!(request.validate().is_err());
+    }
+}
@@ -0,0 +1,26 @@
+// src/routes/auth_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::auth_controller::{
+    request_otp,
+    verify_otp,
+    refresh_token,
+    get_me,
+    logout,
+    logout_all,
+};
+use crate::middlewares::auth_middleware::require_auth;
+
+pub fn auth_routes() -> Scope {
+    web::scope("/auth")
+        .route("/otp", web::post().to(request_otp))
+        .route("/otp/verify", web::post().to(verify_otp))
+        .route("/refresh", web::post().to(refresh_token))
+        .service(
+            web::scope("")
+                .wrap(require_auth())
+                .route("/me", web::get().to(get_me))
+                .route("/logout", web::post().to(logout))
+                .route("/logout/all", web::post().to(logout_all))
+        )
+}
@@ -0,0 +1,18 @@
+// src/routes/contact_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::contact_controller::{
+    get_contact_vcard,
+    scan_qr_code,
+};
+use crate::middlewares::auth_middleware::require_auth;
+
+pub fn contact_routes() -> Scope {
+    web::scope("/contacts")
+        .service(
+            web::scope("")
+                .wrap(require_auth())
+                .route("/scan", web::post().to(scan_qr_code))
+                .route("/{id}/vcard", web::get().to(get_contact_vcard))
+        )
+}
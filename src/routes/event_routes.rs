@@ -0,0 +1,32 @@
+// src/routes/event_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::event_controller::{
+    create_event,
+    get_event,
+    list_events,
+    update_event,
+    delete_event,
+    join_event,
+    leave_event,
+    event_analytics,
+    search_events,
+    get_event_qr,
+};
+use crate::middlewares::auth_middleware::require_auth;
+
+/// Browsing events and their QR codes is public, so shared links open without an
+/// account; everything that changes an event or a registration needs a signed-in user.
+pub fn event_routes() -> Scope {
+    web::scope("/events")
+        .route("", web::post().to(create_event).wrap(require_auth()))
+        .route("", web::get().to(list_events))
+        .route("/search", web::post().to(search_events))
+        .route("/analytics", web::post().to(event_analytics).wrap(require_auth()))
+        .route("/{id}", web::get().to(get_event))
+        .route("/{id}", web::put().to(update_event).wrap(require_auth()))
+        .route("/{id}", web::delete().to(delete_event).wrap(require_auth()))
+        .route("/{id}/join", web::post().to(join_event).wrap(require_auth()))
+        .route("/{id}/leave", web::post().to(leave_event).wrap(require_auth()))
+        .route("/{id}/qr", web::get().to(get_event_qr))
+}
@@ -0,0 +1,20 @@
+// src/routes/invitation_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::invitation_controller::{
+    create_invitations,
+    get_invitation,
+    accept_invitation,
+    decline_invitation,
+};
+use crate::middlewares::auth_middleware::require_auth;
+
+/// Looking an invitation up stays public: the token is the secret the recipient was
+/// sent, and the app shows the invitation before asking them to sign in.
+pub fn invitation_routes() -> Scope {
+    web::scope("/invitations")
+        .route("", web::post().to(create_invitations).wrap(require_auth()))
+        .route("/{id}", web::get().to(get_invitation))
+        .route("/{id}/accept", web::post().to(accept_invitation).wrap(require_auth()))
+        .route("/{id}/decline", web::post().to(decline_invitation).wrap(require_auth()))
+}
@@ -0,0 +1,22 @@
+// src/routes/mod.rs
+pub mod auth_routes;
+pub mod contact_routes;
+pub mod event_routes;
+pub mod invitation_routes;
+pub mod search_routes;
+pub mod support_routes;
+pub mod user_routes;
+
+use actix_web::{web, Scope};
+
+/// Versioned public API consumed by the mobile clients
+pub fn api_v1_routes() -> Scope {
+    web::scope("/api/v1")
+        .service(auth_routes::auth_routes())
+        .service(event_routes::event_routes())
+        .service(invitation_routes::invitation_routes())
+        .service(search_routes::search_routes())
+        .service(contact_routes::contact_routes())
+        .service(user_routes::user_routes())
+        .service(support_routes::support_routes())
+}
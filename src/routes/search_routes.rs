@@ -0,0 +1,9 @@
+// src/routes/search_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::search_controller::search;
+
+pub fn search_routes() -> Scope {
+    web::scope("/search")
+        .route("", web::get().to(search))
+}
@@ -0,0 +1,9 @@
+// src/routes/support_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::support_controller::create_support_ticket;
+
+pub fn support_routes() -> Scope {
+    web::scope("/support")
+        .route("", web::post().to(create_support_ticket))
+}
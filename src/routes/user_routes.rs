@@ -0,0 +1,18 @@
+// src/routes/user_routes.rs
+
+use actix_web::{web, Scope};
+use crate::controllers::user_controller::{
+    get_user_vcard,
+    get_user_qr,
+};
+use crate::middlewares::auth_middleware::require_auth;
+
+pub fn user_routes() -> Scope {
+    web::scope("/users")
+        .service(
+            web::scope("")
+                .wrap(require_auth())
+                .route("/{user_id}/vcard", web::get().to(get_user_vcard))
+                .route("/{user_id}/qr", web::get().to(get_user_qr))
+        )
+}
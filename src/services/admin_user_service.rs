@@ -0,0 +1,247 @@
+// services/admin_user_service.rs
+//
+// AdminX operators. Passwords are argon2id hashes in `admin_users`; signing in checks
+// them, then the TOTP step of two_factor_service. Superadmins manage operators: they
+// create them, assign roles, deactivate them and reset passwords or authenticators.
+// An admin may only rename themselves. The last active superadmin cannot be demoted,
+// deactivated or deleted, so the panel can never lock itself out.
+
+use actix_web::{web, Error};
+use log::{info, warn};
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+
+use crate::config::env_vars::get_custom_env;
+use crate::enums::common_enums::{AdminRoleEnum, AdminStatusEnum};
+use crate::models::admin_user::AdminUser;
+use crate::repositories::Repositories;
+use crate::requests::structures::admin_user_structure::{
+    ChangeAdminPasswordRequest,
+    CreateAdminUserRequest,
+    UpdateAdminUserRequest,
+};
+use crate::requests::validators::open_validator::validate_params;
+use crate::utilities::password_utility::{hash_password, verify_dummy_password, verify_password};
+use crate::custom_error_expression;
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Argon2 is slow on purpose, so it runs on the blocking pool
+async fn hash_blocking(password: String) -> Result<String, Error> {
+    // actix errors are not Send, so the error crosses the thread as text
+    web::block(move || hash_password(&password).map_err(|e| e.to_string())).await
+        .map_err(|e| e.to_string())
+        .and_then(|hashed| hashed)
+        .map_err(|e| custom_error_expression!(internal_error, 500, e).into())
+}
+
+async fn verify_blocking(password: String, password_hash: Option<String>) -> Result<bool, Error> {
+    web::block(move || match password_hash {
+        Some(password_hash) => verify_password(&password, &password_hash),
+        None => {
+            verify_dummy_password(&password);
+            false
+        }
+    }).await
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+}
+
+async fn find(repos: &Repositories, id: ObjectId) -> Result<AdminUser, Error> {
+    repos.admin_users.find_by_id(id).await?
+        .ok_or_else(|| custom_error_expression!(not_found, 404, "Admin user not found").into())
+}
+
+/// The operator behind an AdminX session, as stored now: a role or status changed
+/// since the session started applies right away
+pub async fn acting_admin(repos: &Repositories, admin_id: &str) -> Result<AdminUser, Error> {
+    let admin = match ObjectId::parse_str(admin_id) {
+        Ok(id) => repos.admin_users.find_by_id(id).await?,
+        Err(_) => None,
+    };
+    admin.filter(AdminUser::is_active)
+        .ok_or_else(|| custom_error_expression!(unauthorized, 401, "Not signed in as an admin user").into())
+}
+
+fn ensure_superadmin(actor: &AdminUser, action: &str) -> Result<(), Error> {
+    if actor.role != AdminRoleEnum::Superadmin {
+        return Err(custom_error_expression!(forbidden, 403, format!("Only superadmins can {}", action)).into());
+    }
+    Ok(())
+}
+
+/// Refuses to take away the last active superadmin
+async fn ensure_not_last_superadmin(repos: &Repositories, target: &AdminUser) -> Result<(), Error> {
+    let is_superadmin = target.role == AdminRoleEnum::Superadmin && target.is_active();
+    if is_superadmin && repos.admin_users.count_active_superadmins().await? <= 1 {
+        return Err(custom_error_expression!(conflict, 409, "At least one active superadmin has to remain").into());
+    }
+    Ok(())
+}
+
+
+/*----- START SIGN-IN -----*/
+/// The active operator with this email and password. A wrong email costs as much time
+/// as a wrong password.
+pub async fn authenticate(repos: &Repositories, email: &str, password: &str) -> Result<Option<AdminUser>, Error> {
+    let admin = repos.admin_users.find_by_email(&normalize_email(email)).await?;
+    let password_hash = admin.as_ref().map(|admin| admin.password_hash.clone());
+    if !verify_blocking(password.to_string(), password_hash).await? {
+        return Ok(None);
+    }
+    Ok(admin.filter(AdminUser::is_active))
+}
+
+pub async fn record_login(repos: &Repositories, admin_id: ObjectId) -> Result<(), Error> {
+    repos.admin_users.record_login(admin_id).await
+}
+
+/// Creates a superadmin from `ADMIN_BOOTSTRAP_EMAIL` and `ADMIN_BOOTSTRAP_PASSWORD` when
+/// there is no active one, so a fresh install can be signed in to. The password has to
+/// be changed on first sign-in.
+pub async fn bootstrap_superadmin(repos: &Repositories) -> Result<(), Error> {
+    if repos.admin_users.count_active_superadmins().await? > 0 {
+        return Ok(());
+    }
+
+    let email = normalize_email(&get_custom_env("ADMIN_BOOTSTRAP_EMAIL", ""));
+    let password = get_custom_env("ADMIN_BOOTSTRAP_PASSWORD", "");
+    if email.is_empty() || password.is_empty() {
+        warn!("No active superadmin; set ADMIN_BOOTSTRAP_EMAIL and ADMIN_BOOTSTRAP_PASSWORD to create one");
+        return Ok(());
+    }
+    if repos.admin_users.find_by_email(&email).await?.is_some() {
+        warn!("No active superadmin, and the bootstrap admin {} already exists; not touching it", email);
+        return Ok(());
+    }
+
+    let request = CreateAdminUserRequest {
+        name: "Superadmin".to_string(),
+        email,
+        role: AdminRoleEnum::Superadmin,
+        status: Some(AdminStatusEnum::Active),
+        password,
+    };
+    validate_params(&request).await?;
+    let admin = insert(repos, request).await?;
+    info!("Created bootstrap superadmin {}", admin.email);
+    Ok(())
+}
+/*----- END SIGN-IN -----*/
+
+
+/*----- START MANAGEMENT -----*/
+async fn insert(repos: &Repositories, request: CreateAdminUserRequest) -> Result<AdminUser, Error> {
+    let password_hash = hash_blocking(request.password).await?;
+    let now = BsonDateTime::now();
+    let admin = AdminUser {
+        id: None,
+        name: request.name.trim().to_string(),
+        email: normalize_email(&request.email),
+        role: request.role,
+        status: request.status.unwrap_or(AdminStatusEnum::Active),
+        password_hash,
+        // The creator knows it, so the operator replaces it on first sign-in
+        must_change_password: true,
+        password_changed_at: Some(now),
+        last_login_at: None,
+        created_at: now,
+        updated_at: now,
+    };
+    repos.admin_users.insert(&admin).await
+}
+
+pub async fn create(repos: &Repositories, actor: &AdminUser, request: CreateAdminUserRequest) -> Result<AdminUser, Error> {
+    ensure_superadmin(actor, "add admin users")?;
+    validate_params(&request).await?;
+    let admin = insert(repos, request).await?;
+    info!("Admin user {} ({}) added by {}", admin.email, admin.role.as_str(), actor.email);
+    Ok(admin)
+}
+
+pub async fn update(repos: &Repositories, actor: &AdminUser, id: ObjectId, request: UpdateAdminUserRequest) -> Result<(), Error> {
+    validate_params(&request).await?;
+    let target = find(repos, id).await?;
+    let is_self = actor.id == Some(id);
+
+    let role = request.role.filter(|role| *role != target.role);
+    let status = request.status.filter(|status| *status != target.status);
+    if role.is_some() {
+        ensure_superadmin(actor, "assign roles")?;
+    }
+    if status.is_some() {
+        ensure_superadmin(actor, "activate or deactivate admin users")?;
+    }
+    if !is_self {
+        ensure_superadmin(actor, "edit other admin users")?;
+    }
+    if request.password.is_some() {
+        if is_self {
+            return Err(custom_error_expression!(bad_request, 400, "Change your own password from the account page").into());
+        }
+        ensure_superadmin(actor, "reset passwords")?;
+    }
+    if role.is_some() || status.is_some() {
+        ensure_not_last_superadmin(repos, &target).await?;
+    }
+
+    let name = request.name.as_deref().map(str::trim);
+    if name.is_some() || role.is_some() || status.is_some() {
+        repos.admin_users.update_account(id, name, role, status).await?;
+    }
+    if let Some(password) = request.password {
+        let password_hash = hash_blocking(password).await?;
+        repos.admin_users.set_password(id, &password_hash, true).await?;
+        info!("Password of admin user {} reset by {}", target.email, actor.email);
+    }
+    if let Some(role) = role {
+        info!("Admin user {} is now {} (by {})", target.email, role.as_str(), actor.email);
+    }
+    Ok(())
+}
+
+pub async fn delete(repos: &Repositories, actor: &AdminUser, id: ObjectId) -> Result<(), Error> {
+    ensure_superadmin(actor, "remove admin users")?;
+    if actor.id == Some(id) {
+        return Err(custom_error_expression!(bad_request, 400, "You cannot remove yourself").into());
+    }
+    let target = find(repos, id).await?;
+    ensure_not_last_superadmin(repos, &target).await?;
+
+    repos.admin_users.delete(id).await?;
+    repos.two_factors.delete_by_email(&target.email).await?;
+    info!("Admin user {} removed by {}", target.email, actor.email);
+    Ok(())
+}
+
+/// Drops the operator's authenticator; the next sign-in enrols a new one
+pub async fn reset_two_factor(repos: &Repositories, actor: &AdminUser, id: ObjectId) -> Result<bool, Error> {
+    ensure_superadmin(actor, "reset two-factor authentication")?;
+    let target = find(repos, id).await?;
+    let reset = repos.two_factors.delete_by_email(&target.email).await?;
+    info!("Two-factor authentication of {} reset by {}", target.email, actor.email);
+    Ok(reset)
+}
+/*----- END MANAGEMENT -----*/
+
+
+/*----- START ACCOUNT -----*/
+/// The signed-in operator's own password change, which needs the current password
+pub async fn change_password(repos: &Repositories, actor: &AdminUser, request: ChangeAdminPasswordRequest) -> Result<(), Error> {
+    validate_params(&request).await?;
+    let id = actor.id
+        .ok_or_else(|| Error::from(custom_error_expression!(internal_error, 500, "Admin user has no id")))?;
+
+    if !verify_blocking(request.current_password.clone(), Some(actor.password_hash.clone())).await? {
+        return Err(custom_error_expression!(unauthorized, 401, "Current password is wrong").into());
+    }
+    if request.new_password == request.current_password {
+        return Err(custom_error_expression!(bad_request, 400, "Choose a password different from the current one").into());
+    }
+
+    let password_hash = hash_blocking(request.new_password).await?;
+    repos.admin_users.set_password(id, &password_hash, false).await?;
+    info!("Admin user {} changed their password", actor.email);
+    Ok(())
+}
+/*----- END ACCOUNT -----*/
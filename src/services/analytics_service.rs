@@ -0,0 +1,303 @@
+// services/analytics_service.rs
+
+use std::collections::BTreeMap;
+
+use actix_web::Error;
+use futures::TryStreamExt;
+use mongodb::{
+    Database,
+    bson::{doc, Bson, DateTime as BsonDateTime, Document},
+};
+use serde_json::{json, Value};
+
+use crate::libs::general_library::parse_date_param;
+use crate::requests::structures::event_structure::EventAnalyticsRequest;
+use crate::utilities::bason_utility::{convert_to_bson, parse_object_id};
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+
+/*----- START HELPERS -----*/
+/// Reads a numeric aggregation result regardless of the BSON width Mongo picked
+fn number(doc: &Document, key: &str) -> f64 {
+    match doc.get(key) {
+        Some(Bson::Int32(v)) => *v as f64,
+        Some(Bson::Int64(v)) => *v as f64,
+        Some(Bson::Double(v)) => *v,
+        _ => 0.0,
+    }
+}
+
+fn rate(part: f64, total: f64) -> f64 {
+    if total > 0.0 { (part / total * 10000.0).round() / 100.0 } else { 0.0 }
+}
+
+/// `$match` on the joined `event` document, shared by every attendee pipeline
+fn event_match(request: &EventAnalyticsRequest, prefix: &str) -> Result<Document, Error> {
+    let mut filter = doc! { format!("{prefix}deleted"): false };
+
+    if let Some(event_ids) = request.event_ids.as_ref().filter(|ids| !ids.is_empty()) {
+        let event_ids = event_ids.iter()
+            .map(|id| parse_object_id(id, "event_ids"))
+            .collect::<Result<Vec<_>, Error>>()?;
+        filter.insert(format!("{prefix}_id"), doc! { "$in": event_ids });
+    }
+
+    if let Some(organizer_id) = &request.organizer_id {
+        filter.insert(format!("{prefix}user_id"), parse_object_id(organizer_id, "organizer_id")?);
+    }
+
+    if let Some(category) = &request.category {
+        filter.insert(format!("{prefix}category"), convert_to_bson(category)?);
+    }
+
+    Ok(filter)
+}
+
+/// Date window applied to a timestamp field
+fn date_window(request: &EventAnalyticsRequest, field: &str) -> Result<Document, Error> {
+    let mut range = Document::new();
+    if let Some(start) = &request.start_date {
+        match parse_date_param(start, false) {
+            Some(dt) => { range.insert("$gte", dt); }
+            None => handle_custom_error!(bad_request, 400, "Invalid start_date"),
+        }
+    }
+    if let Some(end) = &request.end_date {
+        match parse_date_param(end, true) {
+            Some(dt) => { range.insert("$lte", dt); }
+            None => handle_custom_error!(bad_request, 400, "Invalid end_date"),
+        }
+    }
+
+    Ok(if range.is_empty() { Document::new() } else { doc! { field: range } })
+}
+
+async fn aggregate(db: &Database, collection: &str, pipeline: Vec<Document>) -> Result<Vec<Document>, Error> {
+    db.collection::<Document>(collection)
+        .aggregate(pipeline, None).await
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+        .try_collect().await
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+}
+/*----- END HELPERS -----*/
+
+
+/*----- START ANALYTICS -----*/
+/// Builds the analytics report: registrations over time, check-in / no-show rates,
+/// revenue and a per-category breakdown, plus the optional engagement and geographic
+/// sections. Registrations are attributed to the period they were created in.
+pub async fn event_analytics(db: &Database, request: &EventAnalyticsRequest) -> Result<Value, Error> {
+    let period = request.period.clone().unwrap_or_else(|| "month".to_string());
+    let include_attendee_stats = request.include_attendee_stats.unwrap_or(true);
+    let include_revenue_stats = request.include_revenue_stats.unwrap_or(true);
+    let include_engagement_stats = request.include_engagement_stats.unwrap_or(false);
+    let include_geographic_stats = request.include_geographic_stats.unwrap_or(false);
+
+    let now = BsonDateTime::now();
+    let confirmed = doc! { "deleted": { "$ne": true }, "registration_status": "active" };
+
+    let mut pipeline = Vec::new();
+    let registration_window = date_window(request, "created_at")?;
+    if !registration_window.is_empty() {
+        pipeline.push(doc! { "$match": registration_window });
+    }
+    pipeline.extend([
+        doc! { "$lookup": { "from": "events", "localField": "event_id", "foreignField": "_id", "as": "event" } },
+        doc! { "$unwind": "$event" },
+        doc! { "$match": event_match(request, "event.")? },
+    ]);
+
+    let mut facets = doc! {
+        "timeline": [
+            { "$group": {
+                "_id": { "$dateTrunc": { "date": "$created_at", "unit": &period } },
+                "registrations": { "$sum": 1 },
+                "cancellations": { "$sum": { "$cond": [{ "$eq": ["$deleted", true] }, 1, 0] } },
+                "check_ins": { "$sum": { "$cond": [{ "$ifNull": ["$check_in_date", false] }, 1, 0] } }
+            } },
+            { "$sort": { "_id": 1 } }
+        ],
+        "categories": [
+            { "$match": &confirmed },
+            { "$group": {
+                "_id": "$event.category",
+                "registrations": { "$sum": 1 },
+                "check_ins": { "$sum": { "$cond": [{ "$ifNull": ["$check_in_date", false] }, 1, 0] } },
+                "revenue": { "$sum": { "$cond": ["$payment_status", { "$ifNull": ["$payment_amount", 0] }, 0] } }
+            } }
+        ]
+    };
+
+    if include_attendee_stats {
+        facets.insert("attendance", vec![
+            doc! { "$match": &confirmed },
+            doc! { "$group": {
+                "_id": Bson::Null,
+                "registered": { "$sum": 1 },
+                "checked_in": { "$sum": { "$cond": [{ "$ifNull": ["$check_in_date", false] }, 1, 0] } },
+                // Only events that already ended can produce no-shows
+                "ended_registrations": { "$sum": { "$cond": [{ "$lt": ["$event.end_time", now] }, 1, 0] } },
+                "no_shows": { "$sum": { "$cond": [
+                    { "$and": [
+                        { "$lt": ["$event.end_time", now] },
+                        { "$not": [{ "$ifNull": ["$check_in_date", false] }] }
+                    ] }, 1, 0
+                ] } }
+            } }
+        ]);
+    }
+
+    if include_revenue_stats {
+        facets.insert("revenue", vec![
+            doc! { "$match": { "deleted": { "$ne": true }, "payment_status": true } },
+            doc! { "$group": {
+                "_id": Bson::Null,
+                "total": { "$sum": { "$ifNull": ["$payment_amount", 0] } },
+                "payments": { "$sum": 1 },
+                "average": { "$avg": "$payment_amount" }
+            } }
+        ]);
+    }
+
+    if include_engagement_stats {
+        facets.insert("engagement", vec![
+            doc! { "$group": {
+                "_id": Bson::Null,
+                "total": { "$sum": 1 },
+                "cancellations": { "$sum": { "$cond": [{ "$eq": ["$deleted", true] }, 1, 0] } },
+                "waitlisted": { "$sum": { "$cond": [{ "$ifNull": ["$waitlisted_at", false] }, 1, 0] } },
+                "promoted": { "$sum": { "$cond": [{ "$ifNull": ["$promoted_at", false] }, 1, 0] } },
+                "checked_out": { "$sum": { "$cond": [{ "$ifNull": ["$check_out_date", false] }, 1, 0] } },
+                "avg_stay_ms": { "$avg": { "$cond": [
+                    { "$and": [{ "$ifNull": ["$check_in_date", false] }, { "$ifNull": ["$check_out_date", false] }] },
+                    { "$subtract": ["$check_out_date", "$check_in_date"] },
+                    Bson::Null
+                ] } }
+            } }
+        ]);
+    }
+
+    if include_geographic_stats {
+        facets.insert("locations", vec![
+            doc! { "$match": &confirmed },
+            doc! { "$group": {
+                "_id": { "$ifNull": ["$event.location", "$event.address"] },
+                "registrations": { "$sum": 1 },
+                "events": { "$addToSet": "$event_id" }
+            } },
+            doc! { "$project": { "registrations": 1, "events": { "$size": "$events" } } },
+            doc! { "$sort": { "registrations": -1 } },
+            doc! { "$limit": 20 }
+        ]);
+    }
+
+    pipeline.push(doc! { "$facet": facets });
+    let result = aggregate(db, "event_attendees", pipeline).await?
+        .into_iter()
+        .next()
+        .unwrap_or_default();
+
+    // Events per category come from `events` so categories without registrations still show up
+    let mut event_pipeline = Vec::new();
+    let mut events_filter = event_match(request, "")?;
+    events_filter.extend(date_window(request, "event_date")?);
+    event_pipeline.push(doc! { "$match": events_filter });
+    event_pipeline.push(doc! { "$group": { "_id": "$category", "events": { "$sum": 1 } } });
+    let events_by_category = aggregate(db, "events", event_pipeline).await?;
+
+    let facet = |name: &str| -> Vec<Document> {
+        result.get_array(name)
+            .map(|items| items.iter().filter_map(|item| item.as_document().cloned()).collect())
+            .unwrap_or_default()
+    };
+    let first = |name: &str| facet(name).into_iter().next().unwrap_or_default();
+
+    let timeline: Vec<Value> = facet("timeline").into_iter()
+        .map(|bucket| json!({
+            "period_start": bucket.get_datetime("_id").ok().and_then(|start| start.try_to_rfc3339_string().ok()),
+            "registrations": number(&bucket, "registrations"),
+            "cancellations": number(&bucket, "cancellations"),
+            "check_ins": number(&bucket, "check_ins"),
+        }))
+        .collect();
+
+    let mut categories: BTreeMap<String, Value> = BTreeMap::new();
+    for row in events_by_category {
+        let category = row.get_str("_id").unwrap_or("Initial").to_string();
+        categories.insert(category.clone(), json!({
+            "category": category,
+            "events": number(&row, "events"),
+            "registrations": 0.0,
+            "check_ins": 0.0,
+            "revenue": 0.0,
+        }));
+    }
+    for row in facet("categories") {
+        let category = row.get_str("_id").unwrap_or("Initial").to_string();
+        let entry = categories.entry(category.clone()).or_insert_with(|| json!({ "category": category, "events": 0.0 }));
+        entry["registrations"] = json!(number(&row, "registrations"));
+        entry["check_ins"] = json!(number(&row, "check_ins"));
+        entry["revenue"] = json!(number(&row, "revenue"));
+    }
+
+    let mut report = json!({
+        "period": period,
+        "start_date": request.start_date,
+        "end_date": request.end_date,
+        "timeline": timeline,
+        "categories": categories.into_values().collect::<Vec<_>>(),
+    });
+
+    if include_attendee_stats {
+        let attendance = first("attendance");
+        let registered = number(&attendance, "registered");
+        let checked_in = number(&attendance, "checked_in");
+        let ended = number(&attendance, "ended_registrations");
+        let no_shows = number(&attendance, "no_shows");
+        report["attendance"] = json!({
+            "registered": registered,
+            "checked_in": checked_in,
+            "check_in_rate": rate(checked_in, registered),
+            "no_shows": no_shows,
+            "no_show_rate": rate(no_shows, ended),
+        });
+    }
+
+    if include_revenue_stats {
+        let revenue = first("revenue");
+        report["revenue"] = json!({
+            "total": number(&revenue, "total"),
+            "payments": number(&revenue, "payments"),
+            "average": number(&revenue, "average"),
+        });
+    }
+
+    if include_engagement_stats {
+        let engagement = first("engagement");
+        let total = number(&engagement, "total");
+        report["engagement"] = json!({
+            "cancellations": number(&engagement, "cancellations"),
+            "cancellation_rate": rate(number(&engagement, "cancellations"), total),
+            "waitlisted": number(&engagement, "waitlisted"),
+            "promoted": number(&engagement, "promoted"),
+            "checked_out": number(&engagement, "checked_out"),
+            "avg_stay_minutes": (number(&engagement, "avg_stay_ms") / 60000.0).round(),
+        });
+    }
+
+    if include_geographic_stats {
+        report["locations"] = json!(facet("locations").into_iter()
+            .map(|row| json!({
+                "location": row.get_str("_id").unwrap_or("Unknown"),
+                "registrations": number(&row, "registrations"),
+                "events": number(&row, "events"),
+            }))
+            .collect::<Vec<_>>());
+    }
+
+    Ok(report)
+}
+/*----- END ANALYTICS -----*/
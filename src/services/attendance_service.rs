@@ -0,0 +1,488 @@
+// services/attendance_service.rs
+
+use actix_web::Error;
+use chrono::Utc;
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+
+use crate::enums::common_enums::StatusEnum;
+use crate::models::{
+    event::Event,
+    event_attendee::EventAttendee,
+    user::User,
+};
+use crate::repositories::Repositories;
+use crate::requests::{
+    enums::event_enums::{
+        EventStatusEnum,
+        RegistrationTypeEnum,
+    },
+    structures::event_structure::{
+        JoinEventRequest,
+        LeaveEventRequest,
+    },
+    validators::event_validators::validate_event_capacity,
+};
+use crate::services::mail_service;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+
+/*----- START JOIN -----*/
+/// Checks that the event currently accepts registrations
+pub fn ensure_event_open(event: &Event) -> Result<(), Error> {
+    if event.locked {
+        handle_custom_error!(forbidden, 403, "Event is locked");
+    }
+
+    if matches!(event.status, EventStatusEnum::Cancelled | EventStatusEnum::Completed) {
+        handle_custom_error!(bad_request, 400, "Event is no longer accepting registrations");
+    }
+
+    // A deadline closes registration whether or not the event requires registering
+    let deadline_passed = event.registration_deadline
+        .is_some_and(|deadline| deadline.to_chrono() <= Utc::now());
+    if deadline_passed {
+        handle_custom_error!(bad_request, 400, "Registration deadline has passed");
+    }
+
+    Ok(())
+}
+
+/// Joins `user_id` to an event through the public API. Invite-only events additionally
+/// require a live invitation, which is marked accepted once the registration exists.
+pub async fn join_event(
+    repos: &Repositories,
+    event_id: ObjectId,
+    user_id: ObjectId,
+    payload: &JoinEventRequest,
+) -> Result<EventAttendee, Error> {
+    let event = repos.events.find_by_id(event_id).await?;
+    ensure_event_open(&event)?;
+
+    let user = repos.users.find_active_by_id(user_id).await?;
+
+    let invitation = if event.registration_type == RegistrationTypeEnum::InviteOnly {
+        match repos.invitations.find_open_for_user(event_id, user_id, user.email.as_deref()).await? {
+            Some(invitation) => Some(invitation),
+            None => handle_custom_error!(forbidden, 403, "This event is invite only"),
+        }
+    } else {
+        None
+    };
+
+    let attendee = register_user(repos, &event, &user, payload, event.registration_type.clone()).await?;
+
+    if let Some(invitation) = invitation.filter(|invitation| invitation.status == StatusEnum::Pending) {
+        repos.invitations.respond(&invitation, StatusEnum::Accepted, Some(user_id), attendee.id, None).await?;
+    }
+    mail_service::notify_registration(repos, &event, &attendee);
+
+    Ok(attendee)
+}
+
+/// Registers a user on an admin's behalf. Capacity and the waitlist apply as for a
+/// join; the invite list does not.
+pub async fn add_attendee(repos: &Repositories, event_id: ObjectId, user_id: ObjectId) -> Result<EventAttendee, Error> {
+    let event = repos.events.find_by_id(event_id).await?;
+    ensure_event_open(&event)?;
+    let user = repos.users.find_active_by_id(user_id).await?;
+
+    let attendee = register_user(repos, &event, &user, &JoinEventRequest::default(), event.registration_type.clone()).await?;
+    mail_service::notify_registration(repos, &event, &attendee);
+    Ok(attendee)
+}
+
+/// Registers a user for an event. The seat on `events` is claimed with a conditional
+/// update first, and given back if the attendee row cannot be written. When the event
+/// is full and runs in waitlist mode the user is queued as `pending` instead.
+pub async fn register_user(
+    repos: &Repositories,
+    event: &Event,
+    user: &User,
+    details: &JoinEventRequest,
+    registration_type: RegistrationTypeEnum,
+) -> Result<EventAttendee, Error> {
+    let (Some(event_id), Some(user_id)) = (event.id, user.id) else {
+        handle_custom_error!(bad_request, 400, "Missing event or user ID");
+    };
+
+    if repos.attendees.find_registration(event_id, user_id).await?.is_some() {
+        handle_custom_error!(conflict, 409, "Already registered for this event");
+    }
+
+    // Full events either queue the user (waitlist mode) or reject the join
+    let accepts_waitlist = event.registration_type == RegistrationTypeEnum::Waitlist;
+    let mut waitlisted = validate_event_capacity(event.current_attendees + 1, event.max_attendees).is_err();
+    if waitlisted && !accepts_waitlist {
+        handle_custom_error!(conflict, 409, "Event is full");
+    }
+
+    if !waitlisted && !repos.events.claim_seat(event_id, Some(user_id)).await? {
+        // Lost a race: re-read to report the actual reason
+        let event = repos.events.find_by_id(event_id).await?;
+        if event.attendees.as_ref().is_some_and(|a| a.contains(&user_id)) {
+            handle_custom_error!(conflict, 409, "Already registered for this event");
+        }
+        ensure_event_open(&event)?;
+        if !accepts_waitlist {
+            handle_custom_error!(conflict, 409, "Event is full");
+        }
+        waitlisted = true;
+    }
+
+    let attendee = EventAttendee {
+        event_id: Some(event_id),
+        user_id: Some(user_id),
+        email: user.email.clone(),
+        phone: user.phone_number.clone(),
+        first_name: user.first_name.clone(),
+        last_name: user.last_name.clone(),
+        company: user.company_name.clone(),
+        designation: user.designation.clone(),
+        registration_status: if waitlisted { StatusEnum::Pending } else { StatusEnum::Active },
+        registration_type,
+        waitlisted_at: waitlisted.then(BsonDateTime::now),
+        notes: details.notes.clone(),
+        emergency_contact: details.emergency_contact.clone(),
+        dietary_requirements: details.dietary_requirements.clone(),
+        accessibility_needs: details.accessibility_needs.clone(),
+        ..EventAttendee::default()
+    };
+
+    match repos.attendees.insert(&attendee).await {
+        Ok(attendee) => Ok(attendee),
+        Err(e) => {
+            if !waitlisted {
+                repos.events.release_seat(event_id, Some(user_id)).await?;
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Registers an attendee without a user account (e.g. from a CSV import). Takes a seat
+/// when one is left; a full event queues them if it runs in waitlist mode.
+pub async fn register_guest(repos: &Repositories, event: &Event, guest: EventAttendee) -> Result<EventAttendee, Error> {
+    let Some(event_id) = event.id else {
+        handle_custom_error!(bad_request, 400, "Missing event ID");
+    };
+
+    let waitlisted = !repos.events.claim_seat(event_id, None).await?;
+    if waitlisted && event.registration_type != RegistrationTypeEnum::Waitlist {
+        handle_custom_error!(conflict, 409, "Event is full");
+    }
+
+    let guest = EventAttendee {
+        event_id: Some(event_id),
+        registration_status: if waitlisted { StatusEnum::Pending } else { StatusEnum::Active },
+        waitlisted_at: waitlisted.then(BsonDateTime::now),
+        ..guest
+    };
+
+    match repos.attendees.insert(&guest).await {
+        Ok(guest) => Ok(guest),
+        Err(e) => {
+            if !waitlisted {
+                repos.events.release_seat(event_id, None).await?;
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Registers the guest an invited attendee brings along. Guests have no user account,
+/// so only the event's head count is touched.
+pub async fn register_plus_one(
+    repos: &Repositories,
+    event_id: ObjectId,
+    host: &EventAttendee,
+    name: Option<String>,
+) -> Result<EventAttendee, Error> {
+    if !repos.events.claim_seat(event_id, None).await? {
+        handle_custom_error!(conflict, 409, "No seat left for a plus one");
+    }
+
+    let host_name = [host.first_name.as_deref(), host.last_name.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let guest = EventAttendee {
+        event_id: Some(event_id),
+        first_name: Some(name.unwrap_or_else(|| "Guest".to_string())),
+        registration_status: StatusEnum::Active,
+        registration_type: RegistrationTypeEnum::Invited,
+        notes: Some(format!("Plus one of {}", host_name)),
+        plus_one_of: host.id,
+        ..EventAttendee::default()
+    };
+
+    match repos.attendees.insert(&guest).await {
+        Ok(guest) => Ok(guest),
+        Err(e) => {
+            repos.events.release_seat(event_id, None).await?;
+            Err(e)
+        }
+    }
+}
+/*----- END JOIN -----*/
+
+
+/*----- START LEAVE -----*/
+/// Cancels `user_id`'s registration and frees their seat
+pub async fn leave_event(
+    repos: &Repositories,
+    event_id: ObjectId,
+    user_id: ObjectId,
+    payload: &LeaveEventRequest,
+) -> Result<(), Error> {
+    let attendee_id = match repos.attendees.find_registration(event_id, user_id).await? {
+        Some(EventAttendee { id: Some(attendee_id), .. }) => attendee_id,
+        _ => handle_custom_error!(not_found, 404, "Not registered for this event"),
+    };
+
+    cancel_registration(repos, attendee_id, payload.reason.clone()).await
+}
+
+/// Cancels `user_id`'s registration on an admin's behalf, as `leave_event` does
+pub async fn remove_attendee(repos: &Repositories, event_id: ObjectId, user_id: ObjectId) -> Result<(), Error> {
+    leave_event(repos, event_id, user_id, &LeaveEventRequest::default()).await
+}
+
+/// Cancel a registration by id. A confirmed attendee frees their seat, which is
+/// handed to the next person on the waitlist; a waitlisted one just leaves the queue.
+/// Guests brought along as a plus one are cancelled with their host.
+pub async fn cancel_registration(repos: &Repositories, attendee_id: ObjectId, reason: Option<String>) -> Result<(), Error> {
+    let mut freed_events = Vec::new();
+    match cancel_single(repos, attendee_id, reason.clone()).await? {
+        Some(freed) => freed_events.extend(freed),
+        None => handle_custom_error!(not_found, 404, "Attendee not found"),
+    }
+
+    for guest_id in repos.attendees.plus_ones_of(attendee_id).await? {
+        if let Some(freed) = cancel_single(repos, guest_id, reason.clone()).await? {
+            freed_events.extend(freed);
+        }
+    }
+
+    for event_id in freed_events {
+        promote_next_waitlisted(repos, event_id).await?;
+    }
+
+    Ok(())
+}
+
+/// Undoes a cancellation. The registration takes a seat again when one is left; a full
+/// event queues it at the end of the waitlist if it runs in waitlist mode. Plus ones
+/// cancelled along with it stay cancelled.
+pub async fn reinstate_registration(repos: &Repositories, attendee_id: ObjectId) -> Result<EventAttendee, Error> {
+    let attendee = match repos.attendees.find_by_id(attendee_id).await? {
+        Some(attendee) if attendee.deleted => attendee,
+        Some(_) => handle_custom_error!(conflict, 409, "Registration is not cancelled"),
+        None => handle_custom_error!(not_found, 404, "Attendee not found"),
+    };
+    let Some(event_id) = attendee.event_id else {
+        handle_custom_error!(bad_request, 400, "Attendee has no event");
+    };
+    if let Some(user_id) = attendee.user_id
+        && repos.attendees.find_registration(event_id, user_id).await?.is_some()
+    {
+        handle_custom_error!(conflict, 409, "Already registered for this event");
+    }
+
+    let event = repos.events.find_by_id(event_id).await?;
+    let waitlisted = !repos.events.claim_seat(event_id, attendee.user_id).await?;
+    if waitlisted && event.registration_type != RegistrationTypeEnum::Waitlist {
+        handle_custom_error!(conflict, 409, "Event is full");
+    }
+
+    let restored = match repos.attendees.restore(attendee_id, waitlisted).await {
+        Ok(Some(restored)) => Ok(restored),
+        Ok(None) => Err(custom_error_expression!(conflict, 409, "Registration is not cancelled").into()),
+        Err(e) => Err(e),
+    };
+    if restored.is_err() && !waitlisted {
+        repos.events.release_seat(event_id, attendee.user_id).await?;
+    }
+    restored
+}
+
+/// Soft delete one registration and release its seat. Returns `None` when nothing
+/// matched, otherwise the event whose seat was freed (if any).
+async fn cancel_single(repos: &Repositories, attendee_id: ObjectId, reason: Option<String>) -> Result<Option<Option<ObjectId>>, Error> {
+    let Some(previous) = repos.attendees.mark_cancelled(attendee_id, reason).await? else {
+        return Ok(None);
+    };
+
+    let still_waitlisted = previous.registration_status == StatusEnum::Pending
+        && previous.waitlisted_at.is_some()
+        && previous.promoted_at.is_none();
+
+    match previous.event_id {
+        Some(event_id) if !still_waitlisted => {
+            repos.events.release_seat(event_id, previous.user_id).await?;
+            Ok(Some(Some(event_id)))
+        }
+        _ => Ok(Some(None)),
+    }
+}
+
+/// Move the longest-waiting attendee off the waitlist if a seat is free.
+/// Returns the promoted attendee id, if any.
+pub async fn promote_next_waitlisted(repos: &Repositories, event_id: ObjectId) -> Result<Option<ObjectId>, Error> {
+    loop {
+        let Some(next) = repos.attendees.next_waitlisted(event_id).await? else {
+            return Ok(None);
+        };
+        let Some(next_id) = next.id else {
+            return Ok(None);
+        };
+
+        if !repos.events.claim_seat(event_id, next.user_id).await? {
+            return Ok(None);
+        }
+
+        if repos.attendees.promote(next_id).await? {
+            return Ok(Some(next_id));
+        }
+
+        // The attendee left the queue in the meantime; give the seat back and try the next one
+        repos.events.release_seat(event_id, next.user_id).await?;
+    }
+}
+/*----- END LEAVE -----*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::StatusCode;
+    use chrono::Duration;
+
+    fn status(result: Result<(), Error>) -> StatusCode {
+        result.expect_err("expected the event to be closed").as_response_error().status_code()
+    }
+
+    fn deadline_in(offset: Duration) -> Option<BsonDateTime> {
+        Some(BsonDateTime::from_chrono(Utc::now() + offset))
+    }
+
+    async fn event_with(repos: &Repositories, max_attendees: u32, registration_type: RegistrationTypeEnum) -> Event {
+        let event = Event { max_attendees: Some(max_attendees), registration_type, ..Event::default() };
+        repos.events.create(&event).await.expect("event")
+    }
+
+    async fn user(repos: &Repositories) -> ObjectId {
+        repos.users.insert(&User::default()).await.expect("user").id.expect("user id")
+    }
+
+    async fn join(repos: &Repositories, event: &Event, user_id: ObjectId) -> Result<EventAttendee, Error> {
+        join_event(repos, event.id.expect("event id"), user_id, &JoinEventRequest::default()).await
+    }
+
+    #[test]
+    fn open_event_accepts_registrations() {
+        assert!(ensure_event_open(&Event::default()).is_ok());
+    }
+
+    #[test]
+    fn locked_event_is_forbidden() {
+        let event = Event { locked: true, ..Event::default() };
+        assert_eq!(status(ensure_event_open(&event)), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn cancelled_and_completed_events_are_closed() {
+        for status_enum in [EventStatusEnum::Cancelled, EventStatusEnum::Completed] {
+            let event = Event { status: status_enum, ..Event::default() };
+            assert_eq!(status(ensure_event_open(&event)), StatusCode::BAD_REQUEST);
+        }
+    }
+
+    #[test]
+    fn passed_deadline_closes_registration_even_without_required_registration() {
+        let event = Event {
+            requires_registration: false,
+            registration_deadline: deadline_in(Duration::minutes(-1)),
+            ..Event::default()
+        };
+        assert_eq!(status(ensure_event_open(&event)), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn future_deadline_keeps_registration_open() {
+        let event = Event {
+            requires_registration: true,
+            registration_deadline: deadline_in(Duration::hours(1)),
+            ..Event::default()
+        };
+        assert!(ensure_event_open(&event).is_ok());
+    }
+
+    #[actix_web::test]
+    async fn joining_takes_a_seat_once() {
+        let repos = Repositories::in_memory();
+        let event = event_with(&repos, 10, RegistrationTypeEnum::Initial).await;
+        let user_id = user(&repos).await;
+
+        let attendee = join(&repos, &event, user_id).await.expect("join");
+        assert_eq!(attendee.registration_status, StatusEnum::Active);
+
+        let error = join(&repos, &event, user_id).await.expect_err("second join");
+        assert_eq!(error.as_response_error().status_code(), StatusCode::CONFLICT);
+        let event = repos.events.find_by_id(event.id.unwrap()).await.unwrap();
+        assert_eq!(event.current_attendees, 1);
+        assert_eq!(event.attendees, Some(vec![user_id]));
+    }
+
+    #[actix_web::test]
+    async fn full_event_without_waitlist_rejects_the_join() {
+        let repos = Repositories::in_memory();
+        let event = event_with(&repos, 1, RegistrationTypeEnum::Initial).await;
+        join(&repos, &event, user(&repos).await).await.expect("first join");
+
+        let error = join(&repos, &event, user(&repos).await).await.expect_err("join of a full event");
+        assert_eq!(error.as_response_error().status_code(), StatusCode::CONFLICT);
+    }
+
+    #[actix_web::test]
+    async fn leaving_hands_the_seat_to_the_waitlist() {
+        let repos = Repositories::in_memory();
+        let event = event_with(&repos, 1, RegistrationTypeEnum::Waitlist).await;
+        let event_id = event.id.unwrap();
+        let (seated, queued) = (user(&repos).await, user(&repos).await);
+
+        join(&repos, &event, seated).await.expect("seated join");
+        let waitlisted = join(&repos, &event, queued).await.expect("waitlisted join");
+        assert_eq!(waitlisted.registration_status, StatusEnum::Pending);
+
+        leave_event(&repos, event_id, seated, &LeaveEventRequest::default()).await.expect("leave");
+
+        let promoted = repos.attendees.find_registration(event_id, queued).await.unwrap().expect("registration");
+        assert_eq!(promoted.registration_status, StatusEnum::Active);
+        let event = repos.events.find_by_id(event_id).await.unwrap();
+        assert_eq!(event.current_attendees, 1);
+        assert_eq!(event.attendees, Some(vec![queued]));
+    }
+
+    #[actix_web::test]
+    async fn reinstated_registration_takes_a_seat_again() {
+        let repos = Repositories::in_memory();
+        let event = event_with(&repos, 1, RegistrationTypeEnum::Initial).await;
+        let attendee = join(&repos, &event, user(&repos).await).await.expect("join");
+        let attendee_id = attendee.id.unwrap();
+
+        cancel_registration(&repos, attendee_id, None).await.expect("cancel");
+        assert_eq!(repos.events.find_by_id(event.id.unwrap()).await.unwrap().current_attendees, 0);
+
+        let restored = reinstate_registration(&repos, attendee_id).await.expect("reinstate");
+        assert!(!restored.deleted);
+        assert_eq!(repos.events.find_by_id(event.id.unwrap()).await.unwrap().current_attendees, 1);
+
+        let error = reinstate_registration(&repos, attendee_id).await.expect_err("second reinstate");
+        assert_eq!(error.as_response_error().status_code(), StatusCode::CONFLICT);
+    }
+}
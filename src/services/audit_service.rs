@@ -0,0 +1,117 @@
+// services/audit_service.rs
+
+use actix_session::SessionExt;
+use actix_web::{web, HttpRequest};
+use adminx::AdminxConfig;
+use adminx::utils::auth::extract_claims_from_session;
+use futures::FutureExt;
+use log::{error, warn};
+use mongodb::bson::{doc, oid::ObjectId, Bson, DateTime as BsonDateTime, Document};
+
+use crate::models::audit_log::AuditLog;
+use crate::repositories::Repositories;
+
+// Bumped on every write, so it would show up in every diff
+const IGNORED_FIELDS: [&str; 1] = ["updated_at"];
+
+// Never copied into the log; a change shows through the fields set along with it
+const SECRET_FIELDS: [&str; 1] = ["password_hash"];
+
+
+/*----- START AUDIT TRAIL -----*/
+/// The request-side half of an audit entry. AdminX resource futures must be `Send` and
+/// cannot hold the request, so this is captured up front and finished in `record`.
+#[derive(Clone)]
+pub struct AuditTrail {
+    repos: web::Data<Repositories>,
+    collection: &'static str,
+    actor: Option<String>,
+    resource: &'static str,
+}
+
+impl AuditTrail {
+    pub fn capture(req: &HttpRequest, resource: &'static str, collection: &'static str) -> Option<Self> {
+        let Some(repos) = Repositories::from_request(req) else {
+            warn!("Repositories unavailable, {} changes are not audited", resource);
+            return None;
+        };
+
+        Some(Self { repos, collection, actor: actor_from_request(req), resource })
+    }
+
+    /// Current state of the document, None if it does not exist or the id is invalid
+    pub async fn snapshot(&self, document_id: &str) -> Option<Document> {
+        let id = ObjectId::parse_str(document_id).ok()?;
+        let mut snapshot = self.repos.records.find_one(self.collection, doc! { "_id": id }).await
+            .inspect_err(|e| error!("Audit snapshot of {} {} failed: {}", self.resource, document_id, e))
+            .ok()
+            .flatten()?;
+        for field in SECRET_FIELDS {
+            snapshot.remove(field);
+        }
+        Some(snapshot)
+    }
+
+    /// Reads the document after the change and writes the entry in the background, so a
+    /// failing audit write is logged but never fails (or delays) the admin's request.
+    pub fn record(self, action: &str, document_id: Option<String>, before: Option<Document>) {
+        let action = action.to_string();
+        tokio::spawn(async move {
+            let after = match document_id.as_deref() {
+                Some(id) => self.snapshot(id).await,
+                None => None,
+            };
+
+            let entry = AuditLog {
+                id: None,
+                actor: self.actor,
+                resource: self.resource.to_string(),
+                document_id,
+                changes: diff(before.as_ref(), after.as_ref()),
+                action,
+                before,
+                after,
+                created_at: BsonDateTime::now(),
+            };
+
+            if let Err(e) = self.repos.audit_logs.insert(&entry).await {
+                error!("Failed to write audit log for {} {}: {}", entry.resource, entry.action, e);
+            }
+        });
+    }
+}
+/*----- END AUDIT TRAIL -----*/
+
+
+/*----- START HELPERS -----*/
+/// Email of the AdminX user behind the request
+pub fn actor_from_request(req: &HttpRequest) -> Option<String> {
+    let config = req.app_data::<web::Data<AdminxConfig>>()?;
+    // Decoding the session token never actually awaits, so it can be resolved inline
+    extract_claims_from_session(&req.get_session(), config)
+        .now_or_never()?
+        .ok()
+        .map(|claims| claims.email)
+}
+
+/// Fields that differ between the two snapshots as `{ field: { from, to } }`
+pub fn diff(before: Option<&Document>, after: Option<&Document>) -> Document {
+    let empty = Document::new();
+    let before = before.unwrap_or(&empty);
+    let after = after.unwrap_or(&empty);
+
+    let mut changes = Document::new();
+    for key in before.keys().chain(after.keys().filter(|key| !before.contains_key(key.as_str()))) {
+        if IGNORED_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+
+        let from = before.get(key).cloned().unwrap_or(Bson::Null);
+        let to = after.get(key).cloned().unwrap_or(Bson::Null);
+        if from != to {
+            changes.insert(key.clone(), doc! { "from": from, "to": to });
+        }
+    }
+    changes
+}
+/*----- END HELPERS -----*/
@@ -0,0 +1,478 @@
+// services/auth_service.rs
+//
+// Passwordless sign-in. A six digit code goes to the email or phone the user signs in
+// with and only its hash is kept, in Redis, for OTP_EXPIRY seconds. Codes sent and wrong
+// codes are counted per identifier and per client IP; too many wrong codes lock either
+// out for OTP_LOCKOUT_SECONDS. A verified code signs the user in, creating the account on
+// a first sign-in by email or phone, and starts a session.
+//
+// A session is one signed-in device. It lives in Redis for JWT_EXPIRY_SECONDS and hands
+// out short-lived access tokens, each naming the session in `sid`, through single-use
+// refresh tokens. Presenting an already used refresh token means it leaked, so the
+// whole session is revoked. Ending a session invalidates its access tokens at once.
+
+use actix_web::Error;
+use log::{error, warn};
+use mongodb::bson::oid::ObjectId;
+use rand::Rng;
+use redis::RedisError;
+use sha2::{Digest, Sha256};
+
+use crate::config::constants::{
+    JWT_EXPIRY_SECONDS,
+    OTP_EXPIRY,
+    OTP_LOCKOUT_SECONDS,
+    OTP_MAX_FAILURES,
+    OTP_MAX_IP_FAILURES,
+    OTP_MAX_IP_REQUESTS,
+    OTP_MAX_REQUESTS,
+    OTP_RATE_WINDOW_SECONDS,
+};
+use crate::enums::common_enums::StatusEnum;
+use crate::errors::custom_error::CustomError;
+use crate::models::user::User;
+use crate::repositories::Repositories;
+use crate::requests::structures::open_structure::{OTPRequest, VerifyOTP};
+use crate::services::{mail_service, sms_service};
+use crate::services::redis_service::{
+    redis_add_to_set_with_expiry,
+    redis_delete_key,
+    redis_get_counter,
+    redis_get_key,
+    redis_get_set_members,
+    redis_incr_with_expiry,
+    redis_remove_from_set,
+    redis_set_key_with_expiry,
+    redis_take_key,
+    redis_ttl,
+};
+use crate::utilities::bason_utility::parse_object_id;
+use crate::utilities::jwt_utility::{
+    decode_access_token,
+    generate_refresh_token,
+    hash_refresh_token,
+    issue_access_token,
+    secret,
+    Claims,
+    IssuedToken,
+};
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+
+/// Who is signing in, normalized so the same person always maps to the same keys
+enum LoginIdentifier {
+    Email(String),
+    Phone(String),
+}
+
+impl LoginIdentifier {
+    /// Emails are lowercased; phone numbers are kept as digits, as `User.phone_number` stores them
+    fn parse(identifier: &str) -> Self {
+        let identifier = identifier.trim();
+        if identifier.contains('@') {
+            LoginIdentifier::Email(identifier.to_lowercase())
+        } else {
+            LoginIdentifier::Phone(identifier.trim_start_matches('+').to_string())
+        }
+    }
+
+    fn value(&self) -> &str {
+        match self {
+            LoginIdentifier::Email(email) => email,
+            LoginIdentifier::Phone(phone) => phone,
+        }
+    }
+
+    fn key(&self, name: &str) -> String {
+        format!("otp:{}:{}", name, self.value())
+    }
+}
+
+/// An access token and the refresh token that replaces it, with their lifetimes in seconds
+pub struct TokenPair {
+    pub access: IssuedToken,
+    pub refresh_token: String,
+    pub refresh_expires_in: i64,
+}
+
+/// A signed-in user and the tokens of their new session
+pub struct SignIn {
+    pub user: User,
+    pub tokens: TokenPair,
+    pub new_user: bool,
+}
+
+/// The user behind a verified access token, and its claims
+#[derive(Clone)]
+pub struct AuthenticatedUser {
+    pub user: User,
+    pub claims: Claims,
+}
+
+impl AuthenticatedUser {
+    /// Id of the signed-in user, for acting on their behalf
+    pub fn user_id(&self) -> Result<ObjectId, Error> {
+        parse_object_id(&self.claims.sub, "user id")
+    }
+}
+
+fn ip_key(name: &str, client_ip: &str) -> String {
+    format!("otp:ip_{}:{}", name, client_ip)
+}
+
+fn redis_error(e: RedisError) -> Error {
+    error!("Auth store unavailable: {}", e);
+    custom_error_expression!(internal_error, 503, "Sign-in is temporarily unavailable").into()
+}
+
+fn too_many(message: &str, retry_after: i64) -> Error {
+    let minutes = (retry_after + 59) / 60;
+    CustomError::InvalidRequest(429, format!("{}, try again in {} minute(s)", message, minutes.max(1))).into()
+}
+
+/// Peppered with the signing secret, so a Redis dump alone cannot be brute-forced offline
+fn hash_code(identifier: &LoginIdentifier, code: &str) -> Result<String, Error> {
+    let digest = Sha256::new()
+        .chain_update(secret()?.as_bytes())
+        .chain_update(identifier.value().as_bytes())
+        .chain_update(b":")
+        .chain_update(code.as_bytes())
+        .finalize();
+    Ok(format!("{:x}", digest))
+}
+
+/// Equal-time comparison of two code hashes
+fn hashes_match(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn generate_code() -> String {
+    format!("{:06}", rand::thread_rng().gen_range(0..1_000_000))
+}
+
+
+/*----- START LIMITS -----*/
+/// Fails with a 429 while the identifier or the client IP is locked out
+async fn ensure_not_locked(identifier: &LoginIdentifier, client_ip: &str) -> Result<(), Error> {
+    let failures_key = identifier.key("failures");
+    if redis_get_counter(failures_key.clone()).await.map_err(redis_error)? >= OTP_MAX_FAILURES {
+        let retry_after = redis_ttl(failures_key).await.map_err(redis_error)?;
+        return Err(too_many("Too many wrong codes", retry_after));
+    }
+
+    let ip_failures_key = ip_key("failures", client_ip);
+    if redis_get_counter(ip_failures_key.clone()).await.map_err(redis_error)? >= OTP_MAX_IP_FAILURES {
+        let retry_after = redis_ttl(ip_failures_key).await.map_err(redis_error)?;
+        return Err(too_many("Too many wrong codes", retry_after));
+    }
+    Ok(())
+}
+
+/// Counts a code sent, failing with a 429 past the per-identifier or per-IP limit
+async fn count_request(identifier: &LoginIdentifier, client_ip: &str) -> Result<(), Error> {
+    let requests_key = identifier.key("requests");
+    if redis_incr_with_expiry(requests_key.clone(), OTP_RATE_WINDOW_SECONDS).await.map_err(redis_error)? > OTP_MAX_REQUESTS {
+        let retry_after = redis_ttl(requests_key).await.map_err(redis_error)?;
+        return Err(too_many("Too many codes requested", retry_after));
+    }
+
+    let ip_requests_key = ip_key("requests", client_ip);
+    if redis_incr_with_expiry(ip_requests_key.clone(), OTP_RATE_WINDOW_SECONDS).await.map_err(redis_error)? > OTP_MAX_IP_REQUESTS {
+        let retry_after = redis_ttl(ip_requests_key).await.map_err(redis_error)?;
+        return Err(too_many("Too many codes requested", retry_after));
+    }
+    Ok(())
+}
+
+/// Counts a wrong code; on the last allowed one the code itself is discarded.
+/// Returns the attempts left for the identifier.
+async fn count_failure(identifier: &LoginIdentifier, client_ip: &str) -> Result<i64, Error> {
+    let failures = redis_incr_with_expiry(identifier.key("failures"), OTP_LOCKOUT_SECONDS).await.map_err(redis_error)?;
+    redis_incr_with_expiry(ip_key("failures", client_ip), OTP_LOCKOUT_SECONDS).await.map_err(redis_error)?;
+
+    if failures >= OTP_MAX_FAILURES {
+        redis_delete_key(identifier.key("code")).await.map_err(redis_error)?;
+    }
+    Ok((OTP_MAX_FAILURES - failures).max(0))
+}
+/*----- END LIMITS -----*/
+
+
+/*----- START OTP -----*/
+/// Sends a fresh sign-in code, replacing any earlier one. Returns its lifetime in seconds.
+pub async fn request_otp(repos: &Repositories, payload: &OTPRequest, client_ip: &str) -> Result<usize, Error> {
+    let identifier = LoginIdentifier::parse(&payload.identifier);
+    ensure_not_locked(&identifier, client_ip).await?;
+
+    // Whether the number has an account is not checked here, so the answer never tells
+    if let LoginIdentifier::Phone(_) = &identifier {
+        sms_service::ensure_available()?;
+    }
+
+    count_request(&identifier, client_ip).await?;
+
+    let code = generate_code();
+    redis_set_key_with_expiry(identifier.key("code"), hash_code(&identifier, &code)?, OTP_EXPIRY)
+        .await
+        .map_err(redis_error)?;
+
+    match &identifier {
+        LoginIdentifier::Email(email) => {
+            mail_service::queue(repos, mail_service::otp_email(email, &code)?).await?;
+        }
+        LoginIdentifier::Phone(phone) => {
+            sms_service::send_otp(phone, &code).await?;
+        }
+    }
+    Ok(OTP_EXPIRY)
+}
+
+/// Checks a sign-in code. A match is single use: it signs the user in, creating the
+/// account on a first sign-in. Locked, blocked and deleted accounts are refused.
+pub async fn verify_otp(repos: &Repositories, payload: &VerifyOTP, client_ip: &str) -> Result<SignIn, Error> {
+    let identifier = LoginIdentifier::parse(&payload.identifier);
+    ensure_not_locked(&identifier, client_ip).await?;
+
+    let code_key = identifier.key("code");
+    let Some(stored_hash) = redis_get_key(code_key.clone()).await.map_err(redis_error)? else {
+        count_failure(&identifier, client_ip).await?;
+        handle_custom_error!(bad_request, 400, "The code has expired, request a new one");
+    };
+
+    if !hashes_match(&stored_hash, &hash_code(&identifier, payload.otp.trim())?) {
+        let attempts_left = count_failure(&identifier, client_ip).await?;
+        if attempts_left == 0 {
+            return Err(too_many("Too many wrong codes", OTP_LOCKOUT_SECONDS as i64));
+        }
+        handle_custom_error!(bad_request, 400, format!("Wrong code, {} attempt(s) left", attempts_left));
+    }
+
+    // Whoever takes the code first uses it; a concurrent check with the same code loses
+    if redis_take_key(code_key).await.map_err(redis_error)?.is_none() {
+        handle_custom_error!(bad_request, 400, "The code has already been used, request a new one");
+    }
+    redis_delete_key(identifier.key("failures")).await.map_err(redis_error)?;
+
+    let (user, new_user) = match &identifier {
+        LoginIdentifier::Email(email) => match repos.users.find_by_email(email).await? {
+            Some(user) => (user, false),
+            None => {
+                let user = repos.users.insert(&User {
+                    email: Some(email.clone()),
+                    firebase_token: payload.firebase_token.clone(),
+                    ..User::default()
+                }).await?;
+                (user, true)
+            }
+        },
+        LoginIdentifier::Phone(phone) => match repos.users.find_by_phone(phone).await? {
+            Some(user) => (user, false),
+            None => {
+                let user = repos.users.insert(&User {
+                    phone_number: Some(phone.clone()),
+                    firebase_token: payload.firebase_token.clone(),
+                    ..User::default()
+                }).await?;
+                (user, true)
+            }
+        },
+    };
+
+    let user_id = ensure_can_sign_in(&user)?;
+
+    if let (false, Some(firebase_token)) = (new_user, payload.firebase_token.as_deref()) {
+        repos.users.set_firebase_token(user_id, firebase_token).await?;
+    }
+
+    let tokens = start_session(user_id).await?;
+    Ok(SignIn { user, tokens, new_user })
+}
+/*----- END OTP -----*/
+
+
+/*----- START SESSIONS -----*/
+fn session_key(session_id: &str) -> String {
+    format!("auth:session:{}", session_id)
+}
+
+fn user_sessions_key(user_id: ObjectId) -> String {
+    format!("auth:sessions:{}", user_id.to_hex())
+}
+
+fn refresh_key(token_hash: &str) -> String {
+    format!("auth:refresh:{}", token_hash)
+}
+
+// Kept after rotation, to recognise a refresh token presented a second time
+fn used_refresh_key(token_hash: &str) -> String {
+    format!("auth:refresh_used:{}", token_hash)
+}
+
+/// Locked and blocked accounts cannot sign in or keep using their sessions
+fn ensure_can_sign_in(user: &User) -> Result<ObjectId, Error> {
+    if user.locked || user.status == StatusEnum::Blocked {
+        handle_custom_error!(forbidden, 403, "This account is locked");
+    }
+    user.id.ok_or_else(|| custom_error_expression!(internal_error, 500, "User has no id").into())
+}
+
+/// Issues the session's next token pair. The refresh token expires with the session.
+async fn issue_tokens(user_id: ObjectId, session_id: &str) -> Result<TokenPair, Error> {
+    let ttl = redis_ttl(session_key(session_id)).await.map_err(redis_error)?;
+    if ttl == 0 {
+        handle_custom_error!(unauthorized, 401, "Session has ended, sign in again");
+    }
+
+    let refresh_token = generate_refresh_token();
+    redis_set_key_with_expiry(
+        refresh_key(&hash_refresh_token(&refresh_token)),
+        format!("{}:{}", user_id.to_hex(), session_id),
+        ttl as usize,
+    ).await.map_err(redis_error)?;
+
+    Ok(TokenPair {
+        access: issue_access_token(user_id, session_id)?,
+        refresh_token,
+        refresh_expires_in: ttl,
+    })
+}
+
+/// Starts a session for a user who just proved who they are
+async fn start_session(user_id: ObjectId) -> Result<TokenPair, Error> {
+    let session_id = uuid::Uuid::new_v4().to_string();
+    redis_set_key_with_expiry(session_key(&session_id), user_id.to_hex(), JWT_EXPIRY_SECONDS as usize)
+        .await
+        .map_err(redis_error)?;
+    redis_add_to_set_with_expiry(user_sessions_key(user_id), session_id.clone(), JWT_EXPIRY_SECONDS as usize)
+        .await
+        .map_err(redis_error)?;
+
+    issue_tokens(user_id, &session_id).await
+}
+
+/// Ends the session of a refresh token that was already rotated, if it was
+async fn revoke_if_reused(token_hash: &str) -> Result<(), Error> {
+    let Some(record) = redis_get_key(used_refresh_key(token_hash)).await.map_err(redis_error)? else {
+        return Ok(());
+    };
+    let Some((user_id, session_id)) = record.split_once(':') else {
+        return Ok(());
+    };
+
+    warn!("Refresh token reused, revoking session {} of user {}", session_id, user_id);
+    end_session(parse_object_id(user_id, "user id")?, session_id).await
+}
+
+/// Trades a refresh token for a new pair. Each refresh token works once; a reused one
+/// revokes its session, since either the client or an attacker holds a stolen copy.
+pub async fn refresh_session(repos: &Repositories, refresh_token: &str) -> Result<TokenPair, Error> {
+    let token_hash = hash_refresh_token(refresh_token.trim());
+
+    let Some(record) = redis_take_key(refresh_key(&token_hash)).await.map_err(redis_error)? else {
+        revoke_if_reused(&token_hash).await?;
+        handle_custom_error!(unauthorized, 401, "Invalid refresh token, sign in again");
+    };
+    let Some((user_id, session_id)) = record.split_once(':') else {
+        handle_custom_error!(unauthorized, 401, "Invalid refresh token, sign in again");
+    };
+
+    let ttl = redis_ttl(session_key(session_id)).await.map_err(redis_error)?;
+    if ttl > 0 {
+        redis_set_key_with_expiry(used_refresh_key(&token_hash), record.clone(), ttl as usize)
+            .await
+            .map_err(redis_error)?;
+    }
+
+    let user = repos.users.find_active_by_id(parse_object_id(user_id, "user id")?).await
+        .map_err(|_| custom_error_expression!(unauthorized, 401, "Account not found"))?;
+    let user_id = ensure_can_sign_in(&user)?;
+    issue_tokens(user_id, session_id).await
+}
+
+/// Verifies an access token and loads its user. Tokens of ended sessions, and of
+/// deleted, locked or blocked accounts, are refused.
+pub async fn authenticate(repos: &Repositories, token: &str) -> Result<AuthenticatedUser, Error> {
+    let claims = decode_access_token(token)?;
+
+    let session_user = redis_get_key(session_key(&claims.sid)).await.map_err(redis_error)?;
+    if session_user.as_deref() != Some(claims.sub.as_str()) {
+        handle_custom_error!(unauthorized, 401, "Session has ended, sign in again");
+    }
+
+    let user = repos.users.find_active_by_id(parse_object_id(&claims.sub, "user id")?).await
+        .map_err(|_| custom_error_expression!(unauthorized, 401, "Account not found"))?;
+    ensure_can_sign_in(&user)?;
+    Ok(AuthenticatedUser { user, claims })
+}
+
+/// Signs one device out. Its access tokens stop working right away and its refresh token with them.
+pub async fn end_session(user_id: ObjectId, session_id: &str) -> Result<(), Error> {
+    redis_delete_key(session_key(session_id)).await.map_err(redis_error)?;
+    redis_remove_from_set(user_sessions_key(user_id), session_id.to_string()).await.map_err(redis_error)?;
+    Ok(())
+}
+
+/// Signs the user out everywhere. Returns how many sessions were ended.
+pub async fn end_all_sessions(user_id: ObjectId) -> Result<usize, Error> {
+    let sessions_key = user_sessions_key(user_id);
+    let session_ids = redis_get_set_members(sessions_key.clone()).await.map_err(redis_error)?;
+
+    let mut ended = 0;
+    for session_id in &session_ids {
+        if redis_take_key(session_key(session_id)).await.map_err(redis_error)?.is_some() {
+            ended += 1;
+        }
+    }
+    redis_delete_key(sessions_key).await.map_err(redis_error)?;
+    Ok(ended)
+}
+/*----- END SESSIONS -----*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(identifier: &str) -> (bool, String) {
+        match LoginIdentifier::parse(identifier) {
+            LoginIdentifier::Email(email) => (true, email),
+            LoginIdentifier::Phone(phone) => (false, phone),
+        }
+    }
+
+    #[test]
+    fn emails_are_trimmed_and_lowercased() {
+        assert_eq!(parsed("  Jane.Doe@Example.COM "), (true, "jane.doe@example.com".to_string()));
+    }
+
+    #[test]
+    fn phones_drop_the_leading_plus() {
+        assert_eq!(parsed(" +919876543210"), (false, "919876543210".to_string()));
+        assert_eq!(parsed("919876543210"), (false, "919876543210".to_string()));
+    }
+
+    #[test]
+    fn spellings_of_one_identifier_share_redis_keys() {
+        assert_eq!(LoginIdentifier::parse("A@B.io").key("code"), LoginIdentifier::parse(" a@b.io").key("code"));
+        assert_eq!(LoginIdentifier::parse("+15550100").key("failures"), "otp:failures:15550100");
+    }
+
+    #[test]
+    fn codes_are_six_digits() {
+        for _ in 0..100 {
+            let code = generate_code();
+            assert_eq!(code.len(), 6);
+            assert!(code.bytes().all(|b| b.is_ascii_digit()));
+        }
+    }
+
+    #[test]
+    fn hashes_match_compares_whole_strings() {
+        assert!(hashes_match("abc123", "abc123"));
+        assert!(!hashes_match("abc123", "abc124"));
+        assert!(!hashes_match("abc123", "abc12"));
+    }
+}
@@ -0,0 +1,102 @@
+// services/ban_service.rs
+
+use std::time::Duration;
+
+use actix_web::Error;
+use log::{error, info};
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+
+use crate::config::constants::BAN_EXPIRY_JOB_SECONDS;
+use crate::enums::common_enums::{BanTypeEnum, StatusEnum};
+use crate::models::user_ban::UserBan;
+use crate::repositories::Repositories;
+use crate::requests::structures::user_structure::BanUserRequest;
+use crate::handle_custom_error;
+
+
+/*----- START BAN -----*/
+/// Bans a user: records the ban, then locks the account and marks it blocked.
+/// Temporary bans carry an `expires_at` picked up by the expiry job.
+pub async fn ban_user(repos: &Repositories, user_id: ObjectId, payload: &BanUserRequest, issued_by: &str) -> Result<UserBan, Error> {
+    let user = repos.users.find_active_by_id(user_id).await?;
+
+    if repos.bans.find_active(user_id).await?.is_some() {
+        handle_custom_error!(conflict, 409, "User is already banned");
+    }
+
+    let ban_type = payload.ban_type();
+    let duration_days = match ban_type {
+        BanTypeEnum::Temporary => payload.duration,
+        BanTypeEnum::Permanent => None,
+    };
+    let expires_at = duration_days
+        .map(|days| BsonDateTime::from_millis(BsonDateTime::now().timestamp_millis() + days * 86_400_000));
+
+    let ban = repos.bans.insert(&UserBan {
+        id: None,
+        user_id,
+        reason: payload.reason.clone().unwrap_or_default(),
+        ban_type,
+        duration_days,
+        expires_at,
+        issued_by: issued_by.to_string(),
+        previous_status: Some(user.status),
+        active: true,
+        lifted_at: None,
+        lifted_by: None,
+        lift_reason: None,
+        created_at: BsonDateTime::now(),
+        updated_at: BsonDateTime::now(),
+    }).await?;
+
+    repos.users.set_lock(user_id, true, StatusEnum::Blocked).await?;
+    Ok(ban)
+}
+
+/// Lifts the active ban and restores the status the user had before it
+pub async fn unban_user(repos: &Repositories, user_id: ObjectId, lifted_by: &str, lift_reason: &str) -> Result<(), Error> {
+    let ban = match repos.bans.find_active(user_id).await? {
+        Some(ban) => ban,
+        None => handle_custom_error!(not_found, 404, "User is not banned"),
+    };
+
+    repos.bans.lift(user_id, lifted_by, lift_reason).await?;
+
+    let status = ban.previous_status
+        .filter(|status| *status != StatusEnum::Blocked)
+        .unwrap_or(StatusEnum::Active);
+    repos.users.set_lock(user_id, false, status).await
+}
+/*----- END BAN -----*/
+
+
+/*----- START EXPIRY JOB -----*/
+/// Lifts every temporary ban past its `expires_at`
+pub async fn expire_bans(repos: &Repositories) -> Result<u64, Error> {
+    let user_ids = repos.bans.expired_user_ids().await?;
+    let mut lifted = 0;
+    for user_id in user_ids {
+        // One broken user must not keep the others banned
+        match unban_user(repos, user_id, "system", "expired").await {
+            Ok(()) => lifted += 1,
+            Err(e) => error!("Could not lift expired ban of user {}: {}", user_id, e),
+        }
+    }
+    Ok(lifted)
+}
+
+/// Periodically lifts expired temporary bans
+pub fn spawn_expiry_job(repos: Repositories) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(BAN_EXPIRY_JOB_SECONDS));
+        loop {
+            interval.tick().await;
+            match expire_bans(&repos).await {
+                Ok(0) => {}
+                Ok(lifted) => info!("Lifted {} expired bans", lifted),
+                Err(e) => error!("Ban expiry job failed: {}", e),
+            }
+        }
+    });
+}
+/*----- END EXPIRY JOB -----*/
@@ -0,0 +1,77 @@
+// services/contact_exchange_service.rs
+//
+// Contact exchange by QR code: scanning someone's user QR saves them as a contact of the
+// scanner, with their profile details copied over.
+
+use actix_web::Error;
+use mongodb::bson::oid::ObjectId;
+
+use crate::enums::common_enums::StatusEnum;
+use crate::models::{contact::Contact, user::User};
+use crate::repositories::Repositories;
+use crate::utilities::qr_utility::QrPayload;
+use crate::handle_custom_error;
+
+/// Saves the user behind a scanned QR `code` as a contact of `scanner_id`. Scanning the
+/// same person again returns the contact saved the first time; the flag says whether it
+/// was just created.
+pub async fn scan_user_qr(
+    repos: &Repositories,
+    scanner_id: ObjectId,
+    code: &str,
+    event_id: Option<ObjectId>,
+) -> Result<(Contact, bool), Error> {
+    let scanned_id = match QrPayload::parse(code) {
+        Some(QrPayload::User(user_id)) => user_id,
+        Some(QrPayload::Event(_)) => handle_custom_error!(bad_request, 400, "This is an event QR code, not a person's"),
+        None => handle_custom_error!(bad_request, 400, "Not a valid QR code, or it has expired"),
+    };
+    if scanned_id == scanner_id {
+        handle_custom_error!(bad_request, 400, "You cannot add yourself as a contact");
+    }
+
+    let scanner = repos.users.find_active_by_id(scanner_id).await?;
+    if scanner.locked {
+        handle_custom_error!(forbidden, 403, "Your account is locked");
+    }
+    let scanned = repos.users.find_active_by_id(scanned_id).await?;
+    if scanned.locked {
+        handle_custom_error!(not_found, 404, "User not found");
+    }
+
+    if let Some(contact) = repos.contacts.find_for_user(scanner_id, scanned_id).await? {
+        return Ok((contact, false));
+    }
+
+    // Where they met, when the scan happened at an event
+    let met_at = match event_id {
+        Some(event_id) => Some(repos.events.find_by_id(event_id).await?.title),
+        None => None,
+    };
+
+    let contact = repos.contacts.insert(&contact_from_user(&scanned, scanner_id, met_at)).await?;
+    Ok((contact, true))
+}
+
+fn contact_from_user(user: &User, owner_id: ObjectId, met_at: Option<String>) -> Contact {
+    Contact {
+        user_id: user.id,
+        parent_user_id: Some(owner_id),
+        first_name: user.first_name.clone(),
+        last_name: user.last_name.clone(),
+        username: user.username.clone(),
+        email: user.email.clone(),
+        official_email: user.official_email.clone(),
+        phone_number: user.phone_number.clone(),
+        age: user.age,
+        gender: user.gender.clone(),
+        designation: user.designation.clone(),
+        company_name: user.company_name.clone(),
+        company_address: user.company_address.clone(),
+        website: user.website.clone(),
+        description: user.description.clone(),
+        notes: met_at.map(|title| format!("Met at {}", title)),
+        status: StatusEnum::Active,
+        ..Contact::default()
+    }
+}
@@ -0,0 +1,598 @@
+// services/import_service.rs
+//
+// CSV import of contacts and event attendees from AdminX. Columns are mapped onto model
+// fields, each row is validated with the model's own `validator` rules and deduplicated
+// by email and phone, against earlier rows of the file and against what is stored. A dry
+// run stops before writing. Every row gets an outcome, and the ones that did not make it
+// come back as a CSV error report.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use actix_web::Error;
+use async_trait::async_trait;
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationErrors};
+
+use crate::enums::common_enums::StatusEnum;
+use crate::models::{contact::Contact, event::Event, event_attendee::EventAttendee, user::User};
+use crate::repositories::Repositories;
+use crate::services::attendance_service::{ensure_event_open, register_guest};
+use crate::utilities::bason_utility::parse_object_id;
+use crate::handle_custom_error;
+
+const MAX_IMPORT_ROWS: usize = 5000;
+
+
+/*----- START TARGETS -----*/
+/// A model field a CSV column can be mapped onto, with the header spellings that map to
+/// it automatically (compared lowercased, with anything but letters and digits dropped)
+pub struct ImportField {
+    pub name: &'static str,
+    pub label: &'static str,
+    aliases: &'static [&'static str],
+}
+
+/// A model that can be imported from CSV. Every row is attached to one scope record:
+/// the owning user for contacts, the event for attendees.
+#[async_trait(?Send)]
+pub trait CsvImport: Validate + Sized + Send + Sync {
+    const RESOURCE_NAME: &'static str;
+    const BASE_PATH: &'static str;
+    /// Reference target (see `admin::references`) the scope is picked from
+    const SCOPE: &'static str;
+    const SCOPE_LABEL: &'static str;
+    const FIELDS: &'static [ImportField];
+
+    type Scope: Send + Sync;
+
+    async fn scope(repos: &Repositories, id: ObjectId) -> Result<Self::Scope, Error>;
+
+    fn from_row(values: &BTreeMap<&'static str, String>, scope: &Self::Scope) -> Self;
+
+    fn email(&self) -> Option<&str>;
+
+    fn phone(&self) -> Option<&str>;
+
+    fn errors(&self) -> Vec<String> {
+        validation_messages(self.validate().err(), &[])
+    }
+
+    /// Stored records of the scope sharing one of the emails or phones
+    async fn existing(repos: &Repositories, scope: &Self::Scope, emails: &[String], phones: &[String]) -> Result<Vec<Self>, Error>;
+
+    async fn save(self, repos: &Repositories, scope: &Self::Scope) -> Result<Option<ObjectId>, Error>;
+}
+
+#[async_trait(?Send)]
+impl CsvImport for Contact {
+    const RESOURCE_NAME: &'static str = "Contacts";
+    const BASE_PATH: &'static str = "contacts";
+    const SCOPE: &'static str = "users";
+    const SCOPE_LABEL: &'static str = "Owner";
+    const FIELDS: &'static [ImportField] = &[
+        ImportField { name: "first_name", label: "First Name", aliases: &["firstname", "first", "givenname", "forename"] },
+        ImportField { name: "last_name", label: "Last Name", aliases: &["lastname", "last", "surname", "familyname"] },
+        ImportField { name: "email", label: "Email", aliases: &["email", "emailaddress", "mail"] },
+        ImportField { name: "phone_number", label: "Phone Number", aliases: &["phonenumber", "phone", "mobile", "mobilenumber", "tel"] },
+        ImportField { name: "username", label: "Username", aliases: &["username"] },
+        ImportField { name: "official_email", label: "Official Email", aliases: &["officialemail", "workemail"] },
+        ImportField { name: "designation", label: "Designation", aliases: &["designation", "jobtitle", "title", "position"] },
+        ImportField { name: "company_name", label: "Company Name", aliases: &["companyname", "company", "organization", "organisation"] },
+        ImportField { name: "company_address", label: "Company Address", aliases: &["companyaddress", "address"] },
+        ImportField { name: "website", label: "Website", aliases: &["website", "url", "web"] },
+        ImportField { name: "description", label: "Description", aliases: &["description"] },
+        ImportField { name: "notes", label: "Notes", aliases: &["notes", "note", "comments"] },
+    ];
+
+    type Scope = User;
+
+    async fn scope(repos: &Repositories, id: ObjectId) -> Result<User, Error> {
+        repos.users.find_active_by_id(id).await
+    }
+
+    fn from_row(values: &BTreeMap<&'static str, String>, owner: &User) -> Self {
+        let text = |field: &str| values.get(field).cloned();
+        Contact {
+            parent_user_id: owner.id,
+            first_name: text("first_name"),
+            last_name: text("last_name"),
+            email: text("email"),
+            phone_number: text("phone_number"),
+            username: text("username"),
+            official_email: text("official_email"),
+            designation: text("designation"),
+            company_name: text("company_name"),
+            company_address: text("company_address"),
+            website: text("website"),
+            description: text("description"),
+            notes: text("notes"),
+            status: StatusEnum::Active,
+            ..Contact::default()
+        }
+    }
+
+    fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+
+    fn phone(&self) -> Option<&str> {
+        self.phone_number.as_deref()
+    }
+
+    // Imported people rarely have an account of their own, so `user_id` may stay empty
+    fn errors(&self) -> Vec<String> {
+        validation_messages(self.validate().err(), &["user_id"])
+    }
+
+    async fn existing(repos: &Repositories, owner: &User, emails: &[String], phones: &[String]) -> Result<Vec<Self>, Error> {
+        let Some(owner_id) = owner.id else {
+            return Ok(Vec::new());
+        };
+        repos.contacts.find_matching(owner_id, emails, phones).await
+    }
+
+    async fn save(self, repos: &Repositories, _owner: &User) -> Result<Option<ObjectId>, Error> {
+        Ok(repos.contacts.insert(&self).await?.id)
+    }
+}
+
+#[async_trait(?Send)]
+impl CsvImport for EventAttendee {
+    const RESOURCE_NAME: &'static str = "Event Attendees";
+    const BASE_PATH: &'static str = "event_attendees";
+    const SCOPE: &'static str = "events";
+    const SCOPE_LABEL: &'static str = "Event";
+    const FIELDS: &'static [ImportField] = &[
+        ImportField { name: "first_name", label: "First Name", aliases: &["firstname", "first", "givenname", "forename"] },
+        ImportField { name: "last_name", label: "Last Name", aliases: &["lastname", "last", "surname", "familyname"] },
+        ImportField { name: "email", label: "Email", aliases: &["email", "emailaddress", "mail"] },
+        ImportField { name: "phone", label: "Phone", aliases: &["phone", "phonenumber", "mobile", "mobilenumber", "tel"] },
+        ImportField { name: "company", label: "Company", aliases: &["company", "companyname", "organization", "organisation"] },
+        ImportField { name: "designation", label: "Designation", aliases: &["designation", "jobtitle", "title", "position"] },
+        ImportField { name: "dietary_requirements", label: "Dietary Requirements", aliases: &["dietaryrequirements", "dietary", "diet"] },
+        ImportField { name: "accessibility_needs", label: "Accessibility Needs", aliases: &["accessibilityneeds", "accessibility"] },
+        ImportField { name: "emergency_contact", label: "Emergency Contact", aliases: &["emergencycontact"] },
+        ImportField { name: "notes", label: "Notes", aliases: &["notes", "note", "comments"] },
+    ];
+
+    type Scope = Event;
+
+    async fn scope(repos: &Repositories, id: ObjectId) -> Result<Event, Error> {
+        let event = repos.events.find_by_id(id).await?;
+        ensure_event_open(&event)?;
+        Ok(event)
+    }
+
+    fn from_row(values: &BTreeMap<&'static str, String>, event: &Event) -> Self {
+        let text = |field: &str| values.get(field).cloned();
+        EventAttendee {
+            event_id: event.id,
+            first_name: text("first_name"),
+            last_name: text("last_name"),
+            email: text("email"),
+            phone: text("phone"),
+            company: text("company"),
+            designation: text("designation"),
+            dietary_requirements: text("dietary_requirements"),
+            accessibility_needs: text("accessibility_needs"),
+            emergency_contact: text("emergency_contact"),
+            notes: text("notes"),
+            registration_type: event.registration_type.clone(),
+            ..EventAttendee::default()
+        }
+    }
+
+    fn email(&self) -> Option<&str> {
+        self.email.as_deref()
+    }
+
+    fn phone(&self) -> Option<&str> {
+        self.phone.as_deref()
+    }
+
+    // Neither is required on the model, but a guest nobody can reach cannot be deduplicated
+    fn errors(&self) -> Vec<String> {
+        let mut errors = validation_messages(self.validate().err(), &[]);
+        if self.email.is_none() && self.phone.is_none() {
+            errors.push("email: An email or phone is required".to_string());
+        }
+        errors
+    }
+
+    async fn existing(repos: &Repositories, event: &Event, emails: &[String], phones: &[String]) -> Result<Vec<Self>, Error> {
+        let Some(event_id) = event.id else {
+            return Ok(Vec::new());
+        };
+        repos.attendees.find_matching(event_id, emails, phones).await
+    }
+
+    /// Goes through the attendance service so the seat is claimed, or the guest waitlisted
+    async fn save(self, repos: &Repositories, event: &Event) -> Result<Option<ObjectId>, Error> {
+        Ok(register_guest(repos, event, self).await?.id)
+    }
+}
+/*----- END TARGETS -----*/
+
+
+/*----- START IMPORT -----*/
+#[derive(Debug, Deserialize)]
+pub struct ImportRequest {
+    pub csv: String,
+    /// CSV header → model field; headers left out (or mapped to "") are ignored.
+    /// Suggested from the headers when absent.
+    #[serde(default)]
+    pub mapping: Option<HashMap<String, String>>,
+    pub scope_id: Option<String>,
+    #[serde(default = "dry_run_by_default")]
+    pub dry_run: bool,
+}
+
+fn dry_run_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowStatus {
+    /// Valid and new; written unless this is a dry run
+    Ready,
+    Imported,
+    Duplicate,
+    Invalid,
+    /// Valid, but the write was refused (e.g. the event is full)
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RowOutcome {
+    /// Line in the file, the header being line 1
+    pub line: usize,
+    pub status: RowStatus,
+    pub errors: Vec<String>,
+    pub values: BTreeMap<&'static str, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSummary {
+    pub total: usize,
+    pub ready: usize,
+    pub imported: usize,
+    pub duplicate: usize,
+    pub invalid: usize,
+    pub failed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub headers: Vec<String>,
+    pub mapping: BTreeMap<String, &'static str>,
+    pub rows: Vec<RowOutcome>,
+    pub summary: ImportSummary,
+    /// The rows that were not imported, as CSV with their errors appended
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_report: Option<String>,
+}
+
+/// Suggested field per header, for the mapping form
+pub fn suggest_mapping<T: CsvImport>(headers: &[String]) -> BTreeMap<String, &'static str> {
+    let mut taken = HashSet::new();
+    headers.iter()
+        .filter_map(|header| {
+            let key = header_key(header);
+            let field = T::FIELDS.iter().find(|field| {
+                !taken.contains(field.name) && (header_key(field.name) == key || field.aliases.contains(&key.as_str()))
+            })?;
+            taken.insert(field.name);
+            Some((header.clone(), field.name))
+        })
+        .collect()
+}
+
+pub async fn import<T: CsvImport>(repos: &Repositories, request: ImportRequest) -> Result<ImportReport, Error> {
+    let (headers, records) = parse_csv(&request.csv)?;
+    let mapping = match &request.mapping {
+        Some(mapping) => checked_mapping::<T>(&headers, mapping)?,
+        None => suggest_mapping::<T>(&headers),
+    };
+    if mapping.is_empty() {
+        handle_custom_error!(bad_request, 400, "Map at least one column");
+    }
+
+    let scope_id = parse_object_id(request.scope_id.as_deref().unwrap_or_default(), &T::SCOPE_LABEL.to_lowercase())?;
+    let scope = T::scope(repos, scope_id).await?;
+
+    let (mut rows, models, first_seen) = map_rows::<T>(&headers, &records, &mapping, &scope);
+
+    // One lookup for the whole file against what is already stored
+    let emails: Vec<String> = first_seen.keys().filter_map(|key| key.strip_prefix("email:")).map(str::to_string).collect();
+    let phones: Vec<String> = first_seen.keys().filter_map(|key| key.strip_prefix("phone:")).map(str::to_string).collect();
+    let stored: HashSet<String> = T::existing(repos, &scope, &emails, &phones).await?
+        .iter()
+        .flat_map(dedupe_keys)
+        .collect();
+
+    for (outcome, model) in rows.iter_mut().zip(&models) {
+        if outcome.status == RowStatus::Ready && dedupe_keys(model).iter().any(|key| stored.contains(key)) {
+            outcome.status = RowStatus::Duplicate;
+            outcome.errors.push(format!("A {} with this email or phone already exists", T::RESOURCE_NAME.to_lowercase().trim_end_matches('s')));
+        }
+    }
+
+    if !request.dry_run {
+        // One at a time: attendee rows claim seats in file order
+        for (outcome, model) in rows.iter_mut().zip(models) {
+            if outcome.status != RowStatus::Ready {
+                continue;
+            }
+            match model.save(repos, &scope).await {
+                Ok(id) => {
+                    outcome.status = RowStatus::Imported;
+                    outcome.id = id.map(|id| id.to_hex());
+                }
+                Err(e) => {
+                    outcome.status = RowStatus::Failed;
+                    outcome.errors.push(e.to_string());
+                }
+            }
+        }
+    }
+
+    let mut summary = ImportSummary { total: rows.len(), ..ImportSummary::default() };
+    for outcome in &rows {
+        match outcome.status {
+            RowStatus::Ready => summary.ready += 1,
+            RowStatus::Imported => summary.imported += 1,
+            RowStatus::Duplicate => summary.duplicate += 1,
+            RowStatus::Invalid => summary.invalid += 1,
+            RowStatus::Failed => summary.failed += 1,
+        }
+    }
+
+    let error_report = error_report(&headers, &records, &rows)?;
+    Ok(ImportReport { dry_run: request.dry_run, headers, mapping, rows, summary, error_report })
+}
+/*----- END IMPORT -----*/
+
+
+/*----- START HELPERS -----*/
+/// Maps each record onto a model and validates it, marking rows that repeat an earlier
+/// row's email or phone. Also returns the line each dedupe key was first seen on.
+fn map_rows<T: CsvImport>(
+    headers: &[String],
+    records: &[csv::StringRecord],
+    mapping: &BTreeMap<String, &'static str>,
+    scope: &T::Scope,
+) -> (Vec<RowOutcome>, Vec<T>, HashMap<String, usize>) {
+    let columns: Vec<Option<&'static str>> = headers.iter().map(|header| mapping.get(header).copied()).collect();
+    let mut rows = Vec::with_capacity(records.len());
+    let mut models = Vec::with_capacity(records.len());
+    let mut first_seen: HashMap<String, usize> = HashMap::new();
+
+    for (index, record) in records.iter().enumerate() {
+        let line = index + 2;
+        let values: BTreeMap<&'static str, String> = columns.iter()
+            .zip(record.iter())
+            .filter_map(|(field, value)| Some(((*field)?, value.trim().to_string())))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+        let model = T::from_row(&values, scope);
+
+        let mut outcome = RowOutcome { line, status: RowStatus::Ready, errors: model.errors(), values, id: None };
+        if !outcome.errors.is_empty() {
+            outcome.status = RowStatus::Invalid;
+        } else if let Some(earlier) = dedupe_keys(&model).into_iter().find_map(|key| first_seen.get(&key).copied()) {
+            outcome.status = RowStatus::Duplicate;
+            outcome.errors.push(format!("Same email or phone as line {}", earlier));
+        } else {
+            for key in dedupe_keys(&model) {
+                first_seen.insert(key, line);
+            }
+        }
+        rows.push(outcome);
+        models.push(model);
+    }
+
+    (rows, models, first_seen)
+}
+
+fn parse_csv(csv: &str) -> Result<(Vec<String>, Vec<csv::StringRecord>), Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::Headers)
+        .from_reader(csv.trim_start_matches('\u{feff}').as_bytes());
+
+    let headers: Vec<String> = match reader.headers() {
+        Ok(headers) => headers.iter().map(str::to_string).collect(),
+        Err(e) => handle_custom_error!(bad_request, 400, format!("Invalid CSV: {}", e)),
+    };
+    if headers.iter().all(String::is_empty) {
+        handle_custom_error!(bad_request, 400, "The CSV has no header row");
+    }
+
+    let mut records = Vec::new();
+    for record in reader.records() {
+        match record {
+            Ok(record) if record.iter().all(|value| value.trim().is_empty()) => continue,
+            Ok(record) => records.push(record),
+            Err(e) => handle_custom_error!(bad_request, 400, format!("Invalid CSV: {}", e)),
+        }
+        if records.len() > MAX_IMPORT_ROWS {
+            handle_custom_error!(bad_request, 400, format!("At most {} rows can be imported at once", MAX_IMPORT_ROWS));
+        }
+    }
+    if records.is_empty() {
+        handle_custom_error!(bad_request, 400, "The CSV has no rows");
+    }
+
+    Ok((headers, records))
+}
+
+/// The admin's mapping, with unknown fields refused and a field mapped twice reported
+fn checked_mapping<T: CsvImport>(headers: &[String], mapping: &HashMap<String, String>) -> Result<BTreeMap<String, &'static str>, Error> {
+    let mut checked = BTreeMap::new();
+    let mut taken = HashSet::new();
+    for (header, field) in mapping {
+        if field.is_empty() || !headers.contains(header) {
+            continue;
+        }
+        let Some(field) = T::FIELDS.iter().find(|known| known.name == field) else {
+            handle_custom_error!(bad_request, 400, format!("{} cannot be imported", field));
+        };
+        if !taken.insert(field.name) {
+            handle_custom_error!(bad_request, 400, format!("{} is mapped to more than one column", field.label));
+        }
+        checked.insert(header.clone(), field.name);
+    }
+    Ok(checked)
+}
+
+fn header_key(header: &str) -> String {
+    header.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_lowercase()
+}
+
+/// Emails compare case-insensitively, phones as written (the validator only lets digits through)
+fn dedupe_keys<T: CsvImport>(model: &T) -> Vec<String> {
+    let email = model.email().map(str::trim).filter(|email| !email.is_empty()).map(|email| format!("email:{}", email.to_lowercase()));
+    let phone = model.phone().map(str::trim).filter(|phone| !phone.is_empty()).map(|phone| format!("phone:{}", phone));
+    email.into_iter().chain(phone).collect()
+}
+
+/// "field: message" per failed rule, leaving out the `ignored` fields
+fn validation_messages(errors: Option<ValidationErrors>, ignored: &[&str]) -> Vec<String> {
+    let Some(errors) = errors else {
+        return Vec::new();
+    };
+    let mut messages: Vec<String> = errors.field_errors()
+        .into_iter()
+        .filter(|(field, _)| !ignored.contains(field))
+        .flat_map(|(field, errors)| errors.iter().map(move |error| {
+            let message = error.message.as_ref().map(|message| message.to_string()).unwrap_or_else(|| error.code.to_string());
+            format!("{}: {}", field, message)
+        }))
+        .collect();
+    messages.sort();
+    messages
+}
+
+/// The original columns of every row that was not imported, plus its line, status and errors
+fn error_report(headers: &[String], records: &[csv::StringRecord], rows: &[RowOutcome]) -> Result<Option<String>, Error> {
+    let rejected: Vec<(&csv::StringRecord, &RowOutcome)> = records.iter()
+        .zip(rows)
+        .filter(|(_, outcome)| !matches!(outcome.status, RowStatus::Ready | RowStatus::Imported))
+        .collect();
+    if rejected.is_empty() {
+        return Ok(None);
+    }
+
+    let write = || -> Result<Vec<u8>, csv::Error> {
+        let mut writer = csv::WriterBuilder::new().flexible(true).from_writer(Vec::new());
+        writer.write_record(headers.iter().map(String::as_str).chain(["line", "status", "errors"]))?;
+        for (record, outcome) in rejected {
+            let status = serde_json::to_value(outcome.status).ok().and_then(|status| status.as_str().map(str::to_string)).unwrap_or_default();
+            let mut row: Vec<String> = record.iter().map(str::to_string).collect();
+            row.resize(headers.len(), String::new());
+            row.extend([outcome.line.to_string(), status, outcome.errors.join("; ")]);
+            writer.write_record(&row)?;
+        }
+        writer.into_inner().map_err(|e| e.into_error().into())
+    };
+
+    match write() {
+        Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).into_owned())),
+        Err(e) => handle_custom_error!(internal_error, 500, format!("Failed to write the error report: {}", e)),
+    }
+}
+/*----- END HELPERS -----*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn owner() -> User {
+        User { id: Some(ObjectId::new()), ..User::default() }
+    }
+
+    fn mapped(csv: &str) -> (Vec<RowOutcome>, Vec<Contact>) {
+        let (headers, records) = parse_csv(csv).unwrap();
+        let mapping = suggest_mapping::<Contact>(&headers);
+        let (rows, models, _) = map_rows::<Contact>(&headers, &records, &mapping, &owner());
+        (rows, models)
+    }
+
+    #[test]
+    fn headers_map_through_their_aliases() {
+        let headers: Vec<String> = ["First Name", "SURNAME", "E-mail", "Mobile", "Shoe size"].map(String::from).to_vec();
+        let mapping = suggest_mapping::<Contact>(&headers);
+        assert_eq!(mapping.get("First Name"), Some(&"first_name"));
+        assert_eq!(mapping.get("SURNAME"), Some(&"last_name"));
+        assert_eq!(mapping.get("E-mail"), Some(&"email"));
+        assert_eq!(mapping.get("Mobile"), Some(&"phone_number"));
+        assert_eq!(mapping.get("Shoe size"), None);
+    }
+
+    #[test]
+    fn a_field_is_suggested_for_one_column_only() {
+        let headers: Vec<String> = ["Phone", "Mobile"].map(String::from).to_vec();
+        let mapping = suggest_mapping::<Contact>(&headers);
+        assert_eq!(mapping.len(), 1);
+        assert_eq!(mapping.get("Phone"), Some(&"phone_number"));
+    }
+
+    #[test]
+    fn explicit_mappings_refuse_unknown_and_repeated_fields() {
+        let headers: Vec<String> = ["a", "b"].map(String::from).to_vec();
+        let unknown = HashMap::from([("a".to_string(), "password".to_string())]);
+        assert!(checked_mapping::<Contact>(&headers, &unknown).is_err());
+
+        let twice = HashMap::from([("a".to_string(), "email".to_string()), ("b".to_string(), "email".to_string())]);
+        assert!(checked_mapping::<Contact>(&headers, &twice).is_err());
+
+        let ignored = HashMap::from([("a".to_string(), "email".to_string()), ("b".to_string(), String::new()), ("c".to_string(), "notes".to_string())]);
+        assert_eq!(checked_mapping::<Contact>(&headers, &ignored).unwrap(), BTreeMap::from([("a".to_string(), "email")]));
+    }
+
+    #[test]
+    fn parse_skips_blank_rows_and_the_byte_order_mark() {
+        let (headers, records) = parse_csv("\u{feff} Email ,Phone\nA@x.io,1\n , \nb@x.io,2\n").unwrap();
+        assert_eq!(headers, vec!["Email", "Phone"]);
+        assert_eq!(records.len(), 2);
+        assert!(parse_csv("Email\n").is_err());
+    }
+
+    #[test]
+    fn rows_are_mapped_onto_the_model_under_the_scope() {
+        let (rows, models) = mapped("First Name,Last Name,Email,Phone,Company\n Jane , Doe ,jane@x.io,15550100,\n");
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].status, RowStatus::Ready, "{:?}", rows[0].errors);
+        assert_eq!(rows[0].values.get("first_name").map(String::as_str), Some("Jane"));
+        assert!(!rows[0].values.contains_key("company_name"));
+        assert_eq!(models[0].email.as_deref(), Some("jane@x.io"));
+        assert!(models[0].parent_user_id.is_some());
+    }
+
+    #[test]
+    fn invalid_rows_carry_their_errors() {
+        let (rows, _) = mapped("First Name,Last Name,Email,Phone\nJane,Doe,not-an-email,15550100\n");
+        assert_eq!(rows[0].status, RowStatus::Invalid);
+        assert!(rows[0].errors.iter().any(|error| error.starts_with("email:")));
+    }
+
+    #[test]
+    fn later_rows_sharing_an_email_or_phone_are_duplicates() {
+        let (rows, _) = mapped(concat!(
+            "First Name,Last Name,Email,Phone\n",
+            "Jane,Doe,jane@x.io,15550100\n",
+            "Jane,Doe,JANE@X.IO,15550199\n",
+            "John,Roe,john@x.io,15550100\n",
+            "Ann,Lee,ann@x.io,15550123\n",
+        ));
+        let statuses: Vec<RowStatus> = rows.iter().map(|row| row.status).collect();
+        assert_eq!(statuses, vec![RowStatus::Ready, RowStatus::Duplicate, RowStatus::Duplicate, RowStatus::Ready]);
+        assert_eq!(rows[1].errors, vec!["Same email or phone as line 2".to_string()]);
+    }
+}
@@ -0,0 +1,236 @@
+// services/invitation_service.rs
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use actix_web::Error;
+use log::{error, info};
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+
+use crate::config::constants::{
+    INVITATION_EXPIRY_JOB_SECONDS,
+    INVITATION_MAX_RECIPIENTS,
+    INVITATION_RATE_WINDOW_SECONDS,
+};
+use crate::enums::common_enums::StatusEnum;
+use crate::errors::custom_error::CustomError;
+use crate::libs::general_library::parse_date_param;
+use crate::models::{
+    event_attendee::EventAttendee,
+    invitation::Invitation,
+    user::User,
+};
+use crate::repositories::Repositories;
+use crate::requests::{
+    enums::event_enums::RegistrationTypeEnum,
+    structures::event_structure::{
+        EventInvitationRequest,
+        InvitationResponseRequest,
+        JoinEventRequest,
+    },
+    validators::event_validators::validate_event_capacity,
+};
+use crate::services::attendance_service::{
+    cancel_registration,
+    ensure_event_open,
+    register_plus_one,
+    register_user,
+};
+use crate::services::mail_service;
+use crate::services::redis_service::{redis_incr_by_with_expiry, redis_ttl};
+use crate::utilities::bason_utility::parse_object_id;
+use crate::{custom_error_expression, handle_custom_error};
+
+
+/*----- START CREATE -----*/
+/// Counts the recipients an organizer invites, so invitations cannot be used to mail
+/// arbitrary addresses in bulk
+async fn count_recipients(organizer_id: ObjectId, recipients: usize) -> Result<(), Error> {
+    let key = format!("invitations:sent:{}", organizer_id.to_hex());
+    let sent = redis_incr_by_with_expiry(key.clone(), recipients as i64, INVITATION_RATE_WINDOW_SECONDS).await
+        .map_err(|e| {
+            error!("Invitation limits unavailable: {}", e);
+            custom_error_expression!(internal_error, 503, "Invitations are temporarily unavailable")
+        })?;
+
+    if sent > INVITATION_MAX_RECIPIENTS {
+        let retry_after = redis_ttl(key).await.unwrap_or(INVITATION_RATE_WINDOW_SECONDS as i64);
+        let minutes = ((retry_after + 59) / 60).max(1);
+        return Err(CustomError::InvalidRequest(429, format!("Too many invitations sent, try again in {} minute(s)", minutes)).into());
+    }
+    Ok(())
+}
+
+/// Creates one invitation per new recipient. Recipients that already hold a live
+/// invitation to the event are skipped. Only the event's organizer can invite.
+pub async fn create_invitations(repos: &Repositories, organizer_id: ObjectId, payload: &EventInvitationRequest) -> Result<Vec<Invitation>, Error> {
+    let event_id = parse_object_id(&payload.event_id, "event_id")?;
+    let event = repos.events.find_by_id(event_id).await?;
+    if event.user_id != organizer_id {
+        handle_custom_error!(forbidden, 403, "Only the organizer can invite to this event");
+    }
+
+    // Invitations stop being usable once registration closes
+    let expires_at = match payload.expires_at.as_deref() {
+        Some(value) => match parse_date_param(value, true) {
+            Some(expires_at) => expires_at,
+            None => handle_custom_error!(bad_request, 400, "Invalid expires_at"),
+        },
+        None => event.registration_deadline.unwrap_or(event.start_time),
+    };
+    if expires_at.timestamp_millis() <= BsonDateTime::now().timestamp_millis() {
+        handle_custom_error!(bad_request, 400, "expires_at must be in the future");
+    }
+
+    let (existing_user_ids, existing_emails) = repos.invitations.existing_recipients(event_id).await?;
+    let mut seen_user_ids: HashSet<ObjectId> = existing_user_ids.into_iter().collect();
+    let mut seen_emails: HashSet<String> = existing_emails.into_iter().collect();
+
+    let mut recipients: Vec<(Option<ObjectId>, Option<String>)> = Vec::new();
+    for user_id in payload.user_ids.iter().flatten() {
+        let user_id = parse_object_id(user_id, "user_ids")?;
+        if seen_user_ids.insert(user_id) {
+            recipients.push((Some(user_id), None));
+        }
+    }
+    for email in payload.emails.iter().flatten() {
+        let email = email.trim().to_lowercase();
+        if seen_emails.insert(email.clone()) {
+            recipients.push((None, Some(email)));
+        }
+    }
+
+    if recipients.is_empty() {
+        return Ok(Vec::new());
+    }
+    count_recipients(organizer_id, recipients.len()).await?;
+
+    let invitations = recipients.into_iter()
+        .map(|(user_id, email)| Invitation {
+            invite_type: Some(payload.invite_type.clone().unwrap_or_else(|| "direct".to_string())),
+            expires_at: Some(expires_at),
+            requires_response: payload.requires_response.unwrap_or(false),
+            allow_plus_one: payload.allow_plus_one.unwrap_or(false),
+            x_platform: payload.x_platform.clone(),
+            ..Invitation::for_event(event_id, user_id, email, payload.message.clone())
+        })
+        .collect();
+
+    let invitations = repos.invitations.insert_many(invitations).await?;
+    mail_service::notify_invitations(repos, &event, &invitations).await;
+    Ok(invitations)
+}
+/*----- END CREATE -----*/
+
+
+/*----- START RESPOND -----*/
+/// Only pending, unexpired invitations can be answered
+async fn ensure_pending(repos: &Repositories, invitation: &Invitation) -> Result<(), Error> {
+    if invitation.status != StatusEnum::Pending {
+        handle_custom_error!(conflict, 409, format!("Invitation already {}", invitation.status.lowercase()));
+    }
+
+    if invitation.is_expired() {
+        repos.invitations.expire_pending().await?;
+        handle_custom_error!(bad_request, 400, "Invitation has expired");
+    }
+
+    Ok(())
+}
+
+/// Only the recipient answers an invitation: the invited user, or for email-only
+/// invitations a signed-in user with that email
+fn ensure_recipient(invitation: &Invitation, user: &User) -> Result<ObjectId, Error> {
+    let Some(user_id) = user.id else {
+        handle_custom_error!(internal_error, 500, "User has no id");
+    };
+
+    let is_recipient = match (invitation.user_id, invitation.email.as_deref()) {
+        (Some(invited), _) => invited == user_id,
+        (None, Some(invited)) => user.email.as_deref().is_some_and(|email| email.eq_ignore_ascii_case(invited)),
+        (None, None) => false,
+    };
+    if !is_recipient {
+        handle_custom_error!(forbidden, 403, "Invitation belongs to another user");
+    }
+    Ok(user_id)
+}
+
+/// Accepts an invitation: registers the user (and optionally a plus one) for the event
+/// and marks the invitation accepted.
+pub async fn accept_invitation(
+    repos: &Repositories,
+    token: &str,
+    user: &User,
+    payload: &InvitationResponseRequest,
+) -> Result<(EventAttendee, Option<EventAttendee>), Error> {
+    let invitation = repos.invitations.find_by_token(token).await?;
+    ensure_pending(repos, &invitation).await?;
+
+    let Some(event_id) = invitation.event_id else {
+        handle_custom_error!(bad_request, 400, "Not an event invitation");
+    };
+    let user_id = ensure_recipient(&invitation, user)?;
+
+    let event = repos.events.find_by_id(event_id).await?;
+    ensure_event_open(&event)?;
+    let user = repos.users.find_active_by_id(user_id).await?;
+
+    let wants_plus_one = payload.plus_one.unwrap_or(false);
+    if wants_plus_one && !invitation.allow_plus_one {
+        handle_custom_error!(bad_request, 400, "Plus one is not allowed for this invitation");
+    }
+    if wants_plus_one && validate_event_capacity(event.current_attendees + 2, event.max_attendees).is_err() {
+        handle_custom_error!(conflict, 409, "Not enough seats for a plus one");
+    }
+
+    let attendee = register_user(repos, &event, &user, &JoinEventRequest::default(), RegistrationTypeEnum::Invited).await?;
+
+    let guest = if wants_plus_one {
+        match register_plus_one(repos, event_id, &attendee, payload.plus_one_name.clone()).await {
+            Ok(guest) => Some(guest),
+            Err(e) => {
+                if let Some(attendee_id) = attendee.id {
+                    cancel_registration(repos, attendee_id, Some("Plus one could not be seated".to_string())).await?;
+                }
+                return Err(e);
+            }
+        }
+    } else {
+        None
+    };
+
+    let plus_one_name = guest.as_ref().and_then(|guest| guest.first_name.clone());
+    repos.invitations.respond(&invitation, StatusEnum::Accepted, Some(user_id), attendee.id, plus_one_name).await?;
+    mail_service::notify_registration(repos, &event, &attendee);
+
+    Ok((attendee, guest))
+}
+
+/// Declines an invitation
+pub async fn decline_invitation(repos: &Repositories, token: &str, user: &User) -> Result<(), Error> {
+    let invitation = repos.invitations.find_by_token(token).await?;
+    ensure_pending(repos, &invitation).await?;
+    let user_id = ensure_recipient(&invitation, user)?;
+
+    repos.invitations.respond(&invitation, StatusEnum::Declined, Some(user_id), None, None).await
+}
+/*----- END RESPOND -----*/
+
+
+/*----- START EXPIRY JOB -----*/
+/// Periodically flips overdue pending invitations to expired
+pub fn spawn_expiry_job(repos: Repositories) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(INVITATION_EXPIRY_JOB_SECONDS));
+        loop {
+            interval.tick().await;
+            match repos.invitations.expire_pending().await {
+                Ok(0) => {}
+                Ok(expired) => info!("Expired {} pending invitations", expired),
+                Err(e) => error!("Invitation expiry job failed: {}", e),
+            }
+        }
+    });
+}
+/*----- END EXPIRY JOB -----*/
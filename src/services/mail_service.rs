@@ -0,0 +1,524 @@
+// services/mail_service.rs
+//
+// Outgoing email. Messages are rendered from the Tera templates in `src/templates/emails`
+// when queued, logged in `sent_emails` and handed to the configured `MailTransport`.
+// Failed attempts are retried with backoff by the delivery job.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use actix_web::Error;
+use async_trait::async_trait;
+use lettre::{
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport,
+    AsyncTransport,
+    Message,
+    Tokio1Executor,
+};
+use log::{error, info, warn};
+use mongodb::bson::{oid::ObjectId, DateTime as BsonDateTime};
+use once_cell::sync::{Lazy, OnceCell};
+use serde_json::{json, Value};
+use tera::{Context, Tera};
+
+use crate::config::constants::{
+    EMAIL_DELIVERY_JOB_SECONDS,
+    EMAIL_GENERAL_LABEL,
+    EVENT_REMINDER_JOB_SECONDS,
+    EVENT_REMINDER_LEAD_SECONDS,
+    OTP_EXPIRY,
+    PRODUCT_NAME,
+    SUPPORT_URI,
+    WEBSITE_URI,
+};
+use crate::config::env_vars::{get_custom_env, is_production};
+use crate::enums::common_enums::{EmailKindEnum, SupportRequest};
+use crate::models::{
+    event::Event,
+    event_attendee::EventAttendee,
+    invitation::Invitation,
+    sent_email::SentEmail,
+};
+use crate::repositories::Repositories;
+use crate::utilities::bason_utility::parse_object_id;
+use crate::{
+    custom_error_expression,
+    handle_custom_error,
+};
+
+/// Wait before each retry; an email is given up on once its last retry fails
+const RETRY_BACKOFF_SECONDS: [i64; 4] = [60, 300, 1800, 7200];
+
+/// How long an attempt may take before the email counts as abandoned and is claimed again
+const SEND_LEASE_SECONDS: i64 = 300;
+
+
+/*----- START TEMPLATES -----*/
+static EMAIL_TEMPLATES: Lazy<Tera> = Lazy::new(|| {
+    let mut tera = Tera::default();
+    tera.add_raw_templates(vec![
+        ("emails/layout.html.tera", include_str!("../templates/emails/layout.html.tera")),
+        ("emails/layout.txt.tera", include_str!("../templates/emails/layout.txt.tera")),
+        ("emails/event_details.html.tera", include_str!("../templates/emails/event_details.html.tera")),
+        ("emails/event_details.txt.tera", include_str!("../templates/emails/event_details.txt.tera")),
+        ("emails/invitation.html.tera", include_str!("../templates/emails/invitation.html.tera")),
+        ("emails/invitation.txt.tera", include_str!("../templates/emails/invitation.txt.tera")),
+        ("emails/registration_confirmation.html.tera", include_str!("../templates/emails/registration_confirmation.html.tera")),
+        ("emails/registration_confirmation.txt.tera", include_str!("../templates/emails/registration_confirmation.txt.tera")),
+        ("emails/event_reminder.html.tera", include_str!("../templates/emails/event_reminder.html.tera")),
+        ("emails/event_reminder.txt.tera", include_str!("../templates/emails/event_reminder.txt.tera")),
+        ("emails/otp_code.html.tera", include_str!("../templates/emails/otp_code.html.tera")),
+        ("emails/otp_code.txt.tera", include_str!("../templates/emails/otp_code.txt.tera")),
+        ("emails/support_ticket.html.tera", include_str!("../templates/emails/support_ticket.html.tera")),
+        ("emails/support_ticket.txt.tera", include_str!("../templates/emails/support_ticket.txt.tera")),
+    ])
+    .unwrap_or_else(|e| panic!("Failed to add email templates: {:?}", e));
+
+    // User-supplied text (names, invitation messages) ends up in the HTML part
+    tera.autoescape_on(vec![".html.tera"]);
+    tera
+});
+
+fn template_name(kind: EmailKindEnum) -> &'static str {
+    match kind {
+        EmailKindEnum::Invitation => "invitation",
+        EmailKindEnum::RegistrationConfirmation => "registration_confirmation",
+        EmailKindEnum::EventReminder => "event_reminder",
+        EmailKindEnum::OtpCode => "otp_code",
+        EmailKindEnum::SupportTicket => "support_ticket",
+    }
+}
+
+/// Renders both parts of a `kind` email to `to`
+fn compose(kind: EmailKindEnum, to: &str, subject: String, mut ctx: Context) -> Result<SentEmail, Error> {
+    ctx.insert("product_name", PRODUCT_NAME);
+    ctx.insert("website_uri", WEBSITE_URI);
+    ctx.insert("support_uri", SUPPORT_URI);
+
+    let render = |extension: &str| {
+        let name = format!("emails/{}.{}.tera", template_name(kind), extension);
+        EMAIL_TEMPLATES.render(&name, &ctx).map_err(|e| {
+            error!("Email template render error for {}: {:?}", name, e);
+            custom_error_expression!(internal_error, 500, format!("Failed to render {}", name))
+        })
+    };
+    let html_body = render("html")?;
+    let text_body = render("txt")?;
+
+    Ok(SentEmail::queued(kind, to.trim().to_string(), subject, html_body, text_body))
+}
+
+fn format_time(time: BsonDateTime) -> String {
+    time.to_chrono().format("%A, %-d %B %Y at %H:%M UTC").to_string()
+}
+
+fn event_context(event: &Event) -> Value {
+    let non_empty = |value: &Option<String>| value.clone().filter(|value| !value.trim().is_empty());
+    json!({
+        "title": event.title,
+        "starts": format_time(event.start_time),
+        "location": non_empty(&event.location).or_else(|| non_empty(&Some(event.address.clone()))),
+        "meeting_link": non_empty(&event.meeting_link),
+        "organizer": non_empty(&event.organizer_name),
+        "instructions": non_empty(&event.special_instructions),
+    })
+}
+
+fn attendee_name(attendee: &EventAttendee) -> Option<String> {
+    attendee.first_name.clone().filter(|name| !name.trim().is_empty())
+}
+/*----- END TEMPLATES -----*/
+
+
+/*----- START MESSAGES -----*/
+pub fn invitation_email(to: &str, event: &Event, invitation: &Invitation) -> Result<SentEmail, Error> {
+    let mut ctx = Context::new();
+    ctx.insert("event", &event_context(event));
+    ctx.insert("message", &invitation.message);
+    ctx.insert("invitation_url", &format!("{}/invitations/{}", WEBSITE_URI, invitation.token.as_deref().unwrap_or_default()));
+    ctx.insert("expires", &invitation.expires_at.map(format_time));
+
+    let email = compose(EmailKindEnum::Invitation, to, format!("You're invited to {}", event.title), ctx)?;
+    Ok(SentEmail { related_id: invitation.id, ..email })
+}
+
+/// Confirms a registration, or a place on the waitlist when the event was full
+pub fn registration_email(to: &str, event: &Event, attendee: &EventAttendee) -> Result<SentEmail, Error> {
+    let waitlisted = attendee.waitlisted_at.is_some();
+    let mut ctx = Context::new();
+    ctx.insert("event", &event_context(event));
+    ctx.insert("name", &attendee_name(attendee));
+    ctx.insert("waitlisted", &waitlisted);
+
+    let subject = if waitlisted {
+        format!("You're on the waitlist for {}", event.title)
+    } else {
+        format!("You're registered for {}", event.title)
+    };
+    let email = compose(EmailKindEnum::RegistrationConfirmation, to, subject, ctx)?;
+    Ok(SentEmail { related_id: attendee.id, ..email })
+}
+
+pub fn reminder_email(to: &str, event: &Event, attendee: &EventAttendee) -> Result<SentEmail, Error> {
+    let mut ctx = Context::new();
+    ctx.insert("event", &event_context(event));
+    ctx.insert("name", &attendee_name(attendee));
+
+    let email = compose(EmailKindEnum::EventReminder, to, format!("Reminder: {} starts soon", event.title), ctx)?;
+    Ok(SentEmail { related_id: attendee.id, ..email })
+}
+
+/// The code is dropped from the log once the email is sent or given up on
+pub fn otp_email(to: &str, code: &str) -> Result<SentEmail, Error> {
+    let mut ctx = Context::new();
+    ctx.insert("code", code);
+    ctx.insert("expires_in_minutes", &(OTP_EXPIRY / 60));
+
+    let email = compose(EmailKindEnum::OtpCode, to, format!("Your {} verification code", PRODUCT_NAME), ctx)?;
+    Ok(SentEmail { sensitive: true, ..email })
+}
+
+/// A ticket for the support inbox; replies go to whoever opened it
+pub fn support_email(to: &str, reply_to: &str, subject: &str, description: &str, user_id: Option<ObjectId>) -> Result<SentEmail, Error> {
+    let mut ctx = Context::new();
+    ctx.insert("reply_to", reply_to);
+    ctx.insert("subject", subject);
+    ctx.insert("description", description);
+    ctx.insert("user_id", &user_id.map(|user_id| user_id.to_hex()));
+
+    let email = compose(EmailKindEnum::SupportTicket, to, format!("[Support] {}", subject), ctx)?;
+    Ok(SentEmail { reply_to: Some(reply_to.to_string()), related_id: user_id, ..email })
+}
+/*----- END MESSAGES -----*/
+
+
+/*----- START TRANSPORTS -----*/
+#[async_trait]
+pub trait MailTransport: Send + Sync {
+    /// Recorded on each email it delivers
+    fn name(&self) -> &'static str;
+
+    /// Delivers one message; the error is kept as the email's `last_error`
+    async fn send(&self, message: Message) -> Result<(), String>;
+}
+
+pub struct SmtpTransport {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpTransport {
+    /// `SMTP_HOST`, `SMTP_PORT`, `SMTP_USERNAME` and `SMTP_PASSWORD`. `SMTP_TLS` is
+    /// `starttls` (default), `tls` for implicit TLS or `none` for a local mail catcher.
+    pub fn from_env() -> Result<Self, String> {
+        let host = get_custom_env("SMTP_HOST", "localhost");
+        let mut builder = match get_custom_env("SMTP_TLS", "starttls").as_str() {
+            "none" => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&host),
+            "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&host).map_err(|e| e.to_string())?,
+            _ => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host).map_err(|e| e.to_string())?,
+        };
+
+        let port = get_custom_env("SMTP_PORT", "");
+        if !port.is_empty() {
+            builder = builder.port(port.parse().map_err(|_| format!("Invalid SMTP_PORT: {}", port))?);
+        }
+        let username = get_custom_env("SMTP_USERNAME", "");
+        if !username.is_empty() {
+            builder = builder.credentials(Credentials::new(username, get_custom_env("SMTP_PASSWORD", "")));
+        }
+
+        Ok(SmtpTransport { transport: builder.build() })
+    }
+}
+
+#[async_trait]
+impl MailTransport for SmtpTransport {
+    fn name(&self) -> &'static str {
+        "smtp"
+    }
+
+    async fn send(&self, message: Message) -> Result<(), String> {
+        self.transport.send(message).await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Writes each message to `dir` as an `.eml` file, for local development and tests
+pub struct FileTransport {
+    dir: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileTransport { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl MailTransport for FileTransport {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, message: Message) -> Result<(), String> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| e.to_string())?;
+        let path = self.dir.join(format!("{}_{}.eml", BsonDateTime::now().timestamp_millis(), nanoid::nanoid!(8)));
+        tokio::fs::write(&path, message.formatted()).await.map_err(|e| e.to_string())
+    }
+}
+/*----- END TRANSPORTS -----*/
+
+
+/*----- START MAILER -----*/
+struct Mailer {
+    transport: Box<dyn MailTransport>,
+    from: Mailbox,
+}
+
+static MAILER: OnceCell<Mailer> = OnceCell::new();
+
+impl Mailer {
+    fn message(&self, email: &SentEmail) -> Result<Message, String> {
+        let to: Mailbox = email.to.parse().map_err(|e| format!("Invalid recipient {}: {}", email.to, e))?;
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(email.subject.as_str());
+        if let Some(reply_to) = &email.reply_to {
+            builder = builder.reply_to(reply_to.parse().map_err(|e| format!("Invalid reply-to {}: {}", reply_to, e))?);
+        }
+
+        builder
+            .multipart(MultiPart::alternative_plain_html(
+                email.text_body.clone().unwrap_or_default(),
+                email.html_body.clone().unwrap_or_default(),
+            ))
+            .map_err(|e| e.to_string())
+    }
+}
+
+fn website_domain() -> &'static str {
+    WEBSITE_URI.trim_start_matches("https://")
+}
+
+/// Sets up the mailer from `MAIL_TRANSPORT` (`smtp`, or `file` to write `.eml` files to
+/// `MAIL_FILE_DIR`; SMTP in production, files otherwise) and `MAIL_FROM`
+pub fn init_mailer() {
+    let default_from = format!("{} <no-reply@{}>", EMAIL_GENERAL_LABEL, website_domain());
+    let from = match get_custom_env("MAIL_FROM", &default_from).parse::<Mailbox>() {
+        Ok(from) => from,
+        Err(e) => {
+            error!("❌ Invalid MAIL_FROM, emails will stay queued: {}", e);
+            return;
+        }
+    };
+
+    let default_transport = if is_production() { "smtp" } else { "file" };
+    let transport: Box<dyn MailTransport> = match get_custom_env("MAIL_TRANSPORT", default_transport).as_str() {
+        "smtp" => match SmtpTransport::from_env() {
+            Ok(transport) => Box::new(transport),
+            Err(e) => {
+                error!("❌ Failed to configure SMTP, emails will stay queued: {}", e);
+                return;
+            }
+        },
+        _ => Box::new(FileTransport::new(get_custom_env("MAIL_FILE_DIR", "tmp/emails"))),
+    };
+
+    let name = transport.name();
+    if MAILER.set(Mailer { transport, from }).is_ok() {
+        info!("Mailer initialized with the {} transport", name);
+    } else {
+        error!("Mailer was already initialized.");
+    }
+}
+/*----- END MAILER -----*/
+
+
+/*----- START QUEUE -----*/
+fn seconds_from_now(seconds: i64) -> BsonDateTime {
+    BsonDateTime::from_millis(BsonDateTime::now().timestamp_millis() + seconds * 1000)
+}
+
+/// Logs the email as queued and makes the first attempt in the background
+pub async fn queue(repos: &Repositories, email: SentEmail) -> Result<SentEmail, Error> {
+    let email = repos.emails.insert(&email).await?;
+    if let Some(email_id) = email.id {
+        let repos = repos.clone();
+        tokio::spawn(async move {
+            deliver(&repos, Some(email_id)).await;
+        });
+    }
+    Ok(email)
+}
+
+/// Puts a failed email back in the queue with fresh attempts and tries it right away.
+/// False when the email had not failed, or its body was already dropped.
+pub async fn retry(repos: &Repositories, email_id: ObjectId) -> Result<bool, Error> {
+    if !repos.emails.requeue(email_id).await? {
+        return Ok(false);
+    }
+    let repos = repos.clone();
+    tokio::spawn(async move {
+        deliver(&repos, Some(email_id)).await;
+    });
+    Ok(true)
+}
+
+/// One attempt at the email `id`, or at the next one due. False when there was
+/// nothing to attempt.
+async fn deliver(repos: &Repositories, id: Option<ObjectId>) -> bool {
+    let Some(mailer) = MAILER.get() else {
+        warn!("Mailer not initialized, leaving emails queued");
+        return false;
+    };
+
+    let email = match repos.emails.claim(id, seconds_from_now(SEND_LEASE_SECONDS)).await {
+        Ok(Some(email)) => email,
+        Ok(None) => return false,
+        Err(e) => {
+            error!("Failed to claim a queued email: {}", e);
+            return false;
+        }
+    };
+    let Some(email_id) = email.id else {
+        return false;
+    };
+
+    // A message that cannot be built now will not build on a retry either
+    let (result, retry) = match mailer.message(&email) {
+        Ok(message) => (mailer.transport.send(message).await, true),
+        Err(reason) => (Err(reason), false),
+    };
+
+    let recorded = match result {
+        Ok(()) => repos.emails.mark_sent(email_id, mailer.transport.name(), email.sensitive).await,
+        Err(reason) => {
+            let retry_at = RETRY_BACKOFF_SECONDS.get(email.attempts.saturating_sub(1) as usize)
+                .filter(|_| retry)
+                .map(|seconds| seconds_from_now(*seconds));
+            warn!("Email {} to {} failed (attempt {}): {}", email_id, email.to, email.attempts, reason);
+            repos.emails.mark_failed(email_id, &reason, retry_at, email.sensitive).await
+        }
+    };
+    if let Err(e) = recorded {
+        error!("Failed to record delivery of email {}: {}", email_id, e);
+    }
+    true
+}
+
+/// Queues `email` in the background, logging instead of failing: the action it is
+/// about has already succeeded and the email is a side effect
+fn queue_or_log(repos: &Repositories, email: Result<SentEmail, Error>) {
+    match email {
+        Ok(email) => {
+            let repos = repos.clone();
+            tokio::spawn(async move {
+                if let Err(e) = queue(&repos, email).await {
+                    error!("Failed to queue email: {}", e);
+                }
+            });
+        }
+        Err(e) => error!("Failed to compose email: {}", e),
+    }
+}
+
+/// Periodically retries queued emails that are due, and ones abandoned mid-attempt
+pub fn spawn_delivery_job(repos: Repositories) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(EMAIL_DELIVERY_JOB_SECONDS));
+        loop {
+            interval.tick().await;
+            let mut attempted = 0;
+            while deliver(&repos, None).await {
+                attempted += 1;
+            }
+            if attempted > 0 {
+                info!("Attempted delivery of {} queued emails", attempted);
+            }
+        }
+    });
+}
+/*----- END QUEUE -----*/
+
+
+/*----- START NOTIFICATIONS -----*/
+/// One email per invitation. Invitations to users go to their account email.
+pub async fn notify_invitations(repos: &Repositories, event: &Event, invitations: &[Invitation]) {
+    for invitation in invitations {
+        let to = match (&invitation.email, invitation.user_id) {
+            (Some(email), _) => Some(email.clone()),
+            (None, Some(user_id)) => repos.users.find_active_by_id(user_id).await.ok().and_then(|user| user.email),
+            (None, None) => None,
+        };
+        if let Some(to) = to {
+            queue_or_log(repos, invitation_email(&to, event, invitation));
+        }
+    }
+}
+
+pub fn notify_registration(repos: &Repositories, event: &Event, attendee: &EventAttendee) {
+    if let Some(to) = attendee.email.as_deref() {
+        queue_or_log(repos, registration_email(to, event, attendee));
+    }
+}
+
+/// Sends a support ticket to the `SUPPORT_EMAIL` inbox
+pub async fn open_support_ticket(repos: &Repositories, payload: &SupportRequest) -> Result<SentEmail, Error> {
+    let user_id = payload.user_id.as_deref()
+        .filter(|user_id| !user_id.trim().is_empty())
+        .map(|user_id| parse_object_id(user_id, "user_id"))
+        .transpose()?;
+    let account_email = match user_id {
+        Some(user_id) => repos.users.find_active_by_id(user_id).await?.email,
+        None => None,
+    };
+    let Some(reply_to) = payload.email.clone().or(account_email) else {
+        handle_custom_error!(bad_request, 400, "email is required to get a reply");
+    };
+
+    let inbox = get_custom_env("SUPPORT_EMAIL", &format!("support@{}", website_domain()));
+    let email = support_email(&inbox, &reply_to, payload.subject.trim(), payload.description.trim(), user_id)?;
+    queue(repos, email).await
+}
+
+/// Queues reminders to the registered attendees of every event starting within
+/// `EVENT_REMINDER_LEAD_SECONDS`. Each event is reminded once.
+pub async fn queue_due_reminders(repos: &Repositories) -> Result<u64, Error> {
+    let starts_before = seconds_from_now(EVENT_REMINDER_LEAD_SECONDS);
+    let mut queued = 0;
+    // `let`s rather than `while let`, so the job's future stays `Send`
+    loop {
+        let Some(event) = repos.events.claim_reminder(starts_before).await? else {
+            break;
+        };
+        let Some(event_id) = event.id else {
+            continue;
+        };
+        let attendees = repos.attendees.registered_for_event(event_id).await?;
+        for attendee in attendees {
+            if let Some(to) = attendee.email.as_deref() {
+                queue_or_log(repos, reminder_email(to, &event, &attendee));
+                queued += 1;
+            }
+        }
+    }
+    Ok(queued)
+}
+
+pub fn spawn_reminder_job(repos: Repositories) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(EVENT_REMINDER_JOB_SECONDS));
+        loop {
+            interval.tick().await;
+            match queue_due_reminders(&repos).await {
+                Ok(0) => {}
+                Ok(queued) => info!("Queued {} event reminders", queued),
+                Err(e) => error!("Event reminder job failed: {}", e),
+            }
+        }
+    });
+}
+/*----- END NOTIFICATIONS -----*/
@@ -1,2 +1,15 @@
 // services/mod.rs
 pub mod redis_service;
+pub mod attendance_service;
+pub mod invitation_service;
+pub mod analytics_service;
+pub mod search_service;
+pub mod ban_service;
+pub mod audit_service;
+pub mod import_service;
+pub mod contact_exchange_service;
+pub mod mail_service;
+pub mod sms_service;
+pub mod auth_service;
+pub mod two_factor_service;
+pub mod admin_user_service;
@@ -83,12 +83,82 @@ pub async fn redis_delete_key(key: String) -> Result<(), RedisError> {
     let mut conn = get_redis_connection().await?;
     conn.del(&key).await
 }
+
+/// Gets and deletes a key in one step, so only one caller ever sees the value
+pub async fn redis_take_key(key: String) -> Result<Option<String>, RedisError> {
+    let mut conn = get_redis_connection().await?;
+    conn.get_del(&key).await
+}
 /*------------------------------------------------------------*/
 /// END Set key with expiry (in seconds)
 /*------------------------------------------------------------*/
 
 
 
+/*------------------------------------------------------------
+/// START  Counters with expiry (in seconds)
+------------------------------------------------------------*/
+/// Increments a counter, starting its expiry when it is created, so it counts
+/// within a fixed window. Returns the new count.
+pub async fn redis_incr_with_expiry(key: String, expiry_seconds: usize) -> Result<i64, RedisError> {
+    redis_incr_by_with_expiry(key, 1, expiry_seconds).await
+}
+
+/// Like `redis_incr_with_expiry`, adding `amount` at once
+pub async fn redis_incr_by_with_expiry(key: String, amount: i64, expiry_seconds: usize) -> Result<i64, RedisError> {
+    let mut conn = get_redis_connection().await?;
+    let count: i64 = conn.incr(&key, amount).await?;
+    if count == amount {
+        let _: () = conn.expire(&key, expiry_seconds as i64).await?;
+    }
+    Ok(count)
+}
+
+/// Current value of a counter; 0 when it does not exist or has expired
+pub async fn redis_get_counter(key: String) -> Result<i64, RedisError> {
+    let mut conn = get_redis_connection().await?;
+    let count: Option<i64> = conn.get(&key).await?;
+    Ok(count.unwrap_or(0))
+}
+
+/// Seconds until the key expires; 0 when it does not exist
+pub async fn redis_ttl(key: String) -> Result<i64, RedisError> {
+    let mut conn = get_redis_connection().await?;
+    let ttl: i64 = conn.ttl(&key).await?;
+    Ok(ttl.max(0))
+}
+/*------------------------------------------------------------
+/// END  Counters with expiry (in seconds)
+------------------------------------------------------------*/
+
+
+
+/*------------------------------------------------------------
+/// START  Sets with expiry (in seconds)
+------------------------------------------------------------*/
+/// Adds a member to a set and restarts the expiry of the whole set
+pub async fn redis_add_to_set_with_expiry(key: String, member: String, expiry_seconds: usize) -> Result<(), RedisError> {
+    let mut conn = get_redis_connection().await?;
+    let _: () = conn.sadd(&key, &member).await?;
+    let _: () = conn.expire(&key, expiry_seconds as i64).await?;
+    Ok(())
+}
+
+pub async fn redis_get_set_members(key: String) -> Result<Vec<String>, RedisError> {
+    let mut conn = get_redis_connection().await?;
+    conn.smembers(&key).await
+}
+
+pub async fn redis_remove_from_set(key: String, member: String) -> Result<(), RedisError> {
+    let mut conn = get_redis_connection().await?;
+    conn.srem(&key, &member).await
+}
+/*------------------------------------------------------------
+/// END  Sets with expiry (in seconds)
+------------------------------------------------------------*/
+
+
+
 /*------------------------------------------------------------
 /// START  Set a list of string values with expiry
 ------------------------------------------------------------*/
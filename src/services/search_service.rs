@@ -0,0 +1,200 @@
+// services/search_service.rs
+
+use actix_web::Error;
+use futures::TryStreamExt;
+use mongodb::{
+    Database,
+    bson::{doc, Bson, Document},
+    options::FindOptions,
+};
+use serde_json::{json, Value};
+
+use crate::requests::structures::search_structure::SearchRequest;
+use crate::custom_error_expression;
+
+const SNIPPET_CONTEXT: usize = 40;
+
+
+/*----- START TARGETS -----*/
+/// A searchable collection. `fields` must match the collection's text index
+/// (see `init_indexes_and_uniqness`); the first non-empty `title_fields` entry labels a hit.
+struct SearchTarget {
+    name: &'static str,
+    collection: &'static str,
+    admin_path: &'static str,
+    fields: &'static [&'static str],
+    title_fields: &'static [&'static str],
+}
+
+const SEARCH_TARGETS: [SearchTarget; 3] = [
+    SearchTarget {
+        name: "events",
+        collection: "events",
+        admin_path: "events",
+        fields: &["title", "description", "tags", "organizer_name"],
+        title_fields: &["title"],
+    },
+    SearchTarget {
+        name: "contacts",
+        collection: "Contacts",
+        admin_path: "contacts",
+        fields: &["first_name", "last_name", "company_name", "email"],
+        title_fields: &["first_name", "company_name", "email"],
+    },
+    SearchTarget {
+        name: "users",
+        collection: "users",
+        admin_path: "users",
+        fields: &["first_name", "last_name", "username", "email", "company_name"],
+        title_fields: &["first_name", "username", "email"],
+    },
+];
+/*----- END TARGETS -----*/
+
+
+/*----- START HIGHLIGHTING -----*/
+fn field_text(doc: &Document, field: &str) -> Option<String> {
+    match doc.get(field)? {
+        Bson::String(value) => Some(value.clone()),
+        Bson::Array(values) => Some(values.iter().filter_map(|v| v.as_str()).collect::<Vec<_>>().join(", ")),
+        _ => None,
+    }
+}
+
+/// One piece of a snippet; `mark` is set on matched terms
+fn segment(text: &str, mark: bool) -> Value {
+    json!({ "text": text, "mark": mark })
+}
+
+/// Snippet around the first matched term, as plain-text segments with the matched terms
+/// flagged. Markup is left to the client, which escapes the text first.
+fn highlight(text: &str, terms: &[String]) -> Option<Vec<Value>> {
+    let lower = text.to_lowercase();
+    // Lowercasing can change byte lengths for some scripts; skip highlighting rather than slice badly
+    if lower.len() != text.len() {
+        return None;
+    }
+
+    let mut matches: Vec<(usize, usize)> = Vec::new();
+    for term in terms {
+        let mut from = 0;
+        while let Some(pos) = lower[from..].find(term.as_str()) {
+            matches.push((from + pos, from + pos + term.len()));
+            from += pos + term.len();
+        }
+    }
+    if matches.is_empty() {
+        return None;
+    }
+    matches.sort();
+
+    let floor = |mut i: usize| { while !text.is_char_boundary(i) { i -= 1; } i };
+    let ceil = |mut i: usize| { while !text.is_char_boundary(i) { i += 1; } i };
+    let start = floor(matches[0].0.saturating_sub(SNIPPET_CONTEXT));
+    let end = ceil((matches[0].1 + SNIPPET_CONTEXT * 2).min(text.len()));
+
+    let mut snippet = Vec::new();
+    if start > 0 { snippet.push(segment("…", false)); }
+    let mut cursor = start;
+    for (from, to) in matches.into_iter().filter(|(from, to)| *from >= start && *to <= end) {
+        if from < cursor { continue; }
+        snippet.push(segment(&text[cursor..from], false));
+        snippet.push(segment(&text[from..to], true));
+        cursor = to;
+    }
+    snippet.push(segment(&text[cursor..end], false));
+    if end < text.len() { snippet.push(segment("…", false)); }
+
+    Some(snippet)
+}
+/*----- END HIGHLIGHTING -----*/
+
+
+/*----- START SEARCH -----*/
+async fn search_target(db: &Database, target: &SearchTarget, query: &str, terms: &[String], limit: i64) -> Result<(Vec<Value>, u64), Error> {
+    let collection = db.collection::<Document>(target.collection);
+    let filter = doc! { "$text": { "$search": query }, "deleted": { "$ne": true } };
+
+    let total = collection.count_documents(filter.clone(), None).await
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+    let mut projection = doc! { "score": { "$meta": "textScore" } };
+    for field in target.fields.iter().chain(target.title_fields) {
+        projection.insert(*field, 1);
+    }
+
+    let find_options = FindOptions::builder()
+        .projection(projection)
+        .sort(doc! { "score": { "$meta": "textScore" } })
+        .limit(limit)
+        .build();
+
+    let docs: Vec<Document> = collection.find(filter, find_options).await
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?
+        .try_collect().await
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+
+    let hits = docs.iter()
+        .map(|doc| {
+            let id = doc.get_object_id("_id").map(|id| id.to_hex()).unwrap_or_default();
+            let title = target.title_fields.iter()
+                .filter_map(|field| field_text(doc, field))
+                .find(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| id.clone());
+
+            let highlights: serde_json::Map<String, Value> = target.fields.iter()
+                .filter_map(|field| {
+                    let snippet = highlight(&field_text(doc, field)?, terms)?;
+                    Some((field.to_string(), json!(snippet)))
+                })
+                .collect();
+
+            json!({
+                "collection": target.name,
+                "id": id,
+                "title": title,
+                "score": doc.get_f64("score").unwrap_or_default(),
+                "highlights": highlights,
+                "admin_url": format!("/adminx/{}/view/{}", target.admin_path, id),
+            })
+        })
+        .collect();
+
+    Ok((hits, total))
+}
+
+/// Runs the query against every requested collection's text index and merges the
+/// hits by relevance. `counts` holds the total matches per collection, not just the
+/// returned page.
+pub async fn search(db: &Database, request: &SearchRequest) -> Result<Value, Error> {
+    let query = request.query.as_deref().unwrap_or_default().trim();
+    let limit = request.limit.unwrap_or(10);
+    let terms: Vec<String> = query.split_whitespace()
+        .map(|term| term.trim_matches(|c: char| c == '"' || c == '-').to_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect();
+
+    let collections = request.collections();
+    let mut results = Vec::new();
+    let mut counts = serde_json::Map::new();
+
+    for target in SEARCH_TARGETS.iter().filter(|t| collections.contains(&t.name)) {
+        let (hits, total) = search_target(db, target, query, &terms, limit).await?;
+        results.extend(hits);
+        counts.insert(target.name.to_string(), json!(total));
+    }
+
+    results.sort_by(|a, b| {
+        let score = |v: &Value| v["score"].as_f64().unwrap_or_default();
+        score(b).total_cmp(&score(a))
+    });
+
+    let total: u64 = counts.values().filter_map(Value::as_u64).sum();
+    Ok(json!({
+        "query": query,
+        "results": results,
+        "counts": counts,
+        "total": total,
+    }))
+}
+/*----- END SEARCH -----*/
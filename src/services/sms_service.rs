@@ -0,0 +1,141 @@
+// services/sms_service.rs
+//
+// Outgoing text messages, for now only sign-in codes. Messages go straight to the
+// configured `SmsTransport` and are never written to the application log: a sign-in
+// code in the log is a live credential. Without a transport, phone sign-in is
+// unavailable for every number alike.
+
+use std::path::PathBuf;
+
+use actix_web::Error;
+use async_trait::async_trait;
+use log::{error, info};
+use mongodb::bson::DateTime as BsonDateTime;
+use once_cell::sync::OnceCell;
+use serde_json::json;
+
+use crate::config::constants::{OTP_EXPIRY, PRODUCT_NAME};
+use crate::config::env_vars::{get_custom_env, is_production};
+use crate::custom_error_expression;
+
+
+/*----- START TRANSPORTS -----*/
+#[async_trait]
+pub trait SmsTransport: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Delivers one message to a phone number, digits only
+    async fn send(&self, to: &str, body: &str) -> Result<(), String>;
+}
+
+/// Posts `{ "to", "body" }` as JSON to `SMS_GATEWAY_URL`, with `SMS_GATEWAY_TOKEN` as a
+/// bearer token, for a provider or a relay in front of one
+pub struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    token: String,
+}
+
+impl HttpTransport {
+    pub fn from_env() -> Result<Self, String> {
+        let url = get_custom_env("SMS_GATEWAY_URL", "");
+        if url.is_empty() {
+            return Err("SMS_GATEWAY_URL is not set".to_string());
+        }
+        Ok(HttpTransport { client: reqwest::Client::new(), url, token: get_custom_env("SMS_GATEWAY_TOKEN", "") })
+    }
+}
+
+#[async_trait]
+impl SmsTransport for HttpTransport {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn send(&self, to: &str, body: &str) -> Result<(), String> {
+        let mut request = self.client.post(&self.url).json(&json!({ "to": format!("+{}", to), "body": body }));
+        if !self.token.is_empty() {
+            request = request.bearer_auth(&self.token);
+        }
+        request.send().await
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Writes each message to `dir` as a `.txt` file, for local development only
+pub struct FileTransport {
+    dir: PathBuf,
+}
+
+impl FileTransport {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileTransport { dir: dir.into() }
+    }
+}
+
+#[async_trait]
+impl SmsTransport for FileTransport {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn send(&self, to: &str, body: &str) -> Result<(), String> {
+        tokio::fs::create_dir_all(&self.dir).await.map_err(|e| e.to_string())?;
+        let path = self.dir.join(format!("{}_{}.txt", BsonDateTime::now().timestamp_millis(), to));
+        tokio::fs::write(&path, body).await.map_err(|e| e.to_string())
+    }
+}
+/*----- END TRANSPORTS -----*/
+
+
+/*----- START SENDER -----*/
+static SMS_TRANSPORT: OnceCell<Box<dyn SmsTransport>> = OnceCell::new();
+
+/// Sets up the transport from `SMS_TRANSPORT`: `http` (the default in production), or
+/// `file` to write messages to `SMS_FILE_DIR`, which production refuses
+pub fn init_sms() {
+    let default_transport = if is_production() { "http" } else { "file" };
+    let transport: Box<dyn SmsTransport> = match get_custom_env("SMS_TRANSPORT", default_transport).as_str() {
+        "file" if is_production() => {
+            error!("❌ The file SMS transport is not allowed in production, phone sign-in is unavailable");
+            return;
+        }
+        "file" => Box::new(FileTransport::new(get_custom_env("SMS_FILE_DIR", "tmp/sms"))),
+        _ => match HttpTransport::from_env() {
+            Ok(transport) => Box::new(transport),
+            Err(e) => {
+                error!("❌ Failed to configure SMS, phone sign-in is unavailable: {}", e);
+                return;
+            }
+        },
+    };
+
+    let name = transport.name();
+    if SMS_TRANSPORT.set(transport).is_ok() {
+        info!("SMS initialized with the {} transport", name);
+    } else {
+        error!("SMS was already initialized.");
+    }
+}
+
+fn unavailable() -> Error {
+    custom_error_expression!(internal_error, 503, "Sign-in by phone is not available right now, use your email").into()
+}
+
+/// Fails when no transport is configured, before a code is generated for nothing
+pub fn ensure_available() -> Result<(), Error> {
+    SMS_TRANSPORT.get().map(|_| ()).ok_or_else(unavailable)
+}
+
+/// Texts a sign-in code. Failures are logged without the message body.
+pub async fn send_otp(to: &str, code: &str) -> Result<(), Error> {
+    let transport = SMS_TRANSPORT.get().ok_or_else(unavailable)?;
+    let body = format!("{} is your {} code. It expires in {} minutes.", code, PRODUCT_NAME, OTP_EXPIRY / 60);
+    transport.send(to, &body).await.map_err(|e| {
+        error!("❌ Failed to send a sign-in code by SMS: {}", e);
+        unavailable()
+    })
+}
+/*----- END SENDER -----*/
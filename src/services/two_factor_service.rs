@@ -0,0 +1,296 @@
+// services/two_factor_service.rs
+//
+// TOTP second factor for AdminX operators (RFC 6238: SHA-1, six digits, 30 second steps).
+// Enrolment stores a random secret, encrypted, and shows it as a provisioning QR code;
+// the first valid code confirms it and yields single-use recovery codes, of which only
+// hashes are kept. A code is accepted one step either side of now, and never twice.
+// Wrong codes are counted per operator in Redis and lock the step for OTP_LOCKOUT_SECONDS.
+
+use actix_web::Error;
+use log::{error, info, warn};
+use otpauth::TOTP;
+use rand::{Rng, RngCore};
+use redis::RedisError;
+use sha2::{Digest, Sha256};
+
+use crate::config::constants::{
+    OTP_LOCKOUT_SECONDS,
+    PRODUCT_NAME,
+    TOTP_MAX_FAILURES,
+    TOTP_PERIOD_SECONDS,
+    TOTP_RECOVERY_CODES,
+};
+use crate::errors::custom_error::CustomError;
+use crate::models::admin_two_factor::AdminTwoFactor;
+use crate::repositories::Repositories;
+use crate::services::redis_service::{
+    redis_delete_key,
+    redis_get_counter,
+    redis_incr_with_expiry,
+    redis_ttl,
+};
+use crate::utilities::crypto_utility::{decrypt_secret, encrypt_secret};
+use crate::utilities::qr_utility::{render_qr_text, QrFormat};
+use crate::custom_error_expression;
+
+/// Bytes of a TOTP secret, the 160 bits RFC 4226 recommends
+const SECRET_LENGTH: usize = 20;
+
+/// Recovery codes avoid characters that are easily confused (0/o, 1/l/i)
+const RECOVERY_ALPHABET: &[u8] = b"abcdefghjkmnpqrstuvwxyz23456789";
+
+/// Characters per half of a recovery code, shown as `xxxxx-xxxxx`
+const RECOVERY_HALF_LENGTH: usize = 5;
+
+/// Side of the provisioning QR code, in pixels
+const QR_SIZE: u32 = 220;
+
+/// A secret waiting for its first code, as the operator's authenticator app needs it
+pub struct Enrolment {
+    pub secret: String,
+    pub uri: String,
+    pub qr_svg: String,
+}
+
+fn failures_key(email: &str) -> String {
+    format!("adminx:2fa_failures:{}", email)
+}
+
+fn redis_error(e: RedisError) -> Error {
+    error!("Two-factor store unavailable: {}", e);
+    custom_error_expression!(internal_error, 503, "Two-factor sign-in is temporarily unavailable").into()
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    format!("{:x}", Sha256::digest(code.as_bytes()))
+}
+
+/// Lowercase without separators, so `ABCDE-FGHJK` and `abcdefghjk` match the same code
+fn normalize_recovery_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+fn generate_recovery_code() -> String {
+    let mut rng = rand::thread_rng();
+    let mut half = || -> String {
+        (0..RECOVERY_HALF_LENGTH)
+            .map(|_| RECOVERY_ALPHABET[rng.gen_range(0..RECOVERY_ALPHABET.len())] as char)
+            .collect()
+    };
+    format!("{}-{}", half(), half())
+}
+
+fn current_step() -> i64 {
+    chrono::Utc::now().timestamp() / TOTP_PERIOD_SECONDS as i64
+}
+
+/// The time step a six digit code belongs to, looking one step either side of now
+fn matching_step(totp: &TOTP, code: &str) -> Option<i64> {
+    if code.len() != 6 || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let code: u32 = code.parse().ok()?;
+    let now = current_step();
+    (now - 1..=now + 1).find(|step| {
+        totp.generate(TOTP_PERIOD_SECONDS, *step as u64 * TOTP_PERIOD_SECONDS) == code
+    })
+}
+
+fn totp_for(enrolment: &AdminTwoFactor) -> Result<TOTP, Error> {
+    Ok(TOTP::from_bytes(&decrypt_secret(&enrolment.secret)?))
+}
+
+fn describe(email: &str, totp: &TOTP) -> Result<Enrolment, Error> {
+    let secret = totp.base32_secret();
+    let uri = format!(
+        "otpauth://totp/{issuer}:{label}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits=6&period={period}",
+        issuer = urlencoding::encode(PRODUCT_NAME),
+        label = urlencoding::encode(email),
+        secret = secret,
+        period = TOTP_PERIOD_SECONDS,
+    );
+    let qr = render_qr_text(&uri, QrFormat::Svg, QR_SIZE)?;
+    let qr_svg = String::from_utf8(qr)
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+    Ok(Enrolment { secret, uri, qr_svg })
+}
+
+
+/*----- START LIMITS -----*/
+/// Fails with a 429 while the operator is locked out
+async fn ensure_not_locked(email: &str) -> Result<(), Error> {
+    let key = failures_key(email);
+    if redis_get_counter(key.clone()).await.map_err(redis_error)? >= TOTP_MAX_FAILURES {
+        let retry_after = redis_ttl(key).await.map_err(redis_error)?;
+        let minutes = ((retry_after + 59) / 60).max(1);
+        return Err(CustomError::InvalidRequest(429, format!("Too many wrong codes, try again in {} minute(s)", minutes)).into());
+    }
+    Ok(())
+}
+
+/// Counts a wrong code and returns the error to show, with the attempts left
+async fn wrong_code(email: &str) -> Error {
+    match redis_incr_with_expiry(failures_key(email), OTP_LOCKOUT_SECONDS).await {
+        Ok(failures) => {
+            warn!("Wrong two-factor code for {}", email);
+            let left = (TOTP_MAX_FAILURES - failures).max(0);
+            custom_error_expression!(unauthorized, 401, format!("Invalid code, {} attempt(s) left", left)).into()
+        }
+        Err(e) => redis_error(e),
+    }
+}
+/*----- END LIMITS -----*/
+
+
+/*----- START ENROLMENT -----*/
+pub async fn is_enrolled(repos: &Repositories, email: &str) -> Result<bool, Error> {
+    Ok(repos.two_factors.find_by_email(email).await?
+        .is_some_and(|enrolment| enrolment.confirmed_at.is_some()))
+}
+
+/// The pending secret for the operator, generating one on the first visit. A reload shows
+/// the same secret, so a code already scanned keeps working.
+pub async fn enrolment(repos: &Repositories, email: &str) -> Result<Enrolment, Error> {
+    if let Some(existing) = repos.two_factors.find_by_email(email).await? {
+        if existing.confirmed_at.is_some() {
+            return Err(custom_error_expression!(conflict, 409, "Two-factor authentication is already set up").into());
+        }
+        return describe(email, &totp_for(&existing)?);
+    }
+
+    let mut secret = [0u8; SECRET_LENGTH];
+    rand::thread_rng().fill_bytes(&mut secret);
+    repos.two_factors.start_enrolment(email, &encrypt_secret(&secret)?).await?
+        .ok_or_else(|| Error::from(custom_error_expression!(conflict, 409, "Two-factor authentication is already set up")))?;
+    describe(email, &TOTP::from_bytes(&secret))
+}
+
+/// Confirms the pending secret with its first code. Returns the recovery codes, which
+/// are shown once and cannot be read back.
+pub async fn confirm_enrolment(repos: &Repositories, email: &str, code: &str) -> Result<Vec<String>, Error> {
+    ensure_not_locked(email).await?;
+
+    let pending = repos.two_factors.find_by_email(email).await?
+        .filter(|enrolment| enrolment.confirmed_at.is_none())
+        .ok_or_else(|| Error::from(custom_error_expression!(not_found, 404, "No two-factor setup in progress")))?;
+    let id = pending.id
+        .ok_or_else(|| Error::from(custom_error_expression!(internal_error, 500, "Two-factor setup has no id")))?;
+
+    let Some(step) = matching_step(&totp_for(&pending)?, code.trim()) else {
+        return Err(wrong_code(email).await);
+    };
+
+    let codes: Vec<String> = (0..TOTP_RECOVERY_CODES).map(|_| generate_recovery_code()).collect();
+    let hashes: Vec<String> = codes.iter().map(|code| hash_recovery_code(&normalize_recovery_code(code))).collect();
+    if !repos.two_factors.confirm(id, step, &hashes).await? {
+        return Err(custom_error_expression!(conflict, 409, "Two-factor authentication is already set up").into());
+    }
+
+    redis_delete_key(failures_key(email)).await.map_err(redis_error)?;
+    info!("Two-factor authentication set up for {}", email);
+    Ok(codes)
+}
+/*----- END ENROLMENT -----*/
+
+
+/*----- START VERIFY -----*/
+/// Checks the second factor at sign-in: a current authenticator code, or one of the
+/// recovery codes, which is used up.
+pub async fn verify(repos: &Repositories, email: &str, code: &str) -> Result<(), Error> {
+    ensure_not_locked(email).await?;
+
+    let enrolment = repos.two_factors.find_by_email(email).await?
+        .filter(|enrolment| enrolment.confirmed_at.is_some())
+        .ok_or_else(|| Error::from(custom_error_expression!(forbidden, 403, "Two-factor authentication is not set up")))?;
+    let id = enrolment.id
+        .ok_or_else(|| Error::from(custom_error_expression!(internal_error, 500, "Two-factor setup has no id")))?;
+
+    let code = code.trim();
+    let accepted = match matching_step(&totp_for(&enrolment)?, code) {
+        // A code seen before, even a valid one, may have been shoulder-surfed
+        Some(step) => repos.two_factors.use_step(id, step).await?,
+        None => {
+            let normalized = normalize_recovery_code(code);
+            let used = normalized.len() == 2 * RECOVERY_HALF_LENGTH
+                && repos.two_factors.use_recovery_code(id, &hash_recovery_code(&normalized)).await?;
+            if used {
+                warn!("Recovery code used by {}, {} left", email, enrolment.recovery_codes.len().saturating_sub(1));
+            }
+            used
+        }
+    };
+    if !accepted {
+        return Err(wrong_code(email).await);
+    }
+
+    redis_delete_key(failures_key(email)).await.map_err(redis_error)?;
+    Ok(())
+}
+/*----- END VERIFY -----*/
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn totp() -> TOTP {
+        TOTP::from_bytes(b"12345678901234567890")
+    }
+
+    fn code_at(totp: &TOTP, step: i64) -> String {
+        format!("{:06}", totp.generate(TOTP_PERIOD_SECONDS, step as u64 * TOTP_PERIOD_SECONDS))
+    }
+
+    #[test]
+    fn codes_from_neighbouring_steps_match() {
+        let totp = totp();
+        let now = current_step();
+        let found: Vec<Option<i64>> = [now - 1, now, now + 1].iter()
+            .map(|step| matching_step(&totp, &code_at(&totp, *step)))
+            .collect();
+        // A step boundary passed mid-test moves the window; the other tests cover misses
+        if current_step() == now {
+            assert!(found.iter().all(|step| step.is_some_and(|step| (now - 1..=now + 1).contains(&step))));
+        }
+    }
+
+    #[test]
+    fn codes_outside_the_window_do_not_match() {
+        let totp = totp();
+        let now = current_step();
+        let window: Vec<String> = (now - 1..=now + 1).map(|step| code_at(&totp, step)).collect();
+        for step in [now - 3, now + 3] {
+            let code = code_at(&totp, step);
+            if !window.contains(&code) {
+                assert_eq!(matching_step(&totp, &code), None);
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_codes_never_match() {
+        let totp = totp();
+        for code in ["", "12345", "1234567", "12a456", " 123456"] {
+            assert_eq!(matching_step(&totp, code), None);
+        }
+    }
+
+    #[test]
+    fn recovery_codes_hash_the_same_however_they_are_typed() {
+        let code = generate_recovery_code();
+        let typed = format!(" {} ", code.to_uppercase().replace('-', ""));
+        assert_eq!(hash_recovery_code(&normalize_recovery_code(&code)), hash_recovery_code(&normalize_recovery_code(&typed)));
+        assert_ne!(hash_recovery_code(&normalize_recovery_code(&code)), normalize_recovery_code(&code));
+    }
+
+    #[test]
+    fn recovery_codes_use_the_unambiguous_alphabet() {
+        let code = generate_recovery_code();
+        let (first, second) = code.split_once('-').unwrap();
+        assert_eq!((first.len(), second.len()), (RECOVERY_HALF_LENGTH, RECOVERY_HALF_LENGTH));
+        assert!(code.bytes().filter(|b| *b != b'-').all(|b| RECOVERY_ALPHABET.contains(&b)));
+    }
+}
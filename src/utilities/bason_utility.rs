@@ -5,3 +5,34 @@ use mongodb::bson::{to_bson, Bson};
 pub fn convert_to_bson<T: serde::Serialize>(value: &T) -> Result<Bson, Error> {
     to_bson(value).map_err(ErrorInternalServerError)
 }
+
+
+/// Converts a BSON value into plain JSON for API responses:
+/// ObjectIds become hex strings and dates become millisecond timestamps.
+pub fn bson_to_json(value: Bson) -> serde_json::Value {
+    match value {
+        Bson::ObjectId(oid) => serde_json::Value::String(oid.to_hex()),
+        Bson::DateTime(dt) => serde_json::Value::from(dt.timestamp_millis()),
+        Bson::Document(doc) => serde_json::Value::Object(
+            doc.into_iter()
+                .map(|(key, value)| {
+                    let key = if key == "_id" { "id".to_string() } else { key };
+                    (key, bson_to_json(value))
+                })
+                .collect(),
+        ),
+        Bson::Array(items) => serde_json::Value::Array(items.into_iter().map(bson_to_json).collect()),
+        other => other.into_relaxed_extjson(),
+    }
+}
+
+/// Serializes a model into API-friendly JSON (see `bson_to_json`).
+pub fn to_api_json<T: serde::Serialize>(value: &T) -> Result<serde_json::Value, Error> {
+    Ok(bson_to_json(convert_to_bson(value)?))
+}
+
+/// Parses a hex ObjectId coming from a request, failing with a 400 naming the field
+pub fn parse_object_id(value: &str, field: &str) -> Result<mongodb::bson::oid::ObjectId, Error> {
+    mongodb::bson::oid::ObjectId::parse_str(value.trim())
+        .map_err(|_| crate::custom_error_expression!(bad_request, 400, format!("Invalid {}", field)).into())
+}
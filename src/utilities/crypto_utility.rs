@@ -0,0 +1,97 @@
+// src/utilities/crypto_utility.rs
+//
+// Encryption of secrets stored in Mongo (TOTP seeds). AES-256-GCM with a random nonce;
+// the stored form is base64 of nonce || ciphertext. The key is SHA-256 of
+// `DATA_ENCRYPTION_KEY`, so rotating that variable makes existing secrets unreadable.
+use actix_web::Error;
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm,
+    Key,
+    Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use log::warn;
+use once_cell::sync::Lazy;
+use sha2::{Digest, Sha256};
+
+use crate::config::env_vars::{get_custom_env, is_production};
+use crate::custom_error_expression;
+
+/// Only used outside production, so local setups work without configuration
+const DEVELOPMENT_ENCRYPTION_KEY: &str = "xard-development-data-encryption-key";
+
+/// Bytes of nonce in front of every ciphertext
+const NONCE_LENGTH: usize = 12;
+
+static CIPHER: Lazy<Option<Aes256Gcm>> = Lazy::new(|| {
+    let mut secret = get_custom_env("DATA_ENCRYPTION_KEY", "");
+    if secret.is_empty() {
+        if is_production() {
+            return None;
+        }
+        warn!("DATA_ENCRYPTION_KEY is not set, encrypting with the development key");
+        secret = DEVELOPMENT_ENCRYPTION_KEY.to_string();
+    }
+    let key = Sha256::digest(secret.as_bytes());
+    Some(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+});
+
+fn cipher() -> Result<&'static Aes256Gcm, Error> {
+    CIPHER.as_ref()
+        .ok_or_else(|| custom_error_expression!(internal_error, 500, "DATA_ENCRYPTION_KEY is not configured").into())
+}
+
+pub fn encrypt_secret(plain: &[u8]) -> Result<String, Error> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher()?.encrypt(&nonce, plain)
+        .map_err(|_| custom_error_expression!(internal_error, 500, "Failed to encrypt secret"))?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(STANDARD.encode(sealed))
+}
+
+/// Fails when the value was tampered with or encrypted under another key
+pub fn decrypt_secret(sealed: &str) -> Result<Vec<u8>, Error> {
+    let sealed = STANDARD.decode(sealed)
+        .map_err(|_| custom_error_expression!(internal_error, 500, "Stored secret is not valid base64"))?;
+    if sealed.len() <= NONCE_LENGTH {
+        return Err(custom_error_expression!(internal_error, 500, "Stored secret is truncated").into());
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+    cipher()?.decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| custom_error_expression!(internal_error, 500, "Failed to decrypt secret").into())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let sealed = encrypt_secret(b"totp seed bytes").unwrap();
+        assert_eq!(decrypt_secret(&sealed).unwrap(), b"totp seed bytes");
+    }
+
+    #[test]
+    fn every_encryption_uses_a_fresh_nonce() {
+        assert_ne!(encrypt_secret(b"same").unwrap(), encrypt_secret(b"same").unwrap());
+    }
+
+    #[test]
+    fn tampered_ciphertext_is_rejected() {
+        let mut sealed = STANDARD.decode(encrypt_secret(b"secret").unwrap()).unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(decrypt_secret(&STANDARD.encode(sealed)).is_err());
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        assert!(decrypt_secret("not base64!").is_err());
+        assert!(decrypt_secret(&STANDARD.encode([0u8; NONCE_LENGTH])).is_err());
+    }
+}
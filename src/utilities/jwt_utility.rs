@@ -0,0 +1,117 @@
+// src/utilities/jwt_utility.rs
+use actix_web::Error;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use log::warn;
+use mongodb::bson::oid::ObjectId;
+use once_cell::sync::Lazy;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::config::constants::{ACCESS_TOKEN_EXPIRY_SECONDS, CONTACT_QR_EXPIRY_SECONDS};
+use crate::config::env_vars::{get_custom_env, is_production};
+use crate::custom_error_expression;
+
+/// Only used outside production, so local setups work without configuration
+const DEVELOPMENT_JWT_SECRET: &str = "xard-development-jwt-secret";
+
+/// Audience of contact QR tokens, so they pass for nothing else
+const CONTACT_TOKEN_AUDIENCE: &str = "xard:contact";
+
+static JWT_SECRET: Lazy<Option<String>> = Lazy::new(|| {
+    let secret = get_custom_env("JWT_SECRET", "");
+    if !secret.is_empty() {
+        return Some(secret);
+    }
+    if is_production() {
+        return None;
+    }
+    warn!("JWT_SECRET is not set, signing tokens with the development secret");
+    Some(DEVELOPMENT_JWT_SECRET.to_string())
+});
+
+/// Claims of an access token. `jti` identifies the token itself and `sid` the
+/// session (sign-in) it was issued for, which outlives it through refreshes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub jti: String,
+    pub sid: String,
+}
+
+/// Claims of the token in a user's contact QR code
+#[derive(Debug, Serialize, Deserialize)]
+struct ContactClaims {
+    sub: String,
+    exp: i64,
+    aud: String,
+}
+
+/// A signed access token and its lifetime in seconds
+pub struct IssuedToken {
+    pub token: String,
+    pub expires_in: i64,
+}
+
+/// The HS256 signing secret; a 500 in production when `JWT_SECRET` is missing
+pub fn secret() -> Result<&'static str, Error> {
+    JWT_SECRET.as_deref()
+        .ok_or_else(|| custom_error_expression!(internal_error, 500, "JWT_SECRET is not configured").into())
+}
+
+/// Signs an access token for the user's session, valid for `ACCESS_TOKEN_EXPIRY_SECONDS`
+pub fn issue_access_token(user_id: ObjectId, session_id: &str) -> Result<IssuedToken, Error> {
+    let now = chrono::Utc::now().timestamp();
+    let claims = Claims {
+        sub: user_id.to_hex(),
+        iat: now,
+        exp: now + ACCESS_TOKEN_EXPIRY_SECONDS,
+        jti: Uuid::new_v4().to_string(),
+        sid: session_id.to_string(),
+    };
+
+    let token = encode(&Header::default(), &claims, &EncodingKey::from_secret(secret()?.as_bytes()))
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()))?;
+    Ok(IssuedToken { token, expires_in: ACCESS_TOKEN_EXPIRY_SECONDS })
+}
+
+/// Checks the signature and expiry of an access token; a 401 when either is off
+pub fn decode_access_token(token: &str) -> Result<Claims, Error> {
+    decode::<Claims>(token, &DecodingKey::from_secret(secret()?.as_bytes()), &Validation::default())
+        .map(|data| data.claims)
+        .map_err(|_| custom_error_expression!(unauthorized, 401, "Invalid or expired token").into())
+}
+
+/// Signs the token a user's contact QR code carries, valid for `CONTACT_QR_EXPIRY_SECONDS`
+pub fn issue_contact_token(user_id: ObjectId) -> Result<String, Error> {
+    let claims = ContactClaims {
+        sub: user_id.to_hex(),
+        exp: chrono::Utc::now().timestamp() + CONTACT_QR_EXPIRY_SECONDS,
+        aud: CONTACT_TOKEN_AUDIENCE.to_string(),
+    };
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret()?.as_bytes()))
+        .map_err(|e| custom_error_expression!(internal_error, 500, e.to_string()).into())
+}
+
+/// The user behind a contact QR token; `None` when forged, expired or not a contact token
+pub fn decode_contact_token(token: &str) -> Option<ObjectId> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[CONTACT_TOKEN_AUDIENCE]);
+    let claims = decode::<ContactClaims>(token, &DecodingKey::from_secret(secret().ok()?.as_bytes()), &validation).ok()?.claims;
+    ObjectId::parse_str(claims.sub).ok()
+}
+
+/// A random opaque refresh token. Only `hash_refresh_token` of it is stored.
+pub fn generate_refresh_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn hash_refresh_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
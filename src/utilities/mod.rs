@@ -1,2 +1,7 @@
 pub mod s3_utility;
-pub mod bason_utility;
\ No newline at end of file
+pub mod bason_utility;
+pub mod vcard_utility;
+pub mod qr_utility;
+pub mod jwt_utility;
+pub mod crypto_utility;
+pub mod password_utility;
@@ -0,0 +1,37 @@
+// src/utilities/password_utility.rs
+//
+// Operator passwords, hashed with argon2id (the crate's default parameters) into PHC
+// strings, which carry their own salt and parameters. Hashing is deliberately slow, so
+// callers on the request path run it through `web::block`.
+use actix_web::Error;
+use argon2::Argon2;
+use once_cell::sync::Lazy;
+use password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use rand::rngs::OsRng;
+
+use crate::custom_error_expression;
+
+/// Verified against when there is no operator to check, so a wrong email takes as
+/// long as a wrong password
+static DUMMY_HASH: Lazy<Option<String>> = Lazy::new(|| hash_password("xard-dummy-password").ok());
+
+pub fn hash_password(password: &str) -> Result<String, Error> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| custom_error_expression!(internal_error, 500, format!("Failed to hash password: {}", e)).into())
+}
+
+/// False for a wrong password as well as for a hash that does not parse
+pub fn verify_password(password: &str, password_hash: &str) -> bool {
+    PasswordHash::new(password_hash)
+        .is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+/// Spends the time of one verification without anything to verify
+pub fn verify_dummy_password(password: &str) {
+    if let Some(hash) = DUMMY_HASH.as_deref() {
+        verify_password(password, hash);
+    }
+}
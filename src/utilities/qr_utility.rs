@@ -0,0 +1,140 @@
+// src/utilities/qr_utility.rs
+//
+// QR codes for users and events, rendered as SVG or PNG. An event code holds
+// `xard:event:<id>`. A user code holds `xard:user:<token>`, a signed token that expires
+// after CONTACT_QR_EXPIRY_SECONDS, so scanning it proves the user showed their code
+// recently and an id alone does not get anyone their profile.
+use std::io::Cursor;
+
+use actix_web::Error;
+use image::{GrayImage, ImageFormat, Luma};
+use mongodb::bson::oid::ObjectId;
+use qrcode::{Color, EcLevel, QrCode};
+use serde::Deserialize;
+use svg::node::element::{Path, Rectangle};
+use svg::Document;
+
+use crate::utilities::jwt_utility::{decode_contact_token, issue_contact_token};
+use crate::custom_error_expression;
+
+/// Light modules around the code, as the spec asks
+const QUIET_ZONE: u32 = 4;
+pub const DEFAULT_QR_SIZE: u32 = 512;
+
+
+/*----- START PAYLOAD -----*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QrPayload {
+    User(ObjectId),
+    Event(ObjectId),
+}
+
+impl QrPayload {
+    /// The text the code holds
+    pub fn encode(&self) -> Result<String, Error> {
+        match self {
+            QrPayload::User(id) => Ok(format!("xard:user:{}", issue_contact_token(*id)?)),
+            QrPayload::Event(id) => Ok(format!("xard:event:{}", id.to_hex())),
+        }
+    }
+
+    /// `None` for anything but a valid event code or a live user code
+    pub fn parse(code: &str) -> Option<Self> {
+        let (kind, value) = code.trim().strip_prefix("xard:")?.split_once(':')?;
+        match kind {
+            "user" => decode_contact_token(value).map(QrPayload::User),
+            "event" => ObjectId::parse_str(value).ok().map(QrPayload::Event),
+            _ => None,
+        }
+    }
+
+    /// Event codes never change; user codes expire, so clients must not keep them long
+    pub fn cache_control(&self) -> &'static str {
+        match self {
+            QrPayload::User(_) => "private, max-age=3600",
+            QrPayload::Event(_) => "public, max-age=86400",
+        }
+    }
+}
+/*----- END PAYLOAD -----*/
+
+
+/*----- START RENDER -----*/
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Svg,
+    Png,
+}
+
+impl QrFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QrFormat::Svg => "image/svg+xml",
+            QrFormat::Png => "image/png",
+        }
+    }
+}
+
+/// `payload` as an image about `size` pixels wide. PNG modules are whole pixels, so
+/// the PNG comes out at the largest multiple of the module count that fits.
+pub fn render_qr(payload: &QrPayload, format: QrFormat, size: u32) -> Result<Vec<u8>, Error> {
+    render_qr_text(&payload.encode()?, format, size)
+}
+
+/// Any text as a QR image, e.g. an `otpauth://` provisioning URI
+pub fn render_qr_text(text: &str, format: QrFormat, size: u32) -> Result<Vec<u8>, Error> {
+    let code = QrCode::with_error_correction_level(text, EcLevel::M)
+        .map_err(|e| custom_error_expression!(internal_error, 500, format!("Failed to encode QR code: {}", e)))?;
+
+    match format {
+        QrFormat::Svg => Ok(svg_of(&code, size).into_bytes()),
+        QrFormat::Png => png_of(&code, size),
+    }
+}
+
+fn modules(code: &QrCode) -> (u32, Vec<bool>) {
+    let width = code.width() as u32;
+    (width, code.to_colors().into_iter().map(|color| color == Color::Dark).collect())
+}
+
+fn svg_of(code: &QrCode, size: u32) -> String {
+    let (width, dark) = modules(code);
+    let total = width + 2 * QUIET_ZONE;
+
+    // One unit square per dark module, drawn in a single path
+    let mut data = String::new();
+    for (index, _) in dark.iter().enumerate().filter(|(_, dark)| **dark) {
+        let (x, y) = (index as u32 % width + QUIET_ZONE, index as u32 / width + QUIET_ZONE);
+        data.push_str(&format!("M{},{}h1v1h-1z", x, y));
+    }
+
+    Document::new()
+        .set("viewBox", (0, 0, total, total))
+        .set("width", size)
+        .set("height", size)
+        .set("shape-rendering", "crispEdges")
+        .add(Rectangle::new().set("width", total).set("height", total).set("fill", "#ffffff"))
+        .add(Path::new().set("d", data).set("fill", "#000000"))
+        .to_string()
+}
+
+fn png_of(code: &QrCode, size: u32) -> Result<Vec<u8>, Error> {
+    let (width, dark) = modules(code);
+    let total = width + 2 * QUIET_ZONE;
+    let scale = (size / total).max(1);
+
+    let image = GrayImage::from_fn(total * scale, total * scale, |x, y| {
+        let (x, y) = (x / scale, y / scale);
+        let inside = (QUIET_ZONE..QUIET_ZONE + width).contains(&x) && (QUIET_ZONE..QUIET_ZONE + width).contains(&y);
+        let is_dark = inside && dark[((y - QUIET_ZONE) * width + (x - QUIET_ZONE)) as usize];
+        Luma([if is_dark { 0 } else { 255 }])
+    });
+
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, ImageFormat::Png)
+        .map_err(|e| custom_error_expression!(internal_error, 500, format!("Failed to encode PNG: {}", e)))?;
+    Ok(png.into_inner())
+}
+/*----- END RENDER -----*/
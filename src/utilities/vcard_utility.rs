@@ -0,0 +1,183 @@
+// src/utilities/vcard_utility.rs
+//
+// vCard 3.0 (RFC 2426) and 4.0 (RFC 6350) for users and contacts
+use serde::Deserialize;
+
+use crate::models::{contact::Contact, user::User};
+
+/// Longest content line in octets, before folding
+const LINE_LIMIT: usize = 75;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+pub enum VCardVersion {
+    #[serde(rename = "3.0", alias = "3")]
+    V3,
+    #[default]
+    #[serde(rename = "4.0", alias = "4")]
+    V4,
+}
+
+impl VCardVersion {
+    fn as_str(&self) -> &'static str {
+        match self {
+            VCardVersion::V3 => "3.0",
+            VCardVersion::V4 => "4.0",
+        }
+    }
+}
+
+/// The card fields a user or contact can fill
+#[derive(Debug, Default, Clone)]
+pub struct VCard {
+    pub uid: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: Option<String>,
+    pub work_email: Option<String>,
+    pub phone: Option<String>,
+    pub organization: Option<String>,
+    pub title: Option<String>,
+    pub work_address: Option<String>,
+    pub url: Option<String>,
+    pub note: Option<String>,
+}
+
+impl From<&User> for VCard {
+    fn from(user: &User) -> Self {
+        // The country code is stored apart from the number
+        let phone = user.phone_number.as_ref().map(|number| match user.ccode.as_deref().map(str::trim) {
+            Some(ccode) if !ccode.is_empty() => format!("+{}{}", ccode.trim_start_matches('+'), number),
+            _ => number.clone(),
+        });
+        VCard {
+            uid: user.id.map(|id| format!("xard:user:{}", id.to_hex())),
+            first_name: user.first_name.clone(),
+            last_name: user.last_name.clone(),
+            email: user.email.clone(),
+            work_email: user.official_email.clone(),
+            phone,
+            organization: user.company_name.clone(),
+            title: user.designation.clone(),
+            work_address: user.company_address.clone(),
+            url: user.website.clone().or_else(|| user.linkedin.clone()),
+            note: user.description.clone(),
+        }
+    }
+}
+
+impl From<&Contact> for VCard {
+    fn from(contact: &Contact) -> Self {
+        VCard {
+            uid: contact.id.map(|id| format!("xard:contact:{}", id.to_hex())),
+            first_name: contact.first_name.clone(),
+            last_name: contact.last_name.clone(),
+            email: contact.email.clone(),
+            work_email: contact.official_email.clone(),
+            phone: contact.phone_number.clone(),
+            organization: contact.company_name.clone(),
+            title: contact.designation.clone(),
+            work_address: contact.company_address.clone(),
+            url: contact.website.clone(),
+            note: contact.notes.clone().or_else(|| contact.description.clone()),
+        }
+    }
+}
+
+impl VCard {
+    pub fn full_name(&self) -> String {
+        let name = [self.first_name.as_deref(), self.last_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        // FN is required by both versions
+        if name.is_empty() {
+            self.email.clone().unwrap_or_else(|| "Unknown".to_string())
+        } else {
+            name
+        }
+    }
+
+    /// File name for downloads, e.g. `jane_doe.vcf`
+    pub fn file_name(&self) -> String {
+        let stem: String = self.full_name()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        format!("{}.vcf", stem.trim_matches('_'))
+    }
+
+    pub fn render(&self, version: VCardVersion) -> String {
+        let v4 = version == VCardVersion::V4;
+        let mut lines = vec!["BEGIN:VCARD".to_string(), format!("VERSION:{}", version.as_str())];
+        if v4 {
+            lines.push("KIND:individual".to_string());
+        }
+
+        lines.push(format!("FN:{}", escape(&self.full_name())));
+        lines.push(format!(
+            "N:{};{};;;",
+            escape(self.last_name.as_deref().unwrap_or_default()),
+            escape(self.first_name.as_deref().unwrap_or_default()),
+        ));
+
+        // URIs (URL, 4.0 TEL) are written as they are; text values are escaped
+        let mut push = |property: &str, value: &Option<String>, text: bool| {
+            if let Some(value) = value.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+                lines.push(format!("{}:{}", property, if text { escape(value) } else { value.to_string() }));
+            }
+        };
+        if v4 {
+            push("EMAIL;TYPE=home", &self.email, true);
+            push("EMAIL;TYPE=work", &self.work_email, true);
+            push("TEL;VALUE=uri;TYPE=cell", &self.phone.as_ref().map(|phone| format!("tel:{}", phone.replace(' ', ""))), false);
+        } else {
+            push("EMAIL;TYPE=INTERNET,HOME", &self.email, true);
+            push("EMAIL;TYPE=INTERNET,WORK", &self.work_email, true);
+            push("TEL;TYPE=CELL", &self.phone, true);
+        }
+        push("ORG", &self.organization, true);
+        push("TITLE", &self.title, true);
+        push("URL", &self.url, false);
+        push("NOTE", &self.note, true);
+        push("UID", &self.uid, false);
+
+        // The address is free text, so it all goes in the street component
+        if let Some(address) = self.work_address.as_deref().map(str::trim).filter(|address| !address.is_empty()) {
+            let property = if v4 { "ADR;TYPE=work" } else { "ADR;TYPE=WORK" };
+            lines.push(format!("{}:;;{};;;;", property, escape(address)));
+        }
+
+        lines.push("END:VCARD".to_string());
+        lines.iter().map(|line| fold(line)).collect()
+    }
+}
+
+/// Escapes a text value; `;` and `,` separate components and list items
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// One content line with its CRLF, folded so no physical line exceeds `LINE_LIMIT`
+/// octets. Continuation lines start with a space and never split a character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > LINE_LIMIT {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
@@ -0,0 +1,131 @@
+// xard-derive/src/lib.rs
+//
+// `#[derive(AdminxModel)]` collects the `#[adminx(...)]` field attributes of a model.
+// Field types, enum variants and required flags come from the model's `JsonSchema` at
+// runtime; see `src/admin/model_schema.rs` in xard-be.
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, LitStr, Token};
+
+#[derive(Default)]
+struct FieldAttrs {
+    name: String,
+    label: Option<String>,
+    group: Option<String>,
+    widget: Option<String>,
+    reference: Option<String>,
+    list: bool,
+    filter: bool,
+    readonly: bool,
+    skip: bool,
+}
+
+#[proc_macro_derive(AdminxModel, attributes(adminx))]
+pub fn derive_adminx_model(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "AdminxModel only supports structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(input, "AdminxModel needs named fields"));
+    };
+
+    let mut entries = Vec::new();
+    for field in &fields.named {
+        let attrs = field_attrs(field)?;
+        let FieldAttrs { name, list, filter, readonly, skip, .. } = &attrs;
+        let label = optional(&attrs.label);
+        let group = optional(&attrs.group);
+        let widget = optional(&attrs.widget);
+        let reference = optional(&attrs.reference);
+        entries.push(quote! {
+            crate::admin::model_schema::FieldAttrs {
+                name: #name,
+                label: #label,
+                group: #group,
+                widget: #widget,
+                reference: #reference,
+                list: #list,
+                filter: #filter,
+                readonly: #readonly,
+                skip: #skip,
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    Ok(quote! {
+        impl crate::admin::model_schema::AdminxModel for #ident {
+            fn adminx_fields() -> Vec<crate::admin::model_schema::FieldAttrs> {
+                vec![#(#entries),*]
+            }
+        }
+    })
+}
+
+fn optional(value: &Option<String>) -> proc_macro2::TokenStream {
+    match value {
+        Some(value) => quote! { Some(#value) },
+        None => quote! { None },
+    }
+}
+
+fn field_attrs(field: &syn::Field) -> syn::Result<FieldAttrs> {
+    let mut attrs = FieldAttrs {
+        name: field.ident.as_ref().map(|ident| ident.to_string()).unwrap_or_default(),
+        ..Default::default()
+    };
+
+    for attr in &field.attrs {
+        if attr.path().is_ident("serde") {
+            // Only `rename` matters: the stored key is what AdminX reads and writes
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    attrs.name = meta.value()?.parse::<LitStr>()?.value();
+                } else if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<Expr>()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    meta.parse_nested_meta(|nested| {
+                        if nested.input.peek(Token![=]) {
+                            nested.value()?.parse::<Expr>()?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                Ok(())
+            })?;
+        } else if attr.path().is_ident("adminx") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    attrs.label = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("group") {
+                    attrs.group = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("widget") {
+                    attrs.widget = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("reference") {
+                    attrs.reference = Some(meta.value()?.parse::<LitStr>()?.value());
+                } else if meta.path.is_ident("list") {
+                    attrs.list = true;
+                } else if meta.path.is_ident("filter") {
+                    attrs.filter = true;
+                } else if meta.path.is_ident("readonly") {
+                    attrs.readonly = true;
+                } else if meta.path.is_ident("skip") {
+                    attrs.skip = true;
+                } else {
+                    return Err(meta.error("expected one of: label, group, widget, reference, list, filter, readonly, skip"));
+                }
+                Ok(())
+            })?;
+        }
+    }
+
+    Ok(attrs)
+}